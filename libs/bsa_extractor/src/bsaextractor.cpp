@@ -89,6 +89,9 @@ void BsaExtractor::modInstalledHandler(IModInterface *mod)
                                 "Please remove the mod then reinstall with a different name."));
     return;
   }
+  // installers have already moved the mod's Data root to the mod folder, so
+  // only the archives right there are unpacked, never nested ones the game
+  // wouldn't load anyway
   QDir dir(mod->absolutePath());
 
   QFileInfoList archives = dir.entryInfoList(QStringList({ "*.bsa", "*.ba2" }));
@@ -114,7 +117,7 @@ void BsaExtractor::modInstalledHandler(IModInterface *mod)
       progress.setValue(0);
       progress.show();
 
-      archive.extractAll(mod->absolutePath().toLocal8Bit().constData(),
+      BSA::EErrorCode extracted = archive.extractAll(mod->absolutePath().toLocal8Bit().constData(),
                          [this, &progress](int value, std::string filename) {
                           return extractProgress(progress, value, filename);
                          },
@@ -126,6 +129,13 @@ void BsaExtractor::modInstalledHandler(IModInterface *mod)
 
       archive.close();
 
+      // canceling only applies to this archive's progress dialog; only some of
+      // its files were unpacked so it is still needed, but the others go on
+      if (extracted == BSA::ERROR_CANCELED) {
+        qWarning("extraction of %s canceled", archiveInfo.fileName().toUtf8().constData());
+        continue;
+      }
+
       if (removeBSAs) {
         if (!QFile::remove(archiveInfo.absoluteFilePath())) {
           qCritical("failed to remove archive %s", archiveInfo.absoluteFilePath().toUtf8().constData());
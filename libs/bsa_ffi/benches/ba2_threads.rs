@@ -0,0 +1,70 @@
+//! Packing a folder of 200 DDS textures into a DX10 BA2 with different
+//! numbers of compression threads
+//!
+//! Run with `cargo bench --bench ba2_threads`.
+
+use std::fs;
+use std::path::Path;
+
+use bsa_ffi::test_support::dds;
+use bsa_ffi::{Ba2Builder, Ba2Format};
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+
+const TEXTURES: u32 = 200;
+const SIZE: u32 = 256;
+
+/// A builder with every texture of `dir`, read from disk like a real pack
+fn texture_builder(dir: &Path, threads: usize) -> Ba2Builder {
+    let mut builder = Ba2Builder::new()
+        .with_format(Ba2Format::DX10)
+        .with_threads(threads);
+
+    for entry in fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        let name = path.file_name().unwrap().to_string_lossy().into_owned();
+        builder.add_file(
+            &format!("textures/bench/{}", name),
+            fs::read(&path).unwrap(),
+        );
+    }
+
+    builder
+}
+
+fn bench_dx10_threads(c: &mut Criterion) {
+    let root = std::env::temp_dir().join(format!("bsa_ffi_bench_{}", std::process::id()));
+    let dir = root.join("textures");
+    fs::create_dir_all(&dir).unwrap();
+    for i in 0..TEXTURES {
+        fs::write(dir.join(format!("t{:03}.dds", i)), dds(SIZE, SIZE, i + 1)).unwrap();
+    }
+
+    let output = root.join("bench.ba2");
+    let cpus = std::thread::available_parallelism().map_or(4, |n| n.get());
+    let mut thread_counts = vec![1, 2, cpus];
+    thread_counts.sort_unstable();
+    thread_counts.dedup();
+
+    let mut group = c.benchmark_group("dx10_textures");
+    group.sample_size(10);
+
+    for threads in thread_counts {
+        group.bench_with_input(
+            BenchmarkId::from_parameter(threads),
+            &threads,
+            |b, &threads| {
+                b.iter_batched(
+                    || texture_builder(&dir, threads),
+                    |builder| builder.build_with_progress(&output, |_, _, _| {}).unwrap(),
+                    BatchSize::PerIteration,
+                );
+            },
+        );
+    }
+
+    group.finish();
+    let _ = fs::remove_dir_all(&root);
+}
+
+criterion_group!(benches, bench_dx10_threads);
+criterion_main!(benches);
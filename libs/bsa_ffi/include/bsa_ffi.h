@@ -14,6 +14,28 @@ typedef struct {
   char *error;
 } BsaFfiStringList;
 
+typedef struct {
+  char *path;
+  uint64_t size;            /* size once extracted */
+  uint64_t compressed_size; /* size stored in the archive, == size if uncompressed */
+  int is_compressed;
+} BsaFfiEntry;
+
+typedef struct {
+  BsaFfiEntry *items;
+  size_t count;
+  char *error;
+} BsaFfiEntryList;
+
+typedef struct {
+  int format;      /* 0 = TES3 BSA, 1 = TES4 BSA, 2 = BA2, -1 on error */
+  uint32_t version;
+  int ba2_format;  /* 0 = not a BA2, 1 = GNRL, 2 = DX10 */
+  uint32_t file_count;
+  uint64_t total_size; /* size of the archive on disk */
+  char *error;
+} BsaFfiArchiveInfo;
+
 typedef void (*BsaProgressCallback)(uint32_t done, uint32_t total,
                                     const char *current_path);
 
@@ -23,10 +45,29 @@ BsaFfiStringList bsa_ffi_list_files(const char *archive_path);
 
 void bsa_ffi_string_list_free(BsaFfiStringList list);
 
+/* Like bsa_ffi_list_files(), with the sizes of each file. Free with
+ * bsa_ffi_entry_list_free(). */
+BsaFfiEntryList bsa_ffi_list_files_detailed(const char *archive_path);
+
+void bsa_ffi_entry_list_free(BsaFfiEntryList list);
+
+/* Reads only the archive header, which is much faster than listing the files when
+ * just the count is needed. On error, error is non-null and must be freed with
+ * bsa_ffi_string_free(). */
+BsaFfiArchiveInfo bsa_ffi_archive_info(const char *archive_path);
+
 /* Returns NULL on success, else an allocated error string (free with bsa_ffi_string_free). */
 char *bsa_ffi_extract_all(const char *archive_path, const char *output_dir,
                           BsaProgressCallback progress_cb, const int *cancel_flag);
 
+/* Extracts only the given paths, matched case-insensitively with either slash as
+ * separator. The progress total is the number of distinct requested paths.
+ * Returns NULL on success, else an allocated error string (free with
+ * bsa_ffi_string_free), also when some of the paths are not in the archive. */
+char *bsa_ffi_extract_files(const char *archive_path, const char *const *wanted_paths,
+                            size_t wanted_count, const char *output_dir,
+                            BsaProgressCallback progress_cb, const int *cancel_flag);
+
 /* game_id uses CLI ids from GameVersion::cli_name():
  * morrowind, oblivion, fo3, fonv, skyrimle, skyrimse,
  * fo4-fo76, fo4ng-v7, fo4ng-v8, starfield-v2, starfield-v3
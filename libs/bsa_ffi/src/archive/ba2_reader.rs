@@ -17,6 +17,13 @@ use tracing::debug;
 #[derive(Debug, Clone)]
 pub struct Ba2FileEntry {
     pub path: String,
+    /// Size of the file data once extracted, summed over all chunks; DX10
+    /// textures get a DDS header on top of this when extracted
+    pub size: u64,
+    /// Size of the data stored in the archive, summed over all chunks
+    pub compressed_size: u64,
+    /// Whether any chunk is compressed
+    pub is_compressed: bool,
 }
 
 /// List all files in a BA2 archive
@@ -26,10 +33,23 @@ pub fn list_files(ba2_path: &Path) -> Result<Vec<Ba2FileEntry>> {
 
     let mut files = Vec::new();
 
-    for (key, _file) in archive.iter() {
+    for (key, file) in archive.iter() {
         let path = String::from_utf8_lossy(key.name().as_bytes()).to_string();
 
-        files.push(Ba2FileEntry { path });
+        let mut entry = Ba2FileEntry {
+            path,
+            size: 0,
+            compressed_size: 0,
+            is_compressed: false,
+        };
+        for chunk in file.iter() {
+            let stored = chunk.as_bytes().len() as u64;
+            entry.size += chunk.decompressed_len().map_or(stored, |len| len as u64);
+            entry.compressed_size += stored;
+            entry.is_compressed |= chunk.is_compressed();
+        }
+
+        files.push(entry);
     }
 
     debug!("Listed {} files in BA2 {}", files.len(), ba2_path.display());
@@ -14,7 +14,10 @@ use rayon::prelude::*;
 use std::collections::HashMap;
 use std::fs;
 use std::io::BufWriter;
+use std::panic;
 use std::path::Path;
+use std::sync::mpsc::{self, Sender};
+use std::thread;
 use tracing::info;
 
 /// BA2 archive version
@@ -80,6 +83,32 @@ pub struct Ba2Builder {
     strings: bool,
     /// Archive version
     version: Ba2Version,
+    /// Worker threads for compression, 0 for rayon's global pool
+    threads: usize,
+}
+
+/// Run `work` on its own thread while this one reports every path it sends
+///
+/// The callback only runs here, so its count only ever goes up and the
+/// workers never wait for it.
+fn with_progress<'a, T, W, F>(total: usize, progress: &F, work: W) -> T
+where
+    T: Send,
+    W: FnOnce(Sender<&'a str>) -> T + Send,
+    F: Fn(usize, usize, &str),
+{
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|s| {
+        let worker = s.spawn(move || work(sender));
+
+        // ends once `work` has returned and dropped the sender
+        for (done, path) in receiver.into_iter().enumerate() {
+            progress(done + 1, total, path);
+        }
+
+        worker.join().unwrap_or_else(|e| panic::resume_unwind(e))
+    })
 }
 
 impl Ba2Builder {
@@ -90,6 +119,7 @@ impl Ba2Builder {
             compression: Ba2CompressionFormat::Zlib,
             strings: true,
             version: Ba2Version::default(),
+            threads: 0,
         }
     }
 
@@ -123,6 +153,7 @@ impl Ba2Builder {
             compression,
             strings: true,
             version: Ba2Version::default(),
+            threads: 0,
         }
     }
 
@@ -144,6 +175,14 @@ impl Ba2Builder {
         self
     }
 
+    /// Compress with a dedicated pool of `threads` workers instead of rayon's
+    /// global pool; 0 goes back to the global pool. The archive is the same
+    /// whatever the number of threads.
+    pub fn with_threads(mut self, threads: usize) -> Self {
+        self.threads = threads;
+        self
+    }
+
     /// Enable or disable string table
     #[allow(dead_code)]
     pub fn with_strings(mut self, strings: bool) -> Self {
@@ -169,6 +208,32 @@ impl Ba2Builder {
         self.files.is_empty()
     }
 
+    /// Files sorted by path, so the archive doesn't depend on hash order or on
+    /// which thread finishes first
+    fn sorted_entries(files: HashMap<String, Vec<u8>>) -> Vec<(String, Vec<u8>)> {
+        let mut entries: Vec<(String, Vec<u8>)> = files.into_iter().collect();
+        entries.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+        entries
+    }
+
+    /// Run `f` in the pool set by `with_threads()`
+    fn install<T, F>(threads: usize, f: F) -> Result<T>
+    where
+        T: Send,
+        F: FnOnce() -> T + Send,
+    {
+        if threads == 0 {
+            return Ok(f());
+        }
+
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build()
+            .context("Failed to create compression thread pool")?;
+
+        Ok(pool.install(f))
+    }
+
     /// Build and write the BA2 to disk with progress callback
     pub fn build_with_progress<F>(self, output_path: &Path, progress: F) -> Result<()>
     where
@@ -196,41 +261,43 @@ impl Ba2Builder {
         }
 
         // Build archive entries in parallel
-        let entries: Vec<(String, Vec<u8>)> = self.files.into_iter().collect();
-        let total = entries.len();
-        let processed_count = std::sync::atomic::AtomicUsize::new(0);
+        let entries = Self::sorted_entries(self.files);
         let compression = self.compression;
-
-        let archive_entries: Result<Vec<(ArchiveKey<'static>, Ba2File<'static>)>> = entries
-            .par_iter()
-            .map(|(path, data)| {
-                // Create chunk from data
-                let chunk = Chunk::from_decompressed(data.clone().into_boxed_slice());
-
-                // Optionally compress the chunk
-                let chunk = if compression != Ba2CompressionFormat::None {
-                    let options = ChunkCompressionOptions::default();
-                    match chunk.compress(&options) {
-                        Ok(compressed) => compressed,
-                        Err(_) => chunk, // Fall back to uncompressed if compression fails
-                    }
-                } else {
-                    chunk
-                };
-
-                // Create file from chunk
-                let file: Ba2File = [chunk].into_iter().collect();
-
-                // Create key from path
-                let key: ArchiveKey = path.as_bytes().into();
-
-                let current =
-                    processed_count.fetch_add(1, std::sync::atomic::Ordering::Relaxed) + 1;
-                progress(current, total, path);
-
-                Ok((key, file))
-            })
-            .collect();
+        let threads = self.threads;
+
+        let archive_entries: Result<Vec<(ArchiveKey<'static>, Ba2File<'static>)>> =
+            with_progress(entries.len(), &progress, |done| {
+                Self::install(threads, || {
+                    entries
+                        .par_iter()
+                        .map(|(path, data)| {
+                            // Create chunk from data
+                            let chunk = Chunk::from_decompressed(data.clone().into_boxed_slice());
+
+                            // Optionally compress the chunk
+                            let chunk = if compression != Ba2CompressionFormat::None {
+                                let options = ChunkCompressionOptions::default();
+                                match chunk.compress(&options) {
+                                    Ok(compressed) => compressed,
+                                    Err(_) => chunk, // Fall back to uncompressed if compression fails
+                                }
+                            } else {
+                                chunk
+                            };
+
+                            // Create file from chunk
+                            let file: Ba2File = [chunk].into_iter().collect();
+
+                            // Create key from path
+                            let key: ArchiveKey = path.as_bytes().into();
+
+                            let _ = done.send(path);
+
+                            Ok((key, file))
+                        })
+                        .collect()
+                })
+            })?;
 
         let archive_entries = archive_entries?;
 
@@ -266,9 +333,9 @@ impl Ba2Builder {
         F: Fn(usize, usize, &str) + Send + Sync,
     {
         let compress = self.compression != Ba2CompressionFormat::None;
-        let entries: Vec<(String, Vec<u8>)> = self.files.into_iter().collect();
+        let entries = Self::sorted_entries(self.files);
         let total = entries.len();
-        let processed_count = std::sync::atomic::AtomicUsize::new(0);
+        let threads = self.threads;
 
         // Build read options for DX10 format
         let read_options = FileReadOptionsBuilder::new()
@@ -282,21 +349,28 @@ impl Ba2Builder {
             })
             .build();
 
-        let archive_entries: Result<Vec<(ArchiveKey<'static>, Ba2File<'static>)>> = entries
-            .par_iter()
-            .map(|(path, data)| {
-                let file = Ba2File::read(Copied(data), &read_options)
-                    .with_context(|| format!("Failed to parse DDS texture: {}", path))?;
-
-                let key: ArchiveKey = path.as_bytes().into();
-
-                let current =
-                    processed_count.fetch_add(1, std::sync::atomic::Ordering::Relaxed) + 1;
-                progress(current, total, path);
-
-                Ok((key, file))
-            })
-            .collect();
+        // compressing the DX10 chunks is most of the work, the pool set by
+        // with_threads() is used for it
+        let archive_entries: Result<Vec<(ArchiveKey<'static>, Ba2File<'static>)>> =
+            with_progress(total, &progress, |done| {
+                Self::install(threads, || {
+                    entries
+                        .par_iter()
+                        .map(|(path, data)| {
+                            let file =
+                                Ba2File::read(Copied(data), &read_options).with_context(|| {
+                                    format!("Failed to parse DDS texture: {}", path)
+                                })?;
+
+                            let key: ArchiveKey = path.as_bytes().into();
+
+                            let _ = done.send(path);
+
+                            Ok((key, file))
+                        })
+                        .collect()
+                })
+            })?;
 
         let archive_entries = archive_entries?;
         let archive: Archive = archive_entries.into_iter().collect();
@@ -334,3 +408,81 @@ impl Default for Ba2Builder {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::dds;
+    use std::path::PathBuf;
+    use std::sync::Mutex;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("bsa_ffi_ba2_{}_{}", name, std::process::id()))
+    }
+
+    fn texture_builder(count: u32, size: u32) -> Ba2Builder {
+        let mut builder = Ba2Builder::new().with_format(Ba2Format::DX10);
+        for i in 0..count {
+            builder.add_file(
+                &format!("textures/test/t{:03}.dds", i),
+                dds(size, size, i + 1),
+            );
+        }
+        builder
+    }
+
+    #[test]
+    fn threaded_dx10_archive_is_identical() {
+        let single = temp_path("single.ba2");
+        let multi = temp_path("multi.ba2");
+
+        texture_builder(24, 64)
+            .with_threads(1)
+            .build_with_progress(&single, |_, _, _| {})
+            .unwrap();
+
+        let counts = Mutex::new(Vec::new());
+        texture_builder(24, 64)
+            .with_threads(4)
+            .build_with_progress(&multi, |done, total, _| {
+                counts.lock().unwrap().push((done, total));
+            })
+            .unwrap();
+
+        assert_eq!(fs::read(&single).unwrap(), fs::read(&multi).unwrap());
+
+        let counts = counts.into_inner().unwrap();
+        assert_eq!(counts.len(), 24);
+        for (i, (done, total)) in counts.iter().enumerate() {
+            assert_eq!(*done, i + 1);
+            assert_eq!(*total, 24);
+        }
+
+        let _ = fs::remove_file(&single);
+        let _ = fs::remove_file(&multi);
+    }
+
+    #[test]
+    fn threaded_general_archive_is_identical() {
+        let build = |threads: usize, path: &Path| {
+            let mut builder = Ba2Builder::new().with_threads(threads);
+            for i in 0..50u32 {
+                builder.add_file(
+                    &format!("meshes/test/m{:02}.nif", i),
+                    i.to_le_bytes().repeat(200 + i as usize),
+                );
+            }
+            builder.build_with_progress(path, |_, _, _| {}).unwrap();
+        };
+
+        let single = temp_path("general_single.ba2");
+        let multi = temp_path("general_multi.ba2");
+        build(1, &single);
+        build(4, &multi);
+
+        assert_eq!(fs::read(&single).unwrap(), fs::read(&multi).unwrap());
+
+        let _ = fs::remove_file(&single);
+        let _ = fs::remove_file(&multi);
+    }
+}
@@ -0,0 +1,177 @@
+//! Header-only archive inspection
+//!
+//! Reads the fixed-size header of a Bethesda archive to get its format and
+//! file count without parsing the record tables, which is much faster when
+//! many archives only need to be summarized.
+
+use anyhow::{bail, Context, Result};
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+use super::{ArchiveFormat, Ba2Format};
+
+/// Summary of an archive read from its header
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArchiveHeader {
+    pub format: ArchiveFormat,
+    /// Format version, 0x100 for TES3 archives
+    pub version: u32,
+    /// Kind of BA2 archive, `None` for BSAs
+    pub ba2_format: Option<Ba2Format>,
+    pub file_count: u32,
+    /// Size of the archive on disk
+    pub total_size: u64,
+}
+
+/// Read the header of any Bethesda archive (TES3 BSA, TES4 BSA, or BA2)
+pub fn read_header(path: &Path) -> Result<ArchiveHeader> {
+    let mut file =
+        File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+    let total_size = file.metadata()?.len();
+
+    // the largest header read here is the TES4 one
+    let mut header = Vec::with_capacity(36);
+    file.by_ref().take(36).read_to_end(&mut header)?;
+
+    let mut parsed = parse_header(&header)
+        .with_context(|| format!("Failed to read the header of {}", path.display()))?;
+    parsed.total_size = total_size;
+
+    Ok(parsed)
+}
+
+fn parse_header(header: &[u8]) -> Result<ArchiveHeader> {
+    let u32_at = |offset: usize| -> Result<u32> {
+        let Some(bytes) = header.get(offset..offset + 4) else {
+            bail!("Archive header is truncated");
+        };
+        Ok(u32::from_le_bytes(bytes.try_into().unwrap()))
+    };
+
+    if header.starts_with(b"BSA\0") {
+        let version = u32_at(4)?;
+        if !matches!(version, 103..=105) {
+            bail!("Unsupported BSA version {}", version);
+        }
+
+        return Ok(ArchiveHeader {
+            format: ArchiveFormat::Bsa,
+            version,
+            ba2_format: None,
+            file_count: u32_at(20)?,
+            total_size: 0,
+        });
+    }
+
+    if header.starts_with(b"BTDX") {
+        let ba2_format = match header.get(8..12) {
+            Some(b"GNRL") => Ba2Format::General,
+            Some(b"DX10") => Ba2Format::DX10,
+            Some(other) => bail!("Unsupported BA2 type {:?}", String::from_utf8_lossy(other)),
+            None => bail!("Archive header is truncated"),
+        };
+
+        return Ok(ArchiveHeader {
+            format: ArchiveFormat::Ba2,
+            version: u32_at(4)?,
+            ba2_format: Some(ba2_format),
+            file_count: u32_at(12)?,
+            total_size: 0,
+        });
+    }
+
+    if u32_at(0)? == 0x100 {
+        return Ok(ArchiveHeader {
+            format: ArchiveFormat::Tes3Bsa,
+            version: 0x100,
+            ba2_format: None,
+            file_count: u32_at(8)?,
+            total_size: 0,
+        });
+    }
+
+    bail!("Not a Bethesda archive")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bsa_header(version: u32, files: u32) -> Vec<u8> {
+        let mut h = b"BSA\0".to_vec();
+        for v in [version, 36, 0x3, 2, files, 10, 40, 0] {
+            h.extend_from_slice(&v.to_le_bytes());
+        }
+        h
+    }
+
+    fn ba2_header(kind: &[u8; 4], version: u32, files: u32) -> Vec<u8> {
+        let mut h = b"BTDX".to_vec();
+        h.extend_from_slice(&version.to_le_bytes());
+        h.extend_from_slice(kind);
+        h.extend_from_slice(&files.to_le_bytes());
+        h.extend_from_slice(&0x1234u64.to_le_bytes());
+        h
+    }
+
+    #[test]
+    fn reads_bsa_headers() {
+        for version in [103, 104, 105] {
+            let h = parse_header(&bsa_header(version, 42)).unwrap();
+            assert_eq!(h.format, ArchiveFormat::Bsa);
+            assert_eq!(h.version, version);
+            assert_eq!(h.file_count, 42);
+            assert_eq!(h.ba2_format, None);
+        }
+
+        assert!(parse_header(&bsa_header(106, 1)).is_err());
+    }
+
+    #[test]
+    fn reads_ba2_headers() {
+        let h = parse_header(&ba2_header(b"GNRL", 1, 7)).unwrap();
+        assert_eq!(h.format, ArchiveFormat::Ba2);
+        assert_eq!(h.ba2_format, Some(Ba2Format::General));
+        assert_eq!(h.file_count, 7);
+
+        let h = parse_header(&ba2_header(b"DX10", 8, 3)).unwrap();
+        assert_eq!(h.version, 8);
+        assert_eq!(h.ba2_format, Some(Ba2Format::DX10));
+        assert_eq!(h.file_count, 3);
+
+        assert!(parse_header(&ba2_header(b"XXXX", 1, 1)).is_err());
+    }
+
+    #[test]
+    fn reads_tes3_headers() {
+        let mut h = 0x100u32.to_le_bytes().to_vec();
+        h.extend_from_slice(&100u32.to_le_bytes());
+        h.extend_from_slice(&5u32.to_le_bytes());
+
+        let h = parse_header(&h).unwrap();
+        assert_eq!(h.format, ArchiveFormat::Tes3Bsa);
+        assert_eq!(h.file_count, 5);
+    }
+
+    #[test]
+    fn rejects_truncated_headers() {
+        assert!(parse_header(b"BSA\0\x68\0\0\0").is_err());
+        assert!(parse_header(b"BTDX").is_err());
+        assert!(parse_header(b"").is_err());
+    }
+
+    #[test]
+    fn read_header_uses_the_file_size() {
+        let path = std::env::temp_dir().join(format!("bsa_ffi_header_{}.bsa", std::process::id()));
+        let mut data = bsa_header(105, 2);
+        data.resize(100, 0);
+        std::fs::write(&path, &data).unwrap();
+
+        let h = read_header(&path).unwrap();
+        assert_eq!(h.total_size, 100);
+        assert_eq!(h.file_count, 2);
+
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -7,6 +7,7 @@
 
 mod ba2_reader;
 mod ba2_writer;
+mod header;
 mod reader;
 mod tes3_reader;
 mod writer;
@@ -14,6 +15,7 @@ mod writer;
 pub use reader::{
     extract_file, extract_files_batch as extract_bsa_files_batch, list_files, BsaFileEntry,
 };
+pub use header::{read_header, ArchiveHeader};
 pub use writer::BsaBuilder;
 
 // TES3 (Morrowind) support
@@ -87,6 +89,12 @@ pub fn detect_format(path: &Path) -> Option<ArchiveFormat> {
 #[derive(Debug, Clone)]
 pub struct ArchiveFileEntry {
     pub path: String,
+    /// Size of the file once extracted
+    pub size: u64,
+    /// Size of the data stored in the archive, same as `size` for
+    /// uncompressed files
+    pub compressed_size: u64,
+    pub is_compressed: bool,
 }
 
 /// List files from any Bethesda archive (TES3 BSA, TES4 BSA, or BA2)
@@ -96,21 +104,36 @@ pub fn list_archive_files(archive_path: &Path) -> Result<Vec<ArchiveFileEntry>>
             let files = list_tes3_files(archive_path)?;
             Ok(files
                 .into_iter()
-                .map(|f| ArchiveFileEntry { path: f.path })
+                .map(|f| ArchiveFileEntry {
+                    path: f.path,
+                    size: f.size,
+                    compressed_size: f.compressed_size,
+                    is_compressed: f.is_compressed,
+                })
                 .collect())
         }
         Some(ArchiveFormat::Bsa) => {
             let files = list_files(archive_path)?;
             Ok(files
                 .into_iter()
-                .map(|f| ArchiveFileEntry { path: f.path })
+                .map(|f| ArchiveFileEntry {
+                    path: f.path,
+                    size: f.size,
+                    compressed_size: f.compressed_size,
+                    is_compressed: f.is_compressed,
+                })
                 .collect())
         }
         Some(ArchiveFormat::Ba2) => {
             let files = list_ba2_files(archive_path)?;
             Ok(files
                 .into_iter()
-                .map(|f| ArchiveFileEntry { path: f.path })
+                .map(|f| ArchiveFileEntry {
+                    path: f.path,
+                    size: f.size,
+                    compressed_size: f.compressed_size,
+                    is_compressed: f.is_compressed,
+                })
                 .collect())
         }
         None => bail!("Unknown archive format: {}", archive_path.display()),
@@ -137,7 +160,7 @@ pub fn extract_archive_file(archive_path: &Path, file_path: &str) -> Result<Vec<
 
 /// Extract multiple files from any Bethesda archive in a single pass.
 /// Opens the archive once and calls the callback for each extracted file.
-/// `wanted_files` should contain the original paths (as returned by list_archive_files).
+/// `wanted_files` are matched case-insensitively, with either slash as separator.
 /// Returns the number of files successfully extracted.
 pub fn extract_archive_files_batch<F>(
     archive_path: &Path,
@@ -150,7 +173,11 @@ where
     let format = detect_format(archive_path);
     match format {
         Some(ArchiveFormat::Tes3Bsa) => {
-            let wanted: HashSet<String> = wanted_files.iter().map(|p| p.to_lowercase()).collect();
+            // TES3 BSA uses backslash-separated paths as well
+            let wanted: HashSet<String> = wanted_files
+                .iter()
+                .map(|p| p.replace('/', "\\").to_lowercase())
+                .collect();
             extract_tes3_files_batch(archive_path, &wanted, callback)
         }
         Some(ArchiveFormat::Bsa) => {
@@ -13,6 +13,11 @@ use tracing::debug;
 #[derive(Debug, Clone)]
 pub struct BsaFileEntry {
     pub path: String,
+    /// Size of the file once extracted
+    pub size: u64,
+    /// Size of the data stored in the archive
+    pub compressed_size: u64,
+    pub is_compressed: bool,
 }
 
 /// List all files in a BSA archive
@@ -25,7 +30,7 @@ pub fn list_files(bsa_path: &Path) -> Result<Vec<BsaFileEntry>> {
     for (dir_key, folder) in archive.iter() {
         let dir_name = String::from_utf8_lossy(dir_key.name().as_bytes());
 
-        for (file_key, _file) in folder.iter() {
+        for (file_key, file) in folder.iter() {
             let file_name = String::from_utf8_lossy(file_key.name().as_bytes());
 
             // Build full path with backslash (BSA convention)
@@ -35,7 +40,15 @@ pub fn list_files(bsa_path: &Path) -> Result<Vec<BsaFileEntry>> {
                 format!("{}\\{}", dir_name, file_name)
             };
 
-            files.push(BsaFileEntry { path: full_path });
+            let compressed_size = file.as_bytes().len() as u64;
+            files.push(BsaFileEntry {
+                path: full_path,
+                size: file
+                    .decompressed_len()
+                    .map_or(compressed_size, |len| len as u64),
+                compressed_size,
+                is_compressed: file.is_compressed(),
+            });
         }
     }
 
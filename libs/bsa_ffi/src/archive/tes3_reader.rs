@@ -18,10 +18,17 @@ pub fn list_files(bsa_path: &Path) -> Result<Vec<BsaFileEntry>> {
 
     let mut files = Vec::new();
 
-    for (key, _file) in archive.iter() {
+    for (key, file) in archive.iter() {
         let path = String::from_utf8_lossy(key.name().as_bytes()).to_string();
 
-        files.push(BsaFileEntry { path });
+        // TES3 BSAs are uncompressed, the stored data is the file
+        let size = file.as_bytes().len() as u64;
+        files.push(BsaFileEntry {
+            path,
+            size,
+            compressed_size: size,
+            is_compressed: false,
+        });
     }
 
     debug!(
@@ -77,7 +84,7 @@ where
     let mut entries: Vec<(String, &Tes3File)> = Vec::new();
     for (key, file) in archive.iter() {
         let path = String::from_utf8_lossy(key.name().as_bytes()).to_string();
-        let lookup = path.to_lowercase();
+        let lookup = path.replace('/', "\\").to_lowercase();
         if wanted.contains(&lookup) {
             entries.push((path, file));
         }
@@ -1,16 +1,21 @@
 mod archive;
+#[doc(hidden)]
+pub mod test_support;
 
+use std::collections::HashSet;
 use std::ffi::{c_char, c_int, CStr, CString};
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::ptr;
 
 use archive::{
-    extract_archive_files_batch, list_archive_files, Ba2Builder, Ba2Format, BsaBuilder,
+    extract_archive_files_batch, list_archive_files, read_header, ArchiveFormat, BsaBuilder,
     GameVersion,
 };
 use walkdir::WalkDir;
 
+pub use archive::{Ba2Builder, Ba2Format};
+
 #[repr(C)]
 pub struct BsaFfiStringList {
     pub items: *mut *mut c_char,
@@ -18,6 +23,33 @@ pub struct BsaFfiStringList {
     pub error: *mut c_char,
 }
 
+#[repr(C)]
+pub struct BsaFfiEntry {
+    pub path: *mut c_char,
+    pub size: u64,
+    pub compressed_size: u64,
+    pub is_compressed: c_int,
+}
+
+#[repr(C)]
+pub struct BsaFfiEntryList {
+    pub items: *mut BsaFfiEntry,
+    pub count: usize,
+    pub error: *mut c_char,
+}
+
+#[repr(C)]
+pub struct BsaFfiArchiveInfo {
+    /// 0 = TES3 BSA, 1 = TES4 BSA, 2 = BA2
+    pub format: c_int,
+    pub version: u32,
+    /// 0 = not a BA2, 1 = GNRL, 2 = DX10
+    pub ba2_format: c_int,
+    pub file_count: u32,
+    pub total_size: u64,
+    pub error: *mut c_char,
+}
+
 pub type BsaProgressCallback =
     Option<unsafe extern "C" fn(done: u32, total: u32, current_path: *const c_char)>;
 
@@ -33,6 +65,14 @@ fn error_list(msg: &str) -> BsaFfiStringList {
     }
 }
 
+fn error_entry_list(msg: &str) -> BsaFfiEntryList {
+    BsaFfiEntryList {
+        items: ptr::null_mut(),
+        count: 0,
+        error: to_cstring(msg),
+    }
+}
+
 unsafe fn from_cstr<'a>(p: *const c_char) -> Result<&'a str, &'static str> {
     if p.is_null() {
         return Err("null pointer");
@@ -53,6 +93,42 @@ fn call_progress(progress_cb: BsaProgressCallback, done: usize, total: usize, pa
     }
 }
 
+fn is_cancelled(cancel_flag: *const c_int) -> bool {
+    !cancel_flag.is_null() && unsafe { *cancel_flag } != 0
+}
+
+/// Path the archive is built at before it's renamed to `output`, so a
+/// cancelled or failed pack never leaves a partial archive under the real name
+fn temp_archive_path(output: &Path) -> PathBuf {
+    let mut name = output.as_os_str().to_owned();
+    name.push(".tmp");
+    PathBuf::from(name)
+}
+
+/// Renames the archive built at `temp` to `output` if building succeeded and
+/// wasn't cancelled meanwhile, removes it otherwise
+fn finish_pack(
+    result: anyhow::Result<()>,
+    temp: &Path,
+    output: &Path,
+    cancel_flag: *const c_int,
+) -> *mut c_char {
+    let result = result.and_then(|_| {
+        if is_cancelled(cancel_flag) {
+            anyhow::bail!("cancelled");
+        }
+        Ok(fs::rename(temp, output)?)
+    });
+
+    match result {
+        Ok(_) => ptr::null_mut(),
+        Err(e) => {
+            let _ = fs::remove_file(temp);
+            to_cstring(&e.to_string())
+        }
+    }
+}
+
 fn path_to_rel(root: &Path, child: &Path) -> anyhow::Result<String> {
     let rel = child.strip_prefix(root)?;
     Ok(rel.to_string_lossy().replace('\\', "/"))
@@ -105,6 +181,96 @@ pub unsafe extern "C" fn bsa_ffi_string_list_free(list: BsaFfiStringList) {
     }
 }
 
+#[no_mangle]
+pub unsafe extern "C" fn bsa_ffi_list_files_detailed(
+    archive_path: *const c_char,
+) -> BsaFfiEntryList {
+    let archive_path = match from_cstr(archive_path) {
+        Ok(v) => v,
+        Err(e) => return error_entry_list(e),
+    };
+
+    let entries = match list_archive_files(Path::new(archive_path)) {
+        Ok(v) => v,
+        Err(e) => return error_entry_list(&e.to_string()),
+    };
+
+    // boxed slice so capacity == count when it's rebuilt in the free function
+    let items: Box<[BsaFfiEntry]> = entries
+        .into_iter()
+        .map(|e| BsaFfiEntry {
+            path: to_cstring(&e.path),
+            size: e.size,
+            compressed_size: e.compressed_size,
+            is_compressed: e.is_compressed as c_int,
+        })
+        .collect();
+
+    let count = items.len();
+    BsaFfiEntryList {
+        items: Box::into_raw(items) as *mut BsaFfiEntry,
+        count,
+        error: ptr::null_mut(),
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn bsa_ffi_entry_list_free(list: BsaFfiEntryList) {
+    if !list.items.is_null() {
+        let items = Box::from_raw(ptr::slice_from_raw_parts_mut(list.items, list.count));
+        for item in items.iter() {
+            if !item.path.is_null() {
+                let _ = CString::from_raw(item.path);
+            }
+        }
+    }
+
+    if !list.error.is_null() {
+        let _ = CString::from_raw(list.error);
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn bsa_ffi_archive_info(archive_path: *const c_char) -> BsaFfiArchiveInfo {
+    let mut info = BsaFfiArchiveInfo {
+        format: -1,
+        version: 0,
+        ba2_format: 0,
+        file_count: 0,
+        total_size: 0,
+        error: ptr::null_mut(),
+    };
+
+    let archive_path = match from_cstr(archive_path) {
+        Ok(v) => v,
+        Err(e) => {
+            info.error = to_cstring(e);
+            return info;
+        }
+    };
+
+    match read_header(Path::new(archive_path)) {
+        Ok(header) => {
+            info.format = match header.format {
+                ArchiveFormat::Tes3Bsa => 0,
+                ArchiveFormat::Bsa => 1,
+                ArchiveFormat::Ba2 => 2,
+            };
+            info.version = header.version;
+            info.ba2_format = match header.ba2_format {
+                None => 0,
+                Some(Ba2Format::General) => 1,
+                Some(Ba2Format::DX10) => 2,
+            };
+            info.file_count = header.file_count;
+            info.total_size = header.total_size;
+        }
+        Err(e) => info.error = to_cstring(&format!("{e:#}")),
+    }
+
+    info
+}
+
 #[no_mangle]
 pub unsafe extern "C" fn bsa_ffi_string_free(s: *mut c_char) {
     if !s.is_null() {
@@ -129,23 +295,101 @@ pub unsafe extern "C" fn bsa_ffi_extract_all(
     };
 
     let archive_path = PathBuf::from(archive_path);
-    let output_dir = PathBuf::from(output_dir);
-
-    if let Err(e) = fs::create_dir_all(&output_dir) {
-        return to_cstring(&format!("failed to create output directory: {e}"));
-    }
 
     let entries = match list_archive_files(&archive_path) {
         Ok(v) => v,
         Err(e) => return to_cstring(&e.to_string()),
     };
 
-    let total = entries.len();
-    let wanted_files: Vec<String> = entries.iter().map(|e| e.path.clone()).collect();
+    let wanted_files: Vec<String> = entries.into_iter().map(|e| e.path).collect();
+    match extract_to_dir(
+        &archive_path,
+        Path::new(output_dir),
+        &wanted_files,
+        progress_cb,
+        cancel_flag,
+    ) {
+        Ok(_) => ptr::null_mut(),
+        Err(e) => to_cstring(&e),
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn bsa_ffi_extract_files(
+    archive_path: *const c_char,
+    wanted_paths: *const *const c_char,
+    wanted_count: usize,
+    output_dir: *const c_char,
+    progress_cb: BsaProgressCallback,
+    cancel_flag: *const c_int,
+) -> *mut c_char {
+    let archive_path = match from_cstr(archive_path) {
+        Ok(v) => v,
+        Err(e) => return to_cstring(e),
+    };
+    let output_dir = match from_cstr(output_dir) {
+        Ok(v) => v,
+        Err(e) => return to_cstring(e),
+    };
+
+    if wanted_count == 0 {
+        return ptr::null_mut();
+    }
+    if wanted_paths.is_null() {
+        return to_cstring("null pointer");
+    }
+
+    // the same file may be asked for with different case or slashes, keep it
+    // once so the progress total matches what gets extracted
+    let mut seen = HashSet::new();
+    let mut wanted_files = Vec::with_capacity(wanted_count);
+    for &p in std::slice::from_raw_parts(wanted_paths, wanted_count) {
+        let path = match from_cstr(p) {
+            Ok(v) => v,
+            Err(e) => return to_cstring(e),
+        };
+
+        if seen.insert(path.replace('\\', "/").to_lowercase()) {
+            wanted_files.push(path.to_string());
+        }
+    }
+
+    let total = wanted_files.len();
+    match extract_to_dir(
+        Path::new(archive_path),
+        Path::new(output_dir),
+        &wanted_files,
+        progress_cb,
+        cancel_flag,
+    ) {
+        Ok(extracted) if extracted < total => to_cstring(&format!(
+            "{} of {} requested files were not found in the archive",
+            total - extracted,
+            total
+        )),
+        Ok(_) => ptr::null_mut(),
+        Err(e) => to_cstring(&e),
+    }
+}
+
+/// Extracts `wanted_files` from the archive into `output_dir`, returns the
+/// number of files that were found and extracted
+fn extract_to_dir(
+    archive_path: &Path,
+    output_dir: &Path,
+    wanted_files: &[String],
+    progress_cb: BsaProgressCallback,
+    cancel_flag: *const c_int,
+) -> Result<usize, String> {
+    if let Err(e) = fs::create_dir_all(output_dir) {
+        return Err(format!("failed to create output directory: {e}"));
+    }
+
+    let total = wanted_files.len();
     let progress_count = std::sync::atomic::AtomicUsize::new(0);
     let cancel_addr = cancel_flag as usize;
 
-    let res = extract_archive_files_batch(&archive_path, &wanted_files, |path, data| {
+    let res = extract_archive_files_batch(archive_path, wanted_files, |path, data| {
         let cancel_ptr = cancel_addr as *const c_int;
         if !cancel_ptr.is_null() {
             let cancelled = unsafe { *cancel_ptr } != 0;
@@ -165,10 +409,7 @@ pub unsafe extern "C" fn bsa_ffi_extract_all(
         Ok(())
     });
 
-    match res {
-        Ok(_) => ptr::null_mut(),
-        Err(e) => to_cstring(&e.to_string()),
-    }
+    res.map_err(|e| e.to_string())
 }
 
 #[no_mangle]
@@ -225,6 +466,7 @@ pub unsafe extern "C" fn bsa_ffi_pack_dir_filtered(
 
     let input_dir = PathBuf::from(input_dir);
     let output_archive = PathBuf::from(output_archive);
+    let temp_archive = temp_archive_path(&output_archive);
 
     let mut files: Vec<(String, Vec<u8>)> = Vec::new();
     for entry in WalkDir::new(&input_dir).into_iter().filter_map(|e| e.ok()) {
@@ -232,11 +474,8 @@ pub unsafe extern "C" fn bsa_ffi_pack_dir_filtered(
             continue;
         }
 
-        if !cancel_flag.is_null() {
-            let cancelled = unsafe { *cancel_flag } != 0;
-            if cancelled {
-                return to_cstring("cancelled");
-            }
+        if is_cancelled(cancel_flag) {
+            return to_cstring("cancelled");
         }
 
         let rel = match path_to_rel(&input_dir, entry.path()) {
@@ -282,20 +521,15 @@ pub unsafe extern "C" fn bsa_ffi_pack_dir_filtered(
             .with_format(format);
 
         for (idx, (rel, data)) in files.into_iter().enumerate() {
-            if !cancel_flag.is_null() {
-                let cancelled = unsafe { *cancel_flag } != 0;
-                if cancelled {
-                    return to_cstring("cancelled");
-                }
+            if is_cancelled(cancel_flag) {
+                return to_cstring("cancelled");
             }
             builder.add_file(&rel, data);
             call_progress(progress_cb, idx + 1, total, &rel);
         }
 
-        match builder.build_with_progress(&output_archive, |_, _, _| {}) {
-            Ok(_) => ptr::null_mut(),
-            Err(e) => to_cstring(&e.to_string()),
-        }
+        let result = builder.build_with_progress(&temp_archive, |_, _, _| {});
+        finish_pack(result, &temp_archive, &output_archive, cancel_flag)
     } else {
         let version = match game.bsa_version() {
             Some(v) => v,
@@ -307,19 +541,63 @@ pub unsafe extern "C" fn bsa_ffi_pack_dir_filtered(
         let mut builder = BsaBuilder::new().with_version(version).with_compression(compress);
 
         for (idx, (rel, data)) in files.into_iter().enumerate() {
-            if !cancel_flag.is_null() {
-                let cancelled = unsafe { *cancel_flag } != 0;
-                if cancelled {
-                    return to_cstring("cancelled");
-                }
+            if is_cancelled(cancel_flag) {
+                return to_cstring("cancelled");
             }
             builder.add_file(&rel, data);
             call_progress(progress_cb, idx + 1, total, &rel);
         }
 
-        match builder.build_with_progress(&output_archive, |_, _, _| {}) {
-            Ok(_) => ptr::null_mut(),
-            Err(e) => to_cstring(&e.to_string()),
+        let result = builder.build_with_progress(&temp_archive, |_, _, _| {});
+        finish_pack(result, &temp_archive, &output_archive, cancel_flag)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicI32, Ordering};
+
+    static CANCEL: AtomicI32 = AtomicI32::new(0);
+
+    // cancels once every file was added, while the archive is being built
+    unsafe extern "C" fn cancel_at_end(done: u32, total: u32, _path: *const c_char) {
+        if done == total {
+            CANCEL.store(1, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn cancelled_pack_leaves_no_archive() {
+        let root = std::env::temp_dir().join(format!("bsa_ffi_cancel_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let input = root.join("input");
+        fs::create_dir_all(input.join("meshes")).unwrap();
+        for i in 0..5 {
+            fs::write(input.join(format!("meshes/m{i}.nif")), vec![i as u8; 4096]).unwrap();
         }
+
+        let output = root.join("test.bsa");
+        let input_c = CString::new(input.to_string_lossy().as_bytes()).unwrap();
+        let output_c = CString::new(output.to_string_lossy().as_bytes()).unwrap();
+        let game_c = CString::new("skyrimse").unwrap();
+
+        let error = unsafe {
+            bsa_ffi_pack_dir(
+                input_c.as_ptr(),
+                output_c.as_ptr(),
+                game_c.as_ptr(),
+                Some(cancel_at_end),
+                CANCEL.as_ptr(),
+            )
+        };
+
+        assert!(!error.is_null());
+        let message = unsafe { CString::from_raw(error) };
+        assert_eq!(message.to_str().unwrap(), "cancelled");
+        assert!(!output.exists());
+        assert!(!temp_archive_path(&output).exists());
+
+        let _ = fs::remove_dir_all(&root);
     }
 }
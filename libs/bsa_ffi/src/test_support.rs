@@ -0,0 +1,42 @@
+//! Fixtures shared by the unit tests and the benchmarks
+
+/// An uncompressed 32-bit BGRA DDS texture, filled from `seed`
+pub fn dds(width: u32, height: u32, seed: u32) -> Vec<u8> {
+    let mut h = b"DDS ".to_vec();
+    let mut put = |v: u32| h.extend_from_slice(&v.to_le_bytes());
+
+    // caps | height | width | pixel format, pitch, depth, mipmaps
+    for v in [124, 0x1007, height, width, width * 4, 0, 1] {
+        put(v);
+    }
+    (0..11).for_each(|_| put(0));
+
+    // pixel format: rgb | alpha pixels, 32 bits bgra
+    for v in [
+        32,
+        0x41,
+        0,
+        32,
+        0x00ff_0000,
+        0x0000_ff00,
+        0x0000_00ff,
+        0xff00_0000,
+    ] {
+        put(v);
+    }
+
+    // texture caps
+    for v in [0x1000, 0, 0, 0, 0] {
+        put(v);
+    }
+
+    let mut state = seed.wrapping_mul(2_654_435_761).max(1);
+    for _ in 0..width * height {
+        state ^= state << 13;
+        state ^= state >> 17;
+        state ^= state << 5;
+        // keep it compressible, like real textures
+        h.extend_from_slice(&(state & 0xff0f_0f0f).to_le_bytes());
+    }
+    h
+}
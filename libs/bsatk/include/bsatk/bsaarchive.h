@@ -124,7 +124,8 @@ public:
    *                        may be absolute or relative
    * @param progress callback function called on progress
    * @param overwrite if true (default) files are overwritten if they exist
-   * @return ERROR_NONE on success or an error code
+   * @return ERROR_NONE on success, ERROR_CANCELED if progress returned false or an
+   *         error code
    */
   EErrorCode
   extractAll(const char* outputDirectory,
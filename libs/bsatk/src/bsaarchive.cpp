@@ -1189,7 +1189,7 @@ EErrorCode Archive::extractAll(
     }
   }
 
-  return ERROR_NONE;
+  return canceled ? ERROR_CANCELED : ERROR_NONE;
 }
 
 bool Archive::compressed(const File::Ptr& file) const
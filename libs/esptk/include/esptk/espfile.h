@@ -32,6 +32,26 @@ public:
   std::string description() const { return m_Description; }
   std::set<std::string> masters() const { return m_Masters; }
 
+  // masters in the order they are listed in the plugin, which is the order the
+  // upper byte of form ids refers to
+  //
+  const std::vector<std::string>& masterList() const { return m_MasterList; }
+
+  // highest object id (the lower 24 bits of the form id) used by a record
+  // this plugin adds, as opposed to records overriding one of its masters;
+  // returns 0 if the plugin adds no records
+  //
+  // this walks every record in the file, reading only the record headers
+  //
+  uint32_t highestNewObjectId();
+
+  // form ids of all records in the plugin, as stored in the file; the upper
+  // byte indexes masterList(), or is its size for records the plugin adds
+  //
+  // same caveats as highestNewObjectId(), returns nothing for TES3 plugins
+  //
+  std::vector<uint32_t> recordFormIds();
+
 private:
   void init();
 
@@ -65,6 +85,7 @@ private:
   std::string m_Description;
 
   std::set<std::string> m_Masters;
+  std::vector<std::string> m_MasterList;
 };
 
 }  // namespace ESP
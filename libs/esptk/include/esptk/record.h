@@ -37,6 +37,9 @@ public:
 
   uint16_t formVersion() const { return m_FormVersion; }
 
+  // Oblivion records have 20 byte headers, without the form version
+  bool oblivionStyle() const { return m_OblivionStyle; }
+
   const std::vector<uint8_t>& data() const { return m_Data; }
 
 private:
@@ -2,6 +2,7 @@
 #include "espexceptions.h"
 #include "subrecord.h"
 #include "tes3subrecord.h"
+#include <algorithm>
 #include <bitset>
 #include <cstring>
 #include <sstream>
@@ -130,8 +131,10 @@ void ESP::File::onHEDR(const SubRecord& rec)
 
 void ESP::File::onMAST(const SubRecord& rec)
 {
-  if (rec.data().size() > 0)
+  if (rec.data().size() > 0) {
     m_Masters.insert(reinterpret_cast<const char*>(&rec.data()[0]));
+    m_MasterList.push_back(reinterpret_cast<const char*>(&rec.data()[0]));
+  }
 }
 
 void ESP::File::onCNAM(const SubRecord& rec)
@@ -153,6 +156,63 @@ ESP::Record ESP::File::readRecord()
   return rec;
 }
 
+uint32_t ESP::File::highestNewObjectId()
+{
+  // records added by this plugin use the index right after the last master
+  const uint32_t newIndex = static_cast<uint32_t>(m_Masters.size());
+  uint32_t highest        = 0;
+
+  for (uint32_t formId : recordFormIds()) {
+    if ((formId >> 24) == newIndex) {
+      highest = std::max(highest, formId & 0x00FFFFFF);
+    }
+  }
+
+  return highest;
+}
+
+std::vector<uint32_t> ESP::File::recordFormIds()
+{
+  struct
+  {
+    char type[4];
+    uint32_t dataSize;
+    uint32_t flags;
+    uint32_t formId;
+    uint32_t revision;
+  } header;
+
+  // the version fields following the common part, missing in Oblivion
+  const std::streamoff versionSize = m_MainRecord.oblivionStyle() ? 0 : 4;
+
+  std::vector<uint32_t> formIds;
+
+  m_File.clear();
+  m_File.seekg(0);
+
+  // skip the main record
+  if (!m_File.read(reinterpret_cast<char*>(&header), sizeof(header)) ||
+      memcmp(header.type, "TES4", 4) != 0) {
+    m_File.clear();
+    return formIds;
+  }
+  m_File.seekg(versionSize + header.dataSize, std::istream::cur);
+
+  while (m_File.read(reinterpret_cast<char*>(&header), sizeof(header))) {
+    if (memcmp(header.type, "GRUP", 4) == 0) {
+      // the records in the group follow the group header directly
+      m_File.seekg(versionSize, std::istream::cur);
+      continue;
+    }
+
+    formIds.push_back(header.formId);
+    m_File.seekg(versionSize + header.dataSize, std::istream::cur);
+  }
+
+  m_File.clear();
+  return formIds;
+}
+
 bool ESP::File::isMaster() const
 {
   return m_MainRecord.flagSet(Record::FLAG_MASTER);
@@ -163,3 +163,65 @@ bool GamebryoBSAInvalidation::prepareProfile(MOBase::IProfile* profile)
 
   return dirty;
 }
+
+bool GamebryoBSAInvalidation::prepareResourceDataDirs(MOBase::IProfile* profile)
+{
+  bool dirty          = false;
+  QString basePath    = profile->localSettingsEnabled()
+                            ? profile->absolutePath()
+                            : m_Game->documentsDirectory().absolutePath();
+  QString iniFilePath = basePath + "/" + m_IniFileName;
+  QString savedIni    = profile->absolutePath() + "/" + "invalidation.ini";
+
+  QString bInvalidateOlderFiles = GameGamebryo::readIniValue(
+      iniFilePath, "Archive", "bInvalidateOlderFiles", "0");
+  QString sResourceDataDirsFinal = GameGamebryo::readIniValue(
+      iniFilePath, "Archive", "sResourceDataDirsFinal", "STRINGS\\");
+  bool alreadyEnabled =
+      (bInvalidateOlderFiles.toLong() == 1 && sResourceDataDirsFinal == "");
+
+  if (profile->invalidationActive(nullptr)) {
+    if (alreadyEnabled) {
+      return false;
+    }
+
+    // keep the values from before, unless they were already saved by an
+    // earlier activation
+    if (!QFile::exists(savedIni)) {
+      MOBase::WriteRegistryValue("Archive", "bInvalidateOlderFiles",
+                                 bInvalidateOlderFiles, savedIni);
+      MOBase::WriteRegistryValue("Archive", "sResourceDataDirsFinal",
+                                 sResourceDataDirsFinal, savedIni);
+    }
+
+    dirty = true;
+    if (!MOBase::WriteRegistryValue("Archive", "bInvalidateOlderFiles", "1",
+                                    iniFilePath) ||
+        !MOBase::WriteRegistryValue("Archive", "sResourceDataDirsFinal", "",
+                                    iniFilePath)) {
+      qWarning("failed to override data directory in \"%s\"",
+               qUtf8Printable(m_IniFileName));
+    }
+  } else if (QFile::exists(savedIni)) {
+    // only revert what was written here and is still there
+    if (alreadyEnabled) {
+      QString savedInvalidate = GameGamebryo::readIniValue(
+          savedIni, "Archive", "bInvalidateOlderFiles", "0");
+      QString savedDataDirs = GameGamebryo::readIniValue(
+          savedIni, "Archive", "sResourceDataDirsFinal", "STRINGS\\");
+
+      dirty = true;
+      if (!MOBase::WriteRegistryValue("Archive", "bInvalidateOlderFiles",
+                                      savedInvalidate, iniFilePath) ||
+          !MOBase::WriteRegistryValue("Archive", "sResourceDataDirsFinal",
+                                      savedDataDirs, iniFilePath)) {
+        qWarning("failed to restore data directory in \"%s\"",
+                 qUtf8Printable(m_IniFileName));
+      }
+    }
+
+    QFile::remove(savedIni);
+  }
+
+  return dirty;
+}
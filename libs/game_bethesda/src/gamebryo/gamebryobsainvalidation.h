@@ -22,6 +22,14 @@ public:
   virtual void activate(MOBase::IProfile* profile) override;
   virtual bool prepareProfile(MOBase::IProfile* profile) override;
 
+protected:
+  // invalidation for games without a dummy archive, which load loose files
+  // over archives when bInvalidateOlderFiles is set and sResourceDataDirsFinal
+  // is empty; the previous values are kept in invalidation.ini in the profile
+  // and only put back when turning it off if these are still the ones written
+  // here, so values set by the user are left alone
+  bool prepareResourceDataDirs(MOBase::IProfile* profile);
+
 private:
   virtual QString invalidationBSAName() const = 0;
   virtual unsigned long
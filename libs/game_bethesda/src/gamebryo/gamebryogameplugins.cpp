@@ -51,6 +51,7 @@ void GamebryoGamePlugins::readPluginLists(MOBase::IPluginList* pluginList)
     QStringList loadOrder = readLoadOrderList(pluginList, loadOrderPath);
     pluginList->setLoadOrder(loadOrder);
     readPluginList(pluginList);
+    warnAboutUnorderedPlugins(pluginList, loadOrder);
   } else {
     // If the plugins is new but not loadorder, we must reparse the load order from the
     // plugin files
@@ -142,6 +143,27 @@ void GamebryoGamePlugins::writeList(const IPluginList* pluginList,
   }
 }
 
+void GamebryoGamePlugins::warnAboutUnorderedPlugins(const IPluginList* pluginList,
+                                                    const QStringList& loadOrder) const
+{
+  // happens when plugins.txt was changed by another tool; the plugins keep the
+  // position they're given here the next time the lists are written
+  QStringList unordered;
+  for (const QString& pluginName : pluginList->pluginNames()) {
+    if (pluginList->state(pluginName) == IPluginList::STATE_ACTIVE &&
+        !loadOrder.contains(pluginName, Qt::CaseInsensitive)) {
+      unordered.append(pluginName);
+    }
+  }
+
+  if (!unordered.isEmpty()) {
+    qWarning("%lld active plugins are not in loadorder.txt and were moved to the end "
+             "of the load order: %s",
+             static_cast<long long>(unordered.size()),
+             qUtf8Printable(unordered.join(", ")));
+  }
+}
+
 QStringList GamebryoGamePlugins::readLoadOrderList(MOBase::IPluginList* pluginList,
                                                    const QString& filePath)
 {
@@ -26,6 +26,11 @@ protected:
                                         const QString& filePath);
   virtual QStringList readPluginList(MOBase::IPluginList* pluginList);
 
+  // logs the active plugins that loadOrder doesn't list, these end up after all
+  // the listed ones
+  void warnAboutUnorderedPlugins(const MOBase::IPluginList* pluginList,
+                                 const QStringList& loadOrder) const;
+
 protected:
   MOBase::IOrganizer* m_Organizer;
   QDateTime m_LastRead;
@@ -142,10 +142,11 @@ void GamebryoSaveGame::FileWrapper::readQDataStream(QDataStream& data, void* buf
   int read    = data.readRawData(static_cast<char*>(buff), static_cast<int>(length));
   bool result = true;
   if (read != length && m_CompressionType == 1) {
-    bool result = readNextChunk();
+    // readNextChunk() replaces m_Data, `data` is gone after this
+    result = readNextChunk();
     if (result) {
-      read += data.readRawData(static_cast<char*>(buff) + read,
-                               static_cast<int>(length - read));
+      read += m_Data->readRawData(static_cast<char*>(buff) + read,
+                                  static_cast<int>(length - read));
     }
   }
   if (read != length || !result) {
@@ -166,9 +167,10 @@ void GamebryoSaveGame::FileWrapper::skipQDataStream(QDataStream& data,
   int skip    = data.skipRawData(static_cast<int>(length));
   bool result = true;
   if (skip != length && m_CompressionType == 1) {
+    // readNextChunk() replaces m_Data, `data` is gone after this
     result = readNextChunk();
     if (result) {
-      skip += data.skipRawData(static_cast<int>(length - skip));
+      skip += m_Data->skipRawData(static_cast<int>(length - skip));
     }
   }
   if (skip != length || !result) {
@@ -327,10 +329,16 @@ bool GamebryoSaveGame::FileWrapper::openCompressedData(int bytesToIgnore)
     read(compressed.data(), compressedSize);
     QByteArray decompressed;
     decompressed.resize(uncompressedSize);
-    LZ4_decompress_safe_partial(compressed.data(), decompressed.data(), compressedSize,
-                                uncompressedSize, uncompressedSize);
+    const int decompressedSize =
+        LZ4_decompress_safe_partial(compressed.data(), decompressed.data(),
+                                    compressedSize, uncompressedSize, uncompressedSize);
     compressed.clear();
 
+    if (decompressedSize < 0) {
+      throw std::runtime_error("failed to decompress save game data");
+    }
+    decompressed.resize(decompressedSize);
+
     m_Data = new QDataStream(decompressed);
     skipQDataStream(*m_Data, bytesToIgnore);
 
@@ -349,8 +357,6 @@ bool GamebryoSaveGame::FileWrapper::readNextChunk()
   std::unique_ptr<char[]> inBuffer  = std::make_unique<char[]>(CHUNK);
   std::unique_ptr<char[]> outBuffer = std::make_unique<char[]>(CHUNK);
   QByteArray finalData;
-  m_Data->device()->close();
-  delete m_Data;
   z_stream stream{};
   try {
     stream.zalloc   = Z_NULL;
@@ -381,6 +387,7 @@ bool GamebryoSaveGame::FileWrapper::readNextChunk()
         zlibRet          = inflate(&stream, Z_NO_FLUSH);
         if ((zlibRet != Z_OK) && (zlibRet != Z_STREAM_END) &&
             (zlibRet != Z_BUF_ERROR)) {
+          (void)inflateEnd(&stream);
           return false;
         }
         have = CHUNK - stream.avail_out;
@@ -396,6 +403,11 @@ bool GamebryoSaveGame::FileWrapper::readNextChunk()
     inflateEnd(&stream);
     return false;
   }
+
+  // only replace the current chunk once the next one was read, so a failure
+  // leaves m_Data usable for closeCompressedData()
+  m_Data->device()->close();
+  delete m_Data;
   m_Data = new QDataStream(finalData);
   return true;
 }
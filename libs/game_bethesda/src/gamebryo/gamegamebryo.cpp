@@ -227,6 +227,28 @@ QFileInfo GameGamebryo::findInGameFolder(const QString& relativePath) const
   return QFileInfo(m_GamePath + "/" + relativePath);
 }
 
+#ifdef HAS_NAK_FFI
+// whether the install of `game` at `path` has all master plugins of the game
+// in its data directory, called `dataDirName`
+//
+static bool hasMasterPlugins(const NakGame& game, const QString& path,
+                             const QString& dataDirName)
+{
+  const QString dataDir = QDir(path).absoluteFilePath(dataDirName);
+
+  char* missing = nak_missing_master_plugins(game.app_id, dataDir.toUtf8().constData());
+  if (missing == nullptr) {
+    return true;
+  }
+
+  MOBase::log::warn("ignoring {} at '{}', its data directory lacks {}",
+                    QString::fromUtf8(game.name), path,
+                    QString::fromUtf8(missing).split('\n').join(", "));
+  nak_string_free(missing);
+  return false;
+}
+#endif
+
 QString GameGamebryo::identifyGamePath() const
 {
 #ifdef HAS_NAK_FFI
@@ -266,11 +288,32 @@ QString GameGamebryo::identifyGamePath() const
         detectedName.contains(fullName, Qt::CaseInsensitive) ||
         detectedName.contains(shortName, Qt::CaseInsensitive) ||
         tokensMatch(detectedName, fullName) || tokensMatch(detectedName, shortName)) {
-      if (looksValid(QDir(detectedPath))) {
+      if (looksValid(QDir(detectedPath)) &&
+          hasMasterPlugins(game, detectedPath, dataDirectory().dirName())) {
         return detectedPath;
       }
     }
   }
+
+  // no launcher knows the game, but its own installer may have registered it
+  // in the configured prefix, or in the game's Steam prefix if there's none
+  const QByteArray appId = steamAPPId().toUtf8();
+  const QByteArray prefix =
+      QSettings().value("fluorine/prefix_path").toString().trimmed().toUtf8();
+
+  char* registryPath =
+      nak_resolve_game_dir_from_registry(appId.constData(), prefix.constData());
+  if (registryPath != nullptr) {
+    const QString path = QString::fromUtf8(registryPath);
+    nak_string_free(registryPath);
+
+    if (looksValid(QDir(path))) {
+      MOBase::log::debug("found {} at '{}' through the prefix registry", gameName(),
+                         path);
+      return path;
+    }
+  }
+
   return {};
 #elif defined(_WIN32)
   QString path = "Software\\Bethesda Softworks\\" + gameShortName();
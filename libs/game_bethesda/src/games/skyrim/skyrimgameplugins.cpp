@@ -42,11 +42,13 @@ void SkyrimGamePlugins::readPluginLists(MOBase::IPluginList* pluginList)
     QStringList loadOrder = readLoadOrderList(pluginList, loadOrderPath);
     pluginList->setLoadOrder(loadOrder);
     readPluginList(pluginList);
+    warnAboutUnorderedPlugins(pluginList, loadOrder);
   } else {
     // read both files if they are both new or both older than the last read
     QStringList loadOrder = readLoadOrderList(pluginList, loadOrderPath);
     pluginList->setLoadOrder(loadOrder);
     readPluginList(pluginList);
+    warnAboutUnorderedPlugins(pluginList, loadOrder);
   }
 
   m_LastRead = QDateTime::currentDateTime();
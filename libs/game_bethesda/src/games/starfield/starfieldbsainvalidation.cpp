@@ -34,35 +34,5 @@ unsigned long StarfieldBSAInvalidation::bsaVersion() const
 
 bool StarfieldBSAInvalidation::prepareProfile(MOBase::IProfile* profile)
 {
-  bool dirty          = false;
-  QString basePath    = profile->localSettingsEnabled()
-                            ? profile->absolutePath()
-                            : m_Game->documentsDirectory().absolutePath();
-  QString iniFilePath = basePath + "/" + m_IniFileName;
-
-  if (profile->invalidationActive(nullptr)) {
-    // write bInvalidateOlderFiles = 1, if needed
-    QString bInvalidateOlderFiles = GameGamebryo::readIniValue(
-        iniFilePath, "Archive", "bInvalidateOlderFiles", "0");
-    if (bInvalidateOlderFiles.toLong() != 1) {
-      dirty = true;
-      if (!MOBase::WriteRegistryValue("Archive", "bInvalidateOlderFiles", "1",
-                                      iniFilePath)) {
-        qWarning("failed to override data directory in \"%s\"",
-                 qUtf8Printable(m_IniFileName));
-      }
-    }
-    QString sResourceDataDirsFinal = GameGamebryo::readIniValue(
-        iniFilePath, "Archive", "sResourceDataDirsFinal", "STRINGS\\");
-    if (sResourceDataDirsFinal != "") {
-      dirty = true;
-      if (!MOBase::WriteRegistryValue("Archive", "sResourceDataDirsFinal", "",
-                                      iniFilePath)) {
-        qWarning("failed to override data directory in \"%s\"",
-                 qUtf8Printable(m_IniFileName));
-      }
-    }
-  }
-
-  return dirty;
+  return prepareResourceDataDirs(profile);
 }
@@ -23,6 +23,7 @@ along with Mod Organizer.  If not, see <http://www.gnu.org/licenses/>.
 #include <uibase/textviewer.h>
 
 #include "baincomplexinstallerdialog.h"
+#include "installerbain.h"
 
 using namespace MOBase;
 
@@ -49,7 +50,10 @@ BainComplexInstallerDialog::BainComplexInstallerDialog(
     QListWidgetItem* item = new QListWidgetItem(name, ui->optionsList);
     item->setFlags(item->flags() | Qt::ItemIsUserCheckable);
 
-    if (name.mid(0, 2) == "00" || defaultOptions.contains(name, Qt::CaseInsensitive)) {
+    // core packages are numbered 0, usually written "00 Core"
+    const bool core = InstallerBAIN::ordinal(name) == 0;
+
+    if (core || defaultOptions.contains(name, Qt::CaseInsensitive)) {
       item->setCheckState(Qt::Checked);
     } else {
       item->setCheckState(Qt::Unchecked);
@@ -74,26 +78,26 @@ QString BainComplexInstallerDialog::getName() const
 
 QStringList BainComplexInstallerDialog::updateTree(std::shared_ptr<IFileTree>& tree)
 {
-  // Retrieve the list of selected names:
-  std::set<QString, FileNameComparator> selectedNames;
+  // Create a new empty tree and merge all the selected folder in it, in the order
+  // they are listed so later options override earlier ones:
+  QStringList selectedNames;
+  auto newTree = tree->createOrphanTree();
   for (int i = 0; i < ui->optionsList->count(); ++i) {
     QListWidgetItem* item = ui->optionsList->item(i);
-    if (item->checkState() == Qt::Checked) {
-      selectedNames.insert(item->text());
+    if (item->checkState() != Qt::Checked) {
+      continue;
     }
-  }
 
-  // Create a new empty tree and merge all the selected folder in it:
-  auto newTree = tree->createOrphanTree();
-  for (auto& entry : *tree) {
-    if (entry->isDir() && selectedNames.count(entry->name()) > 0) {
+    auto entry = tree->find(item->text(), FileTreeEntry::DIRECTORY);
+    if (entry != nullptr) {
       newTree->merge(entry->astree());
+      selectedNames.append(item->text());
     }
   }
 
   tree = newTree;
 
-  return QStringList(selectedNames.begin(), selectedNames.end());
+  return selectedNames;
 }
 
 void BainComplexInstallerDialog::on_okBtn_clicked()
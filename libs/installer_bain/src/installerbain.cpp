@@ -27,6 +27,8 @@ along with Mod Organizer.  If not, see <http://www.gnu.org/licenses/>.
 
 #include <QDir>
 #include <QMessageBox>
+
+#include <algorithm>
 #include <QtPlugin>
 
 #include "baincomplexinstallerdialog.h"
@@ -150,9 +152,34 @@ InstallerBAIN::findSubpackages(std::shared_ptr<const MOBase::IFileTree> tree,
     *invalidFolders = ninvalids;
   }
 
+  // later subpackages override earlier ones, and "10 Extras" comes after
+  // "2 Options" even though it sorts before it by name; unnumbered ones go last
+  std::stable_sort(subpackages.begin(), subpackages.end(),
+                   [](const auto& lhs, const auto& rhs) {
+                     const int left  = ordinal(lhs->name());
+                     const int right = ordinal(rhs->name());
+                     return left != -1 && (right == -1 || left < right);
+                   });
+
   return subpackages;
 }
 
+int InstallerBAIN::ordinal(const QString& name)
+{
+  qsizetype digits = 0;
+  while (digits < name.size() && name[digits].isDigit()) {
+    ++digits;
+  }
+
+  if (digits == 0) {
+    return -1;
+  }
+
+  bool ok     = false;
+  const int n = name.left(digits).toInt(&ok);
+  return ok ? n : -1;
+}
+
 bool InstallerBAIN::isArchiveSupported(std::shared_ptr<const IFileTree> tree) const
 {
   auto checker = m_MOInfo->gameFeatures()->gameFeature<ModDataChecker>();
@@ -67,6 +67,16 @@ protected:
   findSubpackages(std::shared_ptr<const MOBase::IFileTree> tree,
                   std::size_t* invalidFolders = nullptr) const;
 
+  /**
+   * @brief Retrieve the number a subpackage name starts with, such as 10 for
+   * "10 Optional Textures".
+   *
+   * @param name Name of the subpackage.
+   *
+   * @return the number, or -1 if the name does not start with one.
+   */
+  static int ordinal(const QString& name);
+
 private:
   const MOBase::IOrganizer* m_MOInfo;
 
@@ -401,10 +401,13 @@ std::pair<bool, QString>
 FomodInstallerDialog::testCondition(int maxIndex, const SubCondition* condition) const
 {
   ConditionOperator op = condition->m_Operator;
+  QStringList failures;
   for (const Condition* cond : condition->m_Conditions) {
     std::pair<bool, QString> conditionMatches = cond->test(maxIndex, this);
-    if (!conditionMatches.first)
+    if (!conditionMatches.first) {
       qWarning() << conditionMatches.second;
+      failures.append(conditionMatches.second);
+    }
     if (op == OP_OR && conditionMatches.first) {
       return std::make_pair<bool, QString>(
           true, tr("At least one condition was successful in an 'OR' clause!"));
@@ -417,9 +420,14 @@ FomodInstallerDialog::testCondition(int maxIndex, const SubCondition* condition)
   if (op == OP_AND)
     return std::make_pair<bool, QString>(
         true, tr("All conditions were successful in an 'AND' clause!"));
-  else
+  else if (failures.isEmpty())
     return std::make_pair<bool, QString>(
         false, tr("No conditions were successful in an 'OR' clause!"));
+  else
+    // the user only needs one of these, so all of them are listed
+    return std::make_pair<bool, QString>(
+        false, tr("None of these requirements is met, one is needed:\n%1")
+                   .arg(failures.join("\n")));
 }
 
 QString FomodInstallerDialog::toString(IPluginList::PluginStates state)
@@ -619,7 +627,11 @@ FomodInstallerDialog::updateTree(std::shared_ptr<IFileTree>& tree)
   }
 
   if (!failures.empty()) {
-    if (!displayMissingFilesDialog(failures)) {
+    if (m_Unattended) {
+      log::warn("{} files or folders referenced by the installer were not found in "
+                "the archive, the mod may not work properly",
+                failures.size());
+    } else if (!displayMissingFilesDialog(failures)) {
       return IPluginInstaller::RESULT_CANCELED;
     }
   }
@@ -1283,8 +1295,9 @@ void FomodInstallerDialog::readModuleConfiguration(XmlReader& reader)
       readCompositeDependency(reader, condition);
       std::pair<bool, QString> result = testCondition(-1, &condition);
       if (!result.first) {
-        // TODO Better messages?
-        throw Exception(result.second);
+        throw Exception(tr("This mod can't be installed, it has unmet "
+                           "requirements.\n\n%1")
+                            .arg(result.second));
       }
     } else if (elString == "requiredInstallFiles") {
       readFileList(reader, m_RequiredFiles);
@@ -1568,7 +1581,10 @@ void FomodInstallerDialog::displayCurrentPage()
         PluginTypeInfo const info =
             control->property("plugintypeinfo").value<PluginTypeInfo>();
         PluginType const type = getPluginDependencyType(page, info);
+        // the type may have changed since the page was last shown, patterns
+        // depend on the flags set on earlier pages
         control->setEnabled(true);
+        control->setIcon(QIcon());
         switch (type) {
         case TYPE_REQUIRED: {
           if ((groupType == TYPE_SELECTEXACTLYONE) ||
@@ -1655,6 +1671,19 @@ void FomodInstallerDialog::on_nextBtn_clicked()
   }
 }
 
+void FomodInstallerDialog::selectRecommendedOptions()
+{
+  m_Unattended = true;
+
+  // the first visible page was set up by initData(), the following ones are
+  // evaluated with the flags of the pages before them as when clicking next
+  while (ui->stepsStack->currentIndex() < ui->stepsStack->count() - 1 &&
+         nextPage()) {
+    displayCurrentPage();
+    activateCurrentPage();
+  }
+}
+
 void FomodInstallerDialog::on_prevBtn_clicked()
 {
   // FIXME this will go wrong if the first page isn't visible
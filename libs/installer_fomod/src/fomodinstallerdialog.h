@@ -253,6 +253,16 @@ public:
 
   void transformToSmallInstall();
 
+  /**
+   * @brief Walk through all the visible pages without user interaction, keeping the
+   * options preselected on each: required and recommended plugins, or the first
+   * usable one in groups where one has to be selected.
+   *
+   * Files or folders missing from the archive are only logged by updateTree()
+   * afterwards instead of asking whether to install anyway.
+   **/
+  void selectRecommendedOptions();
+
 protected:
   virtual bool eventFilter(QObject* object, QEvent* event);
 
@@ -456,6 +466,9 @@ private:
   QString m_FomodDirName;
   bool m_Manual;
 
+  // set by selectRecommendedOptions(), nothing is asked of the user
+  bool m_Unattended = false;
+
   FileDescriptorList m_RequiredFiles;
   std::vector<ConditionalInstall> m_ConditionalInstalls;
   std::vector<bool> m_PageVisible;
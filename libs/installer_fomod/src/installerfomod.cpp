@@ -1,6 +1,11 @@
 #include "installerfomod.h"
 
+#include <algorithm>
+
+#include <QFile>
 #include <QImageReader>
+#include <QRegularExpression>
+#include <QStringDecoder>
 #include <QStringList>
 #include <QtPlugin>
 
@@ -60,6 +65,11 @@ bool InstallerFomod::checkDisabledMods() const
   return m_MOInfo->pluginSetting(name(), "see_disabled_mods").toBool();
 }
 
+bool InstallerFomod::useRecommendedOptions() const
+{
+  return m_MOInfo->pluginSetting(name(), "use_recommended_options").toBool();
+}
+
 QList<PluginSetting> InstallerFomod::settings() const
 {
   QList<PluginSetting> result;
@@ -71,6 +81,10 @@ QList<PluginSetting> InstallerFomod::settings() const
   result.push_back(PluginSetting("see_disabled_mods",
                                  "treat disabled mods as inactive rather than missing",
                                  QVariant(false)));
+  result.push_back(PluginSetting("use_recommended_options",
+                                 "install with the required and recommended options "
+                                 "without showing the installer, for scripted installs",
+                                 QVariant(false)));
   return result;
 }
 
@@ -145,16 +159,60 @@ InstallerFomod::buildFomodTree(std::shared_ptr<const IFileTree> tree) const
 
   for (auto entry : *fomodTree) {
     if (entry->isFile() &&
-        (entry->compare("info.xml") == 0 || entry->compare("ModuleConfig.xml") == 0)) {
+        (entry->compare("info.xml") == 0 || entry->compare("ModuleConfig.xml") == 0 ||
+         entry->compare("screenshot.png") == 0)) {
       entries.push_back(entry);
     }
   }
 
-  appendImageFiles(entries, tree);
-
   return entries;
 }
 
+bool InstallerFomod::appendReferencedImages(
+    std::vector<std::shared_ptr<const FileTreeEntry>>& entries,
+    std::shared_ptr<const IFileTree> fomodTree, const QString& moduleConfig) const
+{
+  QFile file(moduleConfig);
+  if (!file.open(QIODevice::ReadOnly)) {
+    return false;
+  }
+
+  // the dialog parses the file properly later on, this only needs the path
+  // attributes, so a lenient decode is enough even for files with a broken header
+  const QByteArray data = file.readAll();
+  QStringDecoder decoder(
+      QStringConverter::encodingForData(data).value_or(QStringConverter::Utf8));
+  const QString text = decoder.decode(data);
+
+  static const QRegularExpression imageElement(
+      R"(<(?:image|moduleImage)\b[^>]*?\bpath\s*=\s*(["'])(.*?)\1)",
+      QRegularExpression::CaseInsensitiveOption |
+          QRegularExpression::DotMatchesEverythingOption);
+
+  // image paths are relative to the directory containing the fomod directory
+  auto base = fomodTree->parent();
+
+  auto matches = imageElement.globalMatch(text);
+  while (matches.hasNext()) {
+    const QString path = matches.next().captured(2).trimmed();
+    if (path.isEmpty()) {
+      continue;
+    }
+
+    auto entry = base->find(path, FileTreeEntry::FILE);
+    if (entry == nullptr) {
+      log::debug("fomod image '{}' not found in the archive", path);
+      continue;
+    }
+
+    if (std::find(entries.begin(), entries.end(), entry) == entries.end()) {
+      entries.push_back(entry);
+    }
+  }
+
+  return true;
+}
+
 IPluginList::PluginStates InstallerFomod::fileState(const QString& fileName) const
 {
   QString ext = QFileInfo(fileName).suffix().toLower();
@@ -213,9 +271,26 @@ InstallerFomod::install(GuessedValue<QString>& modName,
 {
   auto installerFiles = buildFomodTree(tree);
   if (manager()->extractFiles(installerFiles).size() == installerFiles.size()) {
-    try {
-      std::shared_ptr<const IFileTree> fomodTree = findFomodDirectory(tree);
+    std::shared_ptr<const IFileTree> fomodTree = findFomodDirectory(tree);
+
+    // only extract the pictures the installer shows instead of every one in the
+    // archive, which can be a lot for mods shipping loose textures; none are
+    // needed when the installer isn't shown at all
+    std::vector<std::shared_ptr<const FileTreeEntry>> images;
+    if (!useRecommendedOptions()) {
+      auto moduleConfig = fomodTree->find("ModuleConfig.xml", FileTreeEntry::FILE);
+      if (moduleConfig == nullptr ||
+          !appendReferencedImages(images, fomodTree,
+                                  QDir::tempPath() + "/" + moduleConfig->path())) {
+        appendImageFiles(images, tree);
+      }
+    }
+
+    if (!images.empty() && manager()->extractFiles(images).size() != images.size()) {
+      return IPluginInstaller::RESULT_CANCELED;
+    }
 
+    try {
       QString fomodPath = fomodTree->parent()->path();
       QString fomodDirName = fomodTree->name();
       FomodInstallerDialog dialog(
@@ -232,6 +307,12 @@ InstallerFomod::install(GuessedValue<QString>& modName,
       m_InstallerUsed = true;
       m_Url           = dialog.getURL();
 
+      if (useRecommendedOptions()) {
+        dialog.selectRecommendedOptions();
+        modName.update(dialog.getName(), GUESS_META);
+        return dialog.updateTree(tree);
+      }
+
       if (!dialog.hasOptions()) {
         dialog.transformToSmallInstall();
       }
@@ -62,8 +62,8 @@ private:
 
   /**
    * @brief Build a list of entries that should be extracted sincce the FOMOD installer
-   * may require access to (currently the .xml files in the FOMOD directory and the
-   * pictures in the archive).
+   * may require access to (currently the .xml files and the screenshot in the FOMOD
+   * directory).
    *
    * @param tree Base tree of the archive.
    *
@@ -82,6 +82,25 @@ private:
   appendImageFiles(std::vector<std::shared_ptr<const MOBase::FileTreeEntry>>& entries,
                    std::shared_ptr<const MOBase::IFileTree> tree) const;
 
+  /**
+   * @brief Add the images referenced by the plugins and the header of an extracted
+   * ModuleConfig.xml to the given vector, so the installer can show them without
+   * extracting every picture in the archive.
+   *
+   * Paths are looked up case-insensitively relative to the directory containing the
+   * FOMOD directory, references to missing files are ignored.
+   *
+   * @param entries Vector of entries to add the images.
+   * @param fomodTree The FOMOD directory of the archive.
+   * @param moduleConfig Path to the extracted ModuleConfig.xml.
+   *
+   * @return false if the ModuleConfig.xml could not be read.
+   */
+  bool appendReferencedImages(
+      std::vector<std::shared_ptr<const MOBase::FileTreeEntry>>& entries,
+      std::shared_ptr<const MOBase::IFileTree> fomodTree,
+      const QString& moduleConfig) const;
+
   MOBase::IPluginList::PluginStates fileState(const QString& fileName) const;
 
 private:
@@ -92,6 +111,7 @@ private:
 
   bool allowAnyFile() const;
   bool checkDisabledMods() const;
+  bool useRecommendedOptions() const;
 
   bool m_InstallerUsed;
   QString m_Url;
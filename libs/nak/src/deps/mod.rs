@@ -2,6 +2,7 @@
 //!
 //! Uses winetricks for all Windows dependency installation.
 
+pub mod state;
 pub mod tools;
 
 use std::error::Error;
@@ -14,6 +15,8 @@ use crate::logging::{log_error, log_install};
 use crate::runtime_wrap;
 use crate::steam::SteamProton;
 
+pub use state::{InstallState, INSTALL_STATE_FILE};
+
 // Re-export tools
 pub use tools::{check_command_available, ensure_cabextract, ensure_winetricks, get_winetricks_path};
 
@@ -0,0 +1,90 @@
+//! Record of the dependency steps already completed in a prefix
+//!
+//! Stored next to the prefix's `drive_c` so re-running the installer can skip
+//! the slow winetricks and .NET steps that already succeeded.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+/// Name of the state file inside the prefix
+pub const INSTALL_STATE_FILE: &str = ".fluorine_deps.json";
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InstallState {
+    /// Steps that finished successfully (winetricks verbs and installer names)
+    #[serde(default)]
+    completed: BTreeSet<String>,
+}
+
+impl InstallState {
+    /// Path of the state file for a prefix
+    pub fn path(prefix_root: &Path) -> PathBuf {
+        prefix_root.join(INSTALL_STATE_FILE)
+    }
+
+    /// Load the state of a prefix; a missing or unreadable file means nothing
+    /// was recorded yet, so every step runs again.
+    pub fn load(prefix_root: &Path) -> Self {
+        fs::read_to_string(Self::path(prefix_root))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    /// Write the state to the prefix
+    pub fn save(&self, prefix_root: &Path) -> io::Result<()> {
+        let json = serde_json::to_string_pretty(self).map_err(io::Error::other)?;
+        fs::write(Self::path(prefix_root), json)
+    }
+
+    pub fn is_completed(&self, step: &str) -> bool {
+        self.completed.contains(step)
+    }
+
+    pub fn mark_completed(&mut self, step: &str) {
+        self.completed.insert(step.to_string());
+    }
+
+    /// The steps of `steps` that haven't completed yet, in their original order
+    pub fn pending<'a>(&self, steps: &[&'a str]) -> Vec<&'a str> {
+        steps
+            .iter()
+            .copied()
+            .filter(|step| !self.is_completed(step))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TempDir;
+
+    #[test]
+    fn state_round_trips_through_the_prefix() {
+        let prefix = TempDir::new("deps_state_round_trip");
+        assert_eq!(InstallState::load(&prefix), InstallState::default());
+
+        let mut state = InstallState::default();
+        state.mark_completed("vcrun2022");
+        state.mark_completed("dotnet-sdk-9");
+        state.save(&prefix).unwrap();
+
+        let loaded = InstallState::load(&prefix);
+        assert!(loaded.is_completed("vcrun2022"));
+        assert!(loaded.is_completed("dotnet-sdk-9"));
+        assert!(!loaded.is_completed("d3dx9"));
+        assert_eq!(loaded.pending(&["vcrun2022", "d3dx9", "xact"]), vec!["d3dx9", "xact"]);
+    }
+
+    #[test]
+    fn corrupt_state_is_ignored() {
+        let prefix = TempDir::new("deps_state_corrupt");
+        fs::write(InstallState::path(&prefix), "{ not json").unwrap();
+        assert_eq!(InstallState::load(&prefix), InstallState::default());
+    }
+}
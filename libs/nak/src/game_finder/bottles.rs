@@ -103,6 +103,7 @@ fn scan_bottle(bottle_path: &Path) -> Vec<Game> {
                 app_id: format!("bottles-{}", known_game.steam_app_id),
                 install_path,
                 prefix_path: Some(bottle_path.to_path_buf()),
+                wine: None,
                 launcher: Launcher::Bottles,
                 my_games_folder: known_game.my_games_folder.map(String::from),
                 appdata_local_folder: known_game.appdata_local_folder.map(String::from),
@@ -9,7 +9,7 @@ use std::path::{Path, PathBuf};
 use serde::Deserialize;
 
 use super::known_games::find_by_gog_id;
-use super::{Game, HeroicStore, Launcher};
+use super::{Game, HeroicStore, Launcher, WineRuntime};
 use crate::logging::{log_info, log_warning};
 
 /// Possible Heroic configuration paths
@@ -104,7 +104,9 @@ fn detect_gog_games(heroic_path: &Path) -> Vec<Game> {
         }
 
         // Get the game config for Wine prefix info
-        let prefix_path = get_heroic_game_prefix(heroic_path, &gog_game.app_name);
+        let config = read_heroic_game_config(heroic_path, &gog_game.app_name);
+        let prefix_path = config.as_ref().and_then(HeroicGameConfig::prefix);
+        let wine = config.as_ref().and_then(HeroicGameConfig::wine);
 
         // Look up known game info
         let known_game = find_by_gog_id(&gog_game.app_name);
@@ -118,6 +120,7 @@ fn detect_gog_games(heroic_path: &Path) -> Vec<Game> {
             app_id: gog_game.app_name,
             install_path,
             prefix_path,
+            wine,
             launcher: Launcher::Heroic {
                 store: HeroicStore::GOG,
             },
@@ -208,14 +211,17 @@ fn detect_epic_games(heroic_path: &Path) -> Vec<Game> {
                     .unwrap_or(app_name)
                     .to_string();
 
-                // Get Wine prefix
-                let prefix_path = get_heroic_game_prefix(heroic_path, app_name);
+                // Get Wine prefix and version
+                let config = read_heroic_game_config(heroic_path, app_name);
+                let prefix_path = config.as_ref().and_then(HeroicGameConfig::prefix);
+                let wine = config.as_ref().and_then(HeroicGameConfig::wine);
 
                 games.push(Game {
                     name,
                     app_id: app_name.clone(),
                     install_path,
                     prefix_path,
+                    wine,
                     launcher: Launcher::Heroic {
                         store: HeroicStore::Epic,
                     },
@@ -253,25 +259,135 @@ struct WineVersion {
     wine_type: Option<String>,
 }
 
-/// Get the Wine prefix for a Heroic game from its config file
-fn get_heroic_game_prefix(heroic_path: &Path, app_name: &str) -> Option<PathBuf> {
-    let config_path = heroic_path.join(format!("GamesConfig/{}.json", app_name));
+impl HeroicGameConfig {
+    /// The prefix the game runs in.
+    ///
+    /// For Proton, Heroic's `winePrefix` is the compatdata-style directory and
+    /// the wine prefix itself is its `pfx` subdirectory.
+    fn prefix(&self) -> Option<PathBuf> {
+        let prefix_path = PathBuf::from(self.wine_prefix.as_deref()?);
+
+        let is_proton = self
+            .wine_version
+            .as_ref()
+            .and_then(|v| v.wine_type.as_deref())
+            == Some("proton");
+        let pfx = prefix_path.join("pfx");
+        let prefix_path = if is_proton && pfx.is_dir() {
+            pfx
+        } else {
+            prefix_path
+        };
 
-    let content = fs::read_to_string(&config_path).ok()?;
+        if prefix_path.exists() {
+            Some(prefix_path)
+        } else {
+            None
+        }
+    }
+
+    /// The Wine or Proton build the game is launched with
+    fn wine(&self) -> Option<WineRuntime> {
+        let version = self.wine_version.as_ref()?;
+        let bin = version.bin.as_deref().filter(|b| !b.is_empty())?;
+
+        Some(WineRuntime {
+            name: version.name.clone().unwrap_or_default(),
+            bin: PathBuf::from(bin),
+            kind: version.wine_type.clone().unwrap_or_else(|| "wine".to_string()),
+        })
+    }
+}
+
+/// Parse a Heroic GamesConfig/<app>.json file
+fn parse_heroic_game_config(content: &str, app_name: &str) -> Option<HeroicGameConfig> {
+    let config: serde_json::Value = serde_json::from_str(content).ok()?;
 
     // The config can be either a direct object or wrapped in the app_name key
-    let config: serde_json::Value = serde_json::from_str(&content).ok()?;
+    let entry = config
+        .get(app_name)
+        .filter(|v| v.is_object())
+        .unwrap_or(&config);
 
-    // Try to get winePrefix from the object or from a nested object
-    let wine_prefix = config
-        .get("winePrefix")
-        .or_else(|| config.get(app_name).and_then(|v| v.get("winePrefix")))
-        .and_then(|v| v.as_str())?;
+    HeroicGameConfig::deserialize(entry).ok()
+}
 
-    let prefix_path = PathBuf::from(wine_prefix);
-    if prefix_path.exists() {
-        Some(prefix_path)
-    } else {
-        None
+/// Read the Wine settings for a Heroic game from its config file
+fn read_heroic_game_config(heroic_path: &Path, app_name: &str) -> Option<HeroicGameConfig> {
+    let config_path = heroic_path.join(format!("GamesConfig/{}.json", app_name));
+    let content = fs::read_to_string(&config_path).ok()?;
+    parse_heroic_game_config(&content, app_name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TempDir;
+
+    #[test]
+    fn test_parse_wine_game_config() {
+        let prefix = TempDir::new("heroic_wine");
+        let content = format!(
+            r#"{{
+                "1207658924": {{
+                    "autoInstallDxvk": true,
+                    "winePrefix": "{}",
+                    "wineVersion": {{
+                        "bin": "/usr/bin/wine",
+                        "name": "Wine - Wine Default",
+                        "type": "wine"
+                    }}
+                }},
+                "version": "v0",
+                "explicit": true
+            }}"#,
+            prefix.display()
+        );
+
+        let config = parse_heroic_game_config(&content, "1207658924").unwrap();
+        assert_eq!(config.prefix(), Some(prefix.to_path_buf()));
+        assert_eq!(
+            config.wine(),
+            Some(WineRuntime {
+                name: "Wine - Wine Default".to_string(),
+                bin: PathBuf::from("/usr/bin/wine"),
+                kind: "wine".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_proton_game_config() {
+        let prefix = TempDir::new("heroic_proton");
+        fs::create_dir_all(prefix.join("pfx")).unwrap();
+        let content = format!(
+            r#"{{
+                "winePrefix": "{}",
+                "wineVersion": {{
+                    "bin": "/home/deck/.config/heroic/tools/proton/GE-Proton9-5/proton",
+                    "name": "Proton - GE-Proton9-5",
+                    "type": "proton"
+                }}
+            }}"#,
+            prefix.display()
+        );
+
+        let config = parse_heroic_game_config(&content, "Fortnite").unwrap();
+        assert_eq!(config.prefix(), Some(prefix.join("pfx")));
+
+        let wine = config.wine().unwrap();
+        assert_eq!(wine.kind, "proton");
+        assert_eq!(wine.name, "Proton - GE-Proton9-5");
+    }
+
+    #[test]
+    fn test_parse_game_config_without_wine() {
+        let config =
+            parse_heroic_game_config(r#"{"winePrefix": "/nonexistent/heroic/prefix"}"#, "game")
+                .unwrap();
+        assert_eq!(config.prefix(), None);
+        assert_eq!(config.wine(), None);
+
+        assert!(parse_heroic_game_config("not json", "game").is_none());
     }
 }
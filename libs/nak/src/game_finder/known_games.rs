@@ -5,6 +5,24 @@
 //! - My Games folder name (Documents/My Games/*)
 //! - AppData/Local folder name
 //! - Registry path for game detection
+//! - Base master plugins
+//! - Launchers that start the actual game executable and exit
+
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::steam::{is_ge_proton, proton_name_meets_minimum};
+
+/// A launcher executable that starts the game and exits right away, so
+/// waiting on it says nothing about the game's lifetime
+#[derive(Debug, Clone, Copy)]
+pub struct GameLauncher {
+    /// Launcher executable, relative to the game directory
+    pub launcher: &'static str,
+    /// Executables the launcher may start, relative to the game directory
+    pub games: &'static [&'static str],
+}
 
 /// Configuration for a known game
 #[derive(Debug, Clone)]
@@ -27,6 +45,98 @@ pub struct KnownGame {
     pub registry_value: &'static str,
     /// Expected folder name in steamapps/common/
     pub steam_folder: &'static str,
+    /// Base master plugins every install ships with, in load order
+    /// (empty for games without plugins)
+    pub master_plugins: &'static [&'static str],
+    /// Launchers that re-launch the actual game executable
+    pub launchers: &'static [GameLauncher],
+    /// Oldest Proton the game runs with, as a version like "9-10" or "8.0";
+    /// see `steam::proton_meets_minimum`
+    pub min_proton: Option<&'static str>,
+    /// Proton the game works best with, as a version or a full name like
+    /// "GE-Proton9-20" when it relies on a fix only GE builds carry
+    pub recommended_proton: Option<&'static str>,
+}
+
+impl KnownGame {
+    /// Master plugins of this game that are not present in `data_dir`
+    ///
+    /// The lookup is case-insensitive since the files may have been renamed
+    /// by the user or another tool.
+    pub fn missing_master_plugins(&self, data_dir: &Path) -> Vec<&'static str> {
+        if self.master_plugins.is_empty() {
+            return Vec::new();
+        }
+
+        let present: HashSet<String> = fs::read_dir(data_dir)
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .map(|e| e.file_name().to_string_lossy().to_lowercase())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        self.master_plugins
+            .iter()
+            .copied()
+            .filter(|m| !present.contains(&m.to_lowercase()))
+            .collect()
+    }
+
+    /// Why the Proton named `proton_name` is a poor fit for this game, if it is
+    ///
+    /// Older than `min_proton` is reported first. A GE recommendation is only
+    /// met by GE builds, any other one by a Proton of at least that version.
+    pub fn proton_warning(&self, proton_name: &str) -> Option<String> {
+        if let Some(min) = self.min_proton {
+            if !proton_name_meets_minimum(proton_name, min) {
+                return Some(format!(
+                    "{} needs Proton {} or newer, but {} is selected",
+                    self.name, min, proton_name
+                ));
+            }
+        }
+
+        let recommended = self.recommended_proton?;
+        let missing_ge = is_ge_proton(recommended) && !is_ge_proton(proton_name);
+        if missing_ge || !proton_name_meets_minimum(proton_name, recommended) {
+            return Some(format!(
+                "{} is recommended for {}, but {} is selected",
+                recommended, self.name, proton_name
+            ));
+        }
+
+        None
+    }
+
+    /// Executables started by `exe` if it is one of this game's launchers
+    ///
+    /// `exe` is the full path of the launcher; the returned paths are in the
+    /// same game directory. The comparison is case-insensitive.
+    pub fn resolve_launcher(&self, exe: &Path) -> Option<Vec<PathBuf>> {
+        self.launchers.iter().find_map(|l| {
+            let root = strip_suffix_ignore_case(exe, Path::new(l.launcher))?;
+            Some(l.games.iter().map(|g| root.join(g)).collect())
+        })
+    }
+}
+
+/// `path` without the trailing components of `suffix`, compared
+/// case-insensitively
+fn strip_suffix_ignore_case<'a>(path: &'a Path, suffix: &Path) -> Option<&'a Path> {
+    let mut root = path;
+    for component in suffix.components().rev() {
+        let name = root.file_name()?;
+        if !name
+            .to_string_lossy()
+            .eq_ignore_ascii_case(&component.as_os_str().to_string_lossy())
+        {
+            return None;
+        }
+        root = root.parent()?;
+    }
+    Some(root)
 }
 
 /// All known games that NaK supports
@@ -42,6 +152,17 @@ pub const KNOWN_GAMES: &[KnownGame] = &[
         registry_path: r"Software\SureAI\Enderal",
         registry_value: "Install_Path",
         steam_folder: "Enderal",
+        master_plugins: &[
+            "Skyrim.esm",
+            "Update.esm",
+            "Enderal - Forgotten Stories.esm",
+        ],
+        launchers: &[GameLauncher {
+            launcher: "Enderal Launcher.exe",
+            games: &["TESV.exe"],
+        }],
+        min_proton: None,
+        recommended_proton: None,
     },
     KnownGame {
         name: "Enderal Special Edition",
@@ -53,6 +174,20 @@ pub const KNOWN_GAMES: &[KnownGame] = &[
         registry_path: r"Software\SureAI\Enderal SE",
         registry_value: "installed path",
         steam_folder: "Enderal Special Edition",
+        master_plugins: &[
+            "Skyrim.esm",
+            "Update.esm",
+            "Dawnguard.esm",
+            "HearthFires.esm",
+            "Dragonborn.esm",
+            "Enderal - Forgotten Stories.esm",
+        ],
+        launchers: &[GameLauncher {
+            launcher: "Enderal Launcher.exe",
+            games: &["SkyrimSE.exe"],
+        }],
+        min_proton: None,
+        recommended_proton: None,
     },
     KnownGame {
         name: "Fallout 3",
@@ -64,6 +199,13 @@ pub const KNOWN_GAMES: &[KnownGame] = &[
         registry_path: r"Software\Bethesda Softworks\Fallout3",
         registry_value: "Installed Path",
         steam_folder: "Fallout 3",
+        master_plugins: &["Fallout3.esm"],
+        launchers: &[GameLauncher {
+            launcher: "Fallout3Launcher.exe",
+            games: &["Fallout3.exe"],
+        }],
+        min_proton: None,
+        recommended_proton: None,
     },
     KnownGame {
         name: "Fallout 4",
@@ -75,6 +217,13 @@ pub const KNOWN_GAMES: &[KnownGame] = &[
         registry_path: r"Software\Bethesda Softworks\Fallout4",
         registry_value: "Installed Path",
         steam_folder: "Fallout 4",
+        master_plugins: &["Fallout4.esm"],
+        launchers: &[GameLauncher {
+            launcher: "Fallout4Launcher.exe",
+            games: &["Fallout4.exe"],
+        }],
+        min_proton: None,
+        recommended_proton: None,
     },
     KnownGame {
         name: "Fallout 4 VR",
@@ -86,6 +235,10 @@ pub const KNOWN_GAMES: &[KnownGame] = &[
         registry_path: r"Software\Bethesda Softworks\Fallout 4 VR",
         registry_value: "Installed Path",
         steam_folder: "Fallout 4 VR",
+        master_plugins: &["Fallout4.esm", "Fallout4_VR.esm"],
+        launchers: &[],
+        min_proton: None,
+        recommended_proton: None,
     },
     KnownGame {
         name: "Fallout New Vegas",
@@ -97,6 +250,13 @@ pub const KNOWN_GAMES: &[KnownGame] = &[
         registry_path: r"Software\Bethesda Softworks\FalloutNV",
         registry_value: "Installed Path",
         steam_folder: "Fallout New Vegas",
+        master_plugins: &["FalloutNV.esm"],
+        launchers: &[GameLauncher {
+            launcher: "FalloutNVLauncher.exe",
+            games: &["FalloutNV.exe"],
+        }],
+        min_proton: None,
+        recommended_proton: None,
     },
     KnownGame {
         name: "Morrowind",
@@ -108,6 +268,13 @@ pub const KNOWN_GAMES: &[KnownGame] = &[
         registry_path: r"Software\Bethesda Softworks\Morrowind",
         registry_value: "Installed Path",
         steam_folder: "Morrowind",
+        master_plugins: &["Morrowind.esm"],
+        launchers: &[GameLauncher {
+            launcher: "Morrowind Launcher.exe",
+            games: &["Morrowind.exe"],
+        }],
+        min_proton: None,
+        recommended_proton: None,
     },
     KnownGame {
         name: "Oblivion",
@@ -119,6 +286,13 @@ pub const KNOWN_GAMES: &[KnownGame] = &[
         registry_path: r"Software\Bethesda Softworks\Oblivion",
         registry_value: "Installed Path",
         steam_folder: "Oblivion",
+        master_plugins: &["Oblivion.esm"],
+        launchers: &[GameLauncher {
+            launcher: "OblivionLauncher.exe",
+            games: &["Oblivion.exe"],
+        }],
+        min_proton: None,
+        recommended_proton: None,
     },
     KnownGame {
         name: "Skyrim",
@@ -130,6 +304,13 @@ pub const KNOWN_GAMES: &[KnownGame] = &[
         registry_path: r"Software\Bethesda Softworks\Skyrim",
         registry_value: "Installed Path",
         steam_folder: "Skyrim",
+        master_plugins: &["Skyrim.esm", "Update.esm"],
+        launchers: &[GameLauncher {
+            launcher: "SkyrimLauncher.exe",
+            games: &["TESV.exe"],
+        }],
+        min_proton: None,
+        recommended_proton: None,
     },
     KnownGame {
         name: "Skyrim Special Edition",
@@ -141,6 +322,19 @@ pub const KNOWN_GAMES: &[KnownGame] = &[
         registry_path: r"Software\Bethesda Softworks\Skyrim Special Edition",
         registry_value: "Installed Path",
         steam_folder: "Skyrim Special Edition",
+        master_plugins: &[
+            "Skyrim.esm",
+            "Update.esm",
+            "Dawnguard.esm",
+            "HearthFires.esm",
+            "Dragonborn.esm",
+        ],
+        launchers: &[GameLauncher {
+            launcher: "SkyrimSELauncher.exe",
+            games: &["SkyrimSE.exe"],
+        }],
+        min_proton: None,
+        recommended_proton: None,
     },
     KnownGame {
         name: "Skyrim VR",
@@ -152,6 +346,17 @@ pub const KNOWN_GAMES: &[KnownGame] = &[
         registry_path: r"Software\Bethesda Softworks\Skyrim VR",
         registry_value: "Installed Path",
         steam_folder: "Skyrim VR",
+        master_plugins: &[
+            "Skyrim.esm",
+            "Update.esm",
+            "Dawnguard.esm",
+            "HearthFires.esm",
+            "Dragonborn.esm",
+            "SkyrimVR.esm",
+        ],
+        launchers: &[],
+        min_proton: None,
+        recommended_proton: None,
     },
     KnownGame {
         name: "Starfield",
@@ -163,6 +368,10 @@ pub const KNOWN_GAMES: &[KnownGame] = &[
         registry_path: r"Software\Bethesda Softworks\Starfield",
         registry_value: "Installed Path",
         steam_folder: "Starfield",
+        master_plugins: &["Starfield.esm", "BlueprintShips-Starfield.esm"],
+        launchers: &[],
+        min_proton: Some("8.0-4"),
+        recommended_proton: Some("9.0"),
     },
     // CD Projekt RED Games
     KnownGame {
@@ -175,6 +384,10 @@ pub const KNOWN_GAMES: &[KnownGame] = &[
         registry_path: r"Software\CD Projekt Red\The Witcher 3",
         registry_value: "InstallFolder",
         steam_folder: "The Witcher 3 Wild Hunt",
+        master_plugins: &[],
+        launchers: &[],
+        min_proton: None,
+        recommended_proton: None,
     },
     KnownGame {
         name: "Cyberpunk 2077",
@@ -186,6 +399,13 @@ pub const KNOWN_GAMES: &[KnownGame] = &[
         registry_path: r"Software\CD Projekt Red\Cyberpunk 2077",
         registry_value: "InstallFolder",
         steam_folder: "Cyberpunk 2077",
+        master_plugins: &[],
+        launchers: &[GameLauncher {
+            launcher: "REDprelauncher.exe",
+            games: &["bin/x64/Cyberpunk2077.exe"],
+        }],
+        min_proton: Some("5.13-4"),
+        recommended_proton: Some("9.0"),
     },
     // Other popular moddable games
     KnownGame {
@@ -198,6 +418,13 @@ pub const KNOWN_GAMES: &[KnownGame] = &[
         registry_path: r"Software\Larian Studios\Baldur's Gate 3",
         registry_value: "InstallDir",
         steam_folder: "Baldurs Gate 3",
+        master_plugins: &[],
+        launchers: &[GameLauncher {
+            launcher: "Launcher/LariLauncher.exe",
+            games: &["bin/bg3.exe", "bin/bg3_dx11.exe"],
+        }],
+        min_proton: Some("8.0-3"),
+        recommended_proton: None,
     },
 ];
 
@@ -222,6 +449,11 @@ pub fn find_by_name(name: &str) -> Option<&'static KnownGame> {
         .find(|g| g.name.to_lowercase() == name_lower)
 }
 
+/// Executables started by `exe` if it is a launcher of any known game
+pub fn resolve_launcher(exe: &Path) -> Option<Vec<PathBuf>> {
+    KNOWN_GAMES.iter().find_map(|g| g.resolve_launcher(exe))
+}
+
 /// Normalize Steam App IDs that have equivalent variants.
 fn normalize_steam_id(app_id: &str) -> &str {
     match app_id {
@@ -234,7 +466,10 @@ fn normalize_steam_id(app_id: &str) -> &str {
 
 #[cfg(test)]
 mod tests {
-    use super::find_by_steam_id;
+    use super::{find_by_name, find_by_steam_id, resolve_launcher, KnownGame};
+    use std::path::{Path, PathBuf};
+
+    use crate::test_utils::TempDir;
 
     #[test]
     fn fallout_3_goty_alias_maps_to_fallout_3() {
@@ -242,4 +477,145 @@ mod tests {
         assert_eq!(game.name, "Fallout 3");
         assert_eq!(game.steam_app_id, "22300");
     }
+
+    #[test]
+    fn skyrim_se_master_plugins() {
+        let game = find_by_steam_id("489830").unwrap();
+        assert_eq!(
+            game.master_plugins,
+            &[
+                "Skyrim.esm",
+                "Update.esm",
+                "Dawnguard.esm",
+                "HearthFires.esm",
+                "Dragonborn.esm",
+            ]
+        );
+    }
+
+    fn game_with_protons(
+        min: Option<&'static str>,
+        recommended: Option<&'static str>,
+    ) -> KnownGame {
+        KnownGame {
+            min_proton: min,
+            recommended_proton: recommended,
+            ..find_by_name("Fallout 4").unwrap().clone()
+        }
+    }
+
+    #[test]
+    fn starfield_needs_proton_8_0_4() {
+        let game = find_by_steam_id("1716740").unwrap();
+        let warning = game.proton_warning("Proton 7.0").unwrap();
+        assert!(warning.contains("needs Proton 8.0-4"), "{}", warning);
+
+        // the Valve name doesn't say which 8.0 build it is
+        let warning = game.proton_warning("Proton 8.0").unwrap();
+        assert!(warning.contains("is recommended"), "{}", warning);
+        assert_eq!(game.proton_warning("Proton 10.0"), None);
+    }
+
+    #[test]
+    fn proton_below_minimum_is_reported_first() {
+        let game = game_with_protons(Some("9.0"), Some("GE-Proton9-20"));
+        let warning = game.proton_warning("Proton 8.0").unwrap();
+        assert!(warning.contains("needs Proton 9.0 or newer"), "{}", warning);
+
+        let warning = game.proton_warning("Proton 9.0").unwrap();
+        assert!(warning.contains("is recommended"), "{}", warning);
+        assert_eq!(game.proton_warning("GE-Proton9-20"), None);
+    }
+
+    #[test]
+    fn proton_recommendations() {
+        let ge = game_with_protons(None, Some("GE-Proton9-20"));
+        assert_eq!(ge.proton_warning("GE-Proton10-4"), None);
+        assert!(ge.proton_warning("GE-Proton9-5").is_some());
+        // a newer official build doesn't carry the GE fixes
+        assert!(ge.proton_warning("Proton 10.0").is_some());
+
+        let version = game_with_protons(None, Some("9.0"));
+        assert_eq!(version.proton_warning("Proton 9.0 (Beta)"), None);
+        assert_eq!(version.proton_warning("GE-Proton9-1"), None);
+        assert!(version.proton_warning("Proton 8.0").is_some());
+
+        let unrestricted = game_with_protons(None, None);
+        assert_eq!(unrestricted.proton_warning("Proton 7.0"), None);
+    }
+
+    #[test]
+    fn fallout_4_master_plugins() {
+        let game = find_by_steam_id("377160").unwrap();
+        assert_eq!(game.master_plugins, &["Fallout4.esm"]);
+    }
+
+    #[test]
+    fn data_dir_validation_uses_master_plugins() {
+        let dir = TempDir::new("known_games_masters");
+
+        let game = find_by_name("Fallout 4 VR").unwrap();
+        assert_eq!(
+            game.missing_master_plugins(&dir),
+            vec!["Fallout4.esm", "Fallout4_VR.esm"]
+        );
+
+        std::fs::write(dir.join("fallout4.esm"), b"").unwrap();
+        assert_eq!(game.missing_master_plugins(&dir), vec!["Fallout4_VR.esm"]);
+
+        std::fs::write(dir.join("Fallout4_VR.esm"), b"").unwrap();
+        assert!(game.missing_master_plugins(&dir).is_empty());
+
+        // games without plugins have nothing to check
+        let cyberpunk = find_by_name("Cyberpunk 2077").unwrap();
+        assert!(cyberpunk.missing_master_plugins(&dir).is_empty());
+    }
+
+    #[test]
+    fn launcher_resolves_to_game_executable() {
+        let game = find_by_name("Skyrim Special Edition").unwrap();
+        let root = Path::new("/games/Skyrim Special Edition");
+
+        assert_eq!(
+            game.resolve_launcher(&root.join("SkyrimSELauncher.exe")),
+            Some(vec![root.join("SkyrimSE.exe")])
+        );
+
+        // launchers are matched case-insensitively
+        assert_eq!(
+            game.resolve_launcher(&root.join("skyrimselauncher.EXE")),
+            Some(vec![root.join("SkyrimSE.exe")])
+        );
+
+        // the game itself and script extenders are not launchers
+        assert_eq!(game.resolve_launcher(&root.join("SkyrimSE.exe")), None);
+        assert_eq!(game.resolve_launcher(&root.join("skse64_loader.exe")), None);
+    }
+
+    #[test]
+    fn launcher_in_subdirectory_resolves_from_game_root() {
+        let root = PathBuf::from("/games/Baldurs Gate 3");
+        let launcher = root.join("Launcher").join("LariLauncher.exe");
+
+        assert_eq!(
+            resolve_launcher(&launcher),
+            Some(vec![
+                root.join("bin/bg3.exe"),
+                root.join("bin/bg3_dx11.exe")
+            ])
+        );
+
+        // the launcher name alone is not enough, it has to be in its folder
+        assert_eq!(resolve_launcher(&root.join("LariLauncher.exe")), None);
+    }
+
+    #[test]
+    fn games_without_launchers_resolve_nothing() {
+        let game = find_by_name("Skyrim VR").unwrap();
+        assert!(game.launchers.is_empty());
+        assert_eq!(
+            game.resolve_launcher(Path::new("/games/SkyrimVR.exe")),
+            None
+        );
+    }
 }
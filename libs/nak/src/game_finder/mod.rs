@@ -20,8 +20,11 @@ use std::path::PathBuf;
 
 pub use bottles::detect_bottles_games;
 pub use heroic::detect_heroic_games;
-pub use known_games::{find_by_gog_id, find_by_name, find_by_steam_id, KnownGame, KNOWN_GAMES};
-pub use registry::{read_registry_value, wine_path_to_linux};
+pub use known_games::{
+    find_by_gog_id, find_by_name, find_by_steam_id, resolve_launcher, GameLauncher, KnownGame,
+    KNOWN_GAMES,
+};
+pub use registry::{read_registry_value, resolve_game_dir_from_registry, wine_path_to_linux};
 pub use steam::{detect_steam_games, find_game_install_path, find_game_prefix_path, get_known_game};
 
 // ============================================================================
@@ -55,6 +58,17 @@ pub enum HeroicStore {
     Epic,
 }
 
+/// Wine or Proton build a launcher runs a game with
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WineRuntime {
+    /// Display name, e.g. "Proton - GE-Proton9-5"
+    pub name: String,
+    /// The wine or proton executable
+    pub bin: PathBuf,
+    /// Kind of build as the launcher reports it ("wine", "proton", ...)
+    pub kind: String,
+}
+
 /// A detected game installation
 #[derive(Debug, Clone)]
 pub struct Game {
@@ -62,6 +76,8 @@ pub struct Game {
     pub app_id: String,
     pub install_path: PathBuf,
     pub prefix_path: Option<PathBuf>,
+    /// Wine build the launcher runs this game with, for launchers that keep one per game
+    pub wine: Option<WineRuntime>,
     pub launcher: Launcher,
     pub my_games_folder: Option<String>,
     pub appdata_local_folder: Option<String>,
@@ -5,6 +5,8 @@
 use std::fs;
 use std::path::{Path, PathBuf};
 
+use super::known_games::KnownGame;
+use super::steam::find_game_prefix_path;
 use crate::logging::log_warning;
 
 /// Read a registry value from a Wine prefix registry file
@@ -75,21 +77,21 @@ fn find_value_in_content(content: &str, key: &str, value_name: &str) -> Option<S
     for line in content.lines() {
         let trimmed = line.trim();
 
-        // Check for key header
-        if trimmed.starts_with('[') && trimmed.ends_with(']') {
-            in_target_key = trimmed.to_lowercase() == key.to_lowercase();
-            continue;
+        // Check for key header, Wine puts a timestamp after the closing bracket
+        // e.g. [Software\\Wine] 1700000000
+        if trimmed.starts_with('[') {
+            if let Some(end) = trimmed.find(']') {
+                in_target_key = trimmed[..=end].to_lowercase() == key.to_lowercase();
+                continue;
+            }
         }
 
         // If we're in the target key, look for the value
         if in_target_key {
-            // Empty line or new section means we've left the key
-            if trimmed.is_empty() {
+            // Skip empty lines and metadata like #time=...
+            if trimmed.is_empty() || trimmed.starts_with('#') {
                 continue;
             }
-            if trimmed.starts_with('[') {
-                break;
-            }
 
             // Parse value line: "ValueName"="value" or @="default value"
             if let Some((name, value)) = parse_reg_value_line(trimmed) {
@@ -138,27 +140,53 @@ fn parse_reg_value_line(line: &str) -> Option<(String, String)> {
 }
 
 /// Parse a quoted registry value, handling Wine's escape sequences
+///
+/// Wine writes characters outside of ASCII as `\x` followed by up to four hex
+/// digits of a UTF-16 code unit, so the value is decoded as UTF-16.
 fn parse_quoted_reg_value(s: &str) -> Option<String> {
     let s = s.trim();
     if !s.starts_with('"') {
         return None;
     }
 
-    let mut result = String::new();
-    let chars = s[1..].chars();
+    fn push(units: &mut Vec<u16>, c: char) {
+        units.extend_from_slice(c.encode_utf16(&mut [0; 2]));
+    }
+
+    let mut result: Vec<u16> = Vec::new();
+    let mut chars = s[1..].chars().peekable();
     let mut prev_was_backslash = false;
 
-    for c in chars {
+    while let Some(c) = chars.next() {
         if prev_was_backslash {
             match c {
-                'n' => result.push('\n'),
-                'r' => result.push('\r'),
-                't' => result.push('\t'),
-                '\\' => result.push('\\'),
-                '"' => result.push('"'),
+                'n' => push(&mut result, '\n'),
+                'r' => push(&mut result, '\r'),
+                't' => push(&mut result, '\t'),
+                '\\' => push(&mut result, '\\'),
+                '"' => push(&mut result, '"'),
+                'x' => {
+                    let mut unit = 0u16;
+                    let mut digits = 0;
+                    while digits < 4 {
+                        let Some(d) = chars.peek().and_then(|d| d.to_digit(16)) else {
+                            break;
+                        };
+                        unit = unit * 16 + d as u16;
+                        digits += 1;
+                        chars.next();
+                    }
+
+                    if digits == 0 {
+                        push(&mut result, '\\');
+                        push(&mut result, 'x');
+                    } else {
+                        result.push(unit);
+                    }
+                }
                 _ => {
-                    result.push('\\');
-                    result.push(c);
+                    push(&mut result, '\\');
+                    push(&mut result, c);
                 }
             }
             prev_was_backslash = false;
@@ -167,11 +195,11 @@ fn parse_quoted_reg_value(s: &str) -> Option<String> {
         } else if c == '"' {
             break; // End of string
         } else {
-            result.push(c);
+            push(&mut result, c);
         }
     }
 
-    Some(result)
+    Some(String::from_utf16_lossy(&result))
 }
 
 /// Convert a Wine path (Z:\path\to\file) to a Linux path
@@ -198,6 +226,63 @@ pub fn wine_path_to_linux(wine_path: &str) -> Option<PathBuf> {
     None
 }
 
+/// Convert a Wine path to a Linux path, resolving drive letters through the
+/// prefix's `dosdevices` links (C: falls back to `drive_c`)
+fn wine_path_in_prefix(prefix_path: &Path, wine_path: &str) -> Option<PathBuf> {
+    let path = wine_path.trim();
+    let bytes = path.as_bytes();
+    if bytes.len() < 2 || !bytes[0].is_ascii_alphabetic() || bytes[1] != b':' {
+        return None;
+    }
+
+    let drive = path[..1].to_ascii_lowercase();
+    let rest = path[2..].trim_start_matches('\\').replace('\\', "/");
+
+    if drive == "z" {
+        return wine_path_to_linux(path);
+    }
+
+    let device = prefix_path.join("dosdevices").join(format!("{}:", drive));
+    let root = if device.exists() {
+        device
+    } else if drive == "c" {
+        prefix_path.join("drive_c")
+    } else {
+        return None;
+    };
+
+    Some(root.join(rest))
+}
+
+/// Locate a known game's install directory from a Wine prefix's registry
+///
+/// Reads the game's `registry_path`/`registry_value` from `prefix_path`, or
+/// from the game's own Steam prefix when no prefix is given. Only returns a
+/// directory that exists, so stale entries from uninstalled games are ignored.
+pub fn resolve_game_dir_from_registry(
+    prefix_path: Option<&Path>,
+    game: &KnownGame,
+) -> Option<PathBuf> {
+    let prefix = match prefix_path {
+        Some(p) => p.to_path_buf(),
+        None => find_game_prefix_path(game.steam_app_id)?,
+    };
+
+    let value = read_registry_value(&prefix, game.registry_path, game.registry_value)?;
+    let dir = wine_path_in_prefix(&prefix, &value)?;
+
+    if dir.is_dir() {
+        Some(dir)
+    } else {
+        log_warning(&format!(
+            "Registry points {} to missing directory {}",
+            game.name,
+            dir.display()
+        ));
+        None
+    }
+}
+
 /// Check if a Wine prefix contains a specific game by registry key
 pub fn has_game_registry(
     prefix_path: &Path,
@@ -209,7 +294,9 @@ pub fn has_game_registry(
 
 #[cfg(test)]
 mod tests {
+    use super::super::known_games::find_by_name;
     use super::*;
+    use crate::test_utils::TempDir;
 
     #[test]
     fn test_parse_reg_value_line() {
@@ -224,4 +311,68 @@ mod tests {
         let linux = wine_path_to_linux(r"Z:\mnt\games\Skyrim").unwrap();
         assert_eq!(linux, PathBuf::from("/mnt/games/Skyrim"));
     }
+
+    #[test]
+    fn test_parse_non_ascii_reg_value() {
+        // how wine saves "Z:\Jeux\Élan Vital\Skyrim" in system.reg
+        let (_, value) =
+            parse_reg_value_line(r#""Installed Path"="Z:\\Jeux\\\x00c9lan Vital\\Skyrim""#)
+                .unwrap();
+        assert_eq!(value, r"Z:\Jeux\Élan Vital\Skyrim");
+
+        // characters outside the BMP are saved as surrogate pairs
+        let (_, value) = parse_reg_value_line(r#""Path"="Z:\\\xd83c\xdfae""#).unwrap();
+        assert_eq!(value, r"Z:\🎮");
+
+        // UTF-8 written by other tools is kept as is
+        let (_, value) = parse_reg_value_line(r#""Path"="Z:\\Jeux\\Élan""#).unwrap();
+        assert_eq!(value, r"Z:\Jeux\Élan");
+    }
+
+    fn sample_prefix(name: &str, install_path: &str) -> TempDir {
+        let root = TempDir::new(&format!("registry_{}", name));
+
+        let reg = format!(
+            "WINE REGISTRY Version 2\n;; All keys relative to \\\\Machine\n\n\
+             [Software\\\\Wow6432Node\\\\SureAI\\\\Enderal] 1700000000\n\
+             #time=1da1b2c3d4e5f60\n\
+             \"Install_Path\"=\"{}\"\n\n\
+             [Software\\\\Wine] 1700000000\n\"Version\"=\"win10\"\n",
+            install_path.replace('\\', "\\\\")
+        );
+        fs::write(root.join("system.reg"), reg).unwrap();
+
+        root
+    }
+
+    #[test]
+    fn test_resolve_game_dir_from_registry() {
+        let enderal = find_by_name("Enderal").unwrap();
+
+        // C: paths live inside the prefix
+        let c_prefix = sample_prefix("c_drive", r"C:\Games\Enderal");
+        let game_dir = c_prefix.join("drive_c/Games/Enderal");
+        fs::create_dir_all(&game_dir).unwrap();
+        assert_eq!(
+            resolve_game_dir_from_registry(Some(c_prefix.path()), enderal),
+            Some(game_dir)
+        );
+
+        // Z: maps to the Linux root
+        let outside = c_prefix.join("outside");
+        fs::create_dir_all(&outside).unwrap();
+        let wine_path = format!("Z:{}", outside.display()).replace('/', "\\");
+        let z_prefix = sample_prefix("z_drive", &wine_path);
+        assert_eq!(
+            resolve_game_dir_from_registry(Some(z_prefix.path()), enderal),
+            Some(outside)
+        );
+
+        // stale entries are ignored
+        let stale_prefix = sample_prefix("missing", r"C:\Games\Gone");
+        assert_eq!(
+            resolve_game_dir_from_registry(Some(stale_prefix.path()), enderal),
+            None
+        );
+    }
 }
@@ -179,6 +179,7 @@ fn parse_appmanifest(
         app_id: manifest.app_id,
         install_path,
         prefix_path,
+        wine: None,
         launcher: Launcher::Steam {
             is_flatpak: steam_info.is_flatpak,
             is_snap: steam_info.is_snap,
@@ -0,0 +1,183 @@
+//! Architecture of a prefix and of the programs run in it
+//!
+//! Wine records whether a prefix is win32 or win64 when it is created and it
+//! can't be changed afterwards. A win32 prefix can't start 64-bit programs,
+//! which only shows up as a vague "bad EXE format" error from Wine.
+
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom};
+use std::path::Path;
+
+/// Architecture of a prefix or a Windows executable
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Arch {
+    Win32,
+    Win64,
+}
+
+impl Arch {
+    pub fn bits(self) -> u32 {
+        match self {
+            Arch::Win32 => 32,
+            Arch::Win64 => 64,
+        }
+    }
+}
+
+/// Architecture of the prefix, from the `#arch=` line Wine writes near the top
+/// of `system.reg`
+///
+/// Returns `None` if the prefix has no `system.reg` yet or the line is missing.
+pub fn prefix_arch(prefix_root: &Path) -> Option<Arch> {
+    let file = File::open(prefix_root.join("system.reg")).ok()?;
+
+    // the line comes right after the header, the rest of the file can be large
+    for line in BufReader::new(file).lines().take(16) {
+        let line = line.ok()?;
+        match line.trim().strip_prefix("#arch=") {
+            Some("win32") => return Some(Arch::Win32),
+            Some("win64") => return Some(Arch::Win64),
+            Some(_) => return None,
+            None => {}
+        }
+    }
+
+    None
+}
+
+/// Architecture of a Windows executable, from the machine field of its PE
+/// header
+///
+/// Returns `None` for files that aren't PE executables, such as native Linux
+/// programs, and for machines other than x86 and x86-64.
+pub fn exe_arch(exe: &Path) -> Option<Arch> {
+    let mut file = File::open(exe).ok()?;
+
+    let mut dos = [0u8; 64];
+    file.read_exact(&mut dos).ok()?;
+    if &dos[..2] != b"MZ" {
+        return None;
+    }
+
+    let pe_offset = u32::from_le_bytes([dos[60], dos[61], dos[62], dos[63]]);
+    file.seek(SeekFrom::Start(u64::from(pe_offset))).ok()?;
+
+    let mut pe = [0u8; 6];
+    file.read_exact(&mut pe).ok()?;
+    if &pe[..4] != b"PE\0\0" {
+        return None;
+    }
+
+    match u16::from_le_bytes([pe[4], pe[5]]) {
+        0x014c => Some(Arch::Win32),
+        0x8664 => Some(Arch::Win64),
+        _ => None,
+    }
+}
+
+/// Describes why `exe` can't run in the prefix, or `None` if it can or either
+/// architecture is unknown
+///
+/// Only a 64-bit program in a win32 prefix is a problem, win64 prefixes run
+/// 32-bit programs as well.
+pub fn arch_mismatch(prefix_root: &Path, exe: &Path) -> Option<String> {
+    let prefix = prefix_arch(prefix_root)?;
+    let program = exe_arch(exe)?;
+
+    if prefix == Arch::Win32 && program == Arch::Win64 {
+        return Some(format!(
+            "'{}' is a {}-bit program but the prefix '{}' is {}-bit, it can't be started \
+             there; recreate the prefix as win64",
+            exe.display(),
+            program.bits(),
+            prefix_root.display(),
+            prefix.bits()
+        ));
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{arch_mismatch, exe_arch, prefix_arch, Arch};
+    use std::fs;
+    use std::path::PathBuf;
+
+    use crate::test_utils::TempDir;
+
+    fn prefix(name: &str, arch: Option<&str>) -> TempDir {
+        let root = TempDir::new(&format!("arch_{}", name));
+
+        let mut reg =
+            String::from("WINE REGISTRY Version 2\n;; All keys relative to \\\\Machine\n\n");
+        if let Some(arch) = arch {
+            reg.push_str(&format!("#arch={}\n", arch));
+        }
+        reg.push_str("\n[Software\\\\Wine] 1700000000\n\"Version\"=\"win10\"\n");
+        fs::write(root.join("system.reg"), reg).unwrap();
+
+        root
+    }
+
+    fn exe(dir: &std::path::Path, name: &str, machine: u16) -> PathBuf {
+        let mut bytes = vec![0u8; 0x80 + 6];
+        bytes[..2].copy_from_slice(b"MZ");
+        bytes[60..64].copy_from_slice(&0x80u32.to_le_bytes());
+        bytes[0x80..0x84].copy_from_slice(b"PE\0\0");
+        bytes[0x84..0x86].copy_from_slice(&machine.to_le_bytes());
+
+        let path = dir.join(name);
+        fs::write(&path, bytes).unwrap();
+        path
+    }
+
+    #[test]
+    fn reads_prefix_arch() {
+        assert_eq!(
+            prefix_arch(&prefix("win64", Some("win64"))),
+            Some(Arch::Win64)
+        );
+        assert_eq!(
+            prefix_arch(&prefix("win32", Some("win32"))),
+            Some(Arch::Win32)
+        );
+        assert_eq!(prefix_arch(&prefix("none", None)), None);
+        assert_eq!(
+            prefix_arch(&std::env::temp_dir().join("nak_arch_missing")),
+            None
+        );
+    }
+
+    #[test]
+    fn reads_exe_arch() {
+        let root = prefix("exe", None);
+
+        assert_eq!(
+            exe_arch(&exe(&root, "game32.exe", 0x014c)),
+            Some(Arch::Win32)
+        );
+        assert_eq!(
+            exe_arch(&exe(&root, "game64.exe", 0x8664)),
+            Some(Arch::Win64)
+        );
+        assert_eq!(exe_arch(&exe(&root, "arm.exe", 0xaa64)), None);
+
+        fs::write(root.join("script.sh"), "#!/bin/sh\n").unwrap();
+        assert_eq!(exe_arch(&root.join("script.sh")), None);
+    }
+
+    #[test]
+    fn only_64_bit_programs_in_win32_prefixes_mismatch() {
+        let win32 = prefix("mismatch32", Some("win32"));
+        let win64 = prefix("mismatch64", Some("win64"));
+
+        let game64 = exe(&win32, "game64.exe", 0x8664);
+        let game32 = exe(&win32, "game32.exe", 0x014c);
+
+        assert!(arch_mismatch(&win32, &game64).is_some());
+        assert!(arch_mismatch(&win32, &game32).is_none());
+        assert!(arch_mismatch(&win64, &game64).is_none());
+        assert!(arch_mismatch(&win64, &game32).is_none());
+    }
+}
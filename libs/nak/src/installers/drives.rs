@@ -0,0 +1,232 @@
+//! Wine drive mappings of a prefix
+//!
+//! Programs running in a prefix only see host paths through the drive letters
+//! in `dosdevices/`. A path that no drive covers (for example because the `z:`
+//! link was removed) can't be opened by a tool, even if Wine was given the
+//! Linux path.
+
+use std::error::Error;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+use crate::logging::log_info;
+
+/// Windows path under which `path` is reachable in the prefix
+///
+/// Uses the drive whose target is the longest prefix of `path`, so a path under
+/// `drive_c` comes out as `C:\...` rather than `Z:\...`. Returns `None` if no
+/// drive covers `path`, the prefix has no `dosdevices/`, or a component isn't
+/// valid UTF-8, which is how Wine decodes host paths.
+pub fn windows_path(prefix_root: &Path, path: &Path) -> Option<String> {
+    let path = normalize(path);
+
+    let mut best: Option<(char, PathBuf)> = None;
+    for (letter, target) in drive_targets(prefix_root) {
+        if !path.starts_with(&target) {
+            continue;
+        }
+
+        let longer = best
+            .as_ref()
+            .map(|(_, t)| target.components().count() > t.components().count())
+            .unwrap_or(true);
+        if longer {
+            best = Some((letter, target));
+        }
+    }
+
+    let (letter, target) = best?;
+    let rest = path
+        .strip_prefix(&target)
+        .ok()?
+        .components()
+        .map(|c| c.as_os_str().to_str())
+        .collect::<Option<Vec<_>>>()?;
+
+    Some(format!("{}:\\{}", letter, rest.join("\\")))
+}
+
+/// Like [`windows_path`], but maps `z:` to `/` first if no drive covers `path`
+///
+/// Fails if `z:` already exists and points somewhere that doesn't cover
+/// `path`, since replacing it could break other programs in the prefix.
+pub fn ensure_windows_path(prefix_root: &Path, path: &Path) -> Result<String, Box<dyn Error>> {
+    if let Some(p) = windows_path(prefix_root, path) {
+        return Ok(p);
+    }
+
+    let dosdevices = prefix_root.join("dosdevices");
+    if !dosdevices.is_dir() {
+        return Err("dosdevices directory not found - is this a valid Wine prefix?".into());
+    }
+
+    let z = dosdevices.join("z:");
+    if let Ok(target) = fs::read_link(&z) {
+        return Err(format!(
+            "{} is not reachable from the prefix, drive Z: points to {}",
+            path.display(),
+            target.display()
+        )
+        .into());
+    }
+
+    std::os::unix::fs::symlink("/", &z)?;
+    log_info(&format!(
+        "Mapped drive Z: to / in {} so {} is reachable",
+        prefix_root.display(),
+        path.display()
+    ));
+
+    windows_path(prefix_root, path).ok_or_else(|| {
+        format!(
+            "{} is still not reachable from the prefix, or its name is not valid UTF-8",
+            path.display()
+        )
+        .into()
+    })
+}
+
+/// Drive letters of the prefix with the absolute host path they point to
+fn drive_targets(prefix_root: &Path) -> Vec<(char, PathBuf)> {
+    let dosdevices = prefix_root.join("dosdevices");
+    let Ok(entries) = fs::read_dir(&dosdevices) else {
+        return Vec::new();
+    };
+
+    entries
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_lowercase();
+            let mut chars = name.chars();
+            let letter = chars.next().filter(|c| c.is_ascii_alphabetic())?;
+
+            // "c::" and the like are raw devices, not drives
+            if chars.as_str() != ":" {
+                return None;
+            }
+
+            let target = fs::read_link(entry.path()).ok()?;
+            Some((
+                letter.to_ascii_uppercase(),
+                normalize(&dosdevices.join(target)),
+            ))
+        })
+        .collect()
+}
+
+/// Lexically resolves `.` and `..` without touching the filesystem, the path
+/// may be a mount point that's not there yet
+fn normalize(path: &Path) -> PathBuf {
+    let mut out = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                out.pop();
+            }
+            other => out.push(other),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ensure_windows_path, windows_path};
+    use std::ffi::OsStr;
+    use std::fs;
+    use std::os::unix::ffi::OsStrExt;
+    use std::os::unix::fs::symlink;
+    use std::path::Path;
+
+    use crate::test_utils::TempDir;
+
+    fn prefix(name: &str) -> TempDir {
+        let root = TempDir::new(&format!("drives_{}", name));
+        fs::create_dir_all(root.join("dosdevices")).unwrap();
+        fs::create_dir_all(root.join("drive_c")).unwrap();
+        symlink("../drive_c", root.join("dosdevices/c:")).unwrap();
+        root
+    }
+
+    #[test]
+    fn mount_point_is_translated_through_z_drive() {
+        let root = prefix("z");
+        symlink("/", root.join("dosdevices/z:")).unwrap();
+
+        assert_eq!(
+            windows_path(&root, Path::new("/games/Skyrim Special Edition/Data")),
+            Some(r"Z:\games\Skyrim Special Edition\Data".to_string())
+        );
+
+        // drive_c is covered by both, the more specific drive wins
+        assert_eq!(
+            windows_path(&root, &root.join("drive_c/Program Files")),
+            Some(r"C:\Program Files".to_string())
+        );
+    }
+
+    #[test]
+    fn other_drives_and_raw_devices() {
+        let root = prefix("d");
+        symlink("/mnt/games", root.join("dosdevices/d:")).unwrap();
+        symlink("/dev/sda1", root.join("dosdevices/d::")).unwrap();
+
+        assert_eq!(
+            windows_path(&root, Path::new("/mnt/games/Fallout 4/Data/")),
+            Some(r"D:\Fallout 4\Data".to_string())
+        );
+        assert_eq!(windows_path(&root, Path::new("/dev/sda1/x")), None);
+        assert_eq!(windows_path(&root, Path::new("/home/user/Data")), None);
+    }
+
+    #[test]
+    fn non_ascii_paths() {
+        let root = prefix("utf8");
+        symlink("/", root.join("dosdevices/z:")).unwrap();
+        symlink("/mnt/Bibliothèque", root.join("dosdevices/e:")).unwrap();
+
+        assert_eq!(
+            windows_path(
+                &root,
+                Path::new("/home/zoë/Jeux/Skyrim Édition Spéciale/Data")
+            ),
+            Some(r"Z:\home\zoë\Jeux\Skyrim Édition Spéciale\Data".to_string())
+        );
+        assert_eq!(
+            windows_path(&root, Path::new("/mnt/Bibliothèque/ゲーム/Data")),
+            Some(r"E:\ゲーム\Data".to_string())
+        );
+
+        // Latin-1 "é", Wine can't decode it and would open the wrong file
+        let latin1 = Path::new(OsStr::from_bytes(b"/games/Skyrim \xC9dition/Data"));
+        assert_eq!(windows_path(&root, latin1), None);
+    }
+
+    #[test]
+    fn missing_z_drive_is_created() {
+        let root = prefix("ensure");
+
+        assert_eq!(windows_path(&root, Path::new("/games/Data")), None);
+        assert_eq!(
+            ensure_windows_path(&root, Path::new("/games/Data")).unwrap(),
+            r"Z:\games\Data"
+        );
+        assert_eq!(
+            fs::read_link(root.join("dosdevices/z:")).unwrap(),
+            Path::new("/")
+        );
+    }
+
+    #[test]
+    fn existing_z_drive_is_not_replaced() {
+        let root = prefix("keep");
+        symlink("/mnt/other", root.join("dosdevices/z:")).unwrap();
+
+        assert!(ensure_windows_path(&root, Path::new("/games/Data")).is_err());
+        assert_eq!(
+            fs::read_link(root.join("dosdevices/z:")).unwrap(),
+            Path::new("/mnt/other")
+        );
+    }
+}
@@ -2,13 +2,16 @@
 //!
 //! Stripped for Fluorine: no common.rs, mo2.rs, plugin.rs, compatdata_scanner.rs.
 
+pub mod arch;
+pub mod drives;
 pub mod symlinks;
 
 mod prefix_setup;
 
 pub use prefix_setup::{
     apply_dpi, apply_registry_for_game_path, auto_apply_game_registries, cleanup_prefix_drives,
-    install_all_dependencies, kill_wineserver, known_game_names, launch_dpi_test_app, DPI_PRESETS,
+    dependency_components, install_all_dependencies, install_selected_dependencies, kill_wineserver,
+    known_game_names, launch_dpi_test_app, DPI_PRESETS,
 };
 
 use std::error::Error;
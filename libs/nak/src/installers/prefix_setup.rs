@@ -15,7 +15,7 @@ use std::process::Child;
 
 use super::{apply_wine_registry_settings, TaskContext};
 use crate::config::AppConfig;
-use crate::deps::{install_standard_deps_cancellable, STANDARD_VERBS};
+use crate::deps::{run_winetricks_cancellable, InstallState, STANDARD_VERBS};
 use crate::game_finder::{detect_all_games, known_games, Game, Launcher};
 use crate::logging::{log_install, log_warning};
 use crate::runtime_wrap;
@@ -38,8 +38,12 @@ const ALLOWED_DRIVE_LETTERS: &[&str] = &["c:", "z:"];
 ///
 /// Order: proton init → winetricks → custom dotnet → game detection → registry → win11 → dotnet fixes
 ///
+/// The winetricks verbs, .NET installers and Windows 11 mode are recorded in
+/// the prefix's [`InstallState`] once they succeed and skipped on later runs.
+///
 /// # Arguments
 /// * `app_id` - Steam AppID (used for registry operations)
+/// * `force` - run every step again, ignoring the recorded state
 pub fn install_all_dependencies(
     prefix_root: &Path,
     install_proton: &SteamProton,
@@ -47,12 +51,18 @@ pub fn install_all_dependencies(
     start_progress: f32,
     end_progress: f32,
     app_id: u32,
+    force: bool,
 ) -> Result<(), Box<dyn Error>> {
     fs::create_dir_all(AppConfig::get_tmp_path())?;
 
+    let mut state = if force {
+        InstallState::default()
+    } else {
+        InstallState::load(prefix_root)
+    };
+
     // Progress distribution
     let init_end = start_progress + (end_progress - start_progress) * 0.10;
-    let winetricks_end = start_progress + (end_progress - start_progress) * 0.50;
     let dotnet_end = start_progress + (end_progress - start_progress) * 0.65;
     let games_end = start_progress + (end_progress - start_progress) * 0.75;
 
@@ -92,56 +102,23 @@ pub fn install_all_dependencies(
     }
 
     // =========================================================================
-    // 1. Standard Dependencies via Winetricks
-    // =========================================================================
-    ctx.set_status("Installing required Windows components (this may take several minutes)...".to_string());
-    ctx.log(format!(
-        "Installing {} dependencies via winetricks: {}",
-        STANDARD_VERBS.len(),
-        STANDARD_VERBS.join(", ")
-    ));
-    log_install(&format!("Running winetricks with {} verbs", STANDARD_VERBS.len()));
-
-    let winetricks_log_cb = {
-        let ctx = ctx.clone();
-        move |msg: String| {
-            ctx.log(msg.clone());
-            ctx.set_status(msg);
-        }
-    };
-
-    if let Err(e) = install_standard_deps_cancellable(prefix_root, install_proton, winetricks_log_cb, &ctx.cancel_flag) {
-        let msg = format!("Winetricks installation had issues: {}", e);
-        ctx.log(format!("Warning: {}", msg));
-        log_warning(&msg);
-    }
-
-    ctx.set_progress(winetricks_end);
-
-    if ctx.is_cancelled() {
-        return Err("Cancelled".into());
-    }
-
-    // =========================================================================
-    // 2. Custom .NET Runtimes (not in winetricks yet)
+    // 1-2. Standard Dependencies via Winetricks, then the custom .NET runtimes
     // =========================================================================
-    ctx.set_status("Installing .NET runtime (1 of 2)...".to_string());
-    ctx.log("Installing .NET 9 SDK...".to_string());
-
-    if let Err(e) = install_dotnet_runtime(prefix_root, install_proton, DOTNET9_SDK_URL, "dotnet-sdk-9", ctx) {
-        ctx.log(format!("Warning: .NET 9 SDK install failed: {}", e));
-        log_warning(&format!(".NET 9 SDK install failed: {}", e));
-    }
-
-    ctx.set_status("Installing .NET runtime (2 of 2)...".to_string());
-    ctx.log("Installing .NET Desktop Runtime 10...".to_string());
-
-    if let Err(e) = install_dotnet_runtime(prefix_root, install_proton, DOTNET_DESKTOP10_URL, "dotnet-desktop-10", ctx) {
-        ctx.log(format!("Warning: .NET Desktop 10 install failed: {}", e));
-        log_warning(&format!(".NET Desktop 10 install failed: {}", e));
-    }
-
-    ctx.set_progress(dotnet_end);
+    let steps = plan_dependency_steps(&dependency_components())?;
+    let (win11_steps, setup_steps): (Vec<_>, Vec<_>) = steps
+        .into_iter()
+        .partition(|step| *step == DependencyStep::Windows11);
+
+    // failed steps are logged and retried on the next run, the setup goes on
+    let _ = run_dependency_steps(
+        &setup_steps,
+        &mut state,
+        prefix_root,
+        ctx,
+        init_end,
+        dotnet_end,
+        |step| install_dependency_step(prefix_root, install_proton, ctx, step),
+    );
 
     if ctx.is_cancelled() {
         return Err("Cancelled".into());
@@ -186,14 +163,15 @@ pub fn install_all_dependencies(
     // =========================================================================
     // 5. Set Windows 11 Mode
     // =========================================================================
-    ctx.set_status("Finalizing compatibility settings...".to_string());
-    ctx.log("Setting Windows 11 mode...".to_string());
-    log_install("Setting Windows 11 mode via winetricks");
-
-    if let Err(e) = set_windows_11_mode(prefix_root, install_proton, ctx) {
-        ctx.log(format!("Warning: Failed to set Windows 11 mode: {}", e));
-        log_warning(&format!("Failed to set Windows 11 mode: {}", e));
-    }
+    let _ = run_dependency_steps(
+        &win11_steps,
+        &mut state,
+        prefix_root,
+        ctx,
+        games_end,
+        end_progress,
+        |step| install_dependency_step(prefix_root, install_proton, ctx, step),
+    );
 
     if ctx.is_cancelled() {
         return Err("Cancelled".into());
@@ -204,6 +182,237 @@ pub fn install_all_dependencies(
     Ok(())
 }
 
+/// Components that aren't winetricks verbs, installed by NaK itself
+const CUSTOM_RUNTIMES: &[(&str, &str)] = &[
+    ("dotnet-sdk-9", DOTNET9_SDK_URL),
+    ("dotnet-desktop-10", DOTNET_DESKTOP10_URL),
+];
+
+/// Windows 11 mode, selectable like the other components
+const WIN11_COMPONENT: &str = "win11";
+
+/// Names accepted by [`install_selected_dependencies`]: the standard winetricks
+/// verbs, the custom .NET runtimes and "win11"
+pub fn dependency_components() -> Vec<&'static str> {
+    let mut components = STANDARD_VERBS.to_vec();
+    components.extend(CUSTOM_RUNTIMES.iter().map(|(name, _)| *name));
+    components.push(WIN11_COMPONENT);
+    components
+}
+
+/// One unit of work of a selected install
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum DependencyStep {
+    /// Winetricks verbs, installed with a single winetricks run
+    Winetricks(Vec<&'static str>),
+    /// A .NET runtime installed from its download
+    DotnetRuntime { name: &'static str, url: &'static str },
+    Windows11,
+}
+
+impl DependencyStep {
+    /// Component names the step installs, as recorded in [`InstallState`]
+    fn components(&self) -> Vec<&'static str> {
+        match self {
+            Self::Winetricks(verbs) => verbs.clone(),
+            Self::DotnetRuntime { name, .. } => vec![name],
+            Self::Windows11 => vec![WIN11_COMPONENT],
+        }
+    }
+}
+
+/// Steps installing `components`, in the same order as a full install
+///
+/// Fails with a message listing the valid names if any of them is unknown.
+fn plan_dependency_steps(components: &[&str]) -> Result<Vec<DependencyStep>, String> {
+    let known = dependency_components();
+    let unknown: Vec<&str> = components
+        .iter()
+        .copied()
+        .filter(|c| !known.contains(c))
+        .collect();
+    if !unknown.is_empty() {
+        return Err(format!(
+            "Unknown dependency component(s): {}. Valid components are: {}",
+            unknown.join(", "),
+            known.join(", ")
+        ));
+    }
+
+    let mut steps = Vec::new();
+
+    let verbs: Vec<&'static str> = STANDARD_VERBS
+        .iter()
+        .copied()
+        .filter(|v| components.contains(v))
+        .collect();
+    if !verbs.is_empty() {
+        steps.push(DependencyStep::Winetricks(verbs));
+    }
+
+    for (name, url) in CUSTOM_RUNTIMES {
+        if components.contains(name) {
+            steps.push(DependencyStep::DotnetRuntime { name, url });
+        }
+    }
+
+    if components.contains(&WIN11_COMPONENT) {
+        steps.push(DependencyStep::Windows11);
+    }
+
+    Ok(steps)
+}
+
+/// Run `steps` with `run`, skipping components recorded in `state` and
+/// recording the ones that succeed
+///
+/// Every step is attempted; the error lists the components that failed.
+/// Progress moves from `start_progress` to `end_progress` as steps finish.
+fn run_dependency_steps(
+    steps: &[DependencyStep],
+    state: &mut InstallState,
+    prefix_root: &Path,
+    ctx: &TaskContext,
+    start_progress: f32,
+    end_progress: f32,
+    mut run: impl FnMut(&DependencyStep) -> Result<(), Box<dyn Error>>,
+) -> Result<(), Box<dyn Error>> {
+    let mut failed = Vec::new();
+
+    for (i, step) in steps.iter().enumerate() {
+        if ctx.is_cancelled() {
+            return Err("Cancelled".into());
+        }
+
+        let pending = state.pending(&step.components());
+        if pending.is_empty() {
+            ctx.log(format!("{} already installed, skipping", step.components().join(", ")));
+        } else {
+            let step = match step {
+                DependencyStep::Winetricks(_) => DependencyStep::Winetricks(pending.clone()),
+                other => other.clone(),
+            };
+
+            let result = run(&step);
+            if let Err(e) = record_step_result(state, prefix_root, &pending, result) {
+                let msg = format!("Failed to install {}: {}", pending.join(", "), e);
+                ctx.log(format!("Warning: {}", msg));
+                log_warning(&msg);
+                failed.extend(pending);
+            }
+        }
+
+        let done = (i + 1) as f32 / steps.len() as f32;
+        ctx.set_progress(start_progress + (end_progress - start_progress) * done);
+    }
+
+    if failed.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("Failed to install: {}", failed.join(", ")).into())
+    }
+}
+
+/// Install only the given components to an existing prefix.
+///
+/// See [`dependency_components`] for the valid names. Components recorded as
+/// done in the prefix's [`InstallState`] are skipped unless `force` is set.
+pub fn install_selected_dependencies(
+    prefix_root: &Path,
+    install_proton: &SteamProton,
+    ctx: &TaskContext,
+    components: &[&str],
+    force: bool,
+) -> Result<(), Box<dyn Error>> {
+    let steps = plan_dependency_steps(components)?;
+    fs::create_dir_all(AppConfig::get_tmp_path())?;
+
+    let mut state = if force {
+        InstallState::default()
+    } else {
+        InstallState::load(prefix_root)
+    };
+
+    ctx.set_status(format!("Installing {}...", components.join(", ")));
+    log_install(&format!("Installing selected dependencies: {}", components.join(", ")));
+
+    run_dependency_steps(&steps, &mut state, prefix_root, ctx, 0.0, 1.0, |step| {
+        install_dependency_step(prefix_root, install_proton, ctx, step)
+    })?;
+
+    ctx.set_status("Dependencies installed".to_string());
+    Ok(())
+}
+
+/// Install one step of a full or selected install
+fn install_dependency_step(
+    prefix_root: &Path,
+    install_proton: &SteamProton,
+    ctx: &TaskContext,
+    step: &DependencyStep,
+) -> Result<(), Box<dyn Error>> {
+    match step {
+        DependencyStep::Winetricks(verbs) => {
+            ctx.set_status(
+                "Installing required Windows components (this may take several minutes)..."
+                    .to_string(),
+            );
+            ctx.log(format!(
+                "Installing {} dependencies via winetricks: {}",
+                verbs.len(),
+                verbs.join(", ")
+            ));
+            log_install(&format!("Running winetricks with {} verbs", verbs.len()));
+
+            let winetricks_log_cb = {
+                let ctx = ctx.clone();
+                move |msg: String| {
+                    ctx.log(msg.clone());
+                    ctx.set_status(msg);
+                }
+            };
+            run_winetricks_cancellable(
+                prefix_root,
+                install_proton,
+                verbs,
+                winetricks_log_cb,
+                &ctx.cancel_flag,
+            )
+        }
+        DependencyStep::DotnetRuntime { name, url } => {
+            ctx.set_status(format!("Installing {}...", name));
+            install_dotnet_runtime(prefix_root, install_proton, url, name, ctx)
+        }
+        DependencyStep::Windows11 => {
+            ctx.set_status("Finalizing compatibility settings...".to_string());
+            ctx.log("Setting Windows 11 mode...".to_string());
+            log_install("Setting Windows 11 mode via winetricks");
+            set_windows_11_mode(prefix_root, install_proton, ctx)
+        }
+    }
+}
+
+/// Record `steps` as completed in the prefix if `result` is a success.
+///
+/// Failing to write the state only costs a re-run next time, so it's logged
+/// and the step's own result is returned.
+fn record_step_result(
+    state: &mut InstallState,
+    prefix_root: &Path,
+    steps: &[&str],
+    result: Result<(), Box<dyn Error>>,
+) -> Result<(), Box<dyn Error>> {
+    result?;
+
+    for step in steps {
+        state.mark_completed(step);
+    }
+    if let Err(e) = state.save(prefix_root) {
+        log_warning(&format!("Failed to save dependency state: {}", e));
+    }
+    Ok(())
+}
+
 /// Install a .NET runtime via direct exe download and wine execution
 fn install_dotnet_runtime(
     prefix_root: &Path,
@@ -682,6 +891,7 @@ pub fn apply_registry_for_game_path(
         install_path: install_path.to_path_buf(),
         app_id: known.map(|k| k.steam_app_id.to_string()).unwrap_or_default(),
         prefix_path: None,
+        wine: None,
         launcher: Launcher::Steam { is_flatpak: false, is_snap: false },
         my_games_folder: known.and_then(|k| k.my_games_folder.map(String::from)),
         appdata_local_folder: known.and_then(|k| k.appdata_local_folder.map(String::from)),
@@ -714,8 +924,14 @@ fn apply_game_registry(
     log_callback(format!("Found {}, applying registry...", game.name));
 
     // Convert Linux path to Wine Z: drive path with escaped backslashes for .reg file
-    let linux_path = game.install_path.to_string_lossy();
-    let wine_path_reg = format!("Z:{}", linux_path.replace('/', "\\\\"));
+    let Some(linux_path) = game.install_path.to_str() else {
+        log_warning(&format!(
+            "Install path of {} is not valid UTF-8, Wine can't map it: {:?}",
+            game.name, game.install_path
+        ));
+        return false;
+    };
+    let wine_path_reg = reg_escape(&format!("Z:{}", linux_path.replace('/', "\\")));
 
     // Create .reg file content
     let reg_content = format!(
@@ -739,7 +955,7 @@ fn apply_game_registry(
     let tmp_dir = AppConfig::get_tmp_path();
     let reg_file = tmp_dir.join(format!("game_reg_{}.reg", game.app_id));
 
-    if let Err(e) = fs::write(&reg_file, &reg_content) {
+    if let Err(e) = fs::write(&reg_file, encode_reg_file(&reg_content)) {
         log_warning(&format!("Failed to write registry file for {}: {}", game.name, e));
         return false;
     }
@@ -776,3 +992,143 @@ fn apply_game_registry(
         }
     }
 }
+
+/// Escapes a string for a quoted value in a .reg file
+fn reg_escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Encodes a .reg file as UTF-16LE with a BOM
+///
+/// regedit reads files without a BOM in the ANSI code page, which mangles
+/// install paths with accented or other non-ASCII characters.
+fn encode_reg_file(content: &str) -> Vec<u8> {
+    let mut bytes = vec![0xFF, 0xFE];
+    for unit in content.encode_utf16() {
+        bytes.extend_from_slice(&unit.to_le_bytes());
+    }
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        dependency_components, encode_reg_file, plan_dependency_steps, reg_escape,
+        run_dependency_steps, DependencyStep, InstallState, TaskContext,
+    };
+    use std::sync::atomic::AtomicBool;
+    use std::sync::Arc;
+
+    use crate::test_utils::TempDir;
+
+    #[test]
+    fn non_ascii_install_path_survives_reg_file() {
+        let value = reg_escape(r"Z:\home\zoë\Jeux\Skyrim Édition Spéciale");
+        assert_eq!(value, r"Z:\\home\\zoë\\Jeux\\Skyrim Édition Spéciale");
+
+        let bytes = encode_reg_file(&format!("\"Installed Path\"=\"{}\"", value));
+        assert_eq!(&bytes[..2], &[0xFF, 0xFE]);
+
+        let units: Vec<u16> = bytes[2..]
+            .chunks(2)
+            .map(|c| u16::from_le_bytes([c[0], c[1]]))
+            .collect();
+        assert_eq!(
+            String::from_utf16(&units).unwrap(),
+            r#""Installed Path"="Z:\\home\\zoë\\Jeux\\Skyrim Édition Spéciale""#
+        );
+    }
+
+    #[test]
+    fn quotes_are_escaped() {
+        assert_eq!(reg_escape(r#"Z:\a "b""#), r#"Z:\\a \"b\""#);
+    }
+
+    #[test]
+    fn second_run_skips_recorded_steps() {
+        let prefix = TempDir::new("prefix_setup_state");
+        let ctx = TaskContext::new(|_| {}, |_| {}, |_| {}, Arc::new(AtomicBool::new(false)));
+
+        // the steps of a full install, with `fails` failing; returns the ones run
+        let steps = plan_dependency_steps(&dependency_components()).unwrap();
+        let install = |fails: &str| {
+            let mut ran = Vec::new();
+            let mut state = InstallState::load(&prefix);
+            let _ = run_dependency_steps(&steps, &mut state, &prefix, &ctx, 0.0, 1.0, |step| {
+                ran.push(step.clone());
+                if step.components().contains(&fails) {
+                    Err("failed".into())
+                } else {
+                    Ok(())
+                }
+            });
+            ran
+        };
+
+        assert_eq!(install("dotnet-sdk-9"), steps);
+
+        // only the step that failed is run again
+        assert_eq!(
+            install(""),
+            vec![DependencyStep::DotnetRuntime {
+                name: "dotnet-sdk-9",
+                url: super::DOTNET9_SDK_URL
+            }]
+        );
+
+        assert!(install("").is_empty());
+    }
+
+    #[test]
+    fn unknown_components_are_rejected() {
+        let err = plan_dependency_steps(&["vcrun2022", "dotnet48", "dxvk"]).unwrap_err();
+        assert!(err.starts_with("Unknown dependency component(s): dotnet48, dxvk."), "{}", err);
+        assert!(err.contains("vcrun2022"), "{}", err);
+    }
+
+    #[test]
+    fn steps_follow_full_install_order() {
+        let steps = plan_dependency_steps(&["win11", "d3dx9", "dotnet-sdk-9", "vcrun2022"]).unwrap();
+        assert_eq!(
+            steps,
+            vec![
+                DependencyStep::Winetricks(vec!["vcrun2022", "d3dx9"]),
+                DependencyStep::DotnetRuntime {
+                    name: "dotnet-sdk-9",
+                    url: super::DOTNET9_SDK_URL
+                },
+                DependencyStep::Windows11,
+            ]
+        );
+    }
+
+    #[test]
+    fn single_component_install_runs_only_that_component() {
+        let prefix = TempDir::new("prefix_setup_selected");
+
+        let ctx = TaskContext::new(|_| {}, |_| {}, |_| {}, Arc::new(AtomicBool::new(false)));
+        let steps = plan_dependency_steps(&["vcrun2022"]).unwrap();
+
+        let mut ran = Vec::new();
+        let mut state = InstallState::load(&prefix);
+        run_dependency_steps(&steps, &mut state, &prefix, &ctx, 0.0, 1.0, |step| {
+            ran.push(step.clone());
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(ran, vec![DependencyStep::Winetricks(vec!["vcrun2022"])]);
+
+        let state = InstallState::load(&prefix);
+        assert!(state.is_completed("vcrun2022"));
+        assert!(!state.is_completed("d3dx9"));
+
+        // a failing runner is reported with the component's name
+        let steps = plan_dependency_steps(&["dotnet-desktop-10"]).unwrap();
+        let mut state = InstallState::load(&prefix);
+        let err = run_dependency_steps(&steps, &mut state, &prefix, &ctx, 0.0, 1.0, |_| {
+            Err("exit code 1".into())
+        })
+        .unwrap_err();
+        assert_eq!(err.to_string(), "Failed to install: dotnet-desktop-10");
+    }
+}
@@ -6,11 +6,16 @@
 pub mod config;
 pub mod dxvk;
 pub mod game_finder;
+pub mod load_order;
 pub mod logging;
 pub mod paths;
 pub mod runtime_wrap;
+pub mod saves;
 pub mod steam;
 pub mod utils;
 
 pub mod deps;
 pub mod installers;
+
+#[cfg(test)]
+mod test_utils;
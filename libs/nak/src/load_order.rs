@@ -0,0 +1,346 @@
+//! LOOT userlist rules
+//!
+//! Reorders a plugin list so it satisfies the `after`, `req` and `group` rules
+//! of a LOOT `userlist.yaml`, without running LOOT. Plugins keep their current
+//! order wherever the rules allow it. Masters, Bash tags and messages in the
+//! userlist are ignored.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fmt;
+
+use serde::Deserialize;
+
+/// Group of plugins that aren't given one by the userlist
+const DEFAULT_GROUP: &str = "default";
+
+/// Why a userlist couldn't be applied
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LoadOrderError {
+    /// The userlist isn't valid YAML or doesn't have the LOOT layout
+    Parse(String),
+    /// The rules contradict each other; the plugins or groups of one cycle,
+    /// starting and ending with the same one
+    Cycle(Vec<String>),
+}
+
+impl fmt::Display for LoadOrderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoadOrderError::Parse(e) => write!(f, "Failed to parse the userlist: {}", e),
+            LoadOrderError::Cycle(names) => {
+                write!(f, "The userlist rules form a cycle: {}", names.join(" -> "))
+            }
+        }
+    }
+}
+
+impl std::error::Error for LoadOrderError {}
+
+#[derive(Debug, Default, Deserialize)]
+struct Userlist {
+    #[serde(default)]
+    plugins: Vec<PluginRules>,
+    #[serde(default)]
+    groups: Vec<GroupRules>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PluginRules {
+    name: String,
+    #[serde(default)]
+    after: Vec<FileRef>,
+    #[serde(default)]
+    req: Vec<FileRef>,
+    group: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GroupRules {
+    name: String,
+    #[serde(default)]
+    after: Vec<String>,
+}
+
+/// A file in `after` or `req`, either just its name or a map with a `name`
+/// and a display name or condition
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum FileRef {
+    Name(String),
+    Detailed { name: String },
+}
+
+impl FileRef {
+    fn name(&self) -> &str {
+        match self {
+            FileRef::Name(name) | FileRef::Detailed { name } => name,
+        }
+    }
+}
+
+/// Reorder `plugins` to satisfy the rules of `userlist_yaml`
+///
+/// Names are matched case-insensitively and rules about plugins that aren't in
+/// the list are ignored. A plugin loads after every plugin it names in `after`
+/// or `req` and after all plugins of the groups its group loads after.
+pub fn apply_loot_userlist(
+    plugins: &[String],
+    userlist_yaml: &str,
+) -> Result<Vec<String>, LoadOrderError> {
+    let userlist: Userlist = if userlist_yaml.trim().is_empty() {
+        Userlist::default()
+    } else {
+        serde_yaml_ng::from_str(userlist_yaml).map_err(|e| LoadOrderError::Parse(e.to_string()))?
+    };
+
+    let index: HashMap<String, usize> = plugins
+        .iter()
+        .enumerate()
+        .map(|(i, name)| (name.to_lowercase(), i))
+        .collect();
+
+    // predecessors[i] must load before plugins[i]
+    let mut predecessors: Vec<BTreeSet<usize>> = vec![BTreeSet::new(); plugins.len()];
+    let mut group_of = vec![DEFAULT_GROUP.to_string(); plugins.len()];
+
+    for rules in &userlist.plugins {
+        let Some(&plugin) = index.get(&rules.name.to_lowercase()) else {
+            continue;
+        };
+
+        for file in rules.after.iter().chain(&rules.req) {
+            if let Some(&before) = index.get(&file.name().to_lowercase()) {
+                predecessors[plugin].insert(before);
+            }
+        }
+
+        if let Some(group) = &rules.group {
+            group_of[plugin] = group.clone();
+        }
+    }
+
+    let earlier_groups = earlier_groups(&userlist.groups)?;
+    let mut members: HashMap<&str, Vec<usize>> = HashMap::new();
+    for (i, group) in group_of.iter().enumerate() {
+        members.entry(group.as_str()).or_default().push(i);
+    }
+
+    for (plugin, group) in group_of.iter().enumerate() {
+        for earlier in earlier_groups.get(group).into_iter().flatten() {
+            for &before in members.get(earlier.as_str()).into_iter().flatten() {
+                predecessors[plugin].insert(before);
+            }
+        }
+    }
+
+    sort_stable(plugins, &predecessors)
+}
+
+/// Every group that loads before each group, following `after` transitively
+fn earlier_groups(
+    groups: &[GroupRules],
+) -> Result<HashMap<String, HashSet<String>>, LoadOrderError> {
+    let after: HashMap<&str, &[String]> = groups
+        .iter()
+        .map(|g| (g.name.as_str(), g.after.as_slice()))
+        .collect();
+
+    let mut result = HashMap::new();
+    for group in groups {
+        let mut earlier = HashSet::new();
+        let mut path = vec![group.name.clone()];
+        collect_earlier_groups(&group.name, &after, &mut earlier, &mut path)?;
+        result.insert(group.name.clone(), earlier);
+    }
+
+    Ok(result)
+}
+
+fn collect_earlier_groups(
+    group: &str,
+    after: &HashMap<&str, &[String]>,
+    earlier: &mut HashSet<String>,
+    path: &mut Vec<String>,
+) -> Result<(), LoadOrderError> {
+    for before in after.get(group).copied().unwrap_or_default() {
+        if let Some(start) = path.iter().position(|g| g == before) {
+            let mut cycle = path[start..].to_vec();
+            cycle.push(before.clone());
+            return Err(LoadOrderError::Cycle(cycle));
+        }
+
+        if earlier.insert(before.clone()) {
+            path.push(before.clone());
+            collect_earlier_groups(before, after, earlier, path)?;
+            path.pop();
+        }
+    }
+
+    Ok(())
+}
+
+/// Topological sort that always picks the ready plugin that came first in
+/// `plugins`, so the order only changes where a rule requires it
+fn sort_stable(
+    plugins: &[String],
+    predecessors: &[BTreeSet<usize>],
+) -> Result<Vec<String>, LoadOrderError> {
+    let mut remaining: Vec<usize> = predecessors.iter().map(|p| p.len()).collect();
+    let mut successors: Vec<Vec<usize>> = vec![Vec::new(); plugins.len()];
+    for (plugin, before) in predecessors.iter().enumerate() {
+        for &b in before {
+            successors[b].push(plugin);
+        }
+    }
+
+    let mut ready: BTreeSet<usize> = (0..plugins.len()).filter(|&i| remaining[i] == 0).collect();
+    let mut sorted = Vec::with_capacity(plugins.len());
+
+    while let Some(next) = ready.pop_first() {
+        sorted.push(plugins[next].clone());
+        for &after in &successors[next] {
+            remaining[after] -= 1;
+            if remaining[after] == 0 {
+                ready.insert(after);
+            }
+        }
+    }
+
+    if sorted.len() < plugins.len() {
+        return Err(LoadOrderError::Cycle(find_cycle(
+            plugins,
+            predecessors,
+            &remaining,
+        )));
+    }
+
+    Ok(sorted)
+}
+
+/// A cycle among the plugins that couldn't be sorted; every one of them still
+/// waits for a predecessor that also couldn't be sorted
+fn find_cycle(
+    plugins: &[String],
+    predecessors: &[BTreeSet<usize>],
+    remaining: &[usize],
+) -> Vec<String> {
+    let Some(start) = (0..plugins.len()).find(|&i| remaining[i] > 0) else {
+        return Vec::new();
+    };
+
+    let mut path = vec![start];
+    loop {
+        let current = *path.last().unwrap();
+        let Some(&before) = predecessors[current].iter().find(|&&b| remaining[b] > 0) else {
+            return Vec::new();
+        };
+
+        if let Some(pos) = path.iter().position(|&p| p == before) {
+            // walked backwards, so reverse to list the cycle in load order
+            return std::iter::once(before)
+                .chain(path[pos..].iter().rev().copied())
+                .map(|i| plugins[i].clone())
+                .collect();
+        }
+
+        path.push(before);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn list(names: &[&str]) -> Vec<String> {
+        names.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn after_chain_is_satisfied() {
+        let plugins = list(&["C.esp", "Unrelated.esp", "B.esp", "A.esp"]);
+        let userlist = r#"
+plugins:
+  - name: 'c.esp'
+    after: ['B.esp']
+  - name: 'B.esp'
+    after:
+      - name: 'A.ESP'
+        display: 'Plugin A'
+  - name: 'Missing.esp'
+    after: ['C.esp']
+"#;
+
+        assert_eq!(
+            apply_loot_userlist(&plugins, userlist).unwrap(),
+            list(&["Unrelated.esp", "A.esp", "B.esp", "C.esp"])
+        );
+    }
+
+    #[test]
+    fn req_and_groups_reorder() {
+        let plugins = list(&["Late.esp", "Patch.esp", "Base.esp", "Other.esp"]);
+        let userlist = r#"
+groups:
+  - name: 'Late Loaders'
+    after: ['Patches']
+  - name: 'Patches'
+    after: ['default']
+plugins:
+  - name: 'Late.esp'
+    group: 'Late Loaders'
+  - name: 'Patch.esp'
+    group: 'Patches'
+    req: ['Base.esp']
+"#;
+
+        assert_eq!(
+            apply_loot_userlist(&plugins, userlist).unwrap(),
+            list(&["Base.esp", "Other.esp", "Patch.esp", "Late.esp"])
+        );
+    }
+
+    #[test]
+    fn cyclic_rules_are_an_error() {
+        let plugins = list(&["A.esp", "B.esp", "C.esp", "D.esp"]);
+        let userlist = r#"
+plugins:
+  - name: 'A.esp'
+    after: ['C.esp']
+  - name: 'B.esp'
+    after: ['A.esp']
+  - name: 'C.esp'
+    after: ['B.esp']
+"#;
+
+        match apply_loot_userlist(&plugins, userlist) {
+            Err(LoadOrderError::Cycle(cycle)) => {
+                assert_eq!(cycle.len(), 4);
+                assert_eq!(cycle.first(), cycle.last());
+                assert!(!cycle.contains(&"D.esp".to_string()));
+            }
+            other => panic!("expected a cycle, got {:?}", other),
+        }
+
+        let groups = r#"
+groups:
+  - name: 'One'
+    after: ['Two']
+  - name: 'Two'
+    after: ['One']
+"#;
+        assert!(matches!(
+            apply_loot_userlist(&plugins, groups),
+            Err(LoadOrderError::Cycle(_))
+        ));
+    }
+
+    #[test]
+    fn empty_and_invalid_userlists() {
+        let plugins = list(&["B.esp", "A.esp"]);
+        assert_eq!(apply_loot_userlist(&plugins, "").unwrap(), plugins);
+        assert!(matches!(
+            apply_loot_userlist(&plugins, "plugins: 12"),
+            Err(LoadOrderError::Parse(_))
+        ));
+    }
+}
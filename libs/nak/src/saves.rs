@@ -0,0 +1,415 @@
+//! Save game headers and co-saves
+//!
+//! Reads the in-game details stored at the start of Skyrim (.ess) and
+//! Fallout 4 (.fos) saves: character, level, location, playtime and the size
+//! of the screenshot. Enderal and Skyrim VR use the Skyrim format.
+//!
+//! Also finds script extender co-saves left behind after their save was
+//! deleted.
+
+use std::collections::HashSet;
+use std::error::Error;
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::{Path, PathBuf};
+
+/// Magic of Skyrim LE/SE/VR and Enderal saves
+const SKYRIM_MAGIC: &[u8] = b"TESV_SAVEGAME";
+
+/// Magic of Fallout 4 saves
+const FALLOUT4_MAGIC: &[u8] = b"FO4_SAVEGAME";
+
+/// Script extender co-save extensions, next to the save with the same name
+const COSAVE_EXTENSIONS: &[&str] = &["skse", "f4se"];
+
+/// Extensions of the saves a co-save can belong to
+const SAVE_EXTENSIONS: &[&str] = &["ess", "fos"];
+
+/// Game a save was written by
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SaveFormat {
+    Skyrim,
+    Fallout4,
+}
+
+/// In-game details from the header of a save
+#[derive(Debug, Clone, PartialEq)]
+pub struct SaveHeader {
+    pub format: SaveFormat,
+    /// Version of the header layout
+    pub version: u32,
+    pub save_number: u32,
+    pub character: String,
+    pub level: u32,
+    pub location: String,
+    /// Playtime as the game shows it, e.g. "000.05.31" or "0d.5h.31m"
+    pub playtime: String,
+    pub screenshot_width: u32,
+    pub screenshot_height: u32,
+    /// Script extender co-save belonging to the save, if there is one
+    pub cosave: Option<PathBuf>,
+}
+
+/// Read the header of the save at `path`
+///
+/// Only the header is read, not the screenshot or the plugin list. A missing
+/// co-save isn't an error, `cosave` is just `None`.
+pub fn parse_save_header(path: &Path) -> Result<SaveHeader, Box<dyn Error>> {
+    let mut reader = BufReader::new(File::open(path)?);
+    let mut header = read_save_header(&mut reader)
+        .map_err(|e| format!("Failed to read save header of {}: {}", path.display(), e))?;
+    header.cosave = find_cosave(path);
+    Ok(header)
+}
+
+/// Read a save header from the start of a save
+pub fn read_save_header(reader: &mut impl Read) -> Result<SaveHeader, Box<dyn Error>> {
+    let mut magic = [0u8; 13];
+    reader.read_exact(&mut magic[..FALLOUT4_MAGIC.len()])?;
+
+    let format = if magic[..FALLOUT4_MAGIC.len()] == *FALLOUT4_MAGIC {
+        SaveFormat::Fallout4
+    } else {
+        reader.read_exact(&mut magic[FALLOUT4_MAGIC.len()..])?;
+        if magic[..] != *SKYRIM_MAGIC {
+            return Err("not a Skyrim or Fallout 4 save".into());
+        }
+        SaveFormat::Skyrim
+    };
+
+    // size of the header, not needed since all fields are read in order
+    let _header_size = read_u32(reader)?;
+
+    let version = read_u32(reader)?;
+    let save_number = read_u32(reader)?;
+    let character = read_wstring(reader)?;
+    let level = read_u32(reader)?;
+    let location = read_wstring(reader)?;
+    let playtime = read_wstring(reader)?;
+    let _race = read_wstring(reader)?;
+    let _sex = read_u16(reader)?;
+    let _current_exp = read_u32(reader)?;
+    let _level_up_exp = read_u32(reader)?;
+    let _filetime = read_u64(reader)?;
+    let screenshot_width = read_u32(reader)?;
+    let screenshot_height = read_u32(reader)?;
+
+    Ok(SaveHeader {
+        format,
+        version,
+        save_number,
+        character,
+        level,
+        location,
+        playtime,
+        screenshot_width,
+        screenshot_height,
+        cosave: None,
+    })
+}
+
+/// The script extender co-save next to `save`
+///
+/// The script extenders name co-saves after their save, so only
+/// `<stem>.<ext>` is probed, with the extension in lower or upper case. This
+/// is called for every save in a list, listing the directory each time would
+/// be quadratic.
+fn find_cosave(save: &Path) -> Option<PathBuf> {
+    COSAVE_EXTENSIONS
+        .iter()
+        .flat_map(|ext| [ext.to_string(), ext.to_uppercase()])
+        .map(|ext| save.with_extension(ext))
+        .find(|p| p.is_file())
+}
+
+/// Co-saves in `save_dir` without a save of the same name, sorted by path
+///
+/// Names are compared case-insensitively on the stem, so `Save3.SKSE` belongs
+/// to `save3.ess`. A directory that can't be read has no orphans.
+pub fn find_orphaned_cosaves(save_dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(save_dir) else {
+        return Vec::new();
+    };
+
+    let files: Vec<PathBuf> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.is_file())
+        .collect();
+
+    let stem = |p: &Path| p.file_stem().map(|s| s.to_string_lossy().to_lowercase());
+    let has_extension = |p: &Path, extensions: &[&str]| {
+        p.extension().is_some_and(|ext| {
+            extensions
+                .iter()
+                .any(|e| ext.to_string_lossy().eq_ignore_ascii_case(e))
+        })
+    };
+
+    let saves: HashSet<String> = files
+        .iter()
+        .filter(|p| has_extension(p, SAVE_EXTENSIONS))
+        .filter_map(|p| stem(p))
+        .collect();
+
+    let mut orphans: Vec<PathBuf> = files
+        .into_iter()
+        .filter(|p| has_extension(p, COSAVE_EXTENSIONS))
+        .filter(|p| stem(p).is_some_and(|s| !saves.contains(&s)))
+        .collect();
+    orphans.sort();
+    orphans
+}
+
+/// Delete `cosaves`, such as the ones [`find_orphaned_cosaves`] found and the
+/// user confirmed, returning how many were deleted
+///
+/// Only co-saves are deleted, any other file is an error and stops before it.
+pub fn delete_cosaves(cosaves: &[PathBuf]) -> Result<usize, Box<dyn Error>> {
+    for (deleted, cosave) in cosaves.iter().enumerate() {
+        let is_cosave = cosave.extension().is_some_and(|ext| {
+            COSAVE_EXTENSIONS
+                .iter()
+                .any(|e| ext.to_string_lossy().eq_ignore_ascii_case(e))
+        });
+        if !is_cosave {
+            return Err(format!(
+                "Not a co-save: {} (deleted {} before it)",
+                cosave.display(),
+                deleted
+            )
+            .into());
+        }
+
+        std::fs::remove_file(cosave)
+            .map_err(|e| format!("Failed to delete {}: {}", cosave.display(), e))?;
+    }
+    Ok(cosaves.len())
+}
+
+fn read_u16(reader: &mut impl Read) -> std::io::Result<u16> {
+    let mut buf = [0u8; 2];
+    reader.read_exact(&mut buf)?;
+    Ok(u16::from_le_bytes(buf))
+}
+
+fn read_u32(reader: &mut impl Read) -> std::io::Result<u32> {
+    let mut buf = [0u8; 4];
+    reader.read_exact(&mut buf)?;
+    Ok(u32::from_le_bytes(buf))
+}
+
+fn read_u64(reader: &mut impl Read) -> std::io::Result<u64> {
+    let mut buf = [0u8; 8];
+    reader.read_exact(&mut buf)?;
+    Ok(u64::from_le_bytes(buf))
+}
+
+/// A string prefixed with its length as u16; the games write Windows-1252,
+/// so anything that isn't UTF-8 is read as Latin-1 instead of failing
+fn read_wstring(reader: &mut impl Read) -> std::io::Result<String> {
+    let len = read_u16(reader)? as usize;
+    let mut buf = vec![0u8; len];
+    reader.read_exact(&mut buf)?;
+
+    Ok(match String::from_utf8(buf) {
+        Ok(s) => s,
+        Err(e) => e.into_bytes().iter().map(|&b| b as char).collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TempDir;
+
+    struct HeaderFields<'a> {
+        version: u32,
+        save_number: u32,
+        character: &'a [u8],
+        level: u32,
+        location: &'a str,
+        playtime: &'a str,
+        width: u32,
+        height: u32,
+    }
+
+    fn craft_save(magic: &[u8], fields: &HeaderFields) -> Vec<u8> {
+        let mut header = Vec::new();
+        let wstring = |out: &mut Vec<u8>, s: &[u8]| {
+            out.extend((s.len() as u16).to_le_bytes());
+            out.extend(s);
+        };
+
+        header.extend(fields.version.to_le_bytes());
+        header.extend(fields.save_number.to_le_bytes());
+        wstring(&mut header, fields.character);
+        header.extend(fields.level.to_le_bytes());
+        wstring(&mut header, fields.location.as_bytes());
+        wstring(&mut header, fields.playtime.as_bytes());
+        wstring(&mut header, b"NordRace");
+        header.extend(0u16.to_le_bytes());
+        header.extend(0f32.to_le_bytes());
+        header.extend(250f32.to_le_bytes());
+        header.extend(0u64.to_le_bytes());
+        header.extend(fields.width.to_le_bytes());
+        header.extend(fields.height.to_le_bytes());
+        if fields.version >= 12 {
+            // compression type, only in Skyrim SE
+            header.extend(2u16.to_le_bytes());
+        }
+
+        let mut save = magic.to_vec();
+        save.extend((header.len() as u32).to_le_bytes());
+        save.extend(header);
+        // start of the screenshot
+        save.extend([0u8; 16]);
+        save
+    }
+
+    #[test]
+    fn skyrim_se_header() {
+        let save = craft_save(
+            SKYRIM_MAGIC,
+            &HeaderFields {
+                version: 12,
+                save_number: 42,
+                character: b"Lydia",
+                level: 27,
+                location: "Whiterun",
+                playtime: "012.34.56",
+                width: 320,
+                height: 192,
+            },
+        );
+
+        let header = read_save_header(&mut save.as_slice()).unwrap();
+        assert_eq!(header.format, SaveFormat::Skyrim);
+        assert_eq!(header.version, 12);
+        assert_eq!(header.save_number, 42);
+        assert_eq!(header.character, "Lydia");
+        assert_eq!(header.level, 27);
+        assert_eq!(header.location, "Whiterun");
+        assert_eq!(header.playtime, "012.34.56");
+        assert_eq!(
+            (header.screenshot_width, header.screenshot_height),
+            (320, 192)
+        );
+    }
+
+    #[test]
+    fn fallout_4_header() {
+        let save = craft_save(
+            FALLOUT4_MAGIC,
+            &HeaderFields {
+                version: 11,
+                save_number: 7,
+                character: b"Nate",
+                level: 3,
+                location: "Sanctuary",
+                playtime: "0d.1h.12m.0 days.0 hours.0 minutes",
+                width: 640,
+                height: 384,
+            },
+        );
+
+        let header = read_save_header(&mut save.as_slice()).unwrap();
+        assert_eq!(header.format, SaveFormat::Fallout4);
+        assert_eq!(header.character, "Nate");
+        assert_eq!(header.level, 3);
+        assert_eq!(header.location, "Sanctuary");
+        assert_eq!(
+            (header.screenshot_width, header.screenshot_height),
+            (640, 384)
+        );
+    }
+
+    #[test]
+    fn latin1_names_and_bad_saves() {
+        let save = craft_save(
+            SKYRIM_MAGIC,
+            &HeaderFields {
+                version: 9,
+                save_number: 1,
+                character: b"Bj\xf6rn",
+                level: 1,
+                location: "Helgen",
+                playtime: "000.00.10",
+                width: 320,
+                height: 192,
+            },
+        );
+        assert_eq!(
+            read_save_header(&mut save.as_slice()).unwrap().character,
+            "Björn"
+        );
+
+        // truncated in the middle of the header
+        assert!(read_save_header(&mut &save[..30]).is_err());
+        // Fallout 3 and New Vegas saves use another layout
+        assert!(read_save_header(&mut &b"FO3SAVEGAME\0\0\0\0\0"[..]).is_err());
+    }
+
+    #[test]
+    fn cosave_is_found_next_to_the_save() {
+        let dir = TempDir::new("saves_cosave");
+
+        let save = craft_save(
+            SKYRIM_MAGIC,
+            &HeaderFields {
+                version: 12,
+                save_number: 3,
+                character: b"Lydia",
+                level: 5,
+                location: "Riverwood",
+                playtime: "001.00.00",
+                width: 320,
+                height: 192,
+            },
+        );
+        let path = dir.join("Save3_Lydia.ess");
+        std::fs::write(&path, &save).unwrap();
+        assert_eq!(parse_save_header(&path).unwrap().cosave, None);
+
+        std::fs::write(dir.join("Save3_Lydia.SKSE"), b"").unwrap();
+        assert_eq!(
+            parse_save_header(&path).unwrap().cosave,
+            Some(dir.join("Save3_Lydia.SKSE"))
+        );
+    }
+
+    #[test]
+    fn orphaned_cosaves_are_found_and_deleted() {
+        let dir = TempDir::new("saves_orphans");
+
+        for name in [
+            "Save1.ess",
+            "save1.skse",
+            "Save2.ESS",
+            "SAVE2.skse",
+            "Save3.skse",
+            "Autosave.fos",
+            "autosave.F4SE",
+            "Quicksave.f4se",
+            "Save4.ess.bak",
+        ] {
+            std::fs::write(dir.join(name), b"").unwrap();
+        }
+
+        assert_eq!(
+            find_orphaned_cosaves(&dir),
+            vec![dir.join("Quicksave.f4se"), dir.join("Save3.skse")]
+        );
+
+        assert!(delete_cosaves(&[dir.join("Save1.ess")]).is_err());
+        assert!(dir.join("Save1.ess").exists());
+
+        assert_eq!(delete_cosaves(&find_orphaned_cosaves(&dir)).unwrap(), 2);
+        assert!(find_orphaned_cosaves(&dir).is_empty());
+        assert!(dir.join("save1.skse").exists());
+        assert!(dir.join("SAVE2.skse").exists());
+        assert!(dir.join("autosave.F4SE").exists());
+
+        assert!(find_orphaned_cosaves(&dir.join("missing")).is_empty());
+    }
+}
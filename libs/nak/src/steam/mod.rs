@@ -13,7 +13,9 @@ pub use paths::{
 };
 
 // Re-export Proton detection
-pub use proton::{find_steam_protons, SteamProton};
+pub use proton::{
+    find_steam_protons, is_ge_proton, proton_meets_minimum, proton_name_meets_minimum, SteamProton,
+};
 
 use std::fs;
 
@@ -48,6 +48,72 @@ impl SteamProton {
     }
 }
 
+/// Version numbers in a Proton name, such as `[9, 20]` for "GE-Proton9-20",
+/// `[9, 0]` for "Proton 9.0" or `[9]` for "proton_9"
+///
+/// Parsing starts at the first digit and stops at the first part that isn't a
+/// number, so suffixes like " (Beta)" or "-slr" are ignored.
+fn parse_proton_version(name: &str) -> Option<Vec<u32>> {
+    let start = name.find(|c: char| c.is_ascii_digit())?;
+
+    let version: Vec<u32> = name[start..]
+        .split(['.', '-', '_', ' '])
+        .map_while(|part| part.parse().ok())
+        .collect();
+
+    if version.is_empty() {
+        None
+    } else {
+        Some(version)
+    }
+}
+
+/// Whether the name is that of a GloriousEggroll build, e.g. "GE-Proton9-20"
+/// or the older "Proton-6.5-GE-2"
+pub fn is_ge_proton(name: &str) -> bool {
+    let name = name.to_lowercase();
+    name.starts_with("ge-proton")
+        || name
+            .match_indices("-ge-")
+            .any(|(i, tag)| name[i + tag.len()..].starts_with(|c: char| c.is_ascii_digit()))
+}
+
+/// Whether a Proton with the given name is at least version `min`
+///
+/// Only the parts both versions have are compared: "Proton 8.0" is whatever
+/// the latest 8.0 build is, so it meets "8.0-4", and "Proton 9.0" meets "9".
+/// Experimental builds always qualify, and so do names without a version
+/// since there's no way to tell.
+pub fn proton_name_meets_minimum(name: &str, min: &str) -> bool {
+    if name.to_lowercase().contains("experimental") {
+        return true;
+    }
+
+    let (Some(version), Some(min)) = (parse_proton_version(name), parse_proton_version(min))
+    else {
+        return true;
+    };
+
+    let len = version.len().min(min.len());
+    version[..len] >= min[..len]
+}
+
+/// Whether `proton` is at least version `min`, e.g. "9-10" or "8.0"
+///
+/// The version is read from the display name, or from the config name for
+/// Steam's "proton_9" style names; see [`proton_name_meets_minimum`].
+pub fn proton_meets_minimum(proton: &SteamProton, min: &str) -> bool {
+    if proton.is_experimental {
+        return true;
+    }
+
+    if parse_proton_version(&proton.name).is_some() {
+        proton_name_meets_minimum(&proton.name, min)
+    } else {
+        proton_name_meets_minimum(&proton.config_name, min)
+    }
+}
+
 /// Find all Protons that Steam can use (Proton 10+ only)
 pub fn find_steam_protons() -> Vec<SteamProton> {
     let mut protons = Vec::new();
@@ -250,3 +316,81 @@ fn find_system_protons() -> Vec<SteamProton> {
 
     found
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn proton(name: &str, config_name: &str) -> SteamProton {
+        SteamProton {
+            name: name.to_string(),
+            config_name: config_name.to_string(),
+            path: PathBuf::from("/nonexistent"),
+            is_steam_proton: false,
+            is_experimental: false,
+        }
+    }
+
+    #[test]
+    fn test_parse_proton_version() {
+        assert_eq!(parse_proton_version("GE-Proton9-20"), Some(vec![9, 20]));
+        assert_eq!(parse_proton_version("Proton 9.0 (Beta)"), Some(vec![9, 0]));
+        assert_eq!(parse_proton_version("proton_8"), Some(vec![8]));
+        assert_eq!(
+            parse_proton_version("proton-cachyos-10.0-20250714-slr"),
+            Some(vec![10, 0, 20250714])
+        );
+        assert_eq!(parse_proton_version("Proton Experimental"), None);
+    }
+
+    #[test]
+    fn test_ge_proton_comparisons() {
+        assert!(proton_name_meets_minimum("GE-Proton9-20", "9-10"));
+        assert!(proton_name_meets_minimum("GE-Proton9-10", "9-10"));
+        assert!(!proton_name_meets_minimum("GE-Proton9-5", "9-10"));
+        assert!(!proton_name_meets_minimum("GE-Proton8-32", "9-10"));
+        assert!(proton_name_meets_minimum("GE-Proton10-1", "9-10"));
+
+        // 9-2 is older than 9-10, not a string comparison
+        assert!(!proton_name_meets_minimum("GE-Proton9-2", "9-10"));
+    }
+
+    #[test]
+    fn test_is_ge_proton() {
+        assert!(is_ge_proton("GE-Proton9-20"));
+        assert!(is_ge_proton("Proton-6.5-GE-2"));
+        assert!(!is_ge_proton("proton-generic"));
+        assert!(!is_ge_proton("wine-gecko"));
+        assert!(!is_ge_proton("Proton-9.0-ge-build"));
+        assert!(!is_ge_proton("Proton 9.0"));
+    }
+
+    #[test]
+    fn test_valve_proton_comparisons() {
+        assert!(proton_name_meets_minimum("Proton 9.0", "9"));
+        assert!(proton_name_meets_minimum("Proton 10.0", "9.0"));
+        assert!(!proton_name_meets_minimum("Proton 8.0", "9.0"));
+        assert!(proton_name_meets_minimum("Proton Experimental", "99"));
+
+        // Valve names don't carry the build, so 8.0 may well be 8.0-5
+        assert!(proton_name_meets_minimum("Proton 8.0", "8.0-4"));
+        assert!(!proton_name_meets_minimum("Proton 7.0", "8.0-4"));
+
+        assert!(proton_meets_minimum(&proton("Proton 9.0 (Beta)", "proton_9"), "9"));
+        assert!(!proton_meets_minimum(&proton("Proton 8.0", "proton_8"), "9"));
+    }
+
+    #[test]
+    fn test_experimental_and_unknown_names() {
+        let mut experimental = proton("Proton Experimental", "proton_experimental");
+        assert!(proton_meets_minimum(&experimental, "10"));
+        experimental.is_experimental = true;
+        assert!(proton_meets_minimum(&experimental, "10"));
+
+        // the config name is used when the display name has no version
+        assert!(!proton_meets_minimum(&proton("Custom", "proton_8"), "9"));
+
+        // nothing to compare against
+        assert!(proton_meets_minimum(&proton("my-wine", "my-wine"), "9"));
+    }
+}
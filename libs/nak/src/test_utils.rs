@@ -0,0 +1,38 @@
+//! Helpers shared by the unit tests
+
+use std::fs;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+
+/// An empty directory under the system temp directory, removed again when
+/// dropped so a failing test doesn't leave it behind
+pub struct TempDir(PathBuf);
+
+impl TempDir {
+    /// Create the directory; `name` has to be unique among the tests, the
+    /// process id keeps concurrent test runs apart
+    pub fn new(name: &str) -> Self {
+        let path = std::env::temp_dir().join(format!("nak_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&path);
+        fs::create_dir_all(&path).unwrap();
+        TempDir(path)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Deref for TempDir {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
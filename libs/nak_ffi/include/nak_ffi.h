@@ -24,6 +24,8 @@ typedef struct {
     char *appdata_roaming_folder;  /* NULL if not applicable */
     char *registry_path;           /* NULL if not applicable */
     char *registry_value;          /* NULL if not applicable */
+    char *wine_name;               /* NULL unless the launcher sets one per game */
+    char *wine_bin;                /* NULL unless the launcher sets one per game */
 } NakGame;
 
 /** List of detected games */
@@ -52,12 +54,20 @@ typedef struct {
     const char *registry_path;
     const char *registry_value;
     const char *steam_folder;
+    const char *min_proton;              /* NULL if any Proton works */
+    const char *recommended_proton;      /* NULL if there's no recommendation */
 } NakKnownGame;
 
 /** Get the list of all known games (static data, do NOT free).
  *  Returns pointer to array; writes count to *out_count. */
 const NakKnownGame *nak_get_known_games(size_t *out_count);
 
+/** Resolve the executables started by a known game launcher, which starts the
+ *  game and exits right away.
+ *  Returns newline-separated full paths, or NULL if exe_path is not a known
+ *  launcher. Caller must free with nak_string_free. */
+char *nak_resolve_game_launcher(const char *exe_path);
+
 /* ========================================================================
  * Tier 2: Proton Detection
  * ======================================================================== */
@@ -83,6 +93,30 @@ NakProtonList nak_find_steam_protons(void);
 /** Free a NakProtonList */
 void nak_proton_list_free(NakProtonList list);
 
+/** Master plugins of the known game with the Steam or GOG app id app_id that
+ *  are missing from data_dir, the directory the game loads plugins from.
+ *  Returns newline-separated file names, or NULL if none are missing or the
+ *  game isn't known. Caller must free with nak_string_free. */
+char *nak_missing_master_plugins(const char *app_id, const char *data_dir);
+
+/** Locate the install directory of the known game with the Steam or GOG app
+ *  id app_id from the registry of prefix_path, or of the game's own Steam
+ *  prefix if prefix_path is NULL or empty.
+ *  Returns NULL if the game isn't known or the registry has no existing
+ *  directory. Caller must free with nak_string_free. */
+char *nak_resolve_game_dir_from_registry(const char *app_id, const char *prefix_path);
+
+/** Whether the Proton named proton_name is at least min_version (such as a
+ *  known game's min_proton, e.g. "9-10" or "8.0").
+ *  Returns 1 if it is or if either version can't be read, 0 otherwise. */
+int nak_proton_meets_minimum(const char *proton_name, const char *min_version);
+
+/** Why the Proton named proton_name is a poor fit for the known game with the
+ *  given Steam app id (older than its minimum or recommended Proton).
+ *  Returns NULL if it's fine or the game isn't known, otherwise a message
+ *  (free with nak_string_free). */
+char *nak_proton_warning(const char *steam_app_id, const char *proton_name);
+
 /* ========================================================================
  * Tier 3: Steam Paths
  * ======================================================================== */
@@ -104,6 +138,7 @@ typedef void (*NakProgressCallback)(float progress);
 
 /** Install all Wine prefix dependencies (blocking call).
  *  cancel_flag: pointer to int, set non-zero to cancel.
+ *  force: non-zero re-runs steps already recorded as done in the prefix.
  *  Returns NULL on success, or error message (free with nak_string_free). */
 char *nak_install_all_dependencies(
     const char *prefix_path,
@@ -113,7 +148,24 @@ char *nak_install_all_dependencies(
     NakLogCallback log_cb,
     NakProgressCallback progress_cb,
     const int *cancel_flag,
-    uint32_t app_id
+    uint32_t app_id,
+    int force
+);
+
+/** Install only the named components to an existing prefix (blocking call).
+ *  components: NULL-terminated array such as {"vcrun2022", "dotnet-sdk-9",
+ *  "win11", NULL}; an unknown name fails with a message listing valid ones.
+ *  Callbacks, cancel_flag and force work as in nak_install_all_dependencies.
+ *  Returns NULL on success, or error message (free with nak_string_free). */
+char *nak_install_dependencies_selected(
+    const char *prefix_path,
+    const char *proton_path,
+    const char *const *components,
+    NakStatusCallback status_cb,
+    NakLogCallback log_cb,
+    NakProgressCallback progress_cb,
+    const int *cancel_flag,
+    int force
 );
 
 /** Apply Wine registry settings to a prefix.
@@ -150,6 +202,19 @@ void nak_ensure_temp_directory(const char *prefix_path);
  *  Call during prefix creation. */
 void nak_create_game_symlinks_auto(const char *prefix_path);
 
+/** Translate a host path to the Windows path programs in the prefix see it
+ *  under (e.g. "Z:\games\Skyrim\Data"), mapping drive Z: to / first if
+ *  no drive covers it.
+ *  Returns NULL if the path can't be reached; the reason is logged.
+ *  Caller must free with nak_string_free. */
+char *nak_ensure_prefix_windows_path(const char *prefix_path, const char *path);
+
+/** Describe why the Windows executable at exe_path can't run in the prefix
+ *  (a 64-bit program in a win32 prefix).
+ *  Returns NULL if it can, or if either architecture is unknown.
+ *  Caller must free with nak_string_free. */
+char *nak_prefix_arch_mismatch(const char *prefix_path, const char *exe_path);
+
 /* ========================================================================
  * Tier 6: Logging
  * ======================================================================== */
@@ -174,6 +239,72 @@ char *nak_ensure_dxvk_conf(void);
  *  Returns newly allocated string (free with nak_string_free). */
 char *nak_get_dxvk_conf_path(void);
 
+/* ========================================================================
+ * Tier 8: Save Games
+ * ======================================================================== */
+
+/** Header of a Skyrim (.ess) or Fallout 4 (.fos) save.
+ *  On failure only error is set. */
+typedef struct {
+    char *character;
+    uint32_t level;
+    char *location;
+    char *playtime;
+    uint32_t save_number;
+    uint32_t screenshot_width;
+    uint32_t screenshot_height;
+    char *cosave;                        /* NULL if there's no co-save */
+    char *error;                         /* NULL on success */
+} NakSaveHeader;
+
+/** Read the header of the save at path.
+ *  Free with nak_save_header_free, also on failure. */
+NakSaveHeader nak_parse_save_header(const char *path);
+
+/** Free the strings of a NakSaveHeader */
+void nak_save_header_free(NakSaveHeader header);
+
+/** Script extender co-saves without a save of the same name */
+typedef struct {
+    char **paths;
+    size_t count;
+} NakCosaveList;
+
+/** Find the .skse/.f4se co-saves in save_dir whose save was deleted.
+ *  Free with nak_cosave_list_free. */
+NakCosaveList nak_find_orphaned_cosaves(const char *save_dir);
+
+/** Free a NakCosaveList */
+void nak_cosave_list_free(NakCosaveList list);
+
+/** Delete the co-saves in the NULL-terminated cosaves array, such as the ones
+ *  nak_find_orphaned_cosaves returned; anything else is an error.
+ *  Stores how many were deleted in out_deleted if it isn't NULL.
+ *  Returns NULL on success, or an error message (free with nak_string_free). */
+char *nak_delete_cosaves(const char *const *cosaves, size_t *out_deleted);
+
+/* ========================================================================
+ * Tier 9: Load Order
+ * ======================================================================== */
+
+/** Plugins reordered by nak_apply_loot_userlist.
+ *  On failure only error is set. */
+typedef struct {
+    char **plugins;
+    size_t count;
+    char *error;                         /* NULL on success */
+} NakPluginOrder;
+
+/** Reorder plugins, a NULL-terminated array in load order, to satisfy the
+ *  after, req and group rules of a LOOT userlist.yaml.
+ *  A cycle in the rules is an error naming the plugins or groups in it.
+ *  Free with nak_plugin_order_free, also on failure. */
+NakPluginOrder nak_apply_loot_userlist(const char *const *plugins,
+                                       const char *userlist_yaml);
+
+/** Free a NakPluginOrder */
+void nak_plugin_order_free(NakPluginOrder order);
+
 /* ========================================================================
  * General
  * ======================================================================== */
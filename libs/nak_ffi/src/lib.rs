@@ -35,6 +35,25 @@ unsafe fn from_cstr<'a>(p: *const c_char) -> &'a str {
     }
 }
 
+/// The strings of a null-terminated array, empty if the array itself is null
+unsafe fn from_cstr_array<'a>(p: *const *const c_char) -> Vec<&'a str> {
+    let mut strings = Vec::new();
+    if p.is_null() {
+        return strings;
+    }
+
+    let mut i = 0;
+    loop {
+        let s = unsafe { *p.add(i) };
+        if s.is_null() {
+            break;
+        }
+        strings.push(unsafe { from_cstr(s) });
+        i += 1;
+    }
+    strings
+}
+
 fn error_to_cstring(e: Box<dyn std::error::Error>) -> *mut c_char {
     to_cstring(&e.to_string())
 }
@@ -70,6 +89,8 @@ pub struct NakGame {
     pub appdata_roaming_folder: *mut c_char,
     pub registry_path: *mut c_char,
     pub registry_value: *mut c_char,
+    pub wine_name: *mut c_char, // null unless the launcher keeps a wine build per game
+    pub wine_bin: *mut c_char,
 }
 
 /// List of detected games
@@ -94,6 +115,8 @@ struct CachedGame {
     appdata_roaming_folder: Option<String>,
     registry_path: Option<String>,
     registry_value: Option<String>,
+    wine_name: Option<String>,
+    wine_bin: Option<String>,
 }
 
 #[derive(Clone, Default)]
@@ -132,6 +155,11 @@ fn detect_games_cached() -> CachedGameList {
                 appdata_roaming_folder: g.appdata_roaming_folder.clone(),
                 registry_path: g.registry_path.clone(),
                 registry_value: g.registry_value.clone(),
+                wine_name: g.wine.as_ref().map(|w| w.name.clone()),
+                wine_bin: g
+                    .wine
+                    .as_ref()
+                    .map(|w| w.bin.to_string_lossy().into_owned()),
             })
             .collect(),
         steam_count: result.steam_count,
@@ -165,6 +193,8 @@ pub extern "C" fn nak_detect_all_games() -> NakGameList {
             appdata_roaming_folder: to_cstring_opt(g.appdata_roaming_folder.as_deref()),
             registry_path: to_cstring_opt(g.registry_path.as_deref()),
             registry_value: to_cstring_opt(g.registry_value.as_deref()),
+            wine_name: to_cstring_opt(g.wine_name.as_deref()),
+            wine_bin: to_cstring_opt(g.wine_bin.as_deref()),
         })
         .collect();
 
@@ -197,6 +227,8 @@ pub unsafe extern "C" fn nak_game_list_free(list: NakGameList) {
         free_if_nonnull(g.appdata_roaming_folder);
         free_if_nonnull(g.registry_path);
         free_if_nonnull(g.registry_value);
+        free_if_nonnull(g.wine_name);
+        free_if_nonnull(g.wine_bin);
     }
 }
 
@@ -218,6 +250,8 @@ pub struct NakKnownGame {
     pub registry_path: *const c_char,
     pub registry_value: *const c_char,
     pub steam_folder: *const c_char,
+    pub min_proton: *const c_char, // null if any Proton works
+    pub recommended_proton: *const c_char, // null if there's no recommendation
 }
 
 // We need to leak CStrings for the static known games list since the Rust statics
@@ -242,6 +276,8 @@ static KNOWN_GAMES_FFI: std::sync::LazyLock<KnownGamesVec> = std::sync::LazyLock
                 registry_path: leak_str(kg.registry_path),
                 registry_value: leak_str(kg.registry_value),
                 steam_folder: leak_str(kg.steam_folder),
+                min_proton: leak_str_opt(kg.min_proton),
+                recommended_proton: leak_str_opt(kg.recommended_proton),
             })
             .collect(),
     )
@@ -270,6 +306,34 @@ pub unsafe extern "C" fn nak_get_known_games(out_count: *mut usize) -> *const Na
     games.as_ptr()
 }
 
+/// Resolve the executables started by a known game launcher.
+///
+/// Returns the full paths of the executables the launcher at `exe_path` may
+/// start, separated by newlines, or null if it is not a known launcher
+/// (caller must free with nak_string_free).
+///
+/// # Safety
+///
+/// `exe_path` must be null or point to a NUL-terminated string that stays valid for the call.
+#[no_mangle]
+pub unsafe extern "C" fn nak_resolve_game_launcher(exe_path: *const c_char) -> *mut c_char {
+    let exe = unsafe { from_cstr(exe_path) };
+    if exe.is_empty() {
+        return ptr::null_mut();
+    }
+
+    match nak_rust::game_finder::resolve_launcher(Path::new(exe)) {
+        Some(games) => {
+            let joined: Vec<String> = games
+                .iter()
+                .map(|p| p.to_string_lossy().into_owned())
+                .collect();
+            to_cstring(&joined.join("\n"))
+        }
+        None => ptr::null_mut(),
+    }
+}
+
 // ============================================================================
 // Tier 2: Proton Detection
 // ============================================================================
@@ -329,6 +393,111 @@ pub unsafe extern "C" fn nak_proton_list_free(list: NakProtonList) {
     }
 }
 
+/// Master plugins of the known game with the Steam or GOG app id `app_id`
+/// that are missing from `data_dir`, the directory the game loads plugins from
+///
+/// Returns the missing file names separated by newlines, or null if none are
+/// missing or the game isn't known (caller must free with nak_string_free).
+///
+/// # Safety
+///
+/// `app_id` and `data_dir` must each be null or point to a NUL-terminated
+/// string that stays valid for the call.
+#[no_mangle]
+pub unsafe extern "C" fn nak_missing_master_plugins(
+    app_id: *const c_char,
+    data_dir: *const c_char,
+) -> *mut c_char {
+    let app_id = unsafe { from_cstr(app_id) };
+    let dir = unsafe { from_cstr(data_dir) };
+
+    let Some(game) = nak_rust::game_finder::find_by_steam_id(app_id)
+        .or_else(|| nak_rust::game_finder::find_by_gog_id(app_id))
+    else {
+        return ptr::null_mut();
+    };
+
+    let missing = game.missing_master_plugins(Path::new(dir));
+    if missing.is_empty() {
+        ptr::null_mut()
+    } else {
+        to_cstring(&missing.join("\n"))
+    }
+}
+
+/// Locate the install directory of the known game with the Steam or GOG app
+/// id `app_id` from the registry of `prefix_path`, or of the game's own Steam
+/// prefix if `prefix_path` is null or empty.
+///
+/// Returns a newly allocated string (caller must free with nak_string_free),
+/// or null if the game isn't known or the registry has no existing directory.
+///
+/// # Safety
+///
+/// `app_id` and `prefix_path` must each be null or point to a NUL-terminated
+/// string that stays valid for the call.
+#[no_mangle]
+pub unsafe extern "C" fn nak_resolve_game_dir_from_registry(
+    app_id: *const c_char,
+    prefix_path: *const c_char,
+) -> *mut c_char {
+    let app_id = unsafe { from_cstr(app_id) };
+    let prefix = unsafe { from_cstr(prefix_path) };
+
+    let Some(game) = nak_rust::game_finder::find_by_steam_id(app_id)
+        .or_else(|| nak_rust::game_finder::find_by_gog_id(app_id))
+    else {
+        return ptr::null_mut();
+    };
+
+    let prefix = (!prefix.is_empty()).then(|| Path::new(prefix));
+    match nak_rust::game_finder::resolve_game_dir_from_registry(prefix, game) {
+        Some(dir) => to_cstring(&dir.to_string_lossy()),
+        None => ptr::null_mut(),
+    }
+}
+
+/// Whether the Proton named `proton_name` is at least version `min_version`,
+/// such as the `min_proton` of a known game; returns 1 if it is or if either
+/// version can't be read, 0 otherwise
+///
+/// # Safety
+///
+/// `proton_name` and `min_version` must each be null or point to a NUL-terminated string that stays valid
+/// for the call.
+#[no_mangle]
+pub unsafe extern "C" fn nak_proton_meets_minimum(
+    proton_name: *const c_char,
+    min_version: *const c_char,
+) -> c_int {
+    let name = unsafe { from_cstr(proton_name) };
+    let min = unsafe { from_cstr(min_version) };
+    nak_rust::steam::proton_name_meets_minimum(name, min) as c_int
+}
+
+/// Why the Proton named `proton_name` is a poor fit for the known game with
+/// the given Steam app id, checking its minimum and recommended Proton
+///
+/// Returns null if it's fine or the game isn't known, otherwise a message
+/// (caller must free with nak_string_free).
+///
+/// # Safety
+///
+/// `steam_app_id` and `proton_name` must each be null or point to a NUL-terminated string that stays valid
+/// for the call.
+#[no_mangle]
+pub unsafe extern "C" fn nak_proton_warning(
+    steam_app_id: *const c_char,
+    proton_name: *const c_char,
+) -> *mut c_char {
+    let app_id = unsafe { from_cstr(steam_app_id) };
+    let name = unsafe { from_cstr(proton_name) };
+
+    nak_rust::game_finder::find_by_steam_id(app_id)
+        .and_then(|game| game.proton_warning(name))
+        .map_or(ptr::null_mut(), |warning| to_cstring(&warning))
+}
+
 // ============================================================================
 // Tier 3: Steam Paths
 // ============================================================================
@@ -362,6 +531,7 @@ pub type NakProgressCallback = Option<unsafe extern "C" fn(c_float)>;
 ///
 /// This is a blocking call. Use callbacks for progress updates.
 /// `cancel_flag` should point to an int that can be set to non-zero to cancel.
+/// Steps recorded as completed in the prefix are skipped unless `force` is non-zero.
 ///
 /// Returns null on success, or an error message (caller must free with nak_string_free).
 #[no_mangle]
@@ -374,6 +544,7 @@ pub unsafe extern "C" fn nak_install_all_dependencies(
     progress_cb: NakProgressCallback,
     cancel_flag: *const c_int,
     app_id: u32,
+    force: c_int,
 ) -> *mut c_char {
     let prefix = unsafe { from_cstr(prefix_path) };
     let _proton_name = unsafe { from_cstr(proton_name) };
@@ -390,6 +561,91 @@ pub unsafe extern "C" fn nak_install_all_dependencies(
         }
     };
 
+    let result = with_task_context(status_cb, log_cb, progress_cb, cancel_flag, |ctx| {
+        nak_rust::installers::install_all_dependencies(
+            Path::new(prefix),
+            &proton,
+            ctx,
+            0.0,
+            1.0,
+            app_id,
+            force != 0,
+        )
+    });
+
+    match result {
+        Ok(()) => ptr::null_mut(),
+        Err(e) => error_to_cstring(e),
+    }
+}
+
+/// Install only the named dependency components to an existing prefix
+///
+/// `components` is a null-terminated array of names such as "vcrun2022",
+/// "dotnet-sdk-9" or "win11"; unknown names fail before anything is installed.
+/// Callbacks, `cancel_flag` and `force` work as in `nak_install_all_dependencies`.
+///
+/// Returns null on success, or an error message (caller must free with nak_string_free).
+///
+/// # Safety
+///
+/// `prefix_path` and `proton_path` must be null or NUL-terminated strings,
+/// `components` null or a null-terminated array of such strings, and
+/// `cancel_flag` null or valid to read until the call returns.
+#[no_mangle]
+pub unsafe extern "C" fn nak_install_dependencies_selected(
+    prefix_path: *const c_char,
+    proton_path: *const c_char,
+    components: *const *const c_char,
+    status_cb: NakStatusCallback,
+    log_cb: NakLogCallback,
+    progress_cb: NakProgressCallback,
+    cancel_flag: *const c_int,
+    force: c_int,
+) -> *mut c_char {
+    let prefix = unsafe { from_cstr(prefix_path) };
+    let proton_path_str = unsafe { from_cstr(proton_path) };
+
+    let names = unsafe { from_cstr_array(components) };
+    if names.is_empty() {
+        return to_cstring("No dependency components selected");
+    }
+
+    let proton = match find_proton_by_path(proton_path_str) {
+        Some(p) => p,
+        None => {
+            return to_cstring(&format!(
+                "Proton not found at path: {}",
+                proton_path_str
+            ));
+        }
+    };
+
+    let result = with_task_context(status_cb, log_cb, progress_cb, cancel_flag, |ctx| {
+        nak_rust::installers::install_selected_dependencies(
+            Path::new(prefix),
+            &proton,
+            ctx,
+            &names,
+            force != 0,
+        )
+    });
+
+    match result {
+        Ok(()) => ptr::null_mut(),
+        Err(e) => error_to_cstring(e),
+    }
+}
+
+/// Run `f` with a task context forwarding to the C callbacks, cancelled once
+/// the int behind `cancel_flag` becomes non-zero
+fn with_task_context<T>(
+    status_cb: NakStatusCallback,
+    log_cb: NakLogCallback,
+    progress_cb: NakProgressCallback,
+    cancel_flag: *const c_int,
+    f: impl FnOnce(&nak_rust::installers::TaskContext) -> T,
+) -> T {
     // Build cancel flag from raw pointer
     let cancel = Arc::new(AtomicBool::new(false));
     let cancel_clone = cancel.clone();
@@ -430,23 +686,13 @@ pub unsafe extern "C" fn nak_install_all_dependencies(
         cancel.clone(),
     );
 
-    let result = nak_rust::installers::install_all_dependencies(
-        Path::new(prefix),
-        &proton,
-        &ctx,
-        0.0,
-        1.0,
-        app_id,
-    );
+    let result = f(&ctx);
 
     // Stop the cancel polling thread
     cancel.store(true, Ordering::Relaxed);
     let _ = poll_handle.join();
 
-    match result {
-        Ok(()) => ptr::null_mut(),
-        Err(e) => error_to_cstring(e),
-    }
+    result
 }
 
 /// Apply Wine registry settings to a prefix
@@ -564,6 +810,63 @@ pub unsafe extern "C" fn nak_create_game_symlinks_auto(prefix_path: *const c_cha
     nak_rust::installers::symlinks::create_game_symlinks_auto(Path::new(prefix));
 }
 
+/// Translate a host path to the Windows path programs in the prefix see it
+/// under, mapping drive Z: to / first if no drive covers it.
+///
+/// Returns a newly allocated string (caller must free with nak_string_free),
+/// or null if the path can't be reached from the prefix; the reason is logged.
+///
+/// # Safety
+///
+/// `prefix_path` and `path` must each be null or point to a NUL-terminated string that stays valid
+/// for the call.
+#[no_mangle]
+pub unsafe extern "C" fn nak_ensure_prefix_windows_path(
+    prefix_path: *const c_char,
+    path: *const c_char,
+) -> *mut c_char {
+    let prefix = unsafe { from_cstr(prefix_path) };
+    let path = unsafe { from_cstr(path) };
+    if prefix.is_empty() || path.is_empty() {
+        return ptr::null_mut();
+    }
+
+    match nak_rust::installers::drives::ensure_windows_path(Path::new(prefix), Path::new(path))
+    {
+        Ok(p) => to_cstring(&p),
+        Err(e) => {
+            nak_rust::logging::log_warning(&e.to_string());
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Describe why the Windows executable at `exe_path` can't run in the prefix.
+///
+/// Returns a newly allocated string (caller must free with nak_string_free),
+/// or null if it can or either architecture is unknown.
+///
+/// # Safety
+///
+/// `prefix_path` and `exe_path` must each be null or point to a NUL-terminated string that stays valid
+/// for the call.
+#[no_mangle]
+pub unsafe extern "C" fn nak_prefix_arch_mismatch(
+    prefix_path: *const c_char,
+    exe_path: *const c_char,
+) -> *mut c_char {
+    let prefix = unsafe { from_cstr(prefix_path) };
+    let exe = unsafe { from_cstr(exe_path) };
+    if prefix.is_empty() || exe.is_empty() {
+        return ptr::null_mut();
+    }
+
+    match nak_rust::installers::arch::arch_mismatch(Path::new(prefix), Path::new(exe)) {
+        Some(message) => to_cstring(&message),
+        None => ptr::null_mut(),
+    }
+}
+
 // ============================================================================
 // Tier 6: Logging
 // ============================================================================
@@ -612,6 +915,220 @@ pub extern "C" fn nak_get_dxvk_conf_path() -> *mut c_char {
     to_cstring(&path.to_string_lossy())
 }
 
+// ============================================================================
+// Tier 8: Save Games
+// ============================================================================
+
+/// Header of a Skyrim (.ess) or Fallout 4 (.fos) save (C-compatible)
+///
+/// On failure only `error` is set; free with nak_save_header_free either way.
+#[repr(C)]
+pub struct NakSaveHeader {
+    pub character: *mut c_char,
+    pub level: u32,
+    pub location: *mut c_char,
+    pub playtime: *mut c_char,
+    pub save_number: u32,
+    pub screenshot_width: u32,
+    pub screenshot_height: u32,
+    pub cosave: *mut c_char, // null if there's no script extender co-save
+    pub error: *mut c_char,  // null on success
+}
+
+/// Read the header of the save at `path`
+///
+/// # Safety
+///
+/// `path` must be null or point to a NUL-terminated string that stays valid for the call.
+#[no_mangle]
+pub unsafe extern "C" fn nak_parse_save_header(path: *const c_char) -> NakSaveHeader {
+    let path = unsafe { from_cstr(path) };
+
+    match nak_rust::saves::parse_save_header(Path::new(path)) {
+        Ok(header) => NakSaveHeader {
+            character: to_cstring(&header.character),
+            level: header.level,
+            location: to_cstring(&header.location),
+            playtime: to_cstring(&header.playtime),
+            save_number: header.save_number,
+            screenshot_width: header.screenshot_width,
+            screenshot_height: header.screenshot_height,
+            cosave: to_cstring_opt(header.cosave.as_deref().and_then(|p| p.to_str())),
+            error: ptr::null_mut(),
+        },
+        Err(e) => NakSaveHeader {
+            character: ptr::null_mut(),
+            level: 0,
+            location: ptr::null_mut(),
+            playtime: ptr::null_mut(),
+            save_number: 0,
+            screenshot_width: 0,
+            screenshot_height: 0,
+            cosave: ptr::null_mut(),
+            error: error_to_cstring(e),
+        },
+    }
+}
+
+/// Free the strings of a NakSaveHeader
+///
+/// # Safety
+///
+/// `header` must come from nak_parse_save_header and not be freed before.
+#[no_mangle]
+pub unsafe extern "C" fn nak_save_header_free(header: NakSaveHeader) {
+    free_if_nonnull(header.character);
+    free_if_nonnull(header.location);
+    free_if_nonnull(header.playtime);
+    free_if_nonnull(header.cosave);
+    free_if_nonnull(header.error);
+}
+
+/// Co-saves without a save of the same name (C-compatible)
+#[repr(C)]
+pub struct NakCosaveList {
+    pub paths: *mut *mut c_char,
+    pub count: usize,
+}
+
+/// Find the script extender co-saves in `save_dir` whose save was deleted
+///
+/// # Safety
+///
+/// `save_dir` must be null or point to a NUL-terminated string that stays valid for the call.
+#[no_mangle]
+pub unsafe extern "C" fn nak_find_orphaned_cosaves(save_dir: *const c_char) -> NakCosaveList {
+    let dir = unsafe { from_cstr(save_dir) };
+
+    let mut paths: Vec<*mut c_char> = nak_rust::saves::find_orphaned_cosaves(Path::new(dir))
+        .iter()
+        .map(|p| to_cstring(&p.to_string_lossy()))
+        .collect();
+
+    let list = NakCosaveList {
+        paths: paths.as_mut_ptr(),
+        count: paths.len(),
+    };
+    std::mem::forget(paths);
+    list
+}
+
+/// Free a NakCosaveList
+///
+/// # Safety
+///
+/// `list` must come from nak_find_orphaned_cosaves and not be freed before.
+#[no_mangle]
+pub unsafe extern "C" fn nak_cosave_list_free(list: NakCosaveList) {
+    if list.paths.is_null() {
+        return;
+    }
+    let paths = unsafe { Vec::from_raw_parts(list.paths, list.count, list.count) };
+    for p in paths {
+        free_if_nonnull(p);
+    }
+}
+
+/// Delete `cosaves`, a null-terminated array of co-save paths such as the ones
+/// nak_find_orphaned_cosaves returned; anything that isn't a co-save is an error
+///
+/// Stores how many were deleted in `out_deleted` if it isn't null. Returns
+/// null on success, or an error message (caller must free with nak_string_free).
+///
+/// # Safety
+///
+/// `cosaves` must be null or a null-terminated array of NUL-terminated strings
+/// valid for the call, and `out_deleted` null or valid to write.
+#[no_mangle]
+pub unsafe extern "C" fn nak_delete_cosaves(
+    cosaves: *const *const c_char,
+    out_deleted: *mut usize,
+) -> *mut c_char {
+    let paths: Vec<PathBuf> = unsafe { from_cstr_array(cosaves) }
+        .into_iter()
+        .map(PathBuf::from)
+        .collect();
+
+    match nak_rust::saves::delete_cosaves(&paths) {
+        Ok(deleted) => {
+            if !out_deleted.is_null() {
+                unsafe { *out_deleted = deleted };
+            }
+            ptr::null_mut()
+        }
+        Err(e) => error_to_cstring(e),
+    }
+}
+
+// ============================================================================
+// Tier 9: Load Order
+// ============================================================================
+
+/// Plugins reordered by nak_apply_loot_userlist (C-compatible)
+///
+/// On failure only `error` is set; free with nak_plugin_order_free either way.
+#[repr(C)]
+pub struct NakPluginOrder {
+    pub plugins: *mut *mut c_char,
+    pub count: usize,
+    pub error: *mut c_char, // null on success
+}
+
+/// Reorder `plugins`, a null-terminated array in load order, to satisfy the
+/// `after`, `req` and `group` rules of a LOOT userlist
+///
+/// # Safety
+///
+/// `plugins` must be null or a null-terminated array of NUL-terminated
+/// strings and `userlist_yaml` null or a NUL-terminated string, all valid for
+/// the call.
+#[no_mangle]
+pub unsafe extern "C" fn nak_apply_loot_userlist(
+    plugins: *const *const c_char,
+    userlist_yaml: *const c_char,
+) -> NakPluginOrder {
+    let names: Vec<String> = unsafe { from_cstr_array(plugins) }
+        .into_iter()
+        .map(String::from)
+        .collect();
+    let userlist = unsafe { from_cstr(userlist_yaml) };
+
+    match nak_rust::load_order::apply_loot_userlist(&names, userlist) {
+        Ok(sorted) => {
+            let mut plugins: Vec<*mut c_char> = sorted.iter().map(|p| to_cstring(p)).collect();
+
+            let order = NakPluginOrder {
+                plugins: plugins.as_mut_ptr(),
+                count: plugins.len(),
+                error: ptr::null_mut(),
+            };
+            std::mem::forget(plugins);
+            order
+        }
+        Err(e) => NakPluginOrder {
+            plugins: ptr::null_mut(),
+            count: 0,
+            error: to_cstring(&e.to_string()),
+        },
+    }
+}
+
+/// Free a NakPluginOrder
+///
+/// # Safety
+///
+/// `order` must come from nak_apply_loot_userlist and not be freed before.
+#[no_mangle]
+pub unsafe extern "C" fn nak_plugin_order_free(order: NakPluginOrder) {
+    if !order.plugins.is_null() {
+        let plugins = unsafe { Vec::from_raw_parts(order.plugins, order.count, order.count) };
+        for p in plugins {
+            free_if_nonnull(p);
+        }
+    }
+    free_if_nonnull(order.error);
+}
+
 // ============================================================================
 // General: String free
 // ============================================================================
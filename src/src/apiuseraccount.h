@@ -1,6 +1,7 @@
 #ifndef APIUSERACCOUNT_H
 #define APIUSERACCOUNT_H
 
+#include <QDateTime>
 #include <QString>
 
 /**
@@ -36,6 +37,11 @@ struct APILimits
 
   // remaining number of requests this hour
   int remainingHourlyRequests = 0;
+
+  // when the daily and hourly requests are replenished, invalid if the server
+  // didn't say
+  QDateTime dailyReset;
+  QDateTime hourlyReset;
 };
 
 /**
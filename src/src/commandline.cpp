@@ -1,5 +1,6 @@
 #include "commandline.h"
 #include "env.h"
+#include "envmodule.h"
 #include "instancemanager.h"
 #include "loglist.h"
 #include "messagedialog.h"
@@ -10,9 +11,12 @@
 #include <log.h>
 #include <report.h>
 #include <QDir>
+#include <QEventLoop>
 #include <QFile>
 #include <QSettings>
+#include <QStandardPaths>
 #include <QTextStream>
+#include <QTimer>
 
 namespace cl
 {
@@ -55,8 +59,8 @@ CommandLine::CommandLine() : m_command(nullptr)
   createOptions();
 
   add<RunCommand, ReloadPluginCommand, DownloadFileCommand, RefreshCommand,
-      CrashDumpCommand, LaunchCommand, CreatePortableCommand,
-      ListInstancesCommand, InfoCommand>();
+      CrashDumpCommand, LaunchCommand, MountCommand, ModFilesCommand,
+      CreatePortableCommand, ListInstancesCommand, InfoCommand>();
 }
 
 std::optional<int> CommandLine::process(const std::wstring& line)
@@ -715,8 +719,12 @@ Command::Meta RunCommand::meta() const
 
           "Runs a program or a file with the virtual filesystem. If NAME is a path\n"
           "to a non-executable file, the program that is associated with the file\n"
-          "extension is run instead. With -e, NAME must refer to the name of an\n"
-          "executable in the instance (for example, \"SKSE\")."};
+          "extension is run instead. A NAME without a path that isn't a file in\n"
+          "the current directory is looked up in PATH, so a shell or a Wine tool can\n"
+          "be run in the prefix without setting it up as an executable. With -e,\n"
+          "NAME must refer to the name of an executable in the instance (for\n"
+          "example, \"SKSE\"). With -t, NAME must refer to a tool chain, whose\n"
+          "executables are run one after another."};
 }
 
 po::options_description RunCommand::getVisibleOptions() const
@@ -726,6 +734,8 @@ po::options_description RunCommand::getVisibleOptions() const
   d.add_options()("executable,e",
                   po::value<bool>()->default_value(false)->zero_tokens(),
                   "the name is a configured executable name")(
+      "chain,t", po::value<bool>()->default_value(false)->zero_tokens(),
+      "the name is a configured tool chain name")(
       "arguments,a", po::value<std::string>(), "override arguments")(
       "cwd,c", po::value<std::string>(), "override working directory");
 
@@ -763,6 +773,35 @@ std::optional<int> RunCommand::runPostOrganizer(OrganizerCore& core)
   try {
     // make sure MO doesn't exit even if locking is disabled, ForceWait and
     // PreventExit will do that
+    if (vm()["chain"].as<bool>()) {
+      const auto& exes = *core.executablesList();
+
+      const auto* chain = exes.findToolChain(program, true);
+      if (!chain) {
+        chain = exes.findToolChain(program, false);
+
+        if (!chain) {
+          reportError(
+              QObject::tr("Tool chain '%1' not found in instance '%2'.")
+                  .arg(program)
+                  .arg(InstanceManager::singleton().currentInstance()->displayName()));
+
+          return 1;
+        }
+      }
+
+      const auto r = core.runToolChain(*chain);
+      if (r == ProcessRunner::Error) {
+        reportError(
+            QObject::tr("Tool chain '%1' failed. The logs might have more information.")
+                .arg(program));
+
+        return 1;
+      }
+
+      return 0;
+    }
+
     auto p = core.processRunner();
 
     if (vm()["executable"].as<bool>()) {
@@ -787,7 +826,16 @@ std::optional<int> RunCommand::runPostOrganizer(OrganizerCore& core)
 
       p.setFromExecutable(*itor);
     } else {
-      p.setFromFile(nullptr, QFileInfo(program));
+      QFileInfo target(program);
+
+      if (!target.exists() && !program.contains('/') && !program.contains('\\')) {
+        const QString found = QStandardPaths::findExecutable(program);
+        if (!found.isEmpty()) {
+          target = QFileInfo(found);
+        }
+      }
+
+      p.setFromFile(nullptr, target);
     }
 
     if (vm().count("arguments")) {
@@ -923,6 +971,215 @@ std::optional<int> RefreshCommand::runPostOrganizer(OrganizerCore& core)
   return {};
 }
 
+Command::Meta MountCommand::meta() const
+{
+  return {"mount", "mounts the virtual filesystem without running a program",
+          "[options]",
+
+          "Mounts the virtual filesystem of the instance for a game started some\n"
+          "other way, such as directly from Steam. Without --wait, it stays mounted\n"
+          "until Mod Organizer is closed. With --wait, Mod Organizer waits for the\n"
+          "game to start and to exit, then unmounts, moves the files the game wrote\n"
+          "to overwrite and exits. The game is recognized by its Steam app id with\n"
+          "--app-id, or else by the name of its program."};
+}
+
+po::options_description MountCommand::getVisibleOptions() const
+{
+  po::options_description d;
+
+  d.add_options()("wait,w", po::value<bool>()->default_value(false)->zero_tokens(),
+                  "unmount and exit once the game has exited")(
+      "app-id", po::value<std::string>(),
+      "Steam app id of the game process to wait for")(
+      "process,p", po::value<std::string>(),
+      "program name of the game process to wait for, defaults to the game's binary")(
+      "timeout", po::value<int>()->default_value(300),
+      "seconds to wait for the game to start");
+
+  return d;
+}
+
+std::optional<int> MountCommand::runPostOrganizer(OrganizerCore& core)
+{
+#ifdef _WIN32
+  reportError(QObject::tr("Mounting without running a program is not supported on "
+                          "this platform."));
+  return 1;
+#else
+  const auto* game = core.managedGame();
+  const QFileInfo binary(game->gameDirectory().absoluteFilePath(game->binaryName()));
+
+  if (!core.beforeRun(binary, game->gameDirectory(), {},
+                      core.currentProfile()->name(), {}, {})) {
+    return 1;
+  }
+
+  if (!vm()["wait"].as<bool>()) {
+    log::info("virtual filesystem mounted, it stays mounted until Mod Organizer is "
+              "closed");
+    return {};
+  }
+
+  const QString appId = vm().count("app-id")
+                            ? QString::fromStdString(vm()["app-id"].as<std::string>())
+                            : QString();
+
+  const QString exeName = vm().count("process")
+                              ? QString::fromStdString(vm()["process"].as<std::string>())
+                              : binary.fileName();
+
+  const bool started =
+      waitForGame(appId, exeName, std::chrono::seconds(vm()["timeout"].as<int>()));
+
+  core.afterRun(binary, 0);
+
+  if (!started) {
+    reportError(QObject::tr("The game didn't start, the virtual filesystem was "
+                            "unmounted."));
+    return 1;
+  }
+
+  return 0;
+#endif
+}
+
+#ifndef _WIN32
+bool MountCommand::waitForGame(const QString& appId, const QString& exeName,
+                               std::chrono::seconds timeout)
+{
+  using namespace std::chrono;
+
+  // a launcher often starts the game and exits, so the game is only gone once
+  // nothing has matched for a few checks in a row
+  constexpr auto Interval = 1s;
+  constexpr int GoneAfter = 5;
+
+  if (appId.isEmpty()) {
+    log::info("waiting for '{}' to start", exeName);
+  } else {
+    log::info("waiting for a process of Steam app {} to start", appId);
+  }
+
+  const auto start = steady_clock::now();
+  bool started     = false;
+  int missing      = 0;
+
+  QEventLoop loop;
+  QTimer timer;
+  timer.setInterval(Interval);
+
+  QObject::connect(&timer, &QTimer::timeout, [&] {
+    const pid_t pid = env::findGameProcess(appId, exeName);
+
+    if (pid != 0) {
+      if (!started) {
+        log::info("game started with pid {}, waiting for it to exit", pid);
+      }
+
+      started = true;
+      missing = 0;
+    } else if (started) {
+      if (++missing >= GoneAfter) {
+        log::info("game exited");
+        loop.quit();
+      }
+    } else if (steady_clock::now() - start > timeout) {
+      log::error("the game didn't start within {} seconds",
+                 duration_cast<seconds>(timeout).count());
+      loop.quit();
+    }
+  });
+
+  timer.start();
+  loop.exec();
+
+  return started;
+}
+#endif
+
+Command::Meta ModFilesCommand::meta() const
+{
+  return {"mod-files", "lists the files of a mod", "[options] MOD",
+
+          "Lists the files and directories of the given mod as they are on disk,\n"
+          "one per line relative to the mod, with directories ending in '/'.\n"
+          "With --path, only that directory of the mod is listed."};
+}
+
+po::options_description ModFilesCommand::getVisibleOptions() const
+{
+  po::options_description d;
+
+  d.add_options()("path,p", po::value<std::string>(),
+                  "directory in the mod to list, relative to the mod")(
+      "depth,d", po::value<int>()->default_value(0),
+      "how many levels of directories to list, 0 for all");
+
+  return d;
+}
+
+po::options_description ModFilesCommand::getInternalOptions() const
+{
+  po::options_description d;
+
+  d.add_options()("MOD", po::value<std::string>()->required(), "mod name");
+
+  return d;
+}
+
+po::positional_options_description ModFilesCommand::getPositional() const
+{
+  po::positional_options_description d;
+
+  d.add("MOD", 1);
+
+  return d;
+}
+
+std::optional<int> ModFilesCommand::runPostOrganizer(OrganizerCore&)
+{
+  env::Console console;
+
+  const QString name       = QString::fromStdString(vm()["MOD"].as<std::string>());
+  const unsigned int index = ModInfo::getIndex(name);
+  if (index == UINT_MAX) {
+    std::cerr << "Error: no mod named '" << name.toStdString() << "'\n";
+    return 1;
+  }
+
+  std::shared_ptr<const MOBase::IFileTree> tree =
+      ModInfo::getByIndex(index)->fileTree();
+
+  if (vm().count("path")) {
+    const QString path = QString::fromStdString(vm()["path"].as<std::string>());
+    tree = tree->findDirectory(path);
+    if (tree == nullptr) {
+      std::cerr << "Error: '" << path.toStdString() << "' is not a directory of '"
+                << name.toStdString() << "'\n";
+      return 1;
+    }
+  }
+
+  const int depth = vm()["depth"].as<int>();
+
+  // only the directories that are listed get read from disk
+  tree->walk(
+      [&](const QString& parent, std::shared_ptr<const MOBase::FileTreeEntry> entry) {
+        std::cout << (parent + entry->name()).toStdString()
+                  << (entry->isDir() ? "/" : "") << "\n";
+
+        if (entry->isDir() && depth > 0 && parent.count('/') + 1 >= depth) {
+          return MOBase::IFileTree::WalkReturn::SKIP;
+        }
+
+        return MOBase::IFileTree::WalkReturn::CONTINUE;
+      },
+      "/");
+
+  return 0;
+}
+
 Command::Meta CreatePortableCommand::meta() const
 {
   return {"create-portable", "creates a portable MO2 instance", "[options]",
@@ -1,6 +1,7 @@
 #ifndef MODORGANIZER_COMMANDLINE_INCLUDED
 #define MODORGANIZER_COMMANDLINE_INCLUDED
 #include "moshortcut.h"
+#include <chrono>
 #include <memory>
 #include <vector>
 
@@ -231,6 +232,44 @@ protected:
   std::optional<int> runPostOrganizer(OrganizerCore& core) override;
 };
 
+// mounts the vfs without running anything, for games started outside of MO
+// such as directly from Steam; with --wait, waits for the game to start and
+// exit, then unmounts and exits
+//
+class MountCommand : public Command
+{
+protected:
+  Meta meta() const override;
+
+  po::options_description getVisibleOptions() const override;
+
+  std::optional<int> runPostOrganizer(OrganizerCore& core) override;
+
+#ifndef _WIN32
+private:
+  // runs an event loop until the game process has started, or the timeout
+  // expired, and until it exits again; returns false on timeout
+  //
+  bool waitForGame(const QString& appId, const QString& exeName,
+                   std::chrono::seconds timeout);
+#endif
+};
+
+// lists the files of a single mod, from the same lazily populated tree the
+// ui uses, so other tools can browse a mod without building the whole vfs
+//
+class ModFilesCommand : public Command
+{
+protected:
+  Meta meta() const override;
+
+  po::options_description getVisibleOptions() const override;
+  po::options_description getInternalOptions() const override;
+  po::positional_options_description getPositional() const override;
+
+  std::optional<int> runPostOrganizer(OrganizerCore& core) override;
+};
+
 // creates a portable MO2 instance with directory structure and config
 //
 class CreatePortableCommand : public Command
@@ -39,6 +39,7 @@ along with Mod Organizer.  If not, see <http://www.gnu.org/licenses/>.
 #include <QString>
 
 #include <fstream>
+#include <map>
 
 using namespace MOBase;
 using namespace MOShared;
@@ -204,6 +205,70 @@ void DirectoryRefresher::cleanStructure(DirectoryEntry* structure)
   }
 }
 
+namespace
+{
+
+// when several mods ship an archive with the same name, only the copy that wins
+// in the data directory is loaded by the game, so the others must not show up
+// as conflicts
+//
+// returns the archives of the given mod that aren't overridden by a loose copy
+// from another origin in the structure; archives that aren't in the structure
+// are kept
+//
+QStringList winningArchives(const DirectoryEntry& root, const QString& modName,
+                            const QStringList& archives)
+{
+  QStringList out;
+
+  for (const auto& archive : archives) {
+    const auto file = root.findFile(QFileInfo(archive).fileName().toStdWString());
+
+    if (file) {
+      bool fromArchive    = false;
+      const auto originID = file->getOrigin(fromArchive);
+      const auto* origin  = root.findOriginByID(originID);
+
+      if (origin != nullptr && !fromArchive &&
+          QString::fromStdWString(origin->getName()) != modName) {
+        log::debug("refresher: not indexing '{}', the copy from '{}' wins", archive,
+                   origin->getName());
+        continue;
+      }
+    }
+
+    out.push_back(archive);
+  }
+
+  return out;
+}
+
+// lowercase file name of every archive mapped to the entry whose copy wins in
+// the data directory, which is the one with the highest priority
+//
+std::map<QString, std::size_t>
+archiveWinners(const std::vector<DirectoryRefresher::EntryInfo>& entries)
+{
+  std::map<QString, std::size_t> winners;
+
+  for (std::size_t i = 0; i < entries.size(); ++i) {
+    for (const auto& archive : entries[i].archives) {
+      const QString name = QFileInfo(archive).fileName().toLower();
+      auto itor          = winners.find(name);
+
+      if (itor == winners.end()) {
+        winners.emplace(name, i);
+      } else if (entries[i].priority >= entries[itor->second].priority) {
+        itor->second = i;
+      }
+    }
+  }
+
+  return winners;
+}
+
+}  // namespace
+
 void DirectoryRefresher::addModBSAToStructure(DirectoryEntry* root,
                                               const QString& modName, int priority,
                                               const QString& directory,
@@ -222,7 +287,7 @@ void DirectoryRefresher::addModBSAToStructure(DirectoryEntry* root,
   }
 
   std::vector<std::wstring> archivesW;
-  for (auto&& a : archives) {
+  for (auto&& a : winningArchives(*root, modName, archives)) {
     archivesW.push_back(a.toStdWString());
   }
 
@@ -406,6 +471,10 @@ void DirectoryRefresher::addMultipleModsFilesToStructure(
     }
   }
 
+  // the loose files of all mods are added concurrently with the archives, so
+  // the winning copy of an archive can't be looked up in the structure here
+  const auto winners = archiveWinners(entries);
+
   for (std::size_t i = 0; i < entries.size(); ++i) {
     const auto& e  = entries[i];
     const int prio = e.priority + 1;
@@ -434,6 +503,11 @@ void DirectoryRefresher::addMultipleModsFilesToStructure(
 
         mt.archives.clear();
         for (auto&& a : e.archives) {
+          if (winners.at(QFileInfo(a).fileName().toLower()) != i) {
+            log::debug("refresher: not indexing '{}', another mod's copy wins", a);
+            continue;
+          }
+
           mt.archives.push_back(a.toStdWString());
         }
 
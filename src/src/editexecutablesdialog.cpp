@@ -126,6 +126,12 @@ EditExecutablesDialog::EditExecutablesDialog(OrganizerCore& oc, int sel,
   connect(ui->hide, &QCheckBox::toggled, [&] {
     save();
   });
+  connect(ui->forceX11, &QCheckBox::toggled, [&] {
+    save();
+  });
+  connect(ui->redirectModPaths, &QCheckBox::toggled, [&] {
+    save();
+  });
   connect(ui->list->model(), &QAbstractItemModel::rowsMoved, [&] {
     saveOrder();
   });
@@ -391,6 +397,10 @@ void EditExecutablesDialog::clearEdits()
   ui->minimizeToSystemTray->setChecked(false);
   ui->hide->setEnabled(false);
   ui->hide->setChecked(false);
+  ui->forceX11->setEnabled(false);
+  ui->forceX11->setChecked(false);
+  ui->redirectModPaths->setEnabled(false);
+  ui->redirectModPaths->setChecked(false);
 
   m_lastGoodTitle = "";
 }
@@ -407,6 +417,8 @@ void EditExecutablesDialog::setEdits(const Executable& e)
   ui->useApplicationIcon->setChecked(e.usesOwnIcon());
   ui->minimizeToSystemTray->setChecked(e.minimizeToSystemTray());
   ui->hide->setChecked(e.hide());
+  ui->forceX11->setChecked(e.forceX11());
+  ui->redirectModPaths->setChecked(e.redirectModPaths());
 
   m_lastGoodTitle = e.title();
 
@@ -453,6 +465,8 @@ void EditExecutablesDialog::setEdits(const Executable& e)
   ui->forceLoadLibraries->setEnabled(true);
   ui->minimizeToSystemTray->setEnabled(true);
   ui->hide->setEnabled(true);
+  ui->forceX11->setEnabled(true);
+  ui->redirectModPaths->setEnabled(true);
 }
 
 void EditExecutablesDialog::save()
@@ -524,6 +538,18 @@ void EditExecutablesDialog::save()
     e->flags(e->flags() & (~Executable::Hide));
   }
 
+  if (ui->forceX11->isChecked()) {
+    e->flags(e->flags() | Executable::ForceX11);
+  } else {
+    e->flags(e->flags() & (~Executable::ForceX11));
+  }
+
+  if (ui->redirectModPaths->isChecked()) {
+    e->flags(e->flags() | Executable::RedirectModPaths);
+  } else {
+    e->flags(e->flags() & (~Executable::RedirectModPaths));
+  }
+
   setDirty(true);
 }
 
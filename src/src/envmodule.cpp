@@ -1084,6 +1084,84 @@ QString getProcessName(pid_t pid)
   return QString::fromStdString(name);
 }
 
+// the null-separated strings of a /proc/[pid] file, such as cmdline or environ
+static std::vector<std::string> readProcStrings(const std::string& path)
+{
+  std::vector<std::string> v;
+
+  std::ifstream file(path, std::ios::binary);
+  std::string s;
+  while (std::getline(file, s, '\0')) {
+    v.push_back(std::move(s));
+  }
+
+  return v;
+}
+
+pid_t findProcess(const std::function<bool(pid_t)>& matches)
+{
+  DIR* dir = ::opendir("/proc");
+  if (dir == nullptr) {
+    return 0;
+  }
+
+  const pid_t self = ::getpid();
+  pid_t found      = 0;
+
+  while (dirent* entry = ::readdir(dir)) {
+    char* end        = nullptr;
+    const long value = std::strtol(entry->d_name, &end, 10);
+    if (end == entry->d_name || *end != '\0' || value <= 0) {
+      continue;
+    }
+
+    const pid_t pid = static_cast<pid_t>(value);
+    if (pid != self && matches(pid)) {
+      found = pid;
+      break;
+    }
+  }
+
+  ::closedir(dir);
+  return found;
+}
+
+pid_t findGameProcess(const QString& appId, const QString& exeName)
+{
+  return findProcess([&](pid_t pid) {
+    const std::string dir = "/proc/" + std::to_string(pid);
+
+    if (!appId.isEmpty()) {
+      for (const auto& var : readProcStrings(dir + "/environ")) {
+        const auto eq = var.find('=');
+        if (eq == std::string::npos) {
+          continue;
+        }
+
+        const auto name = var.substr(0, eq);
+        if ((name == "SteamAppId" || name == "SteamGameId") &&
+            QString::fromStdString(var.substr(eq + 1)) == appId) {
+          return true;
+        }
+      }
+
+      return false;
+    }
+
+    const auto args = readProcStrings(dir + "/cmdline");
+    if (args.empty()) {
+      return false;
+    }
+
+    // wine shows the windows path of the program
+    QString program = QString::fromStdString(args[0]);
+    program         = program.mid(std::max(program.lastIndexOf('/'),
+                                           program.lastIndexOf('\\')) + 1);
+
+    return program.compare(exeName, Qt::CaseInsensitive) == 0;
+  });
+}
+
 pid_t getProcessParentID(pid_t pid)
 {
   std::string statusPath = "/proc/" + std::to_string(pid) + "/status";
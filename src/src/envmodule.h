@@ -3,6 +3,7 @@
 
 #include <QDateTime>
 #include <QString>
+#include <functional>
 
 #ifdef _WIN32
 #include <windows.h>
@@ -221,6 +222,18 @@ Process getProcessTree(pid_t pid);
 
 QString getProcessName(pid_t pid);
 pid_t getProcessParentID(pid_t pid);
+
+// the first running process other than this one for which the given function
+// returns true; 0 if there's none
+//
+pid_t findProcess(const std::function<bool(pid_t)>& matches);
+
+// a running process other than this one that was started for the given Steam
+// app id (SteamAppId or SteamGameId in its environment) or, when appId is
+// empty, whose program matches exeName case-insensitively, including Windows
+// programs run by wine such as "SkyrimSE.exe"; 0 if there's none
+//
+pid_t findGameProcess(const QString& appId, const QString& exeName);
 #endif
 
 }  // namespace env
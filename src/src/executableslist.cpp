@@ -88,6 +88,12 @@ void ExecutablesList::load(const MOBase::IPluginGame* game, const Settings& s)
     if (map["hide"].toBool())
       flags |= Executable::Hide;
 
+    if (map["forceX11"].toBool())
+      flags |= Executable::ForceX11;
+
+    if (map["redirectModPaths"].toBool())
+      flags |= Executable::RedirectModPaths;
+
     if (map.contains("custom")) {
       // the "custom" setting only exists in older versions
       needsUpgrade = true;
@@ -107,6 +113,17 @@ void ExecutablesList::load(const MOBase::IPluginGame* game, const Settings& s)
   if (needsUpgrade)
     upgradeFromCustom(game);
 
+  m_ToolChains.clear();
+
+  for (auto& map : s.toolChains()) {
+    const auto name = map["name"].toString();
+    if (name.isEmpty()) {
+      continue;
+    }
+
+    setToolChain({name, map["steps"].toStringList()});
+  }
+
   dump();
 }
 
@@ -126,11 +143,26 @@ void ExecutablesList::store(Settings& s)
     map["workingDirectory"]     = item.workingDirectory();
     map["steamAppID"]           = item.steamAppID();
     map["minimizeToSystemTray"] = item.minimizeToSystemTray();
+    map["forceX11"]             = item.forceX11();
+    map["redirectModPaths"]     = item.redirectModPaths();
 
     v.push_back(std::move(map));
   }
 
   s.setExecutables(v);
+
+  std::vector<std::map<QString, QVariant>> chains;
+
+  for (const auto& chain : m_ToolChains) {
+    std::map<QString, QVariant> map;
+
+    map["name"]  = chain.name;
+    map["steps"] = chain.steps;
+
+    chains.push_back(std::move(map));
+  }
+
+  s.setToolChains(chains);
 }
 
 std::vector<Executable>
@@ -307,6 +339,49 @@ std::optional<QString> ExecutablesList::makeNonConflictingTitle(const QString& p
   return {};
 }
 
+const std::vector<ToolChain>& ExecutablesList::toolChains() const
+{
+  return m_ToolChains;
+}
+
+const ToolChain* ExecutablesList::findToolChain(const QString& name,
+                                                bool caseSensitive) const
+{
+  const auto cs = (caseSensitive ? Qt::CaseSensitive : Qt::CaseInsensitive);
+
+  for (const auto& chain : m_ToolChains) {
+    if (chain.name.compare(name, cs) == 0) {
+      return &chain;
+    }
+  }
+
+  return nullptr;
+}
+
+void ExecutablesList::setToolChain(const ToolChain& chain)
+{
+  for (auto& existing : m_ToolChains) {
+    if (existing.name == chain.name) {
+      existing = chain;
+      return;
+    }
+  }
+
+  m_ToolChains.push_back(chain);
+}
+
+void ExecutablesList::removeToolChain(const QString& name)
+{
+  auto itor = std::find_if(m_ToolChains.begin(), m_ToolChains.end(),
+                           [&](auto&& c) {
+                             return c.name == name;
+                           });
+
+  if (itor != m_ToolChains.end()) {
+    m_ToolChains.erase(itor);
+  }
+}
+
 void ExecutablesList::upgradeFromCustom(MOBase::IPluginGame const* game)
 {
   log::debug("upgrading executables list");
@@ -368,6 +443,14 @@ void ExecutablesList::dump() const
       flags.push_back("minimizeToSystemTray");
     }
 
+    if (e.flags() & Executable::ForceX11) {
+      flags.push_back("forceX11");
+    }
+
+    if (e.flags() & Executable::RedirectModPaths) {
+      flags.push_back("redirectModPaths");
+    }
+
     log::debug(" . executable '{}'\n"
                "    binary: {}\n"
                "    arguments: {}\n"
@@ -377,6 +460,10 @@ void ExecutablesList::dump() const
                e.title(), e.binaryInfo().filePath(), e.arguments(), e.steamAppID(),
                e.workingDirectory(), flags.join("|"), e.flags());
   }
+
+  for (const auto& c : m_ToolChains) {
+    log::debug(" . tool chain '{}': {}", c.name, c.steps.join(" -> "));
+  }
 }
 
 Executable::Executable(QString title) : m_title(title) {}
@@ -482,6 +569,16 @@ bool Executable::hide() const
   return m_flags.testFlag(Hide);
 }
 
+bool Executable::forceX11() const
+{
+  return m_flags.testFlag(ForceX11);
+}
+
+bool Executable::redirectModPaths() const
+{
+  return m_flags.testFlag(RedirectModPaths);
+}
+
 void Executable::mergeFrom(const Executable& other)
 {
   // this happens after executables are loaded from settings and plugin
@@ -27,6 +27,7 @@ along with Mod Organizer.  If not, see <http://www.gnu.org/licenses/>.
 
 #include <QFileInfo>
 #include <QMetaType>
+#include <QStringList>
 
 #include <uibase/iexecutable.h>
 
@@ -48,7 +49,9 @@ public:
     ShowInToolbar        = 0x02,
     UseApplicationIcon   = 0x04,
     Hide                 = 0x08,
-    MinimizeToSystemTray = 0x16
+    MinimizeToSystemTray = 0x16,
+    ForceX11             = 0x20,
+    RedirectModPaths     = 0x40
   };
 
   Q_DECLARE_FLAGS(Flags, Flag);
@@ -79,6 +82,8 @@ public:
   bool usesOwnIcon() const;
   bool minimizeToSystemTray() const;
   bool hide() const;
+  bool forceX11() const;
+  bool redirectModPaths() const;
 
   void mergeFrom(const Executable& other);
 
@@ -91,6 +96,18 @@ private:
   Flags m_flags;
 };
 
+/*!
+ * @brief A named sequence of executables that are run one after another,
+ *        with the virtual filesystem staying mounted until the last one exits
+ **/
+struct ToolChain
+{
+  QString name;
+
+  // titles of the executables to run, in order
+  QStringList steps;
+};
+
 /*!
  * @brief List of executables configured to by started from MO
  **/
@@ -188,6 +205,28 @@ public:
    */
   std::optional<QString> makeNonConflictingTitle(const QString& prefix);
 
+  /**
+   * @brief the configured tool chains
+   */
+  const std::vector<ToolChain>& toolChains() const;
+
+  /**
+   * @brief find a tool chain by its name
+   * @return the tool chain, or nullptr if there is none with that name
+   */
+  const ToolChain* findToolChain(const QString& name,
+                                 bool caseSensitive = true) const;
+
+  /**
+   * @brief adds the given tool chain, replacing one with the same name
+   */
+  void setToolChain(const ToolChain& chain);
+
+  /**
+   * @brief removes the tool chain with the given name, if any
+   */
+  void removeToolChain(const QString& name);
+
 private:
   enum SetFlags
   {
@@ -202,6 +241,7 @@ private:
   };
 
   std::vector<Executable> m_Executables;
+  std::vector<ToolChain> m_ToolChains;
 
   /**
    * @brief add the executables preconfigured for this game
@@ -205,6 +205,7 @@ void FileTreeModel::refresh()
   TimeThis tt("FileTreeModel::refresh()");
 
   m_fullyLoaded = false;
+  m_mountedTooltips.clear();
   update(*m_root, *m_core.directoryStructure(), L"", false);
   sortItem(*m_root, false);
 }
@@ -212,6 +213,7 @@ void FileTreeModel::refresh()
 void FileTreeModel::clear()
 {
   m_fullyLoaded = false;
+  m_mountedTooltips.clear();
 
   beginResetModel();
   m_root->clear();
@@ -1187,6 +1189,45 @@ QString FileTreeModel::makeTooltip(const FileTreeItem& item) const
     }
   }
 
+#ifndef _WIN32
+  // what programs actually see while the vfs is mounted, which can differ
+  // from the list above when files were added after the last refresh
+  if (m_core.vfsMountState() != FuseConnector::MountState::Mounted) {
+    m_mountedTooltips.clear();
+    return s;
+  }
+
+  const QString path = item.dataRelativeFilePath();
+  auto mounted       = m_mountedTooltips.find(path);
+
+  if (mounted == m_mountedTooltips.end()) {
+    QString m;
+
+    const auto origins = m_core.vfsFileOrigins(path);
+    if (!origins.empty()) {
+      m += line(tr("Mounted from"), QString()) + ListStart;
+
+      for (auto&& [origin, realPath] : origins) {
+        m += "<li><b>" + origin.toHtmlEscaped() + "</b>: " +
+             realPath.toHtmlEscaped() + "</li>";
+      }
+
+      m += ListEnd;
+
+      const auto winner = m_core.vfsExplainWinner(path);
+      if (winner.reason != VfsWinReason::NotFound) {
+        // wrapped, the explanation can be a few sentences long
+        m += "<p style=\"margin: 0; padding: 0;\"><b>" + tr("Why") + ":</b> " +
+             QString::fromStdString(winner.describe()).toHtmlEscaped() + "</p>";
+      }
+    }
+
+    mounted = m_mountedTooltips.emplace(path, std::move(m)).first;
+  }
+
+  s += mounted->second;
+#endif
+
   return s;
 }
 
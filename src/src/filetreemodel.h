@@ -4,6 +4,7 @@
 #include "filetreeitem.h"
 #include "iconfetcher.h"
 #include "shared/fileregisterfwd.h"
+#include <unordered_map>
 #include <unordered_set>
 
 class OrganizerCore;
@@ -89,6 +90,10 @@ private:
   mutable IconFetcher m_iconFetcher;
   mutable std::vector<QModelIndex> m_iconPending;
   mutable QTimer m_iconPendingTimer;
+
+  // the mounted vfs part of file tooltips by data relative path; building it
+  // looks for the file in every mod, so it's kept until the next refresh
+  mutable std::unordered_map<QString, QString> m_mountedTooltips;
   SortInfo m_sort;
   bool m_fullyLoaded;
   bool m_sortingEnabled;
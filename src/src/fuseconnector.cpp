@@ -1,9 +1,12 @@
 #include "fuseconnector.h"
 
 #include "settings.h"
+#include "vfs/symlinkdeployment.h"
 #include "vfs/vfstree.h"
 
 #include <QCoreApplication>
+#include <QCryptographicHash>
+#include <QDateTime>
 #include <QDir>
 #include <QFile>
 #include <QFileInfo>
@@ -13,37 +16,110 @@
 #include <QVariant>
 
 #include <iplugingame.h>
+#include <uibase/utility.h>
 
 #include <algorithm>
 #include <cerrno>
 #include <cstring>
 #include <fcntl.h>
+#include <linux/fs.h>
 #include <filesystem>
 #include <fstream>
+#include <mutex>
 #include <set>
+#include <unordered_set>
+#include <sys/file.h>
+#include <sys/ioctl.h>
 #include <sys/stat.h>
 #include <unistd.h>
 
 using namespace MOBase;
 
-// Global mount point for signal-handler cleanup (async-signal-safe access).
-static char g_fuseMountPoint[4096] = {0};
+// Global mount points for signal-handler cleanup (async-signal-safe access),
+// one per fuse mount in this process; filled from the front, the first empty
+// slot ends the list.
+static constexpr size_t MaxCrashCleanupMounts = 8;
+static char g_fuseMountPoints[MaxCrashCleanupMounts][4096] = {};
 
-void setFuseMountPointForCrashCleanup(const char* path)
+const char* getFuseMountPointForCrashCleanup(size_t index)
 {
-  if (path != nullptr) {
-    std::strncpy(g_fuseMountPoint, path, sizeof(g_fuseMountPoint) - 1);
-    g_fuseMountPoint[sizeof(g_fuseMountPoint) - 1] = '\0';
-  } else {
-    g_fuseMountPoint[0] = '\0';
+  if (index >= MaxCrashCleanupMounts || g_fuseMountPoints[index][0] == '\0') {
+    return nullptr;
+  }
+
+  return g_fuseMountPoints[index];
+}
+
+namespace
+{
+
+// every FuseConnector with something mounted in this process, for the mount
+// points unmounted by the crash handler
+struct ActiveMount
+{
+  const FuseConnector* owner;
+  std::string mountPoint;
+  bool fuse;
+};
+
+std::mutex g_activeMountsMutex;
+std::vector<ActiveMount> g_activeMounts;
+
+// must be called with g_activeMountsMutex held
+void updateCrashCleanupMountPoints()
+{
+  size_t slot = 0;
+  for (const auto& m : g_activeMounts) {
+    if (!m.fuse || slot == MaxCrashCleanupMounts) {
+      continue;
+    }
+
+    std::strncpy(g_fuseMountPoints[slot], m.mountPoint.c_str(),
+                 sizeof(g_fuseMountPoints[slot]) - 1);
+    g_fuseMountPoints[slot][sizeof(g_fuseMountPoints[slot]) - 1] = '\0';
+    ++slot;
+  }
+
+  for (; slot < MaxCrashCleanupMounts; ++slot) {
+    g_fuseMountPoints[slot][0] = '\0';
   }
 }
 
-const char* getFuseMountPointForCrashCleanup()
+// takes an exclusive lock on a file named after the given path, so no other
+// instance, in this process or another one, mounts over the same data
+// directory or shares a staging directory; returns the locked file
+// descriptor, -1 if another instance holds the lock or 0 if locking isn't
+// possible, in which case the path is used anyway
+int lockMountPath(const std::string& path)
 {
-  return g_fuseMountPoint[0] != '\0' ? g_fuseMountPoint : nullptr;
+  const QByteArray id =
+      QCryptographicHash::hash(QByteArray::fromStdString(path), QCryptographicHash::Sha1)
+          .toHex();
+
+  QString dir = QStandardPaths::writableLocation(QStandardPaths::RuntimeLocation);
+  if (dir.isEmpty()) {
+    dir = QDir::tempPath();
+  }
+
+  const QString lockPath = QDir(dir).filePath("mo2-vfs-" + id + ".lock");
+  const int fd           = ::open(QFile::encodeName(lockPath).constData(),
+                                  O_RDWR | O_CREAT | O_CLOEXEC, 0600);
+  if (fd < 0) {
+    log::warn("cannot create the lock file '{}' for '{}': {}", lockPath,
+              QString::fromStdString(path), std::strerror(errno));
+    return 0;
+  }
+
+  if (::flock(fd, LOCK_EX | LOCK_NB) != 0) {
+    ::close(fd);
+    return -1;
+  }
+
+  return fd;
 }
 
+}  // namespace
+
 namespace
 {
 namespace fs = std::filesystem;
@@ -224,12 +300,79 @@ void setupFuseOps(struct fuse_lowlevel_ops* ops)
   ops->unlink  = mo2_unlink;
   ops->mkdir   = mo2_mkdir;
   ops->release = mo2_release;
+
+  ops->setxattr    = mo2_setxattr;
+  ops->getxattr    = mo2_getxattr;
+  ops->listxattr   = mo2_listxattr;
+  ops->removexattr = mo2_removexattr;
+}
+
+void hashCombine(std::size_t& seed, const std::string& value)
+{
+  seed ^= std::hash<std::string>{}(value) + 0x9e3779b9 + (seed << 6) + (seed >> 2);
+}
+
+// latest modification time of the directory or any directory below it, which
+// changes whenever a file is added or removed anywhere in it
+std::string latestDirectoryChange(const std::string& dir)
+{
+  std::error_code ec;
+  auto latest = fs::last_write_time(dir, ec);
+
+  for (auto it = fs::recursive_directory_iterator(
+           dir, fs::directory_options::skip_permission_denied, ec);
+       it != fs::recursive_directory_iterator(); it.increment(ec)) {
+    if (ec) {
+      break;
+    }
+
+    if (it->is_directory(ec) && !it->is_symlink(ec)) {
+      latest = std::max(latest, it->last_write_time(ec));
+    }
+  }
+
+  return std::to_string(latest.time_since_epoch().count());
 }
 
 }  // namespace
 
+static bool isStaleOrMounted(const QString& path);
+
+// Maps what we know about the session and what the kernel reports for the
+// mount point to a single state.  `sessionAlive` is whether we own a running
+// session (or helper), `listed` whether /proc/mounts has the path and
+// `notConnected` whether probing it fails with ENOTCONN.
+static FuseConnector::MountState classifyMount(bool mounting, bool sessionAlive,
+                                               bool listed, bool notConnected)
+{
+  using MountState = FuseConnector::MountState;
+
+  if (mounting) {
+    return MountState::Mounting;
+  }
+
+  if (sessionAlive) {
+    return (listed && !notConnected) ? MountState::Mounted : MountState::Stale;
+  }
+
+  return (listed || notConnected) ? MountState::Stale : MountState::Unmounted;
+}
+
 FuseConnector::FuseConnector(QObject* parent) : QObject(parent)
 {
+  m_stagingTimer.setSingleShot(true);
+  m_stagingTimer.setInterval(StagingChangedDelay);
+
+  connect(&m_stagingTimer, &QTimer::timeout, this, [this] {
+    m_stagingDirty = false;
+    emit stagingChanged();
+  });
+
+  m_flushTimer.setInterval(StagingFlushInterval);
+  connect(&m_flushTimer, &QTimer::timeout, this, [this] {
+    flushStableStaging();
+  });
+
   log::debug("FUSE connector initialized");
 }
 
@@ -238,6 +381,56 @@ FuseConnector::~FuseConnector()
   unmount();
 }
 
+// sets the compression attribute on a directory so files created in it are
+// compressed by the filesystem; in practice only btrfs honours it, ext4 and xfs
+// don't
+static bool enableCompression(const std::string& dir)
+{
+  const int fd = open(dir.c_str(), O_RDONLY | O_DIRECTORY);
+  if (fd < 0) {
+    return false;
+  }
+
+  Guard closeFd([&] {
+    close(fd);
+  });
+
+  int flags = 0;
+  if (ioctl(fd, FS_IOC_GETFLAGS, &flags) != 0) {
+    return false;
+  }
+
+  if ((flags & FS_COMPR_FL) != 0) {
+    return true;
+  }
+
+  flags |= FS_COMPR_FL;
+  return (ioctl(fd, FS_IOC_SETFLAGS, &flags) == 0);
+}
+
+void FuseConnector::prepareStagingDir()
+{
+  if (m_readOnly) {
+    return;
+  }
+
+  std::error_code ec;
+  fs::create_directories(m_stagingDir, ec);
+
+  if (!Settings::instance().compressStaging()) {
+    return;
+  }
+
+  if (enableCompression(m_stagingDir)) {
+    log::debug("compression enabled for staging directory {}",
+               QString::fromStdString(m_stagingDir));
+  } else {
+    log::warn("cannot enable compression for staging directory {}: {}; staged "
+              "files are only compressed on btrfs",
+              QString::fromStdString(m_stagingDir), std::strerror(errno));
+  }
+}
+
 bool FuseConnector::mount(
     const QString& mount_point, const QString& overwrite_dir, const QString& game_dir,
     const QString& data_dir_name,
@@ -247,6 +440,11 @@ bool FuseConnector::mount(
     unmount();
   }
 
+  m_mounting = true;
+  Guard mountingGuard([&] {
+    m_mounting = false;
+  });
+
   m_overwriteDir = overwrite_dir.toStdString();
   m_gameDir      = game_dir.toStdString();
   m_dataDirName  = data_dir_name.toStdString();
@@ -262,20 +460,87 @@ bool FuseConnector::mount(
   if (!fs::exists(m_dataDirPath)) {
     throw FuseConnectorException(
         QObject::tr("Game data directory does not exist: %1")
-            .arg(QString::fromStdString(m_dataDirPath)));
+            .arg(QString::fromStdString(m_dataDirPath)),
+        FuseConnectorException::Reason::NoDataDirectory);
+  }
+
+  if (!fs::is_directory(m_dataDirPath)) {
+    throw FuseConnectorException(
+        QObject::tr("Mount point is not a directory: %1")
+            .arg(QString::fromStdString(m_dataDirPath)),
+        FuseConnectorException::Reason::NoDataDirectory);
+  }
+
+  // a mount of another instance would look like a stale mount below and be
+  // cleaned up from under it; the locks are let go again if mounting fails
+  releaseMountLocks();
+  Guard releaseLocks([&] {
+    if (!m_mounted) {
+      releaseMountLocks();
+    }
+  });
+
+  m_mountLockFd = lockMountPath(m_mountPoint);
+  if (m_mountLockFd < 0) {
+    throw FuseConnectorException(
+        QObject::tr("Another instance has already mounted %1")
+            .arg(QString::fromStdString(m_mountPoint)),
+        FuseConnectorException::Reason::MountPointBusy);
   }
 
   tryCleanupStaleMount(QString::fromStdString(m_mountPoint));
 
-  if (isFlatpak()) {
-    return mountViaHelper(overwrite_dir, game_dir, data_dir_name, mods);
+  // The data directory is expected to hold the base game files (they stay
+  // reachable through the backing fd), but anything still mounted there after
+  // cleanup belongs to someone else.  Stacking on top of it would hide it and
+  // scan the wrong contents as base files, so refuse instead.
+  if (!waitForMountPointRelease(QString::fromStdString(m_mountPoint))) {
+    throw FuseConnectorException(
+        QObject::tr("Mount point is still in use after stale mount cleanup: %1")
+            .arg(QString::fromStdString(m_mountPoint)),
+        FuseConnectorException::Reason::MountPointBusy);
+  }
+
+  m_deployMode = Settings::instance().symlinkDeployment() ? DeployMode::Symlink
+                                                          : DeployMode::Fuse;
+
+  if (m_deployMode == DeployMode::Symlink) {
+    return mountSymlinks(mods);
   }
 
   const fs::path overwritePath(m_overwriteDir);
   m_stagingDir = (overwritePath.parent_path() / "VFS_staging").string();
+  m_readOnly   = Settings::instance().vfsReadOnly();
+
+  m_stagingLockFd = lockMountPath(m_stagingDir);
+  if (m_stagingLockFd < 0) {
+    throw FuseConnectorException(
+        QObject::tr("Another instance is already using the staging directory %1")
+            .arg(QString::fromStdString(m_stagingDir)),
+        FuseConnectorException::Reason::MountPointBusy);
+  }
+
+  if (m_readOnly) {
+    log::debug("mounting the vfs read-only, programs won't be able to write to it");
+  }
+
+  // the helper uses the same staging directory, so it is prepared here in
+  // both cases
+  prepareStagingDir();
+
+  if (isFlatpak()) {
+    return mountViaHelper(overwrite_dir, game_dir, data_dir_name, mods);
+  }
+
+  // checked up front, fuse_session_mount() only reports a generic failure and
+  // would be retried for nothing
+  if (::access("/dev/fuse", R_OK | W_OK) != 0) {
+    throw FuseConnectorException(
+        QObject::tr("FUSE is not available, /dev/fuse can't be opened"),
+        FuseConnectorException::Reason::FuseUnavailable);
+  }
 
   std::error_code ec;
-  fs::create_directories(m_stagingDir, ec);
   fs::create_directories(m_overwriteDir, ec);
 
   // Scan + cache base game files BEFORE mounting (after mount they're hidden).
@@ -306,14 +571,26 @@ bool FuseConnector::mount(
 
   // Inject file-level data-dir mappings (e.g. plugins.txt, loadorder.txt)
   injectExtraFiles(*tree, m_extraVfsFiles);
+  m_treeExtraVfsFiles = m_extraVfsFiles;
 
   m_context                 = std::make_shared<Mo2FsContext>();
   m_context->tree           = tree;
   m_context->inodes         = std::make_unique<InodeTable>();
   m_context->overwrite      = std::make_unique<OverwriteManager>(m_stagingDir, m_overwriteDir);
+  m_context->overwrite->setUmask(Settings::instance().stagingUmask());
   m_context->backing_dir_fd = m_backingFd;
   m_context->uid            = ::getuid();
   m_context->gid            = ::getgid();
+  m_context->read_only      = m_readOnly;
+
+  // runs on the fuse threads for every write, so only the first change after
+  // each signal posts to the main thread; the rest are folded into it
+  m_context->on_staging_changed = [this] {
+    if (!m_stagingDirty.exchange(true)) {
+      QMetaObject::invokeMethod(
+          this, [this] { m_stagingTimer.start(); }, Qt::QueuedConnection);
+    }
+  };
 
   // NOTE: Do NOT include mount_point here — low-level API passes it
   // separately to fuse_session_mount(). Including it here causes
@@ -328,26 +605,45 @@ bool FuseConnector::mount(
     argv.push_back(s.data());
   }
 
-  struct fuse_args args = FUSE_ARGS_INIT(static_cast<int>(argv.size()), argv.data());
-
   struct fuse_lowlevel_ops ops;
   setupFuseOps(&ops);
 
-  m_session = fuse_session_new(&args, &ops, sizeof(ops), m_context.get());
-  if (m_session == nullptr) {
-    close(m_backingFd);
-    m_backingFd = -1;
-    throw FuseConnectorException(QObject::tr("Failed to create FUSE session"));
-  }
+  auto delay = MountRetryDelay;
+  for (int attempt = 1;; ++attempt) {
+    // a new session per attempt, a session isn't reused after a failed mount
+    struct fuse_args args = FUSE_ARGS_INIT(static_cast<int>(argv.size()), argv.data());
+
+    m_session = fuse_session_new(&args, &ops, sizeof(ops), m_context.get());
+    if (m_session == nullptr) {
+      close(m_backingFd);
+      m_backingFd = -1;
+      throw FuseConnectorException(QObject::tr("Failed to create FUSE session"));
+    }
+
+    if (fuse_session_mount(m_session, m_mountPoint.c_str()) == 0) {
+      break;
+    }
 
-  if (fuse_session_mount(m_session, m_mountPoint.c_str()) != 0) {
     fuse_session_destroy(m_session);
     m_session = nullptr;
-    close(m_backingFd);
-    m_backingFd = -1;
-    throw FuseConnectorException(
-        QObject::tr("Failed to mount FUSE at %1")
-            .arg(QString::fromStdString(m_mountPoint)));
+
+    if (attempt == MountAttempts) {
+      close(m_backingFd);
+      m_backingFd = -1;
+      throw FuseConnectorException(
+          QObject::tr("Failed to mount FUSE at %1 after %2 attempts")
+              .arg(QString::fromStdString(m_mountPoint))
+              .arg(MountAttempts));
+    }
+
+    log::warn("mounting FUSE at {} failed, retrying in {}ms (attempt {} of {})",
+              QString::fromStdString(m_mountPoint), delay.count(), attempt + 1,
+              MountAttempts);
+
+    std::this_thread::sleep_for(delay);
+    delay *= 2;
+
+    tryCleanupStaleMount(QString::fromStdString(m_mountPoint));
   }
 
   m_fuseThread = std::thread([this]() {
@@ -355,15 +651,31 @@ bool FuseConnector::mount(
   });
 
   m_mounted = true;
-  setFuseMountPointForCrashCleanup(m_mountPoint.c_str());
+  registerActiveMount();
   log::debug("FUSE mounted on data dir {}", QString::fromStdString(m_mountPoint));
+
+  m_flushPolicy = static_cast<FlushPolicy>(Settings::instance().stagingFlushPolicy());
+
+  // files flushed while running can't be reviewed on exit anymore
+  if (Settings::instance().flushStagingPeriodically() &&
+      !Settings::instance().reviewStagedFiles()) {
+    m_flushTimer.start();
+  }
+
   return true;
 }
 
-void FuseConnector::unmount()
+FuseConnector::FlushReport FuseConnector::unmount(const StagingRouter& router)
 {
+  m_flushTimer.stop();
+
   if (!m_mounted) {
-    return;
+    return {};
+  }
+
+  if (m_deployMode == DeployMode::Symlink) {
+    unmountSymlinks();
+    return {};
   }
 
   if (m_helperProcess) {
@@ -376,11 +688,11 @@ void FuseConnector::unmount()
     delete m_helperProcess;
     m_helperProcess = nullptr;
     m_mounted       = false;
-    setFuseMountPointForCrashCleanup(nullptr);
+    unregisterActiveMount();
     cleanupExternalMappings();
     log::debug("VFS helper stopped, FUSE unmounted from {}",
                QString::fromStdString(m_mountPoint));
-    return;
+    return {};
   }
 
   if (m_session != nullptr) {
@@ -397,21 +709,29 @@ void FuseConnector::unmount()
     m_session = nullptr;
   }
 
-  flushStaging();
+  const FlushReport report = flushStaging(router);
 
   if (m_backingFd >= 0) {
     close(m_backingFd);
     m_backingFd = -1;
   }
 
+  const auto s = stats();
+  log::debug("vfs stats: {} lookups ({} not found), {} opens, {} reads ({} bytes), "
+             "{} writes ({} bytes)",
+             s.lookups, s.failed_lookups, s.opens, s.reads, s.read_bytes, s.writes,
+             s.write_bytes);
+
   m_context.reset();
   m_mounted = false;
-  setFuseMountPointForCrashCleanup(nullptr);
+  unregisterActiveMount();
 
   // Clean up symlinks created for non-data-dir mappings.
   cleanupExternalMappings();
 
   log::debug("FUSE unmounted from {}", QString::fromStdString(m_mountPoint));
+
+  return report;
 }
 
 bool FuseConnector::isMounted() const
@@ -419,6 +739,115 @@ bool FuseConnector::isMounted() const
   return m_mounted;
 }
 
+void FuseConnector::registerActiveMount()
+{
+  std::scoped_lock lock(g_activeMountsMutex);
+
+  std::erase_if(g_activeMounts, [&](auto&& m) {
+    return m.owner == this;
+  });
+
+  g_activeMounts.push_back({this, m_mountPoint, m_deployMode == DeployMode::Fuse});
+  updateCrashCleanupMountPoints();
+}
+
+void FuseConnector::unregisterActiveMount()
+{
+  std::scoped_lock lock(g_activeMountsMutex);
+
+  std::erase_if(g_activeMounts, [&](auto&& m) {
+    return m.owner == this;
+  });
+
+  updateCrashCleanupMountPoints();
+  releaseMountLocks();
+}
+
+void FuseConnector::releaseMountLocks()
+{
+  for (int* fd : {&m_mountLockFd, &m_stagingLockFd}) {
+    if (*fd > 0) {
+      ::close(*fd);
+    }
+    *fd = -1;
+  }
+}
+
+QString FuseConnector::helperConfigPath() const
+{
+  // one per mount point, helpers of other instances keep reading their own
+  const QByteArray id =
+      QCryptographicHash::hash(QByteArray::fromStdString(m_mountPoint),
+                               QCryptographicHash::Sha1)
+          .toHex()
+          .left(12);
+
+  const QString dataDir =
+      QStandardPaths::writableLocation(QStandardPaths::GenericDataLocation);
+  return QDir(dataDir).filePath(
+      QStringLiteral("fluorine/vfs-%1.cfg").arg(QString::fromLatin1(id)));
+}
+
+Mo2FsStatsSnapshot FuseConnector::stats() const
+{
+  return snapshotStats(m_context.get());
+}
+
+std::vector<std::pair<QString, QString>>
+FuseConnector::fileOrigins(const QString& path) const
+{
+  std::vector<std::pair<QString, QString>> result;
+
+  if (!m_mounted || m_context == nullptr) {
+    return result;
+  }
+
+  for (const auto& [origin, realPath] :
+       resolveOrigins(m_baseFileCache, m_dataDirPath, m_lastMods, m_overwriteDir,
+                      path.toStdString())) {
+    result.emplace_back(QString::fromStdString(origin),
+                        QString::fromStdString(realPath));
+  }
+
+  return result;
+}
+
+VfsWinnerExplanation FuseConnector::explainWinner(const QString& path) const
+{
+  if (!m_mounted || m_context == nullptr) {
+    return {};
+  }
+
+  return ::explainWinner(m_baseFileCache, m_dataDirPath, m_lastMods, m_overwriteDir,
+                         path.toStdString());
+}
+
+FuseConnector::MountState FuseConnector::mountState() const
+{
+  if (m_deployMode == DeployMode::Symlink) {
+    return m_mounted ? MountState::Mounted : MountState::Unmounted;
+  }
+
+  if (m_mountPoint.empty()) {
+    return classifyMount(m_mounting, false, false, false);
+  }
+
+  const QString path = QString::fromStdString(m_mountPoint);
+
+  bool sessionAlive = m_mounted;
+  if (m_helperProcess) {
+    sessionAlive = sessionAlive && m_helperProcess->state() == QProcess::Running;
+  } else {
+    sessionAlive = sessionAlive && m_session != nullptr && !fuse_session_exited(m_session);
+  }
+
+  struct stat st;
+  const bool notConnected =
+      ::stat(path.toLocal8Bit().constData(), &st) != 0 && errno == ENOTCONN;
+
+  return classifyMount(m_mounting, sessionAlive, isMountPoint(path), notConnected);
+}
+
 void FuseConnector::rebuild(
     const std::vector<std::pair<std::string, std::string>>& mods,
     const QString& overwrite_dir, const QString& data_dir_name)
@@ -431,14 +860,20 @@ void FuseConnector::rebuild(
   m_dataDirName  = data_dir_name.toStdString();
   m_lastMods     = mods;
 
+  if (m_deployMode == DeployMode::Symlink) {
+    undeploySymlinks(m_manifestPath);
+    deploySymlinkTree(mods);
+    m_deployedFingerprint = deploymentFingerprint(mods, m_overwriteDir, m_dataDirName);
+    return;
+  }
+
   if (m_helperProcess) {
-    const QString dataDir =
-        QStandardPaths::writableLocation(QStandardPaths::GenericDataLocation);
-    const QString configPath = QDir(dataDir).filePath("fluorine/vfs.cfg");
+    const QString configPath = helperConfigPath();
     writeVfsConfig(configPath, QString::fromStdString(m_mountPoint),
                    overwrite_dir, QString::fromStdString(m_gameDir),
                    data_dir_name, mods);
     sendHelperCommand(m_helperProcess, "rebuild", 10000);
+    m_deployedFingerprint = deploymentFingerprint(mods, m_overwriteDir, m_dataDirName);
     return;
   }
 
@@ -452,12 +887,159 @@ void FuseConnector::rebuild(
 
   // Inject file-level data-dir mappings (e.g. plugins.txt, loadorder.txt)
   injectExtraFiles(*newTree, m_extraVfsFiles);
+  m_treeExtraVfsFiles = m_extraVfsFiles;
 
   std::unique_lock lock(m_context->tree_mutex);
   m_context->tree.swap(newTree);
 }
 
-void FuseConnector::updateMapping(const MappingType& mapping)
+void FuseConnector::rebuildIncremental(
+    const std::vector<std::pair<std::string, std::string>>& mods,
+    const QString& overwrite_dir, const QString& data_dir_name)
+{
+  // the helper and symlinks can't be updated in place, but nothing needs to be
+  // redone when the mod list didn't change, such as when only a mod was
+  // selected; refreshes for changed mod files call rebuild() directly
+  if (m_mounted && (m_helperProcess || m_deployMode == DeployMode::Symlink)) {
+    if (deploymentFingerprint(mods, overwrite_dir.toStdString(),
+                              data_dir_name.toStdString()) == m_deployedFingerprint) {
+      return;
+    }
+
+    rebuild(mods, overwrite_dir, data_dir_name);
+    return;
+  }
+
+  if (!m_mounted || m_context == nullptr ||
+      m_overwriteDir != overwrite_dir.toStdString() ||
+      m_dataDirName != data_dir_name.toStdString() ||
+      m_treeExtraVfsFiles != m_extraVfsFiles) {
+    rebuild(mods, overwrite_dir, data_dir_name);
+    return;
+  }
+
+  const auto diff = diffModLists(m_lastMods, mods);
+  m_lastMods      = mods;
+
+  if (diff.empty()) {
+    return;
+  }
+
+  log::debug("updating vfs for {} added, {} removed and {} moved mods",
+             diff.added.size(), diff.removed.size(), diff.reordered.size());
+
+  // the mods in the diff are read from disk while the lock is held, which is
+  // still much shorter than building the whole tree
+  std::unique_lock lock(m_context->tree_mutex);
+  m_context->tree->applyDiff(diff, m_baseFileCache, mods, m_overwriteDir);
+}
+
+bool FuseConnector::mountSymlinks(
+    const std::vector<std::pair<std::string, std::string>>& mods)
+{
+  const fs::path overwritePath(m_overwriteDir);
+  m_manifestPath = (overwritePath.parent_path() / "VFS_symlinks.txt").string();
+
+  std::error_code ec;
+  fs::create_directories(m_overwriteDir, ec);
+
+  // links left behind by a session that didn't end cleanly; these must be gone
+  // before scanning or they would be taken for game files
+  if (fs::exists(m_manifestPath, ec)) {
+    const auto removed = undeploySymlinks(m_manifestPath);
+    log::warn("removed {} symlinks left in {} by a previous session", removed,
+              QString::fromStdString(m_dataDirPath));
+  }
+
+  // always rescanned, unlike with fuse the data directory can be changed
+  // between runs, and anything that's not in the cache is moved to overwrite
+  // when unmounting
+  m_baseFileCache     = scanDataDir(m_dataDirPath);
+  m_cachedDataDirPath = m_dataDirPath;
+
+  deploySymlinkTree(mods);
+  m_deployedFingerprint = deploymentFingerprint(mods, m_overwriteDir, m_dataDirName);
+
+  m_mounted = true;
+  registerActiveMount();
+  log::debug("mods deployed as symlinks in {}", QString::fromStdString(m_dataDirPath));
+
+  return true;
+}
+
+void FuseConnector::deploySymlinkTree(
+    const std::vector<std::pair<std::string, std::string>>& mods)
+{
+  // same tree as the fuse mount, so the same files win
+  VfsTree tree = buildDataDirVfs(m_baseFileCache, m_dataDirPath, mods, m_overwriteDir);
+  injectExtraFiles(tree, m_extraVfsFiles);
+
+  const auto result = deploySymlinks(tree, m_dataDirPath, m_manifestPath);
+
+  log::debug("deployed {} symlinks and {} directories in {}", result.links,
+             result.directories, QString::fromStdString(m_dataDirPath));
+
+  if (!result.skipped.empty()) {
+    log::warn("{} mod files were not deployed because game files are in the way, "
+              "such as '{}'",
+              result.skipped.size(), QString::fromStdString(result.skipped.front()));
+  }
+
+  if (!result.failed.empty()) {
+    log::error("failed to deploy {} mod files, such as '{}'", result.failed.size(),
+               QString::fromStdString(result.failed.front()));
+  }
+}
+
+std::size_t FuseConnector::deploymentFingerprint(
+    const std::vector<std::pair<std::string, std::string>>& mods,
+    const std::string& overwrite_dir, const std::string& data_dir_name) const
+{
+  std::size_t seed = 0;
+
+  for (const auto& [name, path] : mods) {
+    hashCombine(seed, name);
+    hashCombine(seed, path);
+  }
+
+  for (const auto& [relPath, realPath] : m_extraVfsFiles) {
+    hashCombine(seed, relPath);
+    hashCombine(seed, realPath);
+  }
+
+  hashCombine(seed, overwrite_dir);
+  hashCombine(seed, data_dir_name);
+
+  // the helper's fuse mount shows overwrite live, the links need to be redone
+  // for files added there
+  if (m_deployMode == DeployMode::Symlink) {
+    hashCombine(seed, latestDirectoryChange(overwrite_dir));
+  }
+
+  return seed;
+}
+
+void FuseConnector::unmountSymlinks()
+{
+  const auto removed = undeploySymlinks(m_manifestPath);
+
+  std::unordered_set<std::string> baseFiles;
+  baseFiles.reserve(m_baseFileCache.size());
+  for (const auto& cf : m_baseFileCache) {
+    baseFiles.insert(cf.relative_path);
+  }
+
+  const auto moved = collectNewFiles(m_dataDirPath, baseFiles, m_overwriteDir);
+
+  m_mounted = false;
+  unregisterActiveMount();
+  cleanupExternalMappings();
+
+  log::debug("removed {} symlinks from {}, moved {} new files to overwrite", removed,
+             QString::fromStdString(m_dataDirPath), moved);
+}
+
+void FuseConnector::updateMapping(const MappingType& mapping, bool fullRebuild)
 {
   auto* game = qApp->property("managed_game").value<MOBase::IPluginGame*>();
   if (game == nullptr) {
@@ -477,8 +1059,10 @@ void FuseConnector::updateMapping(const MappingType& mapping)
 
   if (!m_mounted) {
     mount(dataDirPath, overwriteDir, gameDir, dataDirName, mods);
-  } else {
+  } else if (fullRebuild) {
     rebuild(mods, overwriteDir, dataDirName);
+  } else {
+    rebuildIncremental(mods, overwriteDir, dataDirName);
   }
 }
 
@@ -528,6 +1112,13 @@ void FuseConnector::deployExternalMappings(const MappingType& mapping,
                srcPath, fs::directory_options::skip_permission_denied);
            it != fs::recursive_directory_iterator(); ++it) {
         const auto& entry = *it;
+        if (it.depth() >= MaxScanDepth && entry.is_directory(ec) &&
+            !entry.is_symlink(ec)) {
+          it.disable_recursion_pending();
+          log::warn("not descending into '{}', it is nested deeper than {} levels",
+                    QString::fromStdString(entry.path().string()), MaxScanDepth);
+        }
+
         const fs::path rel = fs::relative(entry.path(), srcPath, ec);
         if (ec || rel.empty()) {
           continue;
@@ -616,31 +1207,81 @@ void FuseConnector::updateForcedLibraries(
     const QList<MOBase::ExecutableForcedLoadSetting>& /*forced*/)
 {}
 
-void FuseConnector::flushStaging()
+QStringList FuseConnector::stagedFiles() const
+{
+  QStringList files;
+
+  if (m_stagingDir.empty() || m_helperProcess != nullptr) {
+    return files;
+  }
+
+  const fs::path staging(m_stagingDir);
+  std::error_code ec;
+  if (!fs::exists(staging, ec)) {
+    return files;
+  }
+
+  for (auto it = fs::recursive_directory_iterator(
+           staging, fs::directory_options::skip_permission_denied);
+       it != fs::recursive_directory_iterator(); ++it) {
+    const auto& entry = *it;
+    if (it.depth() >= MaxScanDepth && entry.is_directory(ec) &&
+        !entry.is_symlink(ec)) {
+      it.disable_recursion_pending();
+    }
+
+    if (!entry.is_regular_file(ec)) {
+      continue;
+    }
+
+    const fs::path rel = fs::relative(entry.path(), staging, ec);
+    if (!ec && !rel.empty()) {
+      files.append(QString::fromStdString(rel.generic_string()));
+    }
+  }
+
+  files.sort(Qt::CaseInsensitive);
+  return files;
+}
+
+FuseConnector::FlushReport FuseConnector::flushStaging(const StagingRouter& router)
 {
+  FlushReport report;
+
   if (m_stagingDir.empty() || m_overwriteDir.empty()) {
-    return;
+    return report;
   }
 
   const fs::path staging(m_stagingDir);
   const fs::path overwrite(m_overwriteDir);
   if (!fs::exists(staging)) {
-    return;
+    return report;
   }
 
   std::error_code ec;
+  bool complete = true;
+  bool failed   = false;
+
+  // one directory for all the backups of this flush
+  const std::string backupDir = stagingBackupDir(m_overwriteDir);
+
   for (auto it = fs::recursive_directory_iterator(
            staging, fs::directory_options::skip_permission_denied);
        it != fs::recursive_directory_iterator(); ++it) {
     const auto& entry = *it;
+    if (it.depth() >= MaxScanDepth && entry.is_directory(ec) &&
+        !entry.is_symlink(ec)) {
+      it.disable_recursion_pending();
+      complete = false;
+    }
+
     const fs::path rel = fs::relative(entry.path(), staging, ec);
     if (ec || rel.empty()) {
       continue;
     }
 
-    const fs::path dest = overwrite / rel;
     if (entry.is_directory(ec)) {
-      fs::create_directories(dest, ec);
+      fs::create_directories(overwrite / rel, ec);
       continue;
     }
 
@@ -648,18 +1289,68 @@ void FuseConnector::flushStaging()
       continue;
     }
 
-    fs::create_directories(dest.parent_path(), ec);
-    fs::rename(entry.path(), dest, ec);
-    if (ec) {
-      ec.clear();
-      fs::copy_file(entry.path(), dest, fs::copy_options::overwrite_existing, ec);
-      if (!ec) {
+    fs::path dest = overwrite / rel;
+
+    if (router) {
+      const auto route = router(QString::fromStdString(rel.generic_string()));
+
+      if (route.destination == StagingRoute::Destination::Discard) {
+        log::debug("discarding staged file '{}'",
+                   QString::fromStdString(rel.generic_string()));
         fs::remove(entry.path(), ec);
+        continue;
       }
+
+      if (route.destination == StagingRoute::Destination::Mod &&
+          !route.modPath.isEmpty()) {
+        dest = fs::path(route.modPath.toStdString()) / rel;
+      }
+    }
+
+    switch (moveStagedFile(entry.path().string(), dest.string(),
+                           rel.generic_string(), m_flushPolicy, backupDir)) {
+    case StagedMoveResult::Skipped:
+      log::warn("'{}' already exists, discarding the staged file",
+                QString::fromStdString(dest.string()));
+      ++report.skipped;
+      break;
+
+    case StagedMoveResult::BackedUp:
+      ++report.backedUp;
+      ++report.moved;
+      break;
+
+    case StagedMoveResult::Moved:
+      ++report.moved;
+      break;
+
+    case StagedMoveResult::Failed:
+      log::warn("failed to move staged file '{}' to '{}'",
+                QString::fromStdString(entry.path().string()),
+                QString::fromStdString(dest.string()));
+      failed = true;
+      break;
     }
   }
 
+  if (failed) {
+    // don't throw away what couldn't be moved
+    log::warn("leaving the staged files that couldn't be moved in '{}'",
+              QString::fromStdString(m_stagingDir));
+    return report;
+  }
+
+  if (!complete) {
+    // don't throw away what couldn't be moved
+    log::warn("staging directory '{}' is nested deeper than {} levels, leaving "
+              "the remaining files in place",
+              QString::fromStdString(m_stagingDir), MaxScanDepth);
+    return report;
+  }
+
   fs::remove_all(staging, ec);
+
+  return report;
 }
 
 void FuseConnector::flushStagingLive()
@@ -681,24 +1372,42 @@ void FuseConnector::flushStagingLive()
   flushStaging();
 
   // Re-create the staging dir (flushStaging removes it)
-  std::error_code ec;
-  fs::create_directories(m_stagingDir, ec);
+  prepareStagingDir();
 
   // Rebuild the VFS tree to pick up new overwrite files
   auto newTree = std::make_shared<VfsTree>(
       buildDataDirVfs(m_baseFileCache, m_dataDirPath, m_lastMods, m_overwriteDir));
 
+  // the extra files aren't injected here, the next update has to rebuild
+  m_treeExtraVfsFiles.clear();
+
   {
     std::unique_lock lock(m_context->tree_mutex);
     m_context->tree.swap(newTree);
   }
 
   // Re-create OverwriteManager with fresh staging dir
+  const mode_t umask   = m_context->overwrite->umask();
   m_context->overwrite = std::make_unique<OverwriteManager>(m_stagingDir, m_overwriteDir);
+  m_context->overwrite->setUmask(umask);
 
   log::debug("Live staging flush complete");
 }
 
+void FuseConnector::flushStableStaging()
+{
+  if (!m_mounted || m_helperProcess || m_context == nullptr) {
+    return;
+  }
+
+  const size_t moved =
+      ::flushStableStaging(m_context.get(), StableStagingAge, m_flushPolicy);
+  if (moved > 0) {
+    log::debug("moved {} staged file(s) to overwrite while mounted", moved);
+    emit stagingChanged();
+  }
+}
+
 // Detect a stale FUSE mount by probing with stat().  Returns true if
 // the path exists in the mount table OR if accessing it gives ENOTCONN
 // (which happens when the FUSE daemon died but the mount is listed
@@ -742,6 +1451,22 @@ static void doUnmount(const QString& path)
   }
 }
 
+bool FuseConnector::waitForMountPointRelease(const QString& path)
+{
+  auto delay = MountRetryDelay;
+  for (int attempt = 1; attempt < MountAttempts; ++attempt) {
+    if (!isStaleOrMounted(path)) {
+      return true;
+    }
+
+    log::debug("'{}' is still mounted, checking again in {}ms", path, delay.count());
+    std::this_thread::sleep_for(delay);
+    delay *= 2;
+  }
+
+  return !isStaleOrMounted(path);
+}
+
 void FuseConnector::tryCleanupStaleMount(const QString& path)
 {
   if (!isStaleOrMounted(path)) {
@@ -759,7 +1484,7 @@ bool FuseConnector::mountViaHelper(
 {
   const QString dataDir =
       QStandardPaths::writableLocation(QStandardPaths::GenericDataLocation);
-  const QString configPath = QDir(dataDir).filePath("fluorine/vfs.cfg");
+  const QString configPath = helperConfigPath();
   const QString helperBin =
       QDir(dataDir).filePath("fluorine/bin/mo2-vfs-helper");
 
@@ -797,8 +1522,9 @@ bool FuseConnector::mountViaHelper(
         QObject::tr("VFS helper failed to mount FUSE. %1").arg(err));
   }
 
-  m_mounted = true;
-  setFuseMountPointForCrashCleanup(m_mountPoint.c_str());
+  m_mounted             = true;
+  m_deployedFingerprint = deploymentFingerprint(mods, m_overwriteDir, m_dataDirName);
+  registerActiveMount();
   log::debug("FUSE mounted via helper on {}",
              QString::fromStdString(m_mountPoint));
   return true;
@@ -823,6 +1549,9 @@ void FuseConnector::writeVfsConfig(
   out << "game_dir=" << game_dir << "\n";
   out << "data_dir_name=" << data_dir_name << "\n";
   out << "overwrite_dir=" << overwrite_dir << "\n";
+  out << "read_only=" << (m_readOnly ? 1 : 0) << "\n";
+  out << "umask=" << QString::number(Settings::instance().stagingUmask(), 8) << "\n";
+  out << "flush_policy=" << Settings::instance().stagingFlushPolicy() << "\n";
 
   for (const auto& [name, path] : mods) {
     out << "mod=" << QString::fromStdString(name) << "|"
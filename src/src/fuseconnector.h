@@ -6,10 +6,14 @@
 
 #include <QObject>
 #include <QString>
+#include <QTimer>
 
 class QProcess;
 
+#include <atomic>
+#include <chrono>
 #include <exception>
+#include <functional>
 #include <memory>
 #include <thread>
 #include <uibase/executableinfo.h>
@@ -19,14 +23,33 @@ class QProcess;
 class FuseConnectorException : public std::exception
 {
 public:
-  explicit FuseConnectorException(const QString& text)
-      : std::exception(), m_Message(text.toLocal8Bit())
+  // why mounting failed, so callers can tell the user what to do about it
+  enum class Reason
+  {
+    Failed,
+
+    // the game's data directory is missing or isn't a directory
+    NoDataDirectory,
+
+    // something is still mounted on the data directory after cleaning up a
+    // stale mount
+    MountPointBusy,
+
+    // /dev/fuse can't be opened, the fuse module isn't loaded or the sandbox
+    // doesn't expose it
+    FuseUnavailable
+  };
+
+  explicit FuseConnectorException(const QString& text, Reason reason = Reason::Failed)
+      : std::exception(), m_Message(text.toLocal8Bit()), m_Reason(reason)
   {}
 
   const char* what() const throw() override { return m_Message.constData(); }
+  Reason reason() const { return m_Reason; }
 
 private:
   QByteArray m_Message;
+  Reason m_Reason;
 };
 
 class FuseConnector : public QObject
@@ -34,6 +57,73 @@ class FuseConnector : public QObject
   Q_OBJECT
 
 public:
+  // finer-grained status than isMounted(), meant for display
+  //
+  enum class MountState
+  {
+    // nothing is mounted
+    Unmounted,
+
+    // mount() is setting up the session
+    Mounting,
+
+    // the session is alive and the kernel lists the mount
+    Mounted,
+
+    // the kernel mount and the session disagree, such as when the fuse
+    // daemon died or something else unmounted the data directory
+    Stale
+  };
+
+  // how mods are shown to programs, chosen from the settings when mounting
+  //
+  enum class DeployMode
+  {
+    // a fuse filesystem is mounted over the data directory
+    Fuse,
+
+    // the winning files are linked into the real data directory, for systems
+    // without fuse; files of the game are never replaced and programs write
+    // straight into the data directory, so new files are moved to overwrite
+    // when unmounting
+    Symlink
+  };
+
+  // where a file left in staging goes when the vfs is unmounted
+  //
+  struct StagingRoute
+  {
+    enum class Destination
+    {
+      Overwrite,
+      Mod,
+      Discard
+    };
+
+    Destination destination = Destination::Overwrite;
+
+    // absolute path of the mod directory, only used for Destination::Mod
+    QString modPath;
+  };
+
+  // given the path of a staged file relative to the data directory, returns
+  // where it should go
+  //
+  using StagingRouter = std::function<StagingRoute(const QString&)>;
+
+  // what happens when a staged file is flushed to a path that already exists
+  //
+  using FlushPolicy = StagingFlushPolicy;
+
+  // what flushing staging did, for the user to be told about it
+  //
+  struct FlushReport
+  {
+    int moved    = 0;
+    int skipped  = 0;
+    int backedUp = 0;
+  };
+
   explicit FuseConnector(QObject* parent = nullptr);
   ~FuseConnector() override;
 
@@ -41,15 +131,65 @@ public:
              const QString& game_dir, const QString& data_dir_name,
              const std::vector<std::pair<std::string, std::string>>& mods);
 
-  void unmount();
+  // unmounts the vfs and moves staged files to overwrite, or wherever the
+  // router says if one is given
+  //
+  // the router is ignored when the vfs runs in the flatpak helper process,
+  // which flushes staging by itself, and when mods are deployed as symlinks
+  //
+  // existing files at the destination are handled according to the flush
+  // policy setting, read when mounting
+  //
+  FlushReport unmount(const StagingRouter& router = {});
   bool isMounted() const;
+  DeployMode deployMode() const { return m_deployMode; }
+
+  // files currently in staging, relative to the data directory; empty when
+  // the vfs runs in the flatpak helper process
+  //
+  QStringList stagedFiles() const;
+  MountState mountState() const;
+
+  // counters of the running mount, all zero when nothing is mounted in this
+  // process, such as with the flatpak helper or symlink deployment
+  //
+  Mo2FsStatsSnapshot stats() const;
+
+  // (origin, real path) of every layer providing the file at the given path
+  // relative to the data directory, the one programs see first; see
+  // resolveOrigins(), empty when nothing is mounted in this process
+  //
+  std::vector<std::pair<QString, QString>> fileOrigins(const QString& path) const;
+
+  // why the first layer of fileOrigins() wins the file, see explainWinner();
+  // NotFound when nothing is mounted in this process
+  //
+  VfsWinnerExplanation explainWinner(const QString& path) const;
 
   void rebuild(const std::vector<std::pair<std::string, std::string>>& mods,
                const QString& overwrite_dir, const QString& data_dir_name);
 
+  // updates the mounted tree for the mods that were added, removed or moved
+  // since the last build instead of building it again; does a full rebuild()
+  // when anything else changed, or for the flatpak helper process and symlink
+  // deployment when anything they're built from changed
+  //
+  void rebuildIncremental(const std::vector<std::pair<std::string, std::string>>& mods,
+                          const QString& overwrite_dir, const QString& data_dir_name);
+
   void flushStagingLive();
 
-  void updateMapping(const MappingType& mapping);
+  // moves staged files that nothing has written to for StableStagingAge and
+  // that aren't open to overwrite while the vfs stays mounted; does nothing
+  // when the vfs runs in the flatpak helper process
+  //
+  void flushStableStaging();
+
+  // mounts the vfs for the mapping or updates the mounted one; only the mods
+  // that changed are updated unless `fullRebuild` is set, which reads every
+  // mod again for changes made inside mod directories
+  //
+  void updateMapping(const MappingType& mapping, bool fullRebuild = false);
   void updateParams(MOBase::log::Levels logLevel, env::CoreDumpTypes coreDumpType,
                     const QString& crashDumpsPath, std::chrono::seconds spawnDelay,
                     QString executableBlacklist, const QStringList& skipFileSuffixes,
@@ -59,11 +199,84 @@ public:
 
   static void tryCleanupStaleMount(const QString& path);
 
+signals:
+  // emitted on the main thread when a program running in the vfs has created,
+  // changed or removed files in staging; bursts of writes are coalesced into
+  // one signal per StagingChangedDelay
+  //
+  // not emitted when the vfs runs in the flatpak helper process
+  //
+  void stagingChanged();
+
 private:
-  void flushStaging();
+  static constexpr std::chrono::milliseconds StagingChangedDelay{1000};
+
+  // how often staging is flushed while mounted when the user enabled it, and
+  // how long a file must have been left alone to be flushed
+  static constexpr std::chrono::minutes StagingFlushInterval{5};
+  static constexpr std::chrono::seconds StableStagingAge{60};
+
+  // right after a stale mount is cleaned up the kernel may still hold on to
+  // the mount point for a moment, so mounting is tried this many times,
+  // doubling the delay between attempts
+  static constexpr int MountAttempts = 4;
+  static constexpr std::chrono::milliseconds MountRetryDelay{100};
+
+  // waits with backoff until the mount point no longer shows up as mounted,
+  // returns false if it's still in use after all attempts
+  static bool waitForMountPointRelease(const QString& path);
+
+  // adds this instance to the mount points unmounted by the crash handler, or
+  // removes it and lets go of the mount locks
+  void registerActiveMount();
+  void unregisterActiveMount();
+
+  // closes the lock files taken by mount() so other instances can use the
+  // mount point and staging directory again
+  void releaseMountLocks();
+
+  // config file read by the flatpak helper, one per mount point
+  QString helperConfigPath() const;
+
+  QTimer m_stagingTimer;
+  QTimer m_flushTimer;
+  std::atomic<bool> m_stagingDirty{false};
+
+  // creates the staging directory and enables compression on it if the user
+  // asked for it; does nothing for read-only mounts
+  void prepareStagingDir();
+
+  // programs can't change anything in the vfs, see Settings::vfsReadOnly()
+  bool m_readOnly = false;
+
+  FlushReport flushStaging(const StagingRouter& router = {});
+  FlushPolicy m_flushPolicy = FlushPolicy::Overwrite;
+
+  // DeployMode::Symlink counterparts of mount(), rebuild() and unmount()
+  bool mountSymlinks(const std::vector<std::pair<std::string, std::string>>& mods);
+  void deploySymlinkTree(const std::vector<std::pair<std::string, std::string>>& mods);
+  void unmountSymlinks();
+
+  // hash of the mod list and everything else the helper or the symlinks are
+  // built from, so rebuildIncremental() can skip redeploying when none of it
+  // changed; files changed inside mod directories aren't part of it, explicit
+  // refreshes go through updateMapping() with `fullRebuild` set instead
+  std::size_t
+  deploymentFingerprint(const std::vector<std::pair<std::string, std::string>>& mods,
+                        const std::string& overwrite_dir,
+                        const std::string& data_dir_name) const;
+
   void deployExternalMappings(const MappingType& mapping, const QString& dataDir);
   void cleanupExternalMappings();
 
+  DeployMode m_deployMode = DeployMode::Fuse;
+
+  // lists the links and directories created in DeployMode::Symlink
+  std::string m_manifestPath;
+
+  // deploymentFingerprint() of the current helper mount or symlink deployment
+  std::size_t m_deployedFingerprint = 0;
+
   std::string m_mountPoint;
   std::string m_stagingDir;
   std::string m_overwriteDir;
@@ -71,6 +284,11 @@ private:
   std::string m_dataDirName;
   std::string m_dataDirPath;
   int m_backingFd = -1;
+
+  // locked files for the mount point and staging directory while mounted, see
+  // mount(); -1 when not held, 0 when the lock file couldn't be created
+  int m_mountLockFd   = -1;
+  int m_stagingLockFd = -1;
   std::vector<CachedBaseFile> m_baseFileCache;
   std::string m_cachedDataDirPath;
 
@@ -82,11 +300,16 @@ private:
   // Injected into the VFS tree after building.  (relPath, absRealPath)
   std::vector<std::pair<std::string, std::string>> m_extraVfsFiles;
 
+  // the extra files injected into the mounted tree, an incremental rebuild
+  // can't change these
+  std::vector<std::pair<std::string, std::string>> m_treeExtraVfsFiles;
+
   std::shared_ptr<Mo2FsContext> m_context;
 
   struct fuse_session* m_session = nullptr;
   std::thread m_fuseThread;
-  bool m_mounted = false;
+  bool m_mounted  = false;
+  bool m_mounting = false;
 
   QProcess* m_helperProcess = nullptr;
   bool mountViaHelper(const QString& overwrite_dir, const QString& game_dir,
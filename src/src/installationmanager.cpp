@@ -661,7 +661,16 @@ InstallationResult InstallationManager::install(const QString& fileName,
 
   modName.setFilter(&fixDirectoryName);
 
-  modName.update(QFileInfo(fileName).completeBaseName(), GUESS_FALLBACK);
+  const bool cleanNames = Settings::instance().nexus().cleanModNames();
+
+  {
+    QString fallbackName = QFileInfo(fileName).completeBaseName();
+    if (cleanNames) {
+      fallbackName = NexusInterface::cleanModName(fallbackName);
+    }
+
+    modName.update(fallbackName, GUESS_FALLBACK);
+  }
 
   // read out meta information from the download if available
   QString gameName      = "";
@@ -730,6 +739,10 @@ InstallationResult InstallationManager::install(const QString& fileName,
       log::debug("passed mod id: {}, guessed id: {}", modID, guessedModID);
     }
 
+    if (cleanNames && !guessedModName.isEmpty()) {
+      guessedModName = NexusInterface::cleanModName(guessedModName);
+    }
+
     modName.update(guessedModName, GUESS_GOOD);
   }
 
@@ -0,0 +1,81 @@
+#include "junkfiles.h"
+#include "modinfo.h"
+
+#include <QDir>
+#include <QDirIterator>
+#include <QFile>
+#include <log.h>
+
+using namespace MOBase;
+
+namespace
+{
+
+void scanDirectory(const QString& modName, const QDir& root, const QString& path,
+                   std::vector<JunkFile>& out)
+{
+  QDirIterator it(path, QDir::AllEntries | QDir::Hidden | QDir::System |
+                            QDir::NoDotAndDotDot);
+
+  while (it.hasNext()) {
+    it.next();
+    const QFileInfo info = it.fileInfo();
+
+    // don't follow symlinked directories out of the mod
+    const bool isDir = info.isDir() && !info.isSymLink();
+
+    if (isJunkName(info.fileName(), isDir)) {
+      out.push_back({modName, root.relativeFilePath(info.absoluteFilePath()),
+                     info.absoluteFilePath()});
+    } else if (isDir) {
+      scanDirectory(modName, root, info.absoluteFilePath(), out);
+    }
+  }
+}
+
+}  // namespace
+
+bool isJunkName(const QString& name, bool isDirectory)
+{
+  if (isDirectory) {
+    // resource forks and version control metadata
+    return name == "__MACOSX" || name == ".git" || name == ".svn";
+  }
+
+  // Finder and Explorer metadata, and AppleDouble files ("._name")
+  return name == ".DS_Store" || name.startsWith("._") ||
+         name.compare("Thumbs.db", Qt::CaseInsensitive) == 0 ||
+         name.compare("desktop.ini", Qt::CaseInsensitive) == 0;
+}
+
+std::vector<JunkFile> scanJunkFiles()
+{
+  std::vector<JunkFile> junk;
+
+  for (unsigned int i = 0; i < ModInfo::getNumMods(); ++i) {
+    auto mod = ModInfo::getByIndex(i);
+    if (!mod->isRegular()) {
+      continue;
+    }
+
+    const QDir root(mod->absolutePath());
+    scanDirectory(mod->name(), root, root.absolutePath(), junk);
+  }
+
+  log::debug("found {} junk files in mods", junk.size());
+
+  return junk;
+}
+
+bool removeJunkFile(const JunkFile& junk)
+{
+  if (!QFile::moveToTrash(junk.absolutePath)) {
+    log::error("failed to move '{}' to the trash", junk.absolutePath);
+    return false;
+  }
+
+  log::debug("moved '{}' from mod '{}' to the trash", junk.relativePath,
+             junk.modName);
+
+  return true;
+}
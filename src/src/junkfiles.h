@@ -0,0 +1,34 @@
+#ifndef JUNKFILES_H
+#define JUNKFILES_H
+
+#include <QString>
+#include <vector>
+
+// a file or directory inside a mod that is known to be useless for the game,
+// such as metadata left behind by archivers on macOS or Windows Explorer
+//
+struct JunkFile
+{
+  QString modName;
+
+  // path relative to the mod directory, with forward slashes
+  QString relativePath;
+
+  QString absolutePath;
+};
+
+// whether the given file or directory name is known junk; directories are
+// reported as a whole and not descended into
+//
+bool isJunkName(const QString& name, bool isDirectory);
+
+// walks every regular mod and returns all the junk found in them
+//
+std::vector<JunkFile> scanJunkFiles();
+
+// moves the given junk to the desktop trash; returns false if it could not be
+// moved, in which case it is left untouched
+//
+bool removeJunkFile(const JunkFile& junk);
+
+#endif  // JUNKFILES_H
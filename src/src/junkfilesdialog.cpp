@@ -0,0 +1,66 @@
+#include "junkfilesdialog.h"
+#include "settings.h"
+#include "ui_junkfilesdialog.h"
+
+#include <QPushButton>
+
+JunkFilesDialog::JunkFilesDialog(std::vector<JunkFile> junk, QWidget* parent)
+    : QDialog(parent), ui(new Ui::JunkFilesDialog), m_Junk(std::move(junk))
+{
+  ui->setupUi(this);
+  ui->buttonBox->button(QDialogButtonBox::Ok)->setText(tr("Move to Trash"));
+
+  ui->files->setSortingEnabled(false);
+
+  for (std::size_t i = 0; i < m_Junk.size(); ++i) {
+    auto* item = new QTreeWidgetItem({m_Junk[i].modName, m_Junk[i].relativePath});
+    item->setData(0, Qt::UserRole, static_cast<qulonglong>(i));
+    item->setToolTip(1, m_Junk[i].absolutePath);
+    item->setCheckState(0, Qt::Checked);
+    ui->files->addTopLevelItem(item);
+  }
+
+  ui->files->setSortingEnabled(true);
+  ui->files->sortByColumn(0, Qt::AscendingOrder);
+  ui->files->resizeColumnToContents(0);
+
+  connect(ui->checkAll, &QPushButton::clicked, [&] {
+    setAllChecked(true);
+  });
+  connect(ui->uncheckAll, &QPushButton::clicked, [&] {
+    setAllChecked(false);
+  });
+}
+
+JunkFilesDialog::~JunkFilesDialog()
+{
+  delete ui;
+}
+
+int JunkFilesDialog::exec()
+{
+  GeometrySaver gs(Settings::instance(), this);
+  return QDialog::exec();
+}
+
+std::vector<JunkFile> JunkFilesDialog::selected() const
+{
+  std::vector<JunkFile> v;
+
+  for (int i = 0; i < ui->files->topLevelItemCount(); ++i) {
+    const auto* item = ui->files->topLevelItem(i);
+    if (item->checkState(0) == Qt::Checked) {
+      v.push_back(m_Junk[item->data(0, Qt::UserRole).toULongLong()]);
+    }
+  }
+
+  return v;
+}
+
+void JunkFilesDialog::setAllChecked(bool checked)
+{
+  for (int i = 0; i < ui->files->topLevelItemCount(); ++i) {
+    ui->files->topLevelItem(i)->setCheckState(0,
+                                              checked ? Qt::Checked : Qt::Unchecked);
+  }
+}
@@ -0,0 +1,37 @@
+#ifndef JUNKFILESDIALOG_H
+#define JUNKFILESDIALOG_H
+
+#include "junkfiles.h"
+#include <QDialog>
+
+namespace Ui
+{
+class JunkFilesDialog;
+}
+
+// lists junk found in mods and lets the user pick which entries to remove
+//
+class JunkFilesDialog : public QDialog
+{
+  Q_OBJECT
+
+public:
+  JunkFilesDialog(std::vector<JunkFile> junk, QWidget* parent = nullptr);
+  ~JunkFilesDialog();
+
+  // also saves and restores geometry
+  //
+  int exec() override;
+
+  // entries that are checked
+  //
+  std::vector<JunkFile> selected() const;
+
+private:
+  Ui::JunkFilesDialog* ui;
+  std::vector<JunkFile> m_Junk;
+
+  void setAllChecked(bool checked);
+};
+
+#endif  // JUNKFILESDIALOG_H
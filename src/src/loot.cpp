@@ -448,8 +448,9 @@ bool Loot::start(QWidget* parent, bool didUpdateMasterList)
     return false;
   }
 
-  // vfs
-  m_core.prepareVFS();
+  // vfs, read again in full so loot sees plugins that were added to or
+  // removed from mods since it was mounted
+  m_core.prepareVFS(true);
 
   // spawning
   if (!spawnLootcli(parent, didUpdateMasterList, std::move(stdoutHandle))) {
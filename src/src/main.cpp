@@ -280,30 +280,32 @@ void setExceptionHandlers()
 
 #else  // Linux
 
-// Defined in fuseconnector.cpp — returns the current FUSE mount point
-// (or nullptr if nothing is mounted).  The backing buffer is a plain
-// char[] so reading it in a signal handler is async-signal-safe.
-extern const char* getFuseMountPointForCrashCleanup();
+// Defined in fuseconnector.cpp — returns the FUSE mount point at the given
+// index (or nullptr past the last one).  The backing buffers are plain
+// char[] so reading them in a signal handler is async-signal-safe.
+extern const char* getFuseMountPointForCrashCleanup(size_t index);
 
 // Attempt to unmount FUSE from a signal handler context.
 // fork()+exec() is async-signal-safe on Linux.
 static void emergencyFuseUnmount()
 {
-  const char* mp = getFuseMountPointForCrashCleanup();
-  if (mp == nullptr) {
-    return;
-  }
+  for (size_t i = 0;; ++i) {
+    const char* mp = getFuseMountPointForCrashCleanup(i);
+    if (mp == nullptr) {
+      return;
+    }
 
-  const pid_t child = fork();
-  if (child == 0) {
-    // Child — try lazy unmount so it always succeeds even if busy.
-    execlp("fusermount3", "fusermount3", "-uz", mp, nullptr);
-    execlp("fusermount", "fusermount", "-uz", mp, nullptr);
-    _exit(1);
-  } else if (child > 0) {
-    // Parent — wait briefly for the child to finish.
-    int status = 0;
-    waitpid(child, &status, 0);
+    const pid_t child = fork();
+    if (child == 0) {
+      // Child — try lazy unmount so it always succeeds even if busy.
+      execlp("fusermount3", "fusermount3", "-uz", mp, nullptr);
+      execlp("fusermount", "fusermount", "-uz", mp, nullptr);
+      _exit(1);
+    } else if (child > 0) {
+      // Parent — wait briefly for the child to finish.
+      int status = 0;
+      waitpid(child, &status, 0);
+    }
   }
 }
 
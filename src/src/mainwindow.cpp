@@ -42,6 +42,8 @@ along with Mod Organizer.  If not, see <http://www.gnu.org/licenses/>.
 #include "installationmanager.h"
 #include "instancemanager.h"
 #include "instancemanagerdialog.h"
+#include "junkfilesdialog.h"
+#include "modintegrity.h"
 #include "iplugindiagnose.h"
 #include "iplugingame.h"
 #include "isavegame.h"
@@ -70,6 +72,7 @@ along with Mod Organizer.  If not, see <http://www.gnu.org/licenses/>.
 #include "shared/appconfig.h"
 #include "spawn.h"
 #include "statusbar.h"
+#include "supportsummary.h"
 #include "systemtraymanager.h"
 #include <bsainvalidation.h>
 #include <dataarchives.h>
@@ -205,6 +208,9 @@ QString UnmanagedModName()
   return QObject::tr("<Unmanaged>");
 }
 
+// set on the origin column of archives that are in overwrite
+constexpr int OverwriteArchiveRole = Qt::UserRole + 1;
+
 bool runLoot(QWidget* parent, OrganizerCore& core, bool didUpdateMasterList);
 
 void setFilterShortcuts(QWidget* widget, QLineEdit* edit)
@@ -1127,6 +1133,11 @@ void MainWindow::createHelpMenu()
   connect(issueAction, SIGNAL(triggered()), this, SLOT(issueTriggered()));
   menu->addAction(issueAction);
 
+  QAction* diagnosticsAction = new QAction(tr("Copy Diagnostics"), menu);
+  connect(diagnosticsAction, SIGNAL(triggered()), this,
+          SLOT(copyDiagnosticsTriggered()));
+  menu->addAction(diagnosticsAction);
+
   QMenu* tutorialMenu = new QMenu(tr("Tutorials"), menu);
 
   typedef std::vector<std::pair<int, QAction*>> ActionList;
@@ -1742,6 +1753,7 @@ void MainWindow::startExeAction()
 void MainWindow::activateSelectedProfile()
 {
   m_OrganizerCore.setCurrentProfile(ui->profileBox->currentText());
+  restoreSelectedExecutable();
 
   m_SavesTab->refreshSaveList();
   m_OrganizerCore.refresh();
@@ -1919,10 +1931,37 @@ void MainWindow::refreshExecutablesList()
     add(tr("(no executables)"), QFileInfo(":badfile"));
   }
 
-  ui->executablesListBox->setCurrentIndex(1);
+  if (!restoreSelectedExecutable()) {
+    ui->executablesListBox->setCurrentIndex(1);
+  }
+
   ui->executablesListBox->setEnabled(true);
 }
 
+bool MainWindow::restoreSelectedExecutable()
+{
+  const auto profile = m_OrganizerCore.currentProfile();
+  if (!profile) {
+    return false;
+  }
+
+  const QString title =
+      profile->setting("UserInterface", "selected_executable").toString();
+  if (title.isEmpty()) {
+    return false;
+  }
+
+  // index 0 is the <Edit...> entry
+  for (int i = 1; i < ui->executablesListBox->count(); ++i) {
+    if (ui->executablesListBox->itemText(i) == title) {
+      ui->executablesListBox->setCurrentIndex(i);
+      return true;
+    }
+  }
+
+  return false;
+}
+
 static bool BySortValue(const std::pair<UINT32, QTreeWidgetItem*>& LHS,
                         const std::pair<UINT32, QTreeWidgetItem*>& RHS)
 {
@@ -2000,6 +2039,19 @@ void MainWindow::updateBSAList(const QStringList& defaultArchives,
           QStringList() << fileInfo.fileName() << ToQString(origin.getName()));
       newItem->setData(0, Qt::UserRole, index);
       newItem->setData(1, Qt::UserRole, originId);
+
+      // archives written into overwrite by the game or a tool compete with
+      // loose files from every mod, so they're flagged to make them stand out
+      // and to allow moving them into a mod of their own
+      const ModInfo::Ptr overwrite = ModInfo::getOverwrite();
+      const bool fromOverwrite =
+          overwrite && (ToQString(origin.getName()) == overwrite->name());
+      newItem->setData(1, OverwriteArchiveRole, fromOverwrite);
+      if (fromOverwrite) {
+        newItem->setToolTip(
+            1, tr("This archive is in overwrite. Use \"Move to new mod...\" to "
+                  "turn it into a regular mod."));
+      }
       newItem->setFlags(newItem->flags() &
                         ~(Qt::ItemIsDropEnabled | Qt::ItemIsUserCheckable));
       newItem->setCheckState(0, (index != -1) ? Qt::Checked : Qt::Unchecked);
@@ -2177,7 +2229,9 @@ void MainWindow::readSettings()
 
   FilterWidget::setOptions(s.interface().filterOptions());
 
-  {
+  // the profile remembers the executable by title, the index in the INI is
+  // only a fallback for profiles that don't have one yet
+  if (!restoreSelectedExecutable()) {
     // special case in case someone puts 0 in the INI
     auto v = s.widgets().index(ui->executablesListBox);
     if (!v || v == 0) {
@@ -2355,6 +2409,11 @@ void MainWindow::on_executablesListBox_currentIndexChanged(int index)
     } else {
       ui->executablesListBox->setCurrentIndex(newCount - 1);
     }
+  } else if (auto profile = m_OrganizerCore.currentProfile()) {
+    if (const Executable* exe = getSelectedExecutable()) {
+      profile->storeSetting("UserInterface", "selected_executable",
+                            exe->title());
+    }
   }
 }
 
@@ -2383,6 +2442,14 @@ void MainWindow::issueTriggered()
   shell::Open(QUrl("https://github.com/Modorganizer2/modorganizer/issues"));
 }
 
+void MainWindow::copyDiagnosticsTriggered()
+{
+  QApplication::clipboard()->setText(
+      supportSummary(m_OrganizerCore, m_PluginContainer));
+
+  showMessage(tr("Diagnostics copied to the clipboard"));
+}
+
 void MainWindow::tutorialTriggered()
 {
   QAction* tutorialAction = qobject_cast<QAction*>(sender());
@@ -2766,6 +2833,133 @@ void MainWindow::on_linkButton_pressed()
                                                                      : addIcon);
 }
 
+void MainWindow::on_actionCleanJunkFiles_triggered()
+{
+  auto junk = scanJunkFiles();
+  if (junk.empty()) {
+    QMessageBox::information(this, tr("Clean Junk Files"),
+                             tr("No junk files were found in your mods."));
+    return;
+  }
+
+  JunkFilesDialog dialog(std::move(junk), this);
+  if (dialog.exec() != QDialog::Accepted) {
+    return;
+  }
+
+  const auto selected = dialog.selected();
+  if (selected.empty()) {
+    return;
+  }
+
+  QStringList failed;
+  for (const auto& j : selected) {
+    if (!removeJunkFile(j)) {
+      failed.append(QString("%1: %2").arg(j.modName).arg(j.relativePath));
+    }
+  }
+
+  m_OrganizerCore.refresh();
+
+  if (!failed.isEmpty()) {
+    QMessageBox::warning(this, tr("Clean Junk Files"),
+                         tr("The following could not be moved to the trash:\n%1")
+                             .arg(failed.join("\n")));
+  }
+}
+
+void MainWindow::on_actionCheckModIntegrity_triggered()
+{
+  ui->actionCheckModIntegrity->setEnabled(false);
+
+  auto* watcher = new QFutureWatcher<std::vector<IntegrityIssue>>();
+  QObject::connect(
+      watcher, &QFutureWatcher<std::vector<IntegrityIssue>>::finished,
+      [this, watcher]() {
+        const auto issues = watcher->result();
+        watcher->deleteLater();
+        ui->actionCheckModIntegrity->setEnabled(true);
+
+        if (issues.empty()) {
+          QMessageBox::information(this, tr("Check Mod Integrity"),
+                                   tr("No damaged files were found in your mods."));
+          return;
+        }
+
+        QStringList lines;
+        for (const auto& issue : issues) {
+          lines.append(QString("%1: %2 (%3)")
+                           .arg(issue.modName)
+                           .arg(issue.relativePath)
+                           .arg(issue.description()));
+        }
+
+        QMessageBox box(QMessageBox::Warning, tr("Check Mod Integrity"),
+                        tr("%n file(s) look damaged. Reinstalling the affected "
+                           "mods usually fixes this.",
+                           "", static_cast<int>(issues.size())),
+                        QMessageBox::Ok, this);
+
+        box.setDetailedText(lines.join("\n"));
+        box.exec();
+      });
+
+  watcher->setFuture(scanModIntegrity());
+}
+
+void MainWindow::on_actionRepairInstance_triggered()
+{
+  m_OrganizerCore.repairInstance([this](QStringList report) {
+    if (report.isEmpty()) {
+      QMessageBox::information(this, tr("Repair Instance"),
+                               tr("No problems were found."));
+      return;
+    }
+
+    QMessageBox box(QMessageBox::Information, tr("Repair Instance"),
+                    tr("%n change(s) or problem(s) found.", "", report.size()),
+                    QMessageBox::Ok, this);
+
+    box.setDetailedText(report.join("\n"));
+    box.exec();
+  });
+}
+
+void MainWindow::on_actionLastRunChanges_triggered()
+{
+  const auto& changes = m_OrganizerCore.lastRunOverwriteChanges();
+  if (changes.empty()) {
+    QMessageBox::information(this, tr("Overwrite Changes of Last Run"),
+                             tr("The last program didn't change any file in overwrite."));
+    return;
+  }
+
+  QStringList lines;
+  for (const auto& change : changes) {
+    switch (change.kind) {
+    case OverwriteChange::Kind::Created:
+      lines.append(tr("created: %1").arg(change.path));
+      break;
+
+    case OverwriteChange::Kind::Modified:
+      lines.append(tr("modified: %1").arg(change.path));
+      break;
+
+    case OverwriteChange::Kind::Removed:
+      lines.append(tr("removed: %1").arg(change.path));
+      break;
+    }
+  }
+
+  QMessageBox box(QMessageBox::Information, tr("Overwrite Changes of Last Run"),
+                  tr("%n file(s) changed in overwrite.", "",
+                     static_cast<int>(changes.size())),
+                  QMessageBox::Ok, this);
+
+  box.setDetailedText(lines.join("\n"));
+  box.exec();
+}
+
 void MainWindow::on_actionSettings_triggered()
 {
   Settings& settings = m_OrganizerCore.settings();
@@ -2776,6 +2970,8 @@ void MainWindow::on_actionSettings_triggered()
   QString oldManagedGameDirectory(settings.game().directory().value_or(""));
   bool oldDisplayForeign(settings.interface().displayForeign());
   bool oldArchiveParsing(settings.archiveParsing());
+  const QStringList oldConflictExtensions(settings.conflictExtensions());
+  const QStringList oldIgnoredModFolders(settings.ignoredModFolders());
   bool proxy                    = settings.network().useProxy();
   DownloadManager* dlManager    = m_OrganizerCore.downloadManager();
   const bool oldCheckForUpdates = settings.checkForUpdates();
@@ -2837,7 +3033,9 @@ void MainWindow::on_actionSettings_triggered()
   }
 
   if ((settings.paths().mods() != oldModDirectory) ||
-      (settings.interface().displayForeign() != oldDisplayForeign)) {
+      (settings.interface().displayForeign() != oldDisplayForeign) ||
+      (settings.conflictExtensions() != oldConflictExtensions) ||
+      (settings.ignoredModFolders() != oldIgnoredModFolders)) {
     m_OrganizerCore.refresh();
   }
 
@@ -3689,7 +3887,7 @@ void MainWindow::extractBSATriggered(QTreeWidgetItem* item)
       progress.setMaximum(100);
       progress.setValue(0);
       progress.show();
-      archive.extractAll(
+      BSA::EErrorCode extracted = archive.extractAll(
           QDir::toNativeSeparators(targetFolder).toLocal8Bit().constData(),
           boost::bind(&MainWindow::extractProgress, this, boost::ref(progress), _1,
                       _2));
@@ -3698,17 +3896,99 @@ void MainWindow::extractBSATriggered(QTreeWidgetItem* item)
             tr("This archive contains invalid hashes. Some files may be broken."));
       }
       archive.close();
+
+      // canceling only applies to this archive's progress dialog
+      if (extracted == BSA::ERROR_CANCELED) {
+        log::warn("extraction of {} canceled", archiveName);
+        continue;
+      }
+    }
+  }
+}
+
+// whether the game loads `archive` for `plugin`, which is the case for
+// `<plugin>.bsa` and `<plugin> - <anything>.bsa` (or .ba2)
+//
+static bool archiveBelongsToPlugin(const QString& archive, const QString& plugin)
+{
+  const QString archiveBase = QFileInfo(archive).completeBaseName();
+  const QString pluginBase  = QFileInfo(plugin).completeBaseName();
+
+  return archiveBase.compare(pluginBase, Qt::CaseInsensitive) == 0 ||
+         archiveBase.startsWith(pluginBase + " - ", Qt::CaseInsensitive);
+}
+
+void MainWindow::moveOverwriteArchiveToMod(const QString& archiveName)
+{
+  // the plugins loading the archive have to travel with it or it would stop
+  // loading
+  const QDir overwriteDir(m_OrganizerCore.overwritePath());
+  QStringList plugins;
+  for (const QString& plugin :
+       overwriteDir.entryList({"*.esp", "*.esm", "*.esl"}, QDir::Files)) {
+    if (archiveBelongsToPlugin(archiveName, plugin)) {
+      plugins.append(plugin);
+    }
+  }
+
+  GuessedValue<QString> name;
+  name.setFilter(&fixDirectoryName);
+  name.update(QFileInfo(archiveName).completeBaseName(), GUESS_FALLBACK);
+
+  QString text = tr("This will move %1 from overwrite into a new, regular mod.")
+                     .arg(archiveName);
+  if (!plugins.isEmpty()) {
+    text += "\n" + tr("These plugins load it and are moved along: %1")
+                       .arg(plugins.join(", "));
+  }
+  text += "\n" + tr("Please enter a name:");
+
+  bool ok = false;
+  name.update(QInputDialog::getText(this, tr("Create Mod..."), text,
+                                    QLineEdit::Normal, name, &ok),
+              GUESS_USER);
+  if (!ok || name->isEmpty()) {
+    return;
+  }
+
+  if (m_OrganizerCore.modList()->getMod(name) != nullptr) {
+    reportError(tr("A mod with this name already exists"));
+    return;
+  }
+
+  const IModInterface* newMod = m_OrganizerCore.createMod(name);
+  if (newMod == nullptr) {
+    return;
+  }
+
+  const QStringList files = QStringList{archiveName} + plugins;
+  const QDir modDir(newMod->absolutePath());
+  for (const QString& file : files) {
+    if (!shellMove(overwriteDir.absoluteFilePath(file), modDir.absoluteFilePath(file),
+                   true, this)) {
+      const auto e = GetLastError();
+      log::error("failed to move '{}' from overwrite: {}", file,
+                 formatSystemMessage(e));
     }
   }
+
+  m_OrganizerCore.refresh();
 }
 
 void MainWindow::on_bsaList_customContextMenuRequested(const QPoint& pos)
 {
   QMenu menu;
-  menu.addAction(tr("Extract..."), [=, this, item = ui->bsaList->itemAt(pos)]() {
+  QTreeWidgetItem* item = ui->bsaList->itemAt(pos);
+  menu.addAction(tr("Extract..."), [=, this]() {
     extractBSATriggered(item);
   });
 
+  if ((item != nullptr) && item->data(1, OverwriteArchiveRole).toBool()) {
+    menu.addAction(tr("Move to new mod..."), [=, this]() {
+      moveOverwriteArchiveToMod(item->text(0));
+    });
+  }
+
   menu.exec(ui->bsaList->viewport()->mapToGlobal(pos));
 }
 
@@ -221,6 +221,10 @@ private:
   bool refreshProfiles(bool selectProfile = true, QString newProfile = QString());
   void refreshExecutablesList();
 
+  // selects the executable last picked in the current profile, returns false
+  // if there is none or it doesn't exist anymore
+  bool restoreSelectedExecutable();
+
   bool modifyExecutablesDialog(int selection);
 
   // remove invalid category-references from mods
@@ -339,12 +343,17 @@ private slots:
   // main window actions
   void helpTriggered();
   void issueTriggered();
+  void copyDiagnosticsTriggered();
   void wikiTriggered();
   void gameSupportTriggered();
   void discordTriggered();
   void tutorialTriggered();
   void extractBSATriggered(QTreeWidgetItem* item);
 
+  // moves an archive from overwrite, with the plugins loading it, into a new
+  // mod named by the user
+  void moveOverwriteArchiveToMod(const QString& archiveName);
+
   void refreshProfile_activated();
 
   void linkToolbar();
@@ -453,6 +462,10 @@ private slots:  // ui slots
   void on_actionNexus_triggered();
   void on_actionNotifications_triggered();
   void on_actionSettings_triggered();
+  void on_actionCleanJunkFiles_triggered();
+  void on_actionCheckModIntegrity_triggered();
+  void on_actionRepairInstance_triggered();
+  void on_actionLastRunChanges_triggered();
   void on_actionUpdate_triggered();
   void on_actionExit_triggered();
   void on_actionMainMenuToggle_triggered();
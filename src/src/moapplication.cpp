@@ -43,6 +43,7 @@ along with Mod Organizer.  If not, see <http://www.gnu.org/licenses/>.
 #include <QDebug>
 #include <QFile>
 #include <QPainter>
+#include <QProgressDialog>
 #include <QProxyStyle>
 #include <QSslSocket>
 #include <QStringList>
@@ -384,6 +385,9 @@ int MOApplication::setup(MOMultiProcess& multiProcess, bool forceSelect)
     log::debug("this is a portable instance");
   }
 
+  // before anything below reads the prefix from the instance
+  m_settings->processFluorineUpdates();
+
   tt.start("MOApplication::doOneRun() OrganizerCore setup");
 
   sanity::checkPaths(*m_instance->gamePlugin(), *m_settings);
@@ -406,14 +410,7 @@ int MOApplication::setup(MOMultiProcess& multiProcess, bool forceSelect)
     auto prefixPath = FluorineConfig::prefixPath();
     if (!prefixPath || prefixPath->isEmpty()) {
       QSettings instanceSettings(m_settings->filename(), QSettings::IniFormat);
-      for (const auto& key : {"Settings/proton_prefix_path", "Settings/prefix_path",
-                               "Proton/prefix_path", "fluorine/prefix_path"}) {
-        const QString value = instanceSettings.value(key).toString().trimmed();
-        if (!value.isEmpty()) {
-          prefixPath = value;
-          break;
-        }
-      }
+      prefixPath = instanceSettings.value("fluorine/prefix_path").toString().trimmed();
     }
     if (prefixPath && !prefixPath->isEmpty()) {
       WinePrefix prefix(*prefixPath);
@@ -439,7 +436,30 @@ int MOApplication::setup(MOMultiProcess& multiProcess, bool forceSelect)
 
   CategoryFactory::instance().loadCategories();
   m_core->updateExecutablesList();
-  m_core->updateModInfoFromDisc();
+
+  {
+    // only shows up when reading the mods takes a while, so small instances
+    // don't flash a dialog
+    QProgressDialog progress(tr("Reading mods..."), tr("Cancel"), 0, 0);
+    progress.setWindowTitle(m_instance->displayName());
+    progress.setWindowModality(Qt::ApplicationModal);
+    progress.setMinimumDuration(1000);
+
+    const bool loaded =
+        m_core->updateModInfoFromDisc([&](std::size_t done, std::size_t total) {
+          progress.setMaximum(static_cast<int>(total));
+          progress.setValue(static_cast<int>(done));
+          progress.setLabelText(tr("Reading mods (%1 of %2)...").arg(done).arg(total));
+          return !progress.wasCanceled();
+        });
+
+    if (!loaded) {
+      log::info("loading the instance was cancelled");
+      InstanceManager::singleton().clearCurrentInstance();
+      return ReselectExitCode;
+    }
+  }
+
   m_core->setCurrentProfile(m_instance->profileName());
 
   return 0;
@@ -59,6 +59,32 @@ QRecursiveMutex ModInfo::s_Mutex;
 
 QString ModInfo::s_HiddenExt(".mohidden");
 
+namespace
+{
+// moves a mod directory into the hidden .trash folder next to it so the
+// removal can be undone; returns the new path, or an empty string on failure
+QString moveToModTrash(const QString& modPath)
+{
+  const QFileInfo info(modPath);
+  const QDir trash(info.dir().filePath(".trash"));
+
+  if (!trash.mkpath(".")) {
+    return {};
+  }
+
+  QString target = trash.filePath(info.fileName());
+  for (int i = 1; QFileInfo::exists(target); ++i) {
+    target = trash.filePath(QString("%1 (%2)").arg(info.fileName()).arg(i));
+  }
+
+  if (!QDir().rename(modPath, target)) {
+    return {};
+  }
+
+  return target;
+}
+}  // namespace
+
 bool ModInfo::ByName(const ModInfo::Ptr& LHS, const ModInfo::Ptr& RHS)
 {
   return QString::compare(LHS->name(), RHS->name(), Qt::CaseInsensitive) < 0;
@@ -83,6 +109,21 @@ bool ModInfo::isRegularName(const QString& name)
   return !isSeparatorName(name) && !isBackupName(name);
 }
 
+bool ModInfo::isIgnoredName(const QString& name, const QStringList& patterns)
+{
+  for (const auto& pattern : patterns) {
+    const QRegularExpression exp(
+        QRegularExpression::wildcardToRegularExpression(pattern),
+        QRegularExpression::CaseInsensitiveOption);
+
+    if (exp.match(name).hasMatch()) {
+      return true;
+    }
+  }
+
+  return false;
+}
+
 ModInfo::Ptr ModInfo::createFrom(const QDir& dir, OrganizerCore& core)
 {
   QMutexLocker locker(&s_Mutex);
@@ -170,7 +211,7 @@ ModInfo::Ptr ModInfo::getByName(const QString& name)
   return s_Collection[ModInfo::getIndex(name)];
 }
 
-bool ModInfo::removeMod(unsigned int index)
+bool ModInfo::removeMod(unsigned int index, QString* trashPath)
 {
   QMutexLocker locker(&s_Mutex);
 
@@ -182,11 +223,16 @@ bool ModInfo::removeMod(unsigned int index)
 
   // remove the actual mod (this is the most likely to fail so we do this first)
   if (modInfo->isRegular()) {
-    if (!shellDelete(QStringList(modInfo->absolutePath()), true)) {
+    const QString trashed = moveToModTrash(modInfo->absolutePath());
+    if (trashed.isEmpty()) {
       reportError(
           tr("remove: failed to delete mod '%1' directory").arg(modInfo->name()));
       return false;
     }
+
+    if (trashPath != nullptr) {
+      *trashPath = trashed;
+    }
   }
 
   // update the indices
@@ -231,8 +277,10 @@ unsigned int ModInfo::findMod(const boost::function<bool(ModInfo::Ptr)>& filter)
   return UINT_MAX;
 }
 
-void ModInfo::updateFromDisc(const QString& modsDirectory, OrganizerCore& core,
-                             bool displayForeign, std::size_t refreshThreadCount)
+bool ModInfo::updateFromDisc(const QString& modsDirectory, OrganizerCore& core,
+                             bool displayForeign, std::size_t refreshThreadCount,
+                             const QStringList& ignoredFolders,
+                             const ScanProgress& progress)
 {
   TimeThis tt("ModInfo::updateFromDisc()");
 
@@ -247,14 +295,37 @@ void ModInfo::updateFromDisc(const QString& modsDirectory, OrganizerCore& core,
     if (!mods.exists()) {
       log::error("mods directory does not exist: '{}'", cleanModsDir);
     }
+    // hidden folders are skipped by the filter
     mods.setFilter(QDir::Dirs | QDir::NoDotAndDotDot);
     QDirIterator modIter(mods);
-    std::size_t managedCount = 0;
+    std::size_t ignoredCount = 0;
+
+    // listed first so progress knows the total
+    std::vector<QDir> modDirs;
     while (modIter.hasNext()) {
-      createFrom(QDir(modIter.next()), core);
-      ++managedCount;
+      QDir dir(modIter.next());
+      if (isIgnoredName(dir.dirName(), ignoredFolders)) {
+        log::debug("ignoring mod directory '{}'", dir.dirName());
+        ++ignoredCount;
+        continue;
+      }
+
+      modDirs.push_back(std::move(dir));
+    }
+
+    const std::size_t managedCount = modDirs.size();
+    for (std::size_t i = 0; i < managedCount; ++i) {
+      createFrom(modDirs[i], core);
+
+      if (progress && !progress(i + 1, managedCount)) {
+        log::info("reading mods cancelled after {} of {}", i + 1, managedCount);
+        s_Collection.clear();
+        updateIndices();
+        return false;
+      }
     }
-    log::info("found {} managed mod directories in '{}'", managedCount, cleanModsDir);
+    log::info("found {} managed mod directories in '{}', {} ignored", managedCount,
+              cleanModsDir, ignoredCount);
     if (managedCount == 0 && mods.exists()) {
       log::warn("mods directory exists but contains no subdirectories; "
                 "check path and permissions");
@@ -289,6 +360,7 @@ void ModInfo::updateFromDisc(const QString& modsDirectory, OrganizerCore& core,
               refreshThreadCount);
 
   updateIndices();
+  return true;
 }
 
 void ModInfo::updateIndices()
@@ -37,6 +37,7 @@ class QDateTime;
 
 #include <boost/function.hpp>
 
+#include <functional>
 #include <map>
 #include <set>
 #include <vector>
@@ -114,10 +115,21 @@ public:  // Type definitions:
 
 public:  // Static functions:
   /**
-   * @brief Read the mod directory and Mod ModInfo objects for all subdirectories.
+   * @brief Called by updateFromDisc() after each mod directory with the number of
+   *   directories read so far and the total; returning false cancels the scan.
    */
-  static void updateFromDisc(const QString& modDirectory, OrganizerCore& core,
-                             bool displayForeign, std::size_t refreshThreadCount);
+  using ScanProgress = std::function<bool(std::size_t done, std::size_t total)>;
+
+  /**
+   * @brief Read the mod directory and Mod ModInfo objects for all subdirectories,
+   *   except hidden ones and those matching ignoredFolders.
+   *
+   * @return false if progress cancelled the scan, no mods are loaded then
+   */
+  static bool updateFromDisc(const QString& modDirectory, OrganizerCore& core,
+                             bool displayForeign, std::size_t refreshThreadCount,
+                             const QStringList& ignoredFolders = {},
+                             const ScanProgress& progress     = {});
 
   static void clear()
   {
@@ -170,14 +182,17 @@ public:  // Static functions:
   /**
    * @brief Remove a mod by index.
    *
-   * This physically deletes the specified mod from the disc and updates the ModInfo
-   * collection but not other structures that reference mods.
+   * This moves the directory of the specified mod to the ".trash" folder of the
+   * mods directory and updates the ModInfo collection but not other structures
+   * that reference mods.
    *
    * @param index Index of the mod to delete.
+   * @param trashPath If not null, receives the path the mod directory was moved
+   *     to, or an empty string if the mod has no directory.
    *
    * @return true if removal was successful, false otherwise.
    */
-  static bool removeMod(unsigned int index);
+  static bool removeMod(unsigned int index, QString* trashPath = nullptr);
 
   /**
    * @brief Retrieve the mod index by the mod name.
@@ -245,6 +260,14 @@ public:  // Static functions:
    */
   static bool isRegularName(const QString& name);
 
+  /**
+   * @brief Check whether a folder in the mods directory is ignored by one of
+   *   the given wildcard patterns, case-insensitively.
+   *
+   * @return whether the folder should not be listed as a mod.
+   */
+  static bool isIgnoredName(const QString& name, const QStringList& patterns);
+
 public:  // IModInterface implementations / Re-declaration
   // Note: This section contains default-implementation for some of the virtual methods
   // from IModInterface, but also redeclaration of all the pure-virtual methods to
@@ -971,6 +994,12 @@ public:  // Conflicts
     return s_EmptySet;
   }
 
+  // number of loose files of this mod that overwrite the same file in other
+  // mods, and that are overwritten by another mod. Updates may be delayed.
+  //
+  virtual unsigned int winningFileCount() const { return 0; }
+  virtual unsigned int losingFileCount() const { return 0; }
+
 public slots:
 
   /**
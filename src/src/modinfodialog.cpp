@@ -31,6 +31,8 @@ along with Mod Organizer.  If not, see <http://www.gnu.org/licenses/>.
 #include "shared/directoryentry.h"
 #include "shared/filesorigin.h"
 #include "ui_modinfodialog.h"
+#include <QDirIterator>
+#include <algorithm>
 #include <filesystem>
 
 using namespace MOBase;
@@ -109,50 +111,90 @@ FileRenamer::RenameResults hideFile(FileRenamer& renamer, const QString& oldName
   return renamer.rename(oldName, newName);
 }
 
+int hideFiles(FileRenamer& renamer, const QString& modPath,
+              const QStringList& relativePaths)
+{
+  const QDir modDir(modPath);
+  int hidden = 0;
+
+  for (QString relative : relativePaths) {
+    // relative paths from the directory structure start with a separator
+    while (relative.startsWith('/') || relative.startsWith('\\')) {
+      relative.remove(0, 1);
+    }
+
+    if (relative.isEmpty() ||
+        relative.endsWith(ModInfo::s_HiddenExt, Qt::CaseInsensitive)) {
+      continue;
+    }
+
+    const QString oldName = modDir.absoluteFilePath(relative);
+
+    if (!QFileInfo::exists(oldName)) {
+      log::debug("{} doesn't exist, not hiding it", oldName);
+      continue;
+    }
+
+    const auto result = hideFile(renamer, oldName);
+
+    if (result == FileRenamer::RESULT_CANCEL) {
+      break;
+    }
+
+    if (result == FileRenamer::RESULT_OK) {
+      ++hidden;
+    }
+  }
+
+  return hidden;
+}
+
 FileRenamer::RenameResults unhideFile(FileRenamer& renamer, const QString& oldName)
 {
   QString newName = oldName.left(oldName.length() - ModInfo::s_HiddenExt.length());
   return renamer.rename(oldName, newName);
 }
 
-FileRenamer::RenameResults restoreHiddenFilesRecursive(FileRenamer& renamer,
-                                                       const QString& targetDir)
+int unhideFiles(const QString& modPath)
 {
-  FileRenamer::RenameResults results = FileRenamer::RESULT_OK;
-  QDir currentDir                    = targetDir;
-  for (QString hiddenFile :
-       currentDir.entryList((QStringList() << "*" + ModInfo::s_HiddenExt),
-                            QDir::Dirs | QDir::Files | QDir::NoDotAndDotDot)) {
-
-    QString oldName = currentDir.absoluteFilePath(hiddenFile);
-    QString newName = oldName.left(oldName.length() - ModInfo::s_HiddenExt.length());
-
-    auto partialResult = renamer.rename(oldName, newName);
+  // renamed deepest first so unhiding a directory doesn't move entries that
+  // haven't been handled yet
+  QStringList hidden;
 
-    if (partialResult == FileRenamer::RESULT_CANCEL) {
-      return FileRenamer::RESULT_CANCEL;
-    }
+  QDirIterator iter(modPath, QDir::Files | QDir::Dirs | QDir::Hidden |
+                                 QDir::NoDotAndDotDot,
+                    QDirIterator::Subdirectories);
 
-    if (partialResult == FileRenamer::RESULT_SKIP) {
-      results = FileRenamer::RESULT_SKIP;
+  while (iter.hasNext()) {
+    const QString path = iter.next();
+    if (path.endsWith(ModInfo::s_HiddenExt, Qt::CaseInsensitive)) {
+      hidden.push_back(path);
     }
   }
 
-  for (QString dirName : currentDir.entryList(QDir::AllDirs | QDir::NoDotAndDotDot)) {
+  std::sort(hidden.begin(), hidden.end(), [](const QString& a, const QString& b) {
+    return a.size() > b.size();
+  });
+
+  int restored = 0;
 
-    const QString dirPath = currentDir.absoluteFilePath(dirName);
-    // recurse on childrend directories
-    auto partialResult = restoreHiddenFilesRecursive(renamer, dirPath);
+  for (const QString& oldName : hidden) {
+    const QString newName = oldName.left(oldName.size() - ModInfo::s_HiddenExt.size());
 
-    if (partialResult == FileRenamer::RESULT_CANCEL) {
-      return FileRenamer::RESULT_CANCEL;
+    if (QFileInfo::exists(newName)) {
+      log::warn("not unhiding '{}', '{}' already exists", oldName, newName);
+      continue;
     }
 
-    if (partialResult == FileRenamer::RESULT_SKIP) {
-      results = FileRenamer::RESULT_SKIP;
+    if (!QDir().rename(oldName, newName)) {
+      log::error("failed to rename '{}' to '{}'", oldName, newName);
+      continue;
     }
+
+    ++restored;
   }
-  return results;
+
+  return restored;
 }
 
 ModInfoDialog::TabInfo::TabInfo(std::unique_ptr<ModInfoDialogTab> tab)
@@ -1,13 +1,16 @@
 #include "modinfodialogconflicts.h"
 #include "modinfodialog.h"
 #include "modinfodialogconflictsmodels.h"
+#include "modlist.h"
 #include "organizercore.h"
+#include "profile.h"
 #include "settings.h"
 #include "shared/directoryentry.h"
 #include "shared/fileentry.h"
 #include "shared/filesorigin.h"
 #include "ui_modinfodialog.h"
 #include "utility.h"
+#include <QMessageBox>
 
 using namespace MOShared;
 using namespace MOBase;
@@ -113,8 +116,18 @@ bool ConflictsTab::canHandleUnmanaged() const
 
 void ConflictsTab::hideItems(QTreeView* tree)
 {
-  bool changed = false;
-  bool stop    = false;
+#ifndef _WIN32
+  // foreign mods live in the game's data directory, which is the mount point
+  // of the vfs while it's running; their real files can't be reached then
+  if (mod().isForeign() &&
+      core().vfsMountState() != FuseConnector::MountState::Unmounted) {
+    QMessageBox::warning(parentWidget(), tr("Cannot hide files"),
+                         tr("Files in the game's data directory cannot be hidden "
+                            "while an application is running in the virtual "
+                            "file system."));
+    return;
+  }
+#endif
 
   const auto n = smallSelectionSize(tree);
 
@@ -151,43 +164,25 @@ void ConflictsTab::hideItems(QTreeView* tree)
     return;
   }
 
-  forEachInSelection(tree, [&](const ConflictItem* item) {
-    if (stop) {
-      return false;
-    }
+  QStringList relativePaths;
 
+  forEachInSelection(tree, [&](const ConflictItem* item) {
     if (!item->canHide()) {
       log::debug("cannot hide {}, skipping", item->relativeName());
       return true;
     }
 
-    auto result = hideFile(renamer, item->fileName());
-
-    switch (result) {
-    case FileRenamer::RESULT_OK: {
-      // will trigger a refresh at the end
-      changed = true;
-      break;
-    }
-
-    case FileRenamer::RESULT_SKIP: {
-      // nop
-      break;
-    }
-
-    case FileRenamer::RESULT_CANCEL: {
-      // stop right now, but make sure to refresh if needed
-      stop = true;
-      break;
-    }
-    }
-
+    relativePaths.push_back(item->relativeName());
     return true;
   });
 
-  log::debug("hiding conflict files done");
+  // renames in the mod's own directory instead of going through the paths
+  // shown in the tree
+  const int hidden = hideFiles(renamer, mod().absolutePath(), relativePaths);
 
-  if (changed) {
+  log::debug("hiding conflict files done, {} hidden", hidden);
+
+  if (hidden > 0) {
     log::debug("triggering refresh");
 
     if (origin()) {
@@ -262,6 +257,25 @@ void ConflictsTab::exploreItems(QTreeView* tree)
   });
 }
 
+void ConflictsTab::setConflictAcknowledged(const QString& otherMod, bool acknowledged)
+{
+  auto profile = core().currentProfile();
+  if (!profile) {
+    return;
+  }
+
+  profile->setConflictAcknowledged(mod().name(), otherMod, acknowledged);
+
+  // both mods need their conflict flags recomputed
+  for (const auto& name : {mod().name(), otherMod}) {
+    const unsigned int index = ModInfo::getIndex(name);
+    if (index < ModInfo::getNumMods()) {
+      ModInfo::getByIndex(index)->clearCaches();
+      core().modList()->notifyChange(static_cast<int>(index));
+    }
+  }
+}
+
 void ConflictsTab::showContextMenu(const QPoint& pos, QTreeView* tree)
 {
   auto actions = createMenuActions(tree);
@@ -323,6 +337,19 @@ void ConflictsTab::showContextMenu(const QPoint& pos, QTreeView* tree)
     }
   }
 
+  // acknowledge
+  if (actions.ackMenu) {
+    menu.addMenu(actions.ackMenu);
+
+    for (auto* a : actions.ackActions) {
+      connect(a, &QAction::toggled, [&, name = a->text()](bool checked) {
+        setConflictAcknowledged(name, checked);
+      });
+
+      actions.ackMenu->addAction(a);
+    }
+  }
+
   // explore
   if (actions.explore) {
     connect(actions.explore, &QAction::triggered, [&] {
@@ -451,6 +478,9 @@ ConflictsTab::Actions ConflictsTab::createMenuActions(QTreeView* tree)
   actions.gotoMenu = new QMenu(tr("&Go to..."), parentWidget());
   actions.gotoMenu->setEnabled(enableGoto);
 
+  actions.ackMenu = new QMenu(tr("&Acknowledge conflicts with"), parentWidget());
+  actions.ackMenu->setEnabled(enableGoto);
+
   actions.explore = new QAction(tr("Open in &Explorer"), parentWidget());
   actions.explore->setEnabled(enableExplore);
 
@@ -462,6 +492,19 @@ ConflictsTab::Actions ConflictsTab::createMenuActions(QTreeView* tree)
         model->getItem(static_cast<std::size_t>(modelSel.indexes()[0].row()));
 
     actions.gotoActions = createGotoActions(item);
+
+    const auto profile = core().currentProfile();
+    std::set<std::pair<QString, QString>> pairs;
+    if (profile) {
+      pairs = profile->acknowledgedConflicts();
+    }
+
+    for (const auto* a : actions.gotoActions) {
+      auto* ack = new QAction(a->text(), parentWidget());
+      ack->setCheckable(true);
+      ack->setChecked(pairs.contains(Profile::conflictPair(mod().name(), a->text())));
+      actions.ackActions.push_back(ack);
+    }
   }
 
   return actions;
@@ -136,6 +136,7 @@ public:
   void openItem(const ConflictItem* item, bool hooked);
   void previewItem(const ConflictItem* item);
   void hideItems(QTreeView* tree);
+  void setConflictAcknowledged(const QString& otherMod, bool acknowledged);
 
   void showContextMenu(const QPoint& pos, QTreeView* tree);
 
@@ -148,8 +149,10 @@ private:
     QAction* preview   = nullptr;
     QAction* explore   = nullptr;
     QMenu* gotoMenu    = nullptr;
+    QMenu* ackMenu     = nullptr;
 
     std::vector<QAction*> gotoActions;
+    std::vector<QAction*> ackActions;
   };
 
   GeneralConflictsTab m_general;
@@ -32,9 +32,24 @@ bool canHideFile(bool isArchive, const QString& filename);
 bool canUnhideFile(bool isArchive, const QString& filename);
 
 FileRenamer::RenameResults hideFile(FileRenamer& renamer, const QString& oldName);
+
+// hides files of a mod by renaming them in the mod's directory on disk, never
+// through the vfs, which can't rename files that come from a mod; files that
+// are already hidden or don't exist are skipped
+//
+// returns the number of files hidden, stops at the first file the user
+// cancels on
+//
+int hideFiles(FileRenamer& renamer, const QString& modPath,
+              const QStringList& relativePaths);
 FileRenamer::RenameResults unhideFile(FileRenamer& renamer, const QString& oldName);
-FileRenamer::RenameResults restoreHiddenFilesRecursive(FileRenamer& renamer,
-                                                       const QString& targetDir);
+
+// unhides every hidden file and directory in a mod directory; an entry is left
+// hidden if a file with the unhidden name already exists
+//
+// returns the number of entries unhidden
+//
+int unhideFiles(const QString& modPath);
 
 class ElideLeftDelegate : public QStyledItemDelegate
 {
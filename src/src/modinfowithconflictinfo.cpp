@@ -3,17 +3,68 @@
 #include "shared/fileentry.h"
 #include "shared/filesorigin.h"
 #include "utility.h"
+#include <QFileInfo>
+#include <QSet>
 #include <filesystem>
 
 #include "iplugingame.h"
 #include "moddatachecker.h"
 #include "organizercore.h"
+#include "profile.h"
 #include "qdirfiletree.h"
 
 using namespace MOBase;
 using namespace MOShared;
 namespace fs = std::filesystem;
 
+namespace
+{
+
+// extensions from Settings::conflictExtensions(), split into the ones to check
+// and the ones to ignore
+class ConflictExtensionFilter
+{
+public:
+  explicit ConflictExtensionFilter(const QStringList& entries)
+  {
+    for (const auto& entry : entries) {
+      QString ext = entry.trimmed().toLower();
+      const bool exclude = ext.startsWith('!');
+      if (exclude) {
+        ext.remove(0, 1);
+      }
+
+      // accept both "dds" and ".dds"
+      if (ext.startsWith('.')) {
+        ext.remove(0, 1);
+      }
+
+      if (ext.isEmpty()) {
+        continue;
+      }
+
+      (exclude ? m_Excluded : m_Included).insert(ext);
+    }
+  }
+
+  bool accepts(const QString& fileName) const
+  {
+    const QString ext = QFileInfo(fileName).suffix().toLower();
+
+    if (!m_Included.isEmpty() && !m_Included.contains(ext)) {
+      return false;
+    }
+
+    return !m_Excluded.contains(ext);
+  }
+
+private:
+  QSet<QString> m_Included;
+  QSet<QString> m_Excluded;
+};
+
+}  // namespace
+
 ModInfoWithConflictInfo::ModInfoWithConflictInfo(OrganizerCore& core)
     : ModInfo(core), m_FileTree([this]() {
         return QDirFileTree::makeTree(absolutePath());
@@ -117,6 +168,7 @@ ModInfoWithConflictInfo::Conflicts ModInfoWithConflictInfo::doConflictCheck() co
     FilesOrigin& origin = m_Core.directoryStructure()->getOriginByName(name);
     std::vector<FileEntryPtr> files = origin.getFiles();
     std::set<const DirectoryEntry*> checkedDirs;
+    const ConflictExtensionFilter filter(m_Core.settings().conflictExtensions());
 
     // for all files in this origin
     for (FileEntryPtr file : files) {
@@ -152,7 +204,15 @@ ModInfoWithConflictInfo::Conflicts ModInfoWithConflictInfo::doConflictCheck() co
         }
       }
 
-      hasVisibleFiles   = true;
+      hasVisibleFiles = true;
+
+      if (!filter.accepts(QString::fromStdWString(file->getName()))) {
+        // files of ignored types count as provided so they can't make the mod
+        // look redundant
+        providesAnything = true;
+        continue;
+      }
+
       auto alternatives = file->getAlternatives();
       if ((alternatives.size() == 0) ||
           std::find(dataIDs.begin(), dataIDs.end(), alternatives.back().originID()) !=
@@ -190,6 +250,14 @@ ModInfoWithConflictInfo::Conflicts ModInfoWithConflictInfo::doConflictCheck() co
           providesAnything = true;
         }
 
+        // loose files of this mod that win or lose against another mod
+        if (!archiveData.isValid()) {
+          if (file->getOrigin() == origin.getID())
+            ++conflicts.m_WinningFiles;
+          else
+            ++conflicts.m_LosingFiles;
+        }
+
         // Sort out the alternatives
         for (const auto& altInfo : alternatives) {
           if (!(std::find(dataIDs.begin(), dataIDs.end(),
@@ -225,6 +293,25 @@ ModInfoWithConflictInfo::Conflicts ModInfoWithConflictInfo::doConflictCheck() co
       }
     }
 
+    // drop mods the user acknowledged conflicts with
+    if (auto profile = m_Core.currentProfile()) {
+      const QString modName = this->name();
+      const auto pairs      = profile->acknowledgedConflicts();
+      auto acknowledged     = [&](unsigned int index) {
+        return index < ModInfo::getNumMods() &&
+               pairs.contains(
+                   Profile::conflictPair(modName, ModInfo::getByIndex(index)->name()));
+      };
+
+      for (auto* list :
+           {&conflicts.m_OverwriteList, &conflicts.m_OverwrittenList,
+            &conflicts.m_ArchiveOverwriteList, &conflicts.m_ArchiveOverwrittenList,
+            &conflicts.m_ArchiveLooseOverwriteList,
+            &conflicts.m_ArchiveLooseOverwrittenList}) {
+        std::erase_if(*list, acknowledged);
+      }
+    }
+
     if (files.size() != 0) {
       if (hasVisibleFiles && !providesAnything)
         conflicts.m_CurrentConflictState = CONFLICT_REDUNDANT;
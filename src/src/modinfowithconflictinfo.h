@@ -77,6 +77,14 @@ public:
   {
     return m_Conflicts.value().m_ArchiveLooseOverwrittenList;
   }
+  unsigned int winningFileCount() const override
+  {
+    return m_Conflicts.value().m_WinningFiles;
+  }
+  unsigned int losingFileCount() const override
+  {
+    return m_Conflicts.value().m_LosingFiles;
+  }
 
 public slots:
 
@@ -164,6 +172,9 @@ private:
     std::set<unsigned int>
         m_ArchiveLooseOverwrittenList;  // indices of mods with loose files overwriting
                                         // this mod's archive files
+
+    unsigned int m_WinningFiles = 0;  // loose files overwriting other mods
+    unsigned int m_LosingFiles  = 0;  // loose files overwritten by other mods
   };
 
   Conflicts doConflictCheck() const;
@@ -0,0 +1,180 @@
+#include "modintegrity.h"
+#include "modinfo.h"
+
+#include <QDir>
+#include <QDirIterator>
+#include <QFile>
+#include <QtConcurrent/QtConcurrentMap>
+#include <QtConcurrent/QtConcurrentRun>
+#include <QtEndian>
+#include <log.h>
+
+#include <algorithm>
+
+using namespace MOBase;
+
+namespace
+{
+
+struct ModToScan
+{
+  QString name;
+  QString path;
+};
+
+// whether a Bethesda archive is too short for what its header declares; the
+// file data itself isn't checked, a truncated download almost always cuts
+// into the records or the name table
+//
+bool isTruncatedArchive(QFile& file)
+{
+  const QByteArray header = file.read(36);
+  const qint64 size       = file.size();
+
+  auto u32 = [&](int offset) {
+    return qFromLittleEndian<quint32>(header.constData() + offset);
+  };
+
+  if (header.startsWith(QByteArray("BSA\0", 4))) {
+    if (header.size() < 36) {
+      return true;
+    }
+
+    // folder records grew from 16 to 24 bytes in Skyrim SE archives
+    const quint32 version     = u32(4);
+    const quint64 folderSize  = version >= 105 ? 24 : 16;
+    const quint64 folderCount = u32(16);
+    const quint64 fileCount   = u32(20);
+
+    return static_cast<quint64>(size) < 36 + folderCount * folderSize + fileCount * 16;
+  }
+
+  if (header.startsWith("BTDX")) {
+    if (header.size() < 24) {
+      return true;
+    }
+
+    const quint64 nameTableOffset =
+        qFromLittleEndian<quint64>(header.constData() + 16);
+
+    return static_cast<quint64>(size) < nameTableOffset;
+  }
+
+  // Morrowind archives
+  if (header.size() >= 4 && u32(0) == 0x100) {
+    return false;
+  }
+
+  // not an archive at all, most likely an error page saved under the
+  // archive's name or a download filled with zeroes
+  return true;
+}
+
+void scanDirectory(const ModToScan& mod, const QDir& root, const QString& path,
+                   std::vector<IntegrityIssue>& out)
+{
+  QDirIterator it(path, QDir::AllEntries | QDir::Hidden | QDir::System |
+                            QDir::NoDotAndDotDot);
+
+  while (it.hasNext()) {
+    it.next();
+    const QFileInfo info = it.fileInfo();
+
+    // don't follow symlinked directories out of the mod
+    if (info.isDir()) {
+      if (!info.isSymLink()) {
+        scanDirectory(mod, root, info.absoluteFilePath(), out);
+      }
+      continue;
+    }
+
+    IntegrityIssue::Kind kind;
+    if (!checkFileIntegrity(info.absoluteFilePath(), kind)) {
+      out.push_back({mod.name, root.relativeFilePath(info.absoluteFilePath()), kind});
+    }
+  }
+}
+
+std::vector<IntegrityIssue> scanMod(const ModToScan& mod)
+{
+  std::vector<IntegrityIssue> issues;
+  const QDir root(mod.path);
+  scanDirectory(mod, root, root.absolutePath(), issues);
+  return issues;
+}
+
+}  // namespace
+
+QString IntegrityIssue::description() const
+{
+  switch (kind) {
+  case Kind::EmptyFile:
+    return QObject::tr("empty file");
+
+  case Kind::TruncatedArchive:
+    return QObject::tr("damaged or truncated archive");
+
+  case Kind::Unreadable:
+    return QObject::tr("file cannot be read");
+  }
+
+  return {};
+}
+
+bool checkFileIntegrity(const QString& path, IntegrityIssue::Kind& kind)
+{
+  QFile file(path);
+
+  if (!file.open(QIODevice::ReadOnly)) {
+    kind = IntegrityIssue::Kind::Unreadable;
+    return false;
+  }
+
+  if (file.size() == 0) {
+    kind = IntegrityIssue::Kind::EmptyFile;
+    return false;
+  }
+
+  const QString suffix = QFileInfo(path).suffix();
+  if (suffix.compare("bsa", Qt::CaseInsensitive) == 0 ||
+      suffix.compare("ba2", Qt::CaseInsensitive) == 0) {
+    if (isTruncatedArchive(file)) {
+      kind = IntegrityIssue::Kind::TruncatedArchive;
+      return false;
+    }
+  }
+
+  return true;
+}
+
+QFuture<std::vector<IntegrityIssue>> scanModIntegrity()
+{
+  // mod infos are only read here, the worker threads just get paths
+  std::vector<ModToScan> mods;
+  for (unsigned int i = 0; i < ModInfo::getNumMods(); ++i) {
+    auto mod = ModInfo::getByIndex(i);
+    if (mod->isRegular()) {
+      mods.push_back({mod->name(), mod->absolutePath()});
+    }
+  }
+
+  return QtConcurrent::run([mods = std::move(mods)]() {
+    auto issues = QtConcurrent::blockingMappedReduced<std::vector<IntegrityIssue>>(
+        mods, scanMod,
+        [](std::vector<IntegrityIssue>& all, const std::vector<IntegrityIssue>& some) {
+          all.insert(all.end(), some.begin(), some.end());
+        });
+
+    std::sort(issues.begin(), issues.end(), [](const auto& a, const auto& b) {
+      const int c = a.modName.compare(b.modName, Qt::CaseInsensitive);
+      return c != 0
+                 ? c < 0
+                 : a.relativePath.compare(b.relativePath, Qt::CaseInsensitive) < 0;
+    });
+
+    log::debug("integrity scan found {} problems in {} mods", issues.size(),
+               mods.size());
+
+    return issues;
+  });
+}
@@ -0,0 +1,46 @@
+#ifndef MODINTEGRITY_H
+#define MODINTEGRITY_H
+
+#include <QFuture>
+#include <QString>
+#include <vector>
+
+// a file inside a mod that looks like the result of a bad download or
+// extraction
+//
+struct IntegrityIssue
+{
+  enum class Kind
+  {
+    // the file has no content at all
+    EmptyFile,
+
+    // a bsa/ba2 whose header is invalid or points past the end of the file
+    TruncatedArchive,
+
+    // the file cannot be opened for reading
+    Unreadable
+  };
+
+  QString modName;
+
+  // path relative to the mod directory, with forward slashes
+  QString relativePath;
+
+  Kind kind;
+
+  // human readable description of the problem
+  QString description() const;
+};
+
+// checks a single file, returns false if it looks damaged and sets `kind` to
+// the problem that was found
+//
+bool checkFileIntegrity(const QString& path, IntegrityIssue::Kind& kind);
+
+// walks every regular mod in parallel in the background and returns all the
+// problems found, sorted by mod and path; must be called from the main thread
+//
+QFuture<std::vector<IntegrityIssue>> scanModIntegrity();
+
+#endif  // MODINTEGRITY_H
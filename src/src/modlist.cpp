@@ -79,6 +79,17 @@ ModList::~ModList()
 void ModList::setProfile(Profile* profile)
 {
   m_Profile = profile;
+
+  // the journal refers to the previous profile's mod states; trashed mods
+  // left over by a previous session are dropped as well
+  m_Journal.clear();
+
+  if (profile != nullptr) {
+    const QDir trash(QDir(m_Organizer->settings().paths().mods()).filePath(".trash"));
+    if (trash.exists() && !QDir(trash).removeRecursively()) {
+      log::warn("failed to clear mod trash '{}'", trash.absolutePath());
+    }
+  }
 }
 
 int ModList::rowCount(const QModelIndex& parent) const
@@ -217,6 +228,14 @@ QVariant ModList::data(const QModelIndex& modelIndex, int role) const
       } else {
         return QVariant();
       }
+    } else if (column == COL_WINS || column == COL_LOSSES) {
+      const unsigned int count = column == COL_WINS ? modInfo->winningFileCount()
+                                                    : modInfo->losingFileCount();
+      if (count > 0) {
+        return count;
+      } else {
+        return QVariant();
+      }
     } else if (column == COL_GAME) {
       if (m_PluginContainer != nullptr) {
         for (auto game : m_PluginContainer->plugins<IPluginGame>()) {
@@ -500,6 +519,13 @@ bool ModList::renameMod(int index, const QString& newName)
     m_Profile->cancelModlistWrite();
 
     if (modInfo->setName(nameFixed)) {
+      if (!m_Undoing) {
+        ModListJournal::Entry e{ModListJournal::Action::Rename};
+        e.name    = oldName;
+        e.newName = nameFixed;
+        m_Journal.record(std::move(e));
+      }
+
       // Notice there is a good chance that setName() updated the modinfo indexes
       // the modRenamed() call will refresh the indexes in the current profile
       // and update the modlists in all profiles
@@ -533,6 +559,7 @@ bool ModList::setData(const QModelIndex& index, const QVariant& value, int role)
   if (role == Qt::CheckStateRole) {
     bool enabled = value.toInt() == Qt::Checked;
     if (m_Profile->modEnabled(modID) != enabled) {
+      recordEnabledStates({static_cast<unsigned int>(modID)});
       m_Profile->setModEnabled(modID, enabled);
       m_Modified = true;
       m_LastCheck.restart();
@@ -683,6 +710,8 @@ void ModList::changeModPriority(std::vector<int> sourceIndices, int newPriority)
 
   emit layoutAboutToBeChanged();
 
+  ModListJournal::Entry moves{ModListJournal::Action::Reorder};
+
   // sort the moving mods by ascending priorities
   std::sort(sourceIndices.begin(), sourceIndices.end(),
             [=, this](const int& LHS, const int& RHS) {
@@ -694,6 +723,7 @@ void ModList::changeModPriority(std::vector<int> sourceIndices, int newPriority)
     int oldPriority = m_Profile->getModPriority(index);
     if (oldPriority > newPriority) {
       if (m_Profile->setModPriority(index, newPriority)) {
+        moves.moves.emplace_back(ModInfo::getByIndex(index)->name(), oldPriority);
         m_ModMoved(ModInfo::getByIndex(index)->name(), oldPriority, newPriority);
       }
     }
@@ -720,11 +750,16 @@ void ModList::changeModPriority(std::vector<int> sourceIndices, int newPriority)
     int oldPriority = m_Profile->getModPriority(index);
     if (oldPriority < newPriority) {
       if (m_Profile->setModPriority(index, newPriority)) {
+        moves.moves.emplace_back(ModInfo::getByIndex(index)->name(), oldPriority);
         m_ModMoved(ModInfo::getByIndex(index)->name(), oldPriority, newPriority);
       }
     }
   }
 
+  if (!m_Undoing && !moves.moves.empty()) {
+    m_Journal.record(std::move(moves));
+  }
+
   emit layoutChanged();
 
   QModelIndexList indices;
@@ -741,7 +776,12 @@ void ModList::changeModPriority(int sourceIndex, int newPriority)
     return;
   emit layoutAboutToBeChanged();
 
-  m_Profile->setModPriority(sourceIndex, newPriority);
+  const int oldPriority = m_Profile->getModPriority(sourceIndex);
+  if (m_Profile->setModPriority(sourceIndex, newPriority) && !m_Undoing) {
+    ModListJournal::Entry e{ModListJournal::Action::Reorder};
+    e.moves.emplace_back(ModInfo::getByIndex(sourceIndex)->name(), oldPriority);
+    m_Journal.record(std::move(e));
+  }
 
   emit layoutChanged();
   emit modPrioritiesChanged({index(sourceIndex, 0)});
@@ -793,6 +833,157 @@ int ModList::timeElapsedSinceLastChecked() const
   return m_LastCheck.elapsed();
 }
 
+bool ModList::canUndo() const
+{
+  return !m_Journal.empty();
+}
+
+bool ModList::undo()
+{
+  if (m_Profile == nullptr) {
+    return false;
+  }
+
+  auto e = m_Journal.takeLast();
+  if (!e) {
+    return false;
+  }
+
+  m_Undoing = true;
+  Guard g([&] {
+    m_Undoing = false;
+  });
+
+  return undoEntry(*e);
+}
+
+void ModList::recordEnabledStates(const QList<unsigned int>& indices)
+{
+  if (m_Undoing || m_Profile == nullptr) {
+    return;
+  }
+
+  ModListJournal::Entry e{ModListJournal::Action::SetEnabled};
+  for (auto index : indices) {
+    e.states.emplace_back(ModInfo::getByIndex(index)->name(),
+                          m_Profile->modEnabled(index));
+  }
+
+  if (!e.states.empty()) {
+    m_Journal.record(std::move(e));
+  }
+}
+
+bool ModList::removeModToTrash(unsigned int index, int priority, bool enabled)
+{
+  const auto modInfo = ModInfo::getByIndex(index);
+  const QString path = modInfo->absolutePath();
+  const QString name = modInfo->name();
+
+  QString trashPath;
+  if (!ModInfo::removeMod(index, &trashPath)) {
+    return false;
+  }
+
+  if (!trashPath.isEmpty()) {
+    ModListJournal::Entry e{ModListJournal::Action::Delete};
+    e.name      = name;
+    e.path      = path;
+    e.trashPath = trashPath;
+    e.priority  = priority;
+    e.enabled   = enabled;
+    m_Journal.record(std::move(e));
+  }
+
+  return true;
+}
+
+bool ModList::undoEntry(const ModListJournal::Entry& e)
+{
+  switch (e.action) {
+  case ModListJournal::Action::SetEnabled: {
+    QList<unsigned int> toEnable;
+    QList<unsigned int> toDisable;
+
+    for (const auto& [name, enabled] : e.states) {
+      const auto index = ModInfo::getIndex(name);
+      if (index == UINT_MAX) {
+        continue;
+      }
+
+      (enabled ? toEnable : toDisable).append(index);
+    }
+
+    m_Profile->setModsEnabled(toEnable, toDisable);
+    emit dataChanged(index(0, 0), index(rowCount() - 1, columnCount() - 1));
+    return true;
+  }
+
+  case ModListJournal::Action::Rename: {
+    const auto index = ModInfo::getIndex(e.newName);
+    if (index == UINT_MAX) {
+      log::warn("cannot undo rename, mod '{}' is gone", e.newName);
+      return false;
+    }
+
+    return renameMod(index, e.name);
+  }
+
+  case ModListJournal::Action::Reorder: {
+    emit layoutAboutToBeChanged();
+
+    // moves are replayed backwards so every mod lands where it was, even when
+    // several of them were shifted by the same drop
+    QModelIndexList changed;
+    for (auto itor = e.moves.rbegin(); itor != e.moves.rend(); ++itor) {
+      const auto index = ModInfo::getIndex(itor->first);
+      if (index == UINT_MAX) {
+        continue;
+      }
+
+      int priority = itor->second;
+      m_Profile->setModPriority(index, priority);
+      changed.append(this->index(index, 0));
+    }
+
+    emit layoutChanged();
+    emit modPrioritiesChanged(changed);
+    return true;
+  }
+
+  case ModListJournal::Action::Delete: {
+    if (QFileInfo::exists(e.path)) {
+      log::warn("cannot restore mod '{}', '{}' already exists", e.name, e.path);
+      return false;
+    }
+
+    if (!QDir().rename(e.trashPath, e.path)) {
+      log::error("failed to restore mod '{}' from '{}'", e.name, e.trashPath);
+      return false;
+    }
+
+    m_Organizer->refresh();
+
+    const auto index = ModInfo::getIndex(e.name);
+    if (index == UINT_MAX) {
+      log::error("restored mod '{}' was not picked up", e.name);
+      return false;
+    }
+
+    changeModPriority(index, e.priority);
+
+    if (e.enabled) {
+      m_Profile->setModEnabled(index, true);
+      notifyChange(index);
+    }
+
+    return true;
+  }
+  }
+
+  return false;
+}
+
 IModList::ModStates ModList::state(unsigned int modIndex) const
 {
   IModList::ModStates result;
@@ -870,9 +1061,16 @@ MOBase::IModInterface* ModList::getMod(const QString& name) const
 
 bool ModList::removeMod(MOBase::IModInterface* mod)
 {
-  bool result = ModInfo::removeMod(ModInfo::getIndex(mod->name()));
+  const QString name       = mod->name();
+  const unsigned int index = ModInfo::getIndex(name);
+  if (index == UINT_MAX) {
+    return false;
+  }
+
+  bool result = removeModToTrash(index, m_Profile->getModPriority(index),
+                                 m_Profile->modEnabled(index));
   if (result) {
-    notifyModRemoved(mod->name());
+    notifyModRemoved(name);
   }
   return result;
 }
@@ -912,6 +1110,7 @@ bool ModList::setActive(const QString& name, bool active)
                active ? "enable" : "disable", name);
     return false;
   } else {
+    recordEnabledStates({modIndex});
     m_Profile->setModEnabled(modIndex, active);
     return true;
   }
@@ -933,6 +1132,8 @@ int ModList::setActive(const QStringList& names, bool active)
     }
   }
 
+  recordEnabledStates(indices);
+
   if (active) {
     m_Profile->setModsEnabled(indices, {});
   } else {
@@ -1210,11 +1411,13 @@ void ModList::removeRowForce(int row, const QModelIndex& parent)
 
   bool wasEnabled = m_Profile->modEnabled(row);
 
+  const int oldPriority = m_Profile->getModPriority(row);
+
   m_Profile->setModEnabled(row, false);
 
   m_Profile->cancelModlistWrite();
   beginRemoveRows(parent, row, row);
-  ModInfo::removeMod(row);
+  removeModToTrash(row, oldPriority, wasEnabled);
   m_Profile->refreshModStatus();  // removes the mod from the status list
   endRemoveRows();
   m_Profile->writeModlist();  // this ensures the modified list gets written back before
@@ -1263,7 +1466,6 @@ bool ModList::removeRows(int row, int count, const QModelIndex& parent)
         QMessageBox::Yes | QMessageBox::No);
 
     if (confirmBox.exec() == QMessageBox::Yes) {
-      m_Profile->setModEnabled(row + i, false);
       removeRowForce(row + i, parent);
     }
   }
@@ -1365,6 +1567,10 @@ QString ModList::getColumnName(int column)
     return tr("Installation");
   case COL_NOTES:
     return tr("Notes");
+  case COL_WINS:
+    return tr("Wins");
+  case COL_LOSSES:
+    return tr("Losses");
   default:
     return tr("unknown");
   }
@@ -1413,6 +1619,11 @@ QString ModList::getColumnToolTip(int column) const
     return tr("Time this mod was installed");
   case COL_NOTES:
     return tr("User notes about the mod");
+  case COL_WINS:
+    return tr("Number of loose files of the mod that overwrite the same file in "
+              "another mod.");
+  case COL_LOSSES:
+    return tr("Number of loose files of the mod that are overwritten by another mod.");
   default:
     return tr("unknown");
   }
@@ -1485,6 +1696,7 @@ bool ModList::toggleState(const QModelIndexList& indices)
     }
   }
 
+  recordEnabledStates(modsToEnable + modsToDisable);
   m_Profile->setModsEnabled(modsToEnable, modsToDisable);
 
   emit tutorialModlistUpdate();
@@ -1506,6 +1718,8 @@ void ModList::setActive(const QModelIndexList& indices, bool active)
     mods.append(index.data(IndexRole).toInt());
   }
 
+  recordEnabledStates(mods);
+
   if (active) {
     m_Profile->setModsEnabled(mods, {});
   } else {
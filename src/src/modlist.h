@@ -23,6 +23,7 @@ along with Mod Organizer.  If not, see <http://www.gnu.org/licenses/>.
 #include "categories.h"
 #include "moddatacontent.h"
 #include "modinfo.h"
+#include "modlistjournal.h"
 #include "nexusinterface.h"
 #include "profile.h"
 
@@ -93,7 +94,9 @@ public:
     COL_INSTALLTIME,
     COL_PRIORITY,
     COL_NOTES,
-    COL_LASTCOLUMN = COL_NOTES,
+    COL_WINS,
+    COL_LOSSES,
+    COL_LASTCOLUMN = COL_LOSSES,
   };
 
   using SignalModInstalled    = boost::signals2::signal<void(MOBase::IModInterface*)>;
@@ -147,6 +150,16 @@ public:
 
   int timeElapsedSinceLastChecked() const;
 
+  // whether there is a change that undo() can revert
+  //
+  bool canUndo() const;
+
+  // reverts the most recent enable/disable, rename, reorder or removal done
+  // through the mod list; returns false if there was nothing to undo or it
+  // could not be reverted
+  //
+  bool undo();
+
 public:
   /**
    * @brief Notify the mod list that the given mod has been installed. This is used
@@ -363,6 +376,16 @@ private:
 
   bool renameMod(int index, const QString& newName);
 
+  // records the current enabled state of the given mods before it changes
+  //
+  void recordEnabledStates(const QList<unsigned int>& indices);
+
+  // removes the mod and records it so it can be restored from the trash
+  //
+  bool removeModToTrash(unsigned int index, int priority, bool enabled);
+
+  bool undoEntry(const ModListJournal::Entry& e);
+
   MOBase::IModList::ModStates state(unsigned int modIndex) const;
 
   // handle dropping of local URLs files
@@ -416,6 +439,9 @@ private:
 
   QElapsedTimer m_LastCheck;
 
+  ModListJournal m_Journal;
+  bool m_Undoing = false;
+
   PluginContainer* m_PluginContainer;
 };
 
@@ -6,6 +6,7 @@
 #include "modlistview.h"
 #include "modlistviewactions.h"
 #include "organizercore.h"
+#include "profile.h"
 
 #include <memory>
 
@@ -63,6 +64,9 @@ void ModListGlobalContextMenu::populate(OrganizerCore& core, ModListView* view,
     addAction(tr("Install mod..."), [=, this]() {
       view->actions().installMod();
     });
+    addAction(tr("Install mods from folder..."), [=, this]() {
+      view->actions().installModsFromFolder();
+    });
     addAction(tr("Create empty mod"), [=, this]() {
       view->actions().createEmptyMod();
     });
@@ -103,6 +107,9 @@ void ModListGlobalContextMenu::populate(OrganizerCore& core, ModListView* view,
   addAction(tr("Export to csv..."), [=, this]() {
     view->actions().exportModListCSV();
   });
+  addAction(tr("Export manifest..."), [=, this]() {
+    view->actions().exportModListManifest();
+  });
 }
 
 ModListChangeCategoryMenu::ModListChangeCategoryMenu(CategoryFactory* categories,
@@ -500,6 +507,12 @@ void ModListContextMenu::addRegularActions(ModInfo::Ptr mod)
     m_core.modList()->setActive(m_selected, false);
   });
 
+  if (!m_core.currentProfile()->modEnabled(m_index.data(ModList::IndexRole).toInt())) {
+    addAction(tr("Preview conflicts if enabled..."), [=, this]() {
+      m_actions.previewEnableConflicts(m_index);
+    });
+  }
+
   addSeparator();
 
   if (m_view->sortColumn() == ModList::COL_PRIORITY) {
@@ -0,0 +1,53 @@
+#include "modlistjournal.h"
+
+#include <QDir>
+#include <log.h>
+
+using namespace MOBase;
+
+void ModListJournal::record(Entry e)
+{
+  m_Entries.push_back(std::move(e));
+
+  while (m_Entries.size() > MaxEntries) {
+    purge(m_Entries.front());
+    m_Entries.pop_front();
+  }
+}
+
+std::optional<ModListJournal::Entry> ModListJournal::takeLast()
+{
+  if (m_Entries.empty()) {
+    return {};
+  }
+
+  auto e = std::move(m_Entries.back());
+  m_Entries.pop_back();
+
+  return e;
+}
+
+bool ModListJournal::empty() const
+{
+  return m_Entries.empty();
+}
+
+void ModListJournal::clear()
+{
+  for (const auto& e : m_Entries) {
+    purge(e);
+  }
+
+  m_Entries.clear();
+}
+
+void ModListJournal::purge(const Entry& e)
+{
+  if (e.action != Action::Delete || e.trashPath.isEmpty()) {
+    return;
+  }
+
+  if (!QDir(e.trashPath).removeRecursively()) {
+    log::warn("failed to delete trashed mod '{}'", e.trashPath);
+  }
+}
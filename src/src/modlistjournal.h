@@ -0,0 +1,85 @@
+#ifndef MODLISTJOURNAL_H
+#define MODLISTJOURNAL_H
+
+#include <QString>
+
+#include <deque>
+#include <optional>
+#include <utility>
+#include <vector>
+
+// bounded history of reversible changes made through the mod list, so they
+// can be undone in reverse order
+//
+// deleted mods are moved to a trash folder instead of being removed; the
+// journal owns those directories and deletes them for good once their entry
+// falls off the end of the history or the journal is cleared
+//
+class ModListJournal
+{
+public:
+  enum class Action
+  {
+    // mods were enabled or disabled
+    SetEnabled,
+
+    // a mod was renamed
+    Rename,
+
+    // one or more mods changed priority
+    Reorder,
+
+    // a mod was removed and its directory moved to the trash
+    Delete
+  };
+
+  struct Entry
+  {
+    Action action;
+
+    // SetEnabled: every affected mod with its state before the change
+    std::vector<std::pair<QString, bool>> states;
+
+    // Reorder: every moved mod with its priority before the move, in the
+    // order the moves happened
+    std::vector<std::pair<QString, int>> moves;
+
+    // Rename: name before the change; Delete: name of the removed mod
+    QString name;
+
+    // Rename: name after the change
+    QString newName;
+
+    // Delete: original mod directory and where it was moved to
+    QString path;
+    QString trashPath;
+
+    // Delete: state of the mod in the profile before it was removed
+    int priority = -1;
+    bool enabled = false;
+  };
+
+  // number of entries kept before the oldest ones are dropped
+  static constexpr std::size_t MaxEntries = 20;
+
+  // adds an entry, dropping the oldest one if the history is full
+  //
+  void record(Entry e);
+
+  // removes and returns the most recent entry, if any
+  //
+  std::optional<Entry> takeLast();
+
+  bool empty() const;
+
+  // drops every entry, deleting trashed mods permanently
+  //
+  void clear();
+
+private:
+  std::deque<Entry> m_Entries;
+
+  void purge(const Entry& e);
+};
+
+#endif  // MODLISTJOURNAL_H
@@ -217,6 +217,14 @@ bool ModListSortProxy::lessThan(const QModelIndex& left, const QModelIndex& righ
     if (leftMod->nexusId() != rightMod->nexusId())
       lt = leftMod->nexusId() < rightMod->nexusId();
   } break;
+  case ModList::COL_WINS: {
+    if (leftMod->winningFileCount() != rightMod->winningFileCount())
+      lt = leftMod->winningFileCount() < rightMod->winningFileCount();
+  } break;
+  case ModList::COL_LOSSES: {
+    if (leftMod->losingFileCount() != rightMod->losingFileCount())
+      lt = leftMod->losingFileCount() < rightMod->losingFileCount();
+  } break;
   case ModList::COL_VERSION: {
     if (leftMod->version() != rightMod->version())
       lt = leftMod->version() < rightMod->version();
@@ -446,6 +446,22 @@ void ModListView::refreshExpandedItems()
   }
 }
 
+void ModListView::collapseAllSeparators()
+{
+  if (!hasCollapsibleSeparators()) {
+    return;
+  }
+
+  auto* model = m_sortProxy->sourceModel();
+  for (auto i = 0; i < model->rowCount(); ++i) {
+    auto idx = model->index(i, 0);
+    if (model->hasChildren(idx)) {
+      m_collapsed[model].insert(idx.data(Qt::DisplayRole).toString());
+      setExpanded(m_sortProxy->mapFromSource(idx), false);
+    }
+  }
+}
+
 void ModListView::onModPrioritiesChanged(const QModelIndexList& indices)
 {
   // expand separator whose priority has changed and parents
@@ -822,6 +838,8 @@ void ModListView::setup(OrganizerCore& core, CategoryFactory& factory, MainWindo
     header()->setSectionHidden(ModList::COL_GAME, true);
     header()->setSectionHidden(ModList::COL_INSTALLTIME, true);
     header()->setSectionHidden(ModList::COL_NOTES, true);
+    header()->setSectionHidden(ModList::COL_WINS, true);
+    header()->setSectionHidden(ModList::COL_LOSSES, true);
 
     // resize mod list to fit content
     for (int i = 0; i < header()->count(); ++i) {
@@ -892,6 +910,10 @@ void ModListView::restoreState(const Settings& s)
   s.widgets().restoreIndex(ui.groupBy);
   s.widgets().restoreTreeExpandState(this);
 
+  if (s.interface().collapseSeparatorsOnOpen()) {
+    collapseAllSeparators();
+  }
+
   m_filters->restoreState(s);
 }
 
@@ -1036,6 +1058,8 @@ void ModListView::onDoubleClicked(const QModelIndex& index)
         tab = ModInfoTabIDs::Categories;
         break;
       case ModList::COL_CONFLICTFLAGS:
+      case ModList::COL_WINS:
+      case ModList::COL_LOSSES:
         tab = ModInfoTabIDs::Conflicts;
         break;
       }
@@ -1493,6 +1517,16 @@ void ModListView::mouseReleaseEvent(QMouseEvent* event)
 
 bool ModListView::event(QEvent* event)
 {
+  if (event->type() == QEvent::KeyPress && m_core->currentProfile()) {
+    // ctrl+z undoes the last change to the mod list, with or without a
+    // selection
+    QKeyEvent* keyEvent = static_cast<QKeyEvent*>(event);
+    if (keyEvent->matches(QKeySequence::Undo)) {
+      m_core->modList()->undo();
+      return true;
+    }
+  }
+
   if (event->type() == QEvent::KeyPress && m_core->currentProfile() &&
       selectionModel()->hasSelection()) {
     QKeyEvent* keyEvent = static_cast<QKeyEvent*>(event);
@@ -314,6 +314,11 @@ private:  // private functions
   //
   void refreshExpandedItems();
 
+  // collapses every separator that has children and remembers them as
+  // collapsed, so refreshExpandedItems() keeps them that way
+  //
+  void collapseAllSeparators();
+
   // refresh the group-by proxy, if the index is -1 will refresh the
   // current one (e.g. when changing the sort column)
   //
@@ -1,8 +1,12 @@
 #include "modlistviewactions.h"
 
+#include <QDirIterator>
 #include <QGridLayout>
 #include <QGroupBox>
 #include <QInputDialog>
+#include <QJsonArray>
+#include <QJsonDocument>
+#include <QJsonObject>
 #include <QLabel>
 
 #include "filesystemutilities.h"
@@ -25,6 +29,7 @@
 #include "nxmaccessmanager.h"
 #include "organizercore.h"
 #include "overwriteinfodialog.h"
+#include "pluginlist.h"
 #include "pluginlistview.h"
 #include "savetextasdialog.h"
 #include "shared/directoryentry.h"
@@ -110,6 +115,82 @@ void ModListViewActions::installMod(const QString& archivePath,
   }
 }
 
+void ModListViewActions::installModsFromFolder(const QModelIndex& index) const
+{
+  const QString path = FileDialogMemory::getExistingDirectory(
+      "installModsFromFolder", m_parent, tr("Choose a folder containing mod archives"));
+
+  if (path.isEmpty()) {
+    return;
+  }
+
+  QStringList filters;
+  for (const auto& ext : m_core.installationManager()->getSupportedExtensions()) {
+    filters.append("*." + ext);
+  }
+
+  const QFileInfoList archives =
+      QDir(path).entryInfoList(filters, QDir::Files, QDir::Name | QDir::IgnoreCase);
+
+  if (archives.isEmpty()) {
+    QMessageBox::information(m_parent, tr("No archives found"),
+                             tr("There are no mod archives in %1.").arg(path));
+    return;
+  }
+
+  if (QMessageBox::question(m_parent, tr("Install mods"),
+                            tr("Install %n archive(s) from %1?", "", archives.size())
+                                .arg(QDir::toNativeSeparators(path)),
+                            QMessageBox::Yes | QMessageBox::No) != QMessageBox::Yes) {
+    return;
+  }
+
+  int priority = findInstallPriority(index);
+  QStringList installed;
+  QStringList failed;
+
+  for (const QFileInfo& archive : archives) {
+    log::info("batch install: '{}'", archive.absoluteFilePath());
+
+    ModInfo::Ptr mod;
+    try {
+      mod = m_core.installArchive(archive.absoluteFilePath(), priority);
+    } catch (const std::exception& e) {
+      log::error("batch install of '{}' failed: {}", archive.fileName(), e.what());
+    }
+
+    if (mod == nullptr) {
+      failed.append(archive.fileName());
+      continue;
+    }
+
+    installed.append(QString("%1 -> %2").arg(archive.fileName()).arg(mod->name()));
+
+    // keep the mods in the same order as the archives in the folder
+    if (priority != -1) {
+      ++priority;
+    }
+  }
+
+  QMessageBox box(failed.isEmpty() ? QMessageBox::Information : QMessageBox::Warning,
+                  tr("Install mods"),
+                  tr("%1 of %2 archive(s) installed.")
+                      .arg(installed.size())
+                      .arg(archives.size()),
+                  QMessageBox::Ok, m_parent);
+
+  QStringList details;
+  if (!installed.isEmpty()) {
+    details << tr("Installed:") << installed << "";
+  }
+  if (!failed.isEmpty()) {
+    details << tr("Failed or cancelled:") << failed;
+  }
+
+  box.setDetailedText(details.join("\n").trimmed());
+  box.exec();
+}
+
 void ModListViewActions::createEmptyMod(const QModelIndex& index) const
 {
   GuessedValue<QString> name;
@@ -535,6 +616,171 @@ void ModListViewActions::exportModListCSV() const
   }
 }
 
+void ModListViewActions::exportModListManifest() const
+{
+  auto profile = m_core.currentProfile();
+  if (!profile) {
+    return;
+  }
+
+  QJsonArray mods;
+  for (auto& [priority, index] : profile->getAllIndexesByPriority()) {
+    ModInfo::Ptr info = ModInfo::getByIndex(index);
+    if (info->isOverwrite() || info->isBackup() || info->isForeign()) {
+      continue;
+    }
+
+    QJsonObject mod;
+    mod["name"]     = info->name();
+    mod["priority"] = priority;
+    mod["enabled"]  = profile->modEnabled(index);
+
+    if (info->isSeparator()) {
+      mod["separator"] = true;
+      mods.append(mod);
+      continue;
+    }
+
+    mod["version"]          = info->version().canonicalString();
+    mod["repository"]       = info->repository();
+    mod["gameName"]         = info->gameName();
+    mod["modId"]            = info->nexusId();
+    mod["installationFile"] = QFileInfo(info->installationFile()).fileName();
+    mod["url"]              = info->url();
+
+    // the (mod id, file id) pairs that were installed into this mod, as
+    // recorded in meta.ini
+    QJsonArray files;
+    for (auto& [modId, fileId] : info->installedFiles()) {
+      files.append(QJsonObject{{"modId", modId}, {"fileId", fileId}});
+    }
+    mod["files"] = files;
+
+    mods.append(mod);
+  }
+
+  const PluginList* pluginList = m_core.pluginList();
+  QStringList pluginNames      = pluginList->pluginNames();
+  std::sort(pluginNames.begin(), pluginNames.end(),
+            [pluginList](const QString& lhs, const QString& rhs) {
+              return pluginList->priority(lhs) < pluginList->priority(rhs);
+            });
+
+  QJsonArray plugins;
+  for (const QString& name : pluginNames) {
+    QJsonObject plugin;
+    plugin["name"]      = name;
+    plugin["enabled"]   = pluginList->state(name) == IPluginList::STATE_ACTIVE;
+    plugin["loadOrder"] = pluginList->loadOrder(name);
+    plugin["origin"]    = pluginList->origin(name);
+    plugins.append(plugin);
+  }
+
+  QJsonObject manifest;
+  manifest["manifestVersion"] = 1;
+  manifest["game"]            = m_core.managedGame()->gameShortName();
+  manifest["profile"]         = profile->name();
+  manifest["mods"]            = mods;
+  manifest["plugins"]         = plugins;
+
+  SaveTextAsDialog saveDialog(m_parent);
+  saveDialog.setText(QJsonDocument(manifest).toJson(QJsonDocument::Indented));
+  saveDialog.exec();
+}
+
+void ModListViewActions::previewEnableConflicts(const QModelIndex& index) const
+{
+  ModInfo::Ptr mod = ModInfo::getByIndex(index.data(ModList::IndexRole).toInt());
+  const QDir modDir(mod->absolutePath());
+  const DirectoryEntry* structure = m_core.directoryStructure();
+
+  // files only coming from the game directory aren't conflicts, same as in
+  // the conflicts tab
+  const OriginID dataID =
+      structure->originExists(L"data") ? structure->getOriginByName(L"data").getID()
+                                       : InvalidOriginID;
+
+  QStringList overwritten;
+  QStringList stillLost;
+
+  QDirIterator it(modDir.absolutePath(), QDir::Files | QDir::Hidden,
+                  QDirIterator::Subdirectories);
+  while (it.hasNext()) {
+    const QString relative = modDir.relativeFilePath(it.next());
+    if (relative.compare("meta.ini", Qt::CaseInsensitive) == 0 ||
+        relative.contains(ModInfo::s_HiddenExt, Qt::CaseInsensitive)) {
+      continue;
+    }
+
+    const FileEntryPtr file = structure->searchFile(ToWString(relative));
+    if (!file) {
+      continue;
+    }
+
+    std::vector<OriginID> origins;
+    if (!file->isFromArchive()) {
+      origins.push_back(file->getOrigin());
+    }
+    for (const auto& alt : file->getAlternatives()) {
+      if (!alt.isFromArchive()) {
+        origins.push_back(alt.originID());
+      }
+    }
+
+    QStringList losers;
+    bool inOverwrite = false;
+    for (OriginID id : origins) {
+      if (id == dataID) {
+        continue;
+      }
+
+      const QString name = ToQString(structure->getOriginByID(id).getName());
+      const unsigned int modIndex = ModInfo::getIndex(name);
+      if (modIndex != UINT_MAX && ModInfo::getByIndex(modIndex)->isOverwrite()) {
+        // overwrite always comes last, even after the highest priority mod
+        inOverwrite = true;
+      } else {
+        losers.append(name);
+      }
+    }
+
+    if (inOverwrite) {
+      stillLost.append(relative);
+    } else if (!losers.isEmpty()) {
+      overwritten.append(QString("%1  (%2)").arg(relative, losers.join(", ")));
+    }
+  }
+
+  if (overwritten.isEmpty() && stillLost.isEmpty()) {
+    QMessageBox::information(
+        m_parent, tr("Conflict preview"),
+        tr("%1 doesn't share any files with the enabled mods.").arg(mod->name()));
+    return;
+  }
+
+  overwritten.sort(Qt::CaseInsensitive);
+  stillLost.sort(Qt::CaseInsensitive);
+
+  QString text =
+      tr("Files %1 would overwrite if it were enabled at the highest priority:")
+          .arg(mod->name()) +
+      "\n\n";
+  for (const auto& line : overwritten) {
+    text += "  " + line + "\n";
+  }
+
+  if (!stillLost.isEmpty()) {
+    text += "\n" + tr("Files that would still come from Overwrite:") + "\n\n";
+    for (const auto& line : stillLost) {
+      text += "  " + line + "\n";
+    }
+  }
+
+  SaveTextAsDialog saveDialog(m_parent);
+  saveDialog.setText(text);
+  saveDialog.exec();
+}
+
 void ModListViewActions::displayModInformation(const QString& modName,
                                                ModInfoTabIDs tab) const
 {
@@ -1050,87 +1296,65 @@ void ModListViewActions::restoreHiddenFiles(const QModelIndexList& indices) cons
 {
   const int max_items = 20;
 
-  QFlags<FileRenamer::RenameFlags> flags = FileRenamer::UNHIDE;
-  flags |= FileRenamer::MULTIPLE;
-
-  FileRenamer renamer(m_parent, flags);
+  std::vector<ModInfo::Ptr> mods;
 
-  FileRenamer::RenameResults result = FileRenamer::RESULT_OK;
-
-  // multi selection
-  if (indices.size() > 1) {
+  for (auto& idx : indices) {
+    ModInfo::Ptr modInfo = ModInfo::getByIndex(idx.data(ModList::IndexRole).toInt());
+    const auto flags     = modInfo->getFlags();
 
-    QStringList modNames;
-    for (auto& idx : indices) {
+    if (!modInfo->isRegular() ||
+        std::find(flags.begin(), flags.end(), ModInfo::FLAG_HIDDEN_FILES) ==
+            flags.end()) {
+      continue;
+    }
 
-      ModInfo::Ptr modInfo = ModInfo::getByIndex(idx.data(ModList::IndexRole).toInt());
-      const auto flags     = modInfo->getFlags();
+    mods.push_back(modInfo);
+  }
 
-      if (!modInfo->isRegular() ||
-          std::find(flags.begin(), flags.end(), ModInfo::FLAG_HIDDEN_FILES) ==
-              flags.end()) {
-        continue;
-      }
+  if (mods.empty()) {
+    return;
+  }
 
-      modNames.append(idx.data(Qt::DisplayRole).toString());
+  if (mods.size() > 1) {
+    QStringList modNames;
+    for (const auto& modInfo : mods) {
+      modNames.append(modInfo->name());
     }
 
-    QString mods = "<li>" + modNames.mid(0, max_items).join("</li><li>") + "</li>";
+    QString list = "<li>" + modNames.mid(0, max_items).join("</li><li>") + "</li>";
     if (modNames.size() > max_items) {
-      mods += "<li>...</li>";
+      list += "<li>...</li>";
     }
 
     if (QMessageBox::question(
             m_parent, tr("Confirm"),
             tr("Restore all hidden files in the following mods?<br><ul>%1</ul>")
-                .arg(mods),
-            QMessageBox::Yes | QMessageBox::No) == QMessageBox::Yes) {
-
-      for (auto& idx : indices) {
-
-        ModInfo::Ptr modInfo =
-            ModInfo::getByIndex(idx.data(ModList::IndexRole).toInt());
-
-        const auto flags = modInfo->getFlags();
-        if (std::find(flags.begin(), flags.end(), ModInfo::FLAG_HIDDEN_FILES) !=
-            flags.end()) {
-          const QString modDir = modInfo->absolutePath();
-
-          auto partialResult = restoreHiddenFilesRecursive(renamer, modDir);
-
-          if (partialResult == FileRenamer::RESULT_CANCEL) {
-            result = FileRenamer::RESULT_CANCEL;
-            break;
-          }
-          emit originModified((m_core.directoryStructure()->getOriginByName(
-                                   ToWString(modInfo->internalName())))
-                                  .getID());
-        }
-      }
+                .arg(list),
+            QMessageBox::Yes | QMessageBox::No) != QMessageBox::Yes) {
+      return;
     }
-  } else if (!indices.isEmpty()) {
-    // single selection
-    ModInfo::Ptr modInfo =
-        ModInfo::getByIndex(indices[0].data(ModList::IndexRole).toInt());
-    const QString modDir = modInfo->absolutePath();
+  } else if (QMessageBox::question(
+                 m_parent, tr("Are you sure?"),
+                 tr("About to restore all hidden files in:\n") + mods[0]->name(),
+                 QMessageBox::Ok | QMessageBox::Cancel) != QMessageBox::Ok) {
+    return;
+  }
 
-    if (QMessageBox::question(
-            m_parent, tr("Are you sure?"),
-            tr("About to restore all hidden files in:\n") + modInfo->name(),
-            QMessageBox::Ok | QMessageBox::Cancel) == QMessageBox::Ok) {
+  int restored = 0;
 
-      result = restoreHiddenFilesRecursive(renamer, modDir);
+  for (const auto& modInfo : mods) {
+    // files that would replace an existing file are left hidden
+    restored += unhideFiles(modInfo->absolutePath());
 
-      emit originModified((m_core.directoryStructure()->getOriginByName(
-                               ToWString(modInfo->internalName())))
-                              .getID());
-    }
+    emit originModified((m_core.directoryStructure()->getOriginByName(
+                             ToWString(modInfo->internalName())))
+                            .getID());
   }
 
-  if (result == FileRenamer::RESULT_CANCEL) {
-    log::debug("Restoring hidden files operation cancelled");
-  } else {
-    log::debug("Finished restoring hidden files");
+  log::debug("Finished restoring hidden files, {} restored", restored);
+
+  if (restored > 0) {
+    m_core.refreshVFS();
   }
 }
 
@@ -42,6 +42,12 @@ public:
   //
   void installMod(const QString& archivePath = "",
                   const QModelIndex& index   = QModelIndex()) const;
+
+  // ask the user for a folder and install every supported archive in it, one
+  // after the other, starting at the priority given by the index; a summary
+  // of installed and failed archives is shown at the end
+  //
+  void installModsFromFolder(const QModelIndex& index = QModelIndex()) const;
   void createEmptyMod(const QModelIndex& index = QModelIndex()) const;
   void createSeparator(const QModelIndex& index = QModelIndex()) const;
 
@@ -62,6 +68,17 @@ public:
   //
   void exportModListCSV() const;
 
+  // build a JSON manifest of the current profile (mods with their repository
+  // ids and versions in priority order, and plugins in load order) that can be
+  // used to reconstruct the setup elsewhere, and show it in a "save as" dialog
+  //
+  void exportModListManifest() const;
+
+  // list the files a disabled mod would take over from the enabled mods if it
+  // were enabled at the highest priority, without changing anything
+  //
+  void previewEnableConflicts(const QModelIndex& index) const;
+
   // display mod information
   //
   void displayModInformation(const QString& modName,
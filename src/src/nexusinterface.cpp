@@ -31,6 +31,7 @@ along with Mod Organizer.  If not, see <http://www.gnu.org/licenses/>.
 
 #include <QApplication>
 #include <QJsonDocument>
+#include <QLocale>
 #include <QNetworkCookieJar>
 #include <QRegularExpression>
 
@@ -263,6 +264,12 @@ NexusInterface::parseLimits(const QList<QNetworkReply::RawHeaderPair>& headers)
       limits.maxHourlyRequests = pair.second.toInt();
     } else if (name == "x-rl-hourly-remaining") {
       limits.remainingHourlyRequests = pair.second.toInt();
+    } else if (name == "x-rl-daily-reset") {
+      limits.dailyReset =
+          QDateTime::fromString(QString::fromLatin1(pair.second), Qt::ISODate);
+    } else if (name == "x-rl-hourly-reset") {
+      limits.hourlyReset =
+          QDateTime::fromString(QString::fromLatin1(pair.second), Qt::ISODate);
     }
   }
 
@@ -392,6 +399,46 @@ void NexusInterface::interpretNexusFileName(const QString& fileName, QString& mo
   }
 }
 
+QString NexusInterface::cleanModName(const QString& name)
+{
+  // "-1234-1-0-1600000000", the mod id, version and upload time appended by
+  // Nexus, possibly followed by a duplicate counter like "(1)"
+  static const QRegularExpression nexusSuffix(
+      R"([-_ ]+[0-9]+(?:-[0-9a-zA-Z]+)*-[0-9]{9,11}(?:[ _]*\([0-9]+\))?$)");
+
+  // trailing version, either prefixed with a "v" ("v2", "V1.0.3") or made of at
+  // least two numbers ("1.2", "1-0-3", "2_1"); a lone number is kept since it is
+  // usually part of the name ("Fallout 4", "SkyUI 5")
+  static const QRegularExpression version(
+      R"([-_ ]+(?:[vV][0-9]+(?:[-._][0-9]+)*|[0-9]+(?:[-._][0-9]+)+)[a-z]?$)");
+
+  static const QRegularExpression whitespace(R"(\s+)");
+
+  QString result = name;
+  result.replace('_', ' ');
+  result.remove(nexusSuffix);
+
+  // versions may be chained, e.g. "Mod v1.2 - 1.2.1"
+  QString previous;
+  while (previous != result) {
+    previous = result;
+    result.remove(version);
+    result = result.trimmed();
+    while (result.endsWith('-') || result.endsWith('.')) {
+      result.chop(1);
+      result = result.trimmed();
+    }
+  }
+
+  result = result.replace(whitespace, " ").trimmed();
+
+  if (result.isEmpty()) {
+    return name.trimmed();
+  }
+
+  return result;
+}
+
 bool NexusInterface::isURLGameRelated(const QUrl& url) const
 {
   QString const name(url.toString());
@@ -1053,6 +1100,18 @@ void NexusInterface::requestFinished(std::list<NXMRequestInfo>::iterator iter)
 
       emit requestsChanged(getAPIStats(), m_User);
       log::warn("Error: {}", errorMsg);
+
+      // the generic network error only says "Too Many Requests"
+      const QDateTime reset = m_User.limits().remainingDailyRequests > 0
+                                  ? m_User.limits().hourlyReset
+                                  : m_User.limits().dailyReset;
+      errorMsg = reset.isValid()
+                     ? tr("The Nexus API request limit has been reached, requests "
+                          "are allowed again at %1.")
+                           .arg(QLocale().toString(reset.toLocalTime(),
+                                                   QLocale::ShortFormat))
+                     : tr("The Nexus API request limit has been reached, try again "
+                          "later.");
     } else {
       QByteArray data = reply->readAll();
       if (!data.isEmpty()) {
@@ -528,6 +528,15 @@ public:
   static void interpretNexusFileName(const QString& fileName, QString& modName,
                                      int& modID, bool query);
 
+  /**
+   * @brief strips the cruft that is typically left in a mod name guessed from an
+   *        archive name: underscores, trailing version numbers ("v1.2", "1-0-3")
+   *        and Nexus suffixes ("-1234-1-0-1600000000")
+   * @param name the guessed name
+   * @return the cleaned up name, or the given name if nothing would be left
+   */
+  static QString cleanModName(const QString& name);
+
   /**
    * @brief get the currently managed game
    */
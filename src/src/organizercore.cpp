@@ -42,6 +42,7 @@
 #include <usvfs/usvfs.h>
 #else
 #include "fluorineconfig.h"
+#include "stagingreviewdialog.h"
 #include "wineprefix.h"
 #include <nak_ffi.h>
 #endif
@@ -54,7 +55,6 @@
 #include <QMessageBox>
 #include <QNetworkInterface>
 #include <QProcess>
-#include <QTextStream>
 #include <QTimer>
 #include <QUrl>
 #include <QWidget>
@@ -102,6 +102,18 @@ QStringList toStringList(InputIterator current, InputIterator end)
   return result;
 }
 
+// entries of `from` that are not in `other`, case-insensitive
+static QStringList missingFrom(const QStringList& from, const QStringList& other)
+{
+  QStringList result;
+  for (const auto& s : from) {
+    if (!other.contains(s, Qt::CaseInsensitive)) {
+      result.append(s);
+    }
+  }
+  return result;
+}
+
 #ifndef _WIN32
 QString resolveWinePrefixPath(const Settings& settings,
                               const IPluginGame* managedGame)
@@ -113,15 +125,7 @@ QString resolveWinePrefixPath(const Settings& settings,
   }
 
   const QSettings instanceSettings(settings.filename(), QSettings::IniFormat);
-  for (const auto& key : {"Settings/proton_prefix_path", "Settings/prefix_path",
-                           "Proton/prefix_path", "fluorine/prefix_path"}) {
-    const QString value = instanceSettings.value(key).toString().trimmed();
-    if (!value.isEmpty()) {
-      return value;
-    }
-  }
-
-  return {};
+  return instanceSettings.value("fluorine/prefix_path").toString().trimmed();
 }
 
 QString resolveWineDataDirName(const IPluginGame* managedGame)
@@ -144,6 +148,11 @@ QString resolveSaveRelativePath(std::shared_ptr<Profile> profile,
     return "Saves";
   }
 
+  const QString saveSubdir = profile->saveSubdirOverride();
+  if (!saveSubdir.isEmpty()) {
+    return saveSubdir;
+  }
+
   const QString profileSaveDir =
       QDir(profile->absolutePath()).filePath("saves");
   const QString gameDocumentsDir =
@@ -220,6 +229,17 @@ OrganizerCore::OrganizerCore(Settings& settings)
     modPrioritiesChanged(indexes);
   });
 
+#ifndef _WIN32
+  // files written by a running program land in staging and are only moved to
+  // overwrite when it exits; refresh the overwrite entry while it runs
+  connect(&m_USVFS, &FuseConnector::stagingChanged, this, [this] {
+    if (auto overwrite = ModInfo::getOverwrite()) {
+      overwrite->diskContentModified();
+      m_ModList.notifyChange(ModInfo::getIndex(overwrite->name()));
+    }
+  });
+#endif
+
   connect(NexusInterface::instance().getAccessManager(),
           SIGNAL(validateSuccessful(bool)), this, SLOT(loginSuccessful(bool)));
   connect(NexusInterface::instance().getAccessManager(),
@@ -307,14 +327,15 @@ void OrganizerCore::updateExecutablesList()
   m_ExecutablesList.load(managedGame(), m_Settings);
 }
 
-void OrganizerCore::updateModInfoFromDisc()
+bool OrganizerCore::updateModInfoFromDisc(const ModInfo::ScanProgress& progress)
 {
   const QString modsPath = m_Settings.paths().mods();
   log::debug("updateModInfoFromDisc: base='{}', mods='{}'",
              m_Settings.paths().base(), modsPath);
-  ModInfo::updateFromDisc(modsPath, *this,
-                          m_Settings.interface().displayForeign(),
-                          m_Settings.refreshThreadCount());
+  return ModInfo::updateFromDisc(modsPath, *this,
+                                 m_Settings.interface().displayForeign(),
+                                 m_Settings.refreshThreadCount(),
+                                 m_Settings.ignoredModFolders(), progress);
 }
 
 void OrganizerCore::setUserInterface(IUserInterface* ui)
@@ -573,9 +594,30 @@ void OrganizerCore::createOverwriteDirectories()
   }
 }
 
-void OrganizerCore::prepareVFS()
+void OrganizerCore::prepareVFS(bool fullRebuild)
 {
+#ifdef _WIN32
+  Q_UNUSED(fullRebuild);
   m_USVFS.updateMapping(fileMapping(m_CurrentProfile->name(), QString()));
+#else
+  m_USVFS.updateMapping(fileMapping(m_CurrentProfile->name(), QString()), fullRebuild);
+#endif
+}
+
+void OrganizerCore::refreshVFS()
+{
+  if (m_CurrentProfile == nullptr) {
+    return;
+  }
+
+#ifndef _WIN32
+  if (m_USVFS.mountState() != FuseConnector::MountState::Mounted) {
+    return;
+  }
+#endif
+
+  // the mod list may be unchanged, the files are what changed
+  prepareVFS(true);
 }
 
 void OrganizerCore::updateVFSParams(log::Levels logLevel,
@@ -1389,6 +1431,72 @@ void OrganizerCore::refresh(bool saveChanges)
   emit refreshTriggered();
 }
 
+void OrganizerCore::repairInstance(std::function<void(QStringList)> done)
+{
+  if (m_CurrentProfile == nullptr) {
+    done({});
+    return;
+  }
+
+  QStringList report;
+
+  // modlist.txt against the mods folder; the profile adds missing mods, drops
+  // entries without a folder and renumbers priorities while it parses the
+  // file, so what it reports is what modlist.txt held before the repair
+  updateModInfoFromDisc();
+  const auto reconciled = m_CurrentProfile->reconcileModList();
+  m_CurrentProfile->writeModlistNow(true);
+
+  for (const auto& name : reconciled.added) {
+    report << tr("Added mod that was missing from the mod list: %1").arg(name);
+  }
+
+  for (const auto& name : reconciled.dropped) {
+    report << tr("Removed mod list entry without a mod folder: %1").arg(name);
+  }
+
+  m_ModList.notifyChange(-1);
+
+  // plugins are refreshed after the directory structure, which is
+  // asynchronous; directoryStructureReady is emitted once the plugin list
+  // has caught up
+  const QStringList pluginsBefore = m_PluginList.pluginNames();
+  auto connection                 = std::make_shared<QMetaObject::Connection>();
+
+  *connection = connect(
+      this, &OrganizerCore::directoryStructureReady, this,
+      [this, connection, report, pluginsBefore, done]() mutable {
+        disconnect(*connection);
+
+        const QStringList pluginsAfter = m_PluginList.pluginNames();
+
+        for (const auto& name : missingFrom(pluginsAfter, pluginsBefore)) {
+          report << tr("Added plugin to the load order: %1").arg(name);
+        }
+
+        for (const auto& name : missingFrom(pluginsBefore, pluginsAfter)) {
+          report << tr("Removed plugin that no longer exists: %1").arg(name);
+        }
+
+        for (const auto& name : pluginsAfter) {
+          if (m_PluginList.state(name) != IPluginList::STATE_ACTIVE) {
+            continue;
+          }
+
+          const QStringList missing = m_PluginList.missingMasters(name);
+          if (!missing.isEmpty()) {
+            report << tr("%1 is missing masters: %2").arg(name).arg(missing.join(", "));
+          }
+        }
+
+        savePluginList();
+        done(report);
+      });
+
+  refreshDirectoryStructure();
+  emit refreshTriggered();
+}
+
 void OrganizerCore::refreshESPList(bool force)
 {
   onNextRefresh(
@@ -2127,6 +2235,35 @@ bool OrganizerCore::beforeRun(
 #else
   } catch (const FuseConnectorException& e) {
     log::error("VFS mount failed: {}", e.what());
+
+    QString hint;
+    switch (e.reason()) {
+    case FuseConnectorException::Reason::NoDataDirectory:
+      hint = tr("Check that the game is installed and that the game path of this "
+                "instance is correct.");
+      break;
+    case FuseConnectorException::Reason::MountPointBusy:
+      hint = tr("Another program still has the data directory mounted. Close it, "
+                "or unmount the directory manually, then try again.");
+      break;
+    case FuseConnectorException::Reason::FuseUnavailable:
+      hint = tr("Load the fuse kernel module, or enable symlink deployment in the "
+                "Workarounds settings.");
+      break;
+    case FuseConnectorException::Reason::Failed:
+      break;
+    }
+
+    QWidget* w = nullptr;
+    if (m_UserInterface) {
+      w = m_UserInterface->mainWindow();
+    }
+    QMessageBox::warning(w, tr("Error"),
+                         hint.isEmpty()
+                             ? tr("Failed to mount the VFS: %1").arg(e.what())
+                             : tr("Failed to mount the VFS: %1\n\n%2")
+                                   .arg(e.what())
+                                   .arg(hint));
     return false;
 #endif
   } catch (const std::exception& e) {
@@ -2138,6 +2275,17 @@ bool OrganizerCore::beforeRun(
     return false;
   }
 
+  // the steps of a tool chain all call beforeRun(), keep the snapshot of the
+  // first one so the changes of the whole chain are reported
+  if (m_OverwriteSnapshot.directory().isEmpty()) {
+    const QString target =
+        customOverwrite.isEmpty()
+            ? m_Settings.paths().overwrite()
+            : QDir(m_Settings.paths().mods()).filePath(customOverwrite);
+
+    m_OverwriteSnapshot = OverwriteSnapshot::take(target);
+  }
+
 #ifndef _WIN32
   // Deploy plugins.txt and loadorder.txt to Wine prefix before launch
   if (m_CurrentProfile != nullptr) {
@@ -2152,23 +2300,17 @@ bool OrganizerCore::beforeRun(
             resolveSaveRelativePath(m_CurrentProfile, managedGame(),
                                     localSavesFeature.get());
 
-        // Read plugin lines from profile's plugins.txt
-        QFile pluginsFile(m_CurrentProfile->getPluginsFileName());
-        if (pluginsFile.open(QIODevice::ReadOnly | QIODevice::Text)) {
-          QStringList plugins;
-          QTextStream stream(&pluginsFile);
-          while (!stream.atEnd()) {
-            const QString line = stream.readLine().trimmed();
-            if (!line.isEmpty() && !line.startsWith('#')) {
-              plugins.append(line);
-            }
-          }
-          pluginsFile.close();
-
-          if (!plugins.isEmpty()) {
-            prefix.deployPlugins(plugins, dataDirName);
-            log::debug("Deployed {} plugins to prefix '{}'", plugins.size(),
-                       prefixPathStr);
+        // the game reads the load order straight from the prefix, so both
+        // files are written there in the formats it expects
+        const QString pluginsDir = prefix.ensurePluginsDirectory(dataDirName);
+        if (!pluginsDir.isEmpty() && !m_PluginList.pluginNames().isEmpty()) {
+          const auto format = PluginList::pluginsFileFormat(managedGame());
+          if (m_PluginList.writeCombined(QDir(pluginsDir).filePath("Plugins.txt"),
+                                         format) &&
+              m_PluginList.writeCombined(QDir(pluginsDir).filePath("loadorder.txt"),
+                                         PluginList::LoadOrderFormat::LoadOrder)) {
+            log::debug("Deployed {} plugins to prefix '{}'",
+                       m_PluginList.pluginNames().size(), prefixPathStr);
           }
         }
 
@@ -2240,7 +2382,17 @@ void OrganizerCore::afterRun(const QFileInfo& binary, DWORD exitCode)
   // flushes the staging directory (moves new/changed files to overwrite)
   // and tears down the FUSE session.  This mirrors Windows behaviour where
   // USVFS is only active while a hooked process is running.
-  m_USVFS.unmount();
+  const auto flushed = m_USVFS.unmount(reviewStagedFiles());
+  if (flushed.skipped > 0) {
+    log::warn("{} file(s) written by the program already existed in overwrite and "
+              "were discarded",
+              flushed.skipped);
+  }
+  if (flushed.backedUp > 0) {
+    log::warn("{} file(s) in overwrite were replaced by the program, the old "
+              "versions were kept in VFS_backups next to overwrite",
+              flushed.backedUp);
+  }
 
   if (m_CurrentProfile != nullptr) {
     const QString prefixPathStr = resolveWinePrefixPath(m_Settings, managedGame());
@@ -2287,6 +2439,19 @@ void OrganizerCore::afterRun(const QFileInfo& binary, DWORD exitCode)
   }
 #endif
 
+  // staged files have been moved to overwrite by now
+  if (!m_OverwriteSnapshot.directory().isEmpty()) {
+    m_LastRunOverwriteChanges =
+        OverwriteSnapshot::take(m_OverwriteSnapshot.directory())
+            .changesSince(m_OverwriteSnapshot);
+
+    log::debug("{} file(s) changed in '{}' while running '{}'",
+               m_LastRunOverwriteChanges.size(), m_OverwriteSnapshot.directory(),
+               binary.fileName());
+
+    m_OverwriteSnapshot = {};
+  }
+
   // Refresh directory structure after VFS is unmounted so the refresher
   // reads the real (vanilla) data directory plus individual mod directories,
   // matching Windows USVFS behaviour.
@@ -2300,6 +2465,137 @@ void OrganizerCore::afterRun(const QFileInfo& binary, DWORD exitCode)
   m_FinishedRun(binary.absoluteFilePath(), exitCode);
 }
 
+#ifndef _WIN32
+FuseConnector::StagingRouter OrganizerCore::reviewStagedFiles()
+{
+  if (!m_Settings.reviewStagedFiles() || m_CurrentProfile == nullptr) {
+    return {};
+  }
+
+  const QStringList files = m_USVFS.stagedFiles();
+  if (files.isEmpty()) {
+    return {};
+  }
+
+  std::vector<std::pair<QString, QString>> mods;
+  for (const auto& [priority, index] : m_CurrentProfile->getAllIndexesByPriority()) {
+    if (!m_CurrentProfile->modEnabled(index)) {
+      continue;
+    }
+
+    auto modInfo = ModInfo::getByIndex(index);
+    if (modInfo->isSeparator() || modInfo->isForeign() || modInfo->isOverwrite() ||
+        modInfo->isBackup()) {
+      continue;
+    }
+
+    mods.emplace_back(modInfo->name(), modInfo->absolutePath());
+  }
+
+  QWidget* parent = m_UserInterface ? m_UserInterface->mainWindow() : nullptr;
+
+  StagingReviewDialog dialog(files, std::move(mods), parent);
+  if (dialog.exec() != QDialog::Accepted) {
+    return {};
+  }
+
+  return [routes = dialog.routes()](const QString& file) {
+    return routes.value(file);
+  };
+}
+#endif
+
+ProcessRunner::Results OrganizerCore::runToolChain(const ToolChain& chain)
+{
+  // resolve all the steps first so a missing executable doesn't leave the
+  // chain half-run
+  std::vector<Executable> steps;
+
+  for (const auto& title : chain.steps) {
+    auto itor = m_ExecutablesList.find(title);
+    if (itor == m_ExecutablesList.end()) {
+      log::error("tool chain '{}': executable '{}' not found", chain.name, title);
+      return ProcessRunner::Error;
+    }
+
+    steps.push_back(*itor);
+  }
+
+  if (steps.empty()) {
+    log::warn("tool chain '{}' has no steps", chain.name);
+    return ProcessRunner::Completed;
+  }
+
+  auto r = ProcessRunner::Completed;
+  QFileInfo lastBinary;
+  DWORD lastExitCode = 0;
+
+  for (std::size_t i = 0; i < steps.size(); ++i) {
+    const auto& exe = steps[i];
+    log::info("tool chain '{}': running step {}/{} '{}'", chain.name, i + 1,
+              steps.size(), exe.title());
+
+    // TriggerRefresh is deliberately not set: afterRun() unmounts the vfs and
+    // flushes the staging directory into overwrite, which must only happen
+    // once the whole chain is done
+    auto runner = processRunner();
+    runner.setFromExecutable(exe).setWaitForCompletion(ProcessRunner::ForceWait,
+                                                       UILocker::LockUI);
+
+    r            = runner.run();
+    lastBinary   = exe.binaryInfo();
+    lastExitCode = runner.exitCode();
+
+    if (r != ProcessRunner::Completed) {
+      log::warn("tool chain '{}': step '{}' did not complete, stopping", chain.name,
+                exe.title());
+      break;
+    }
+
+    if (lastExitCode != 0) {
+      log::warn("tool chain '{}': step '{}' exited with code {}, stopping",
+                chain.name, exe.title(), lastExitCode);
+      r = ProcessRunner::Error;
+      break;
+    }
+  }
+
+  // same as ProcessRunner::shouldRefresh(): a cancelled or force unlocked
+  // process may still be running, so the vfs must stay up
+  if (r == ProcessRunner::Completed || r == ProcessRunner::Error) {
+    afterRun(lastBinary, lastExitCode);
+  }
+
+  return r;
+}
+
+#ifndef _WIN32
+int OrganizerCore::importLoadOrderFromPrefix()
+{
+  const QString prefixPathStr = resolveWinePrefixPath(m_Settings, managedGame());
+  if (prefixPathStr.isEmpty()) {
+    log::warn("cannot import load order, no wine prefix is configured");
+    return -1;
+  }
+
+  const WinePrefix prefix(prefixPathStr);
+  const bool asteriskFormat = PluginList::pluginsFileFormat(managedGame()) ==
+                              PluginList::LoadOrderFormat::AsteriskPlugins;
+  const auto plugins =
+      prefix.readPlugins(resolveWineDataDirName(managedGame()), asteriskFormat);
+  if (plugins.isEmpty()) {
+    log::warn("no plugins.txt found in prefix '{}'", prefixPathStr);
+    return -1;
+  }
+
+  const int found = m_PluginList.importLoadOrder(plugins);
+  log::info("imported load order from prefix '{}', {} of {} plugins found",
+            prefixPathStr, found, plugins.size());
+
+  return found;
+}
+#endif
+
 ProcessRunner::Results OrganizerCore::waitForAllUSVFSProcesses(UILocker::Reasons reason)
 {
 #ifdef _WIN32
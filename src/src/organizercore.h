@@ -30,6 +30,7 @@
 #include "modinfo.h"
 #include "modlist.h"
 #include "moshortcut.h"
+#include "overwritesnapshot.h"
 #include "pluginlist.h"
 #include "processrunner.h"
 #include "selfupdater.h"
@@ -258,7 +259,8 @@ public:
   void setManagedGame(MOBase::IPluginGame* game);
 
   void updateExecutablesList();
-  void updateModInfoFromDisc();
+  // false if progress cancelled reading the mods, see ModInfo::updateFromDisc()
+  bool updateModInfoFromDisc(const ModInfo::ScanProgress& progress = {});
 
   void checkForUpdates();
   void startMOUpdate();
@@ -294,6 +296,26 @@ public:
 
   MOBase::IPluginGame const* managedGame() const;
 
+#ifndef _WIN32
+  // current state of the fuse mount over the game's data directory
+  //
+  FuseConnector::MountState vfsMountState() const { return m_USVFS.mountState(); }
+
+  // layers of the mounted vfs providing a file, see FuseConnector::fileOrigins()
+  //
+  std::vector<std::pair<QString, QString>> vfsFileOrigins(const QString& path) const
+  {
+    return m_USVFS.fileOrigins(path);
+  }
+
+  // why the mounted vfs picked the file it shows, see FuseConnector::explainWinner()
+  //
+  VfsWinnerExplanation vfsExplainWinner(const QString& path) const
+  {
+    return m_USVFS.explainWinner(path);
+  }
+#endif
+
   /**
    * @brief Retrieve the organizer proxy of the currently managed game.
    *
@@ -318,6 +340,28 @@ public:
 
   void afterRun(const QFileInfo& binary, DWORD exitCode);
 
+  // files that were created, modified or removed in overwrite (or the custom
+  // write target) by the last executable or tool chain that was run
+  //
+  const std::vector<OverwriteChange>& lastRunOverwriteChanges() const
+  {
+    return m_LastRunOverwriteChanges;
+  }
+
+  // runs every executable of the given tool chain in order, waiting for each
+  // one to exit; the chain stops at the first step that fails or exits with a
+  // non-zero code, and the vfs is only torn down once, after the last step
+  //
+  ProcessRunner::Results runToolChain(const ToolChain& chain);
+
+#ifndef _WIN32
+  // merges the load order from the game's own plugins.txt in the wine prefix
+  // into the current profile; returns the number of plugins that were found
+  // in the profile, or -1 if the prefix has no plugins.txt
+  //
+  int importLoadOrderFromPrefix();
+#endif
+
   ProcessRunner::Results
   waitForAllUSVFSProcesses(UILocker::Reasons reason = UILocker::PreventExit);
 
@@ -349,7 +393,13 @@ public:
 
   MOBase::DelayedFileWriter& pluginsWriter() { return m_PluginListsWriter; }
 
-  void prepareVFS();
+  // mounts the vfs for the current profile or updates the mounted one for
+  // changes to the mod list; `fullRebuild` reads all mods again instead
+  void prepareVFS(bool fullRebuild = false);
+
+  // rebuilds the vfs from the current mods if it's mounted, so programs that
+  // are running see files that were changed in mod directories
+  void refreshVFS();
 
   void updateVFSParams(MOBase::log::Levels logLevel, env::CoreDumpTypes coreDumpType,
                        const QString& coreDumpsPath, std::chrono::seconds spawnDelay,
@@ -401,6 +451,14 @@ public:
   ModList* modList();
   void refresh(bool saveChanges = true);
 
+  // reconciles modlist.txt with the mods folder and the load order with the
+  // plugins that exist, then calls `done` with a line for every change made
+  // and every problem that is left, such as missing masters
+  //
+  // `done` is called once the asynchronous refresh has finished
+  //
+  void repairInstance(std::function<void(QStringList)> done);
+
   boost::signals2::connection onAboutToRun(
       const std::function<bool(const QString&, const QDir&, const QString&)>& func);
   boost::signals2::connection
@@ -514,6 +572,14 @@ private:
 
   QString oldMO1HookDll() const;
 
+#ifndef _WIN32
+  // if the user wants to review staged files, shows the files left in staging
+  // and returns a router with their choices; returns an empty router when
+  // everything should go to overwrite
+  //
+  FuseConnector::StagingRouter reviewStagedFiles();
+#endif
+
   /**
    * @brief return a descriptor of the mappings real file->virtual file
    */
@@ -596,6 +662,10 @@ private:
 #endif
 
   UILocker m_UILocker;
+
+  // taken when an executable starts, compared when it has finished
+  OverwriteSnapshot m_OverwriteSnapshot;
+  std::vector<OverwriteChange> m_LastRunOverwriteChanges;
 };
 
 #endif  // ORGANIZERCORE_H
@@ -0,0 +1,52 @@
+#include "overwritesnapshot.h"
+
+#include <QDir>
+#include <QDirIterator>
+
+OverwriteSnapshot OverwriteSnapshot::take(const QString& directory)
+{
+  OverwriteSnapshot snapshot;
+  snapshot.m_directory = directory;
+
+  const QDir root(directory);
+  QDirIterator it(directory, QDir::Files | QDir::Hidden | QDir::System,
+                  QDirIterator::Subdirectories);
+
+  while (it.hasNext()) {
+    it.next();
+    const QFileInfo info = it.fileInfo();
+
+    snapshot.m_files.emplace(root.relativeFilePath(info.absoluteFilePath()),
+                             FileState{info.size(), info.lastModified()});
+  }
+
+  return snapshot;
+}
+
+std::vector<OverwriteChange>
+OverwriteSnapshot::changesSince(const OverwriteSnapshot& before) const
+{
+  std::vector<OverwriteChange> changes;
+
+  // both maps are sorted by path, walk them side by side
+  auto a = before.m_files.begin();
+  auto b = m_files.begin();
+
+  while (a != before.m_files.end() || b != m_files.end()) {
+    if (b == m_files.end() || (a != before.m_files.end() && a->first < b->first)) {
+      changes.push_back({a->first, OverwriteChange::Kind::Removed});
+      ++a;
+    } else if (a == before.m_files.end() || b->first < a->first) {
+      changes.push_back({b->first, OverwriteChange::Kind::Created});
+      ++b;
+    } else {
+      if (!(a->second == b->second)) {
+        changes.push_back({b->first, OverwriteChange::Kind::Modified});
+      }
+      ++a;
+      ++b;
+    }
+  }
+
+  return changes;
+}
@@ -0,0 +1,62 @@
+#ifndef OVERWRITESNAPSHOT_H
+#define OVERWRITESNAPSHOT_H
+
+#include <QDateTime>
+#include <QString>
+#include <map>
+#include <vector>
+
+// a file in overwrite that was added, changed or removed while an executable
+// was running
+//
+struct OverwriteChange
+{
+  enum class Kind
+  {
+    Created,
+    Modified,
+    Removed
+  };
+
+  // path relative to the overwrite directory, with forward slashes
+  QString path;
+
+  Kind kind;
+};
+
+// sizes and modification times of all the files in a directory, taken before
+// running an executable so the files it wrote can be listed afterwards
+//
+class OverwriteSnapshot
+{
+public:
+  // an empty snapshot, every file is reported as created when compared to it
+  //
+  OverwriteSnapshot() = default;
+
+  // walks the given directory
+  //
+  static OverwriteSnapshot take(const QString& directory);
+
+  // directory this snapshot was taken of, empty if it's default constructed
+  //
+  const QString& directory() const { return m_directory; }
+
+  // files that differ between `before` and this snapshot, sorted by path
+  //
+  std::vector<OverwriteChange> changesSince(const OverwriteSnapshot& before) const;
+
+private:
+  struct FileState
+  {
+    qint64 size;
+    QDateTime modified;
+
+    bool operator==(const FileState& other) const = default;
+  };
+
+  QString m_directory;
+  std::map<QString, FileState> m_files;
+};
+
+#endif  // OVERWRITESNAPSHOT_H
@@ -20,6 +20,9 @@ along with Mod Organizer.  If not, see <http://www.gnu.org/licenses/>.
 
 #include <algorithm>
 #include <ctime>
+#include <map>
+#include <mutex>
+#include <numeric>
 #include <stdexcept>
 
 #include <QApplication>
@@ -33,8 +36,10 @@ along with Mod Organizer.  If not, see <http://www.gnu.org/licenses/>.
 #include <QMessageBox>
 #include <QMimeData>
 #include <QRegularExpression>
+#include <QPromise>
 #include <QSortFilterProxyModel>
 #include <QString>
+#include <QtConcurrent/QtConcurrentRun>
 #include <QtDebug>
 
 #include <esptk/espfile.h>
@@ -55,6 +60,8 @@ along with Mod Organizer.  If not, see <http://www.gnu.org/licenses/>.
 #include "shared/windows_error.h"
 #include "viewmarkingscrollbar.h"
 
+#include <nak_ffi.h>
+
 #ifndef _WIN32
 #include <sys/stat.h>
 #include <fcntl.h>
@@ -457,6 +464,43 @@ void PluginList::fixPluginRelationships()
   }
 }
 
+bool PluginList::sortRespectingMasters()
+{
+  std::vector<int> rows(m_ESPs.size());
+  std::iota(rows.begin(), rows.end(), 0);
+
+  std::sort(rows.begin(), rows.end(), [this](int lhs, int rhs) {
+    return m_ESPs[lhs].priority < m_ESPs[rhs].priority;
+  });
+
+  // blueprint plugins load after everything else, masters load first in each
+  // group
+  auto group = [this](int row) {
+    const ESPInfo& esp = m_ESPs[row];
+    const bool master =
+        esp.hasLightExtension || esp.hasMasterExtension || esp.isMasterFlagged;
+    return (esp.isBlueprintFlagged ? 2 : 0) + (master ? 0 : 1);
+  };
+
+  std::stable_sort(rows.begin(), rows.end(), [&](int lhs, int rhs) {
+    return group(lhs) < group(rhs);
+  });
+
+  bool changed = false;
+  for (int i = 0; i < static_cast<int>(rows.size()); ++i) {
+    if (m_ESPs[rows[i]].priority != i) {
+      m_ESPs[rows[i]].priority = i;
+      changed                  = true;
+    }
+  }
+
+  if (changed) {
+    updateIndices();
+  }
+
+  return changed;
+}
+
 void PluginList::fixPriorities()
 {
   std::vector<std::pair<int, int>> espPrios;
@@ -951,6 +995,166 @@ void PluginList::refreshLoadOrder()
   }
 }
 
+int PluginList::importLoadOrder(const QList<QPair<QString, bool>>& plugins)
+{
+  ChangeBracket<PluginList> layoutChange(this);
+
+  QStringList order;
+  QStringList enabled;
+  QStringList disabled;
+
+  for (const auto& [name, enable] : plugins) {
+    auto iter = m_ESPsByName.find(name);
+    if (iter == m_ESPsByName.end()) {
+      log::debug("plugin '{}' from imported load order is not available", name);
+      continue;
+    }
+
+    ESPInfo& info = m_ESPs[iter->second];
+    order.append(info.name);
+
+    const bool wasEnabled = info.enabled;
+    setState(info.name, enable ? IPluginList::STATE_ACTIVE : IPluginList::STATE_INACTIVE);
+
+    if (info.enabled != wasEnabled) {
+      (info.enabled ? enabled : disabled).append(info.name);
+    }
+  }
+
+  setLoadOrder(order);
+
+  fixPrimaryPlugins();
+  fixPluginRelationships();
+  testMasters();
+  updateIndices();
+
+  layoutChange.finish();
+
+  refreshLoadOrder();
+  emit dataChanged(this->index(0, 0),
+                   this->index(static_cast<int>(m_ESPs.size()), columnCount()));
+  emit writePluginsList();
+
+  pluginStatesChanged(enabled, IPluginList::STATE_ACTIVE);
+  pluginStatesChanged(disabled, IPluginList::STATE_INACTIVE);
+
+  return static_cast<int>(order.size());
+}
+
+void PluginList::applyLootUserlist(const QString& userlistYaml)
+{
+  std::vector<QByteArray> names;
+  names.reserve(m_ESPsByPriority.size());
+  for (int index : m_ESPsByPriority) {
+    names.push_back(m_ESPs[index].name.toUtf8());
+  }
+
+  std::vector<const char*> namePtrs;
+  namePtrs.reserve(names.size() + 1);
+  for (const auto& name : names) {
+    namePtrs.push_back(name.constData());
+  }
+  namePtrs.push_back(nullptr);
+
+  NakPluginOrder sorted =
+      nak_apply_loot_userlist(namePtrs.data(), userlistYaml.toUtf8().constData());
+
+  if (sorted.error != nullptr) {
+    const QString error = QString::fromUtf8(sorted.error);
+    nak_plugin_order_free(sorted);
+    throw MyException(error);
+  }
+
+  QStringList order;
+  for (size_t i = 0; i < sorted.count; ++i) {
+    order.append(QString::fromUtf8(sorted.plugins[i]));
+  }
+  nak_plugin_order_free(sorted);
+
+  ChangeBracket<PluginList> layoutChange(this);
+
+  setLoadOrder(order);
+
+  fixPrimaryPlugins();
+  fixPluginRelationships();
+  testMasters();
+  updateIndices();
+
+  layoutChange.finish();
+
+  refreshLoadOrder();
+  emit dataChanged(this->index(0, 0),
+                   this->index(static_cast<int>(m_ESPs.size()), columnCount()));
+  emit writePluginsList();
+}
+
+PluginList::LoadOrderFormat
+PluginList::pluginsFileFormat(const MOBase::IPluginGame* game)
+{
+  // games whose plugins are handled by CreationGamePlugins
+  static const QStringList asteriskGames = {
+      "SkyrimSE",   "SkyrimVR",       "EnderalSE", "Fallout4",
+      "Fallout4VR", "Fallout4London", "Fallout76", "Starfield"};
+
+  if (game != nullptr &&
+      asteriskGames.contains(game->gameShortName(), Qt::CaseInsensitive)) {
+    return LoadOrderFormat::AsteriskPlugins;
+  }
+
+  return LoadOrderFormat::ActivePlugins;
+}
+
+QStringList PluginList::loadOrderLines(const QList<LoadOrderEntry>& plugins,
+                                       LoadOrderFormat format)
+{
+  QStringList lines;
+
+  for (const auto& plugin : plugins) {
+    switch (format) {
+    case LoadOrderFormat::AsteriskPlugins:
+      // force loaded plugins are always loaded first and must not be listed
+      if (!plugin.forceLoaded) {
+        lines.append(plugin.enabled ? "*" + plugin.name : plugin.name);
+      }
+      break;
+
+    case LoadOrderFormat::ActivePlugins:
+      if (plugin.enabled) {
+        lines.append(plugin.name);
+      }
+      break;
+
+    case LoadOrderFormat::LoadOrder:
+      lines.append(plugin.name);
+      break;
+    }
+  }
+
+  return lines;
+}
+
+bool PluginList::writeCombined(const QString& path, LoadOrderFormat format) const
+{
+  QList<LoadOrderEntry> plugins;
+  for (int index : m_ESPsByPriority) {
+    const ESPInfo& esp = m_ESPs[index];
+    plugins.append({esp.name, esp.enabled, esp.forceLoaded});
+  }
+
+  QFile file(path);
+  if (!file.open(QIODevice::WriteOnly | QIODevice::Truncate)) {
+    log::error("failed to write load order to '{}': {}", path, file.errorString());
+    return false;
+  }
+
+  for (const QString& line : loadOrderLines(plugins, format)) {
+    file.write(line.toUtf8());
+    file.write("\r\n");
+  }
+
+  return true;
+}
+
 void PluginList::disconnectSlots()
 {
   m_PluginMoved.disconnect_all_slots();
@@ -1017,6 +1221,9 @@ void PluginList::setLoadOrder(const QStringList& pluginList)
     }
   }
   updateIndices();
+
+  // the given list may not respect the master/plugin split
+  sortRespectingMasters();
 }
 
 int PluginList::priority(const QString& name) const
@@ -1077,6 +1284,237 @@ QStringList PluginList::masters(const QString& name) const
   }
 }
 
+QStringList PluginList::missingMasters(const QString& name) const
+{
+  auto iter = m_ESPsByName.find(name);
+  if (iter == m_ESPsByName.end()) {
+    return QStringList();
+  } else {
+    const auto& unset = m_ESPs[iter->second].masterUnset;
+    return QStringList(unset.begin(), unset.end());
+  }
+}
+
+namespace
+{
+
+// a plugin for the record scans, copied so the worker thread doesn't touch the
+// plugin list
+//
+struct PluginToScan
+{
+  QString name;
+  QString fullPath;
+  std::set<QString, FileNameComparator> masters;
+};
+
+// pairs of plugins sharing records, given every record key with the position
+// of the plugin containing it in `plugins`
+//
+std::vector<PluginList::RecordConflict>
+recordConflicts(const std::vector<PluginToScan>& plugins,
+                std::vector<std::pair<uint64_t, int>> records)
+{
+  std::sort(records.begin(), records.end());
+  records.erase(std::unique(records.begin(), records.end()), records.end());
+
+  // records are sorted by plugin position within a key, so the first of each
+  // pair is always the one loading earlier
+  std::map<std::pair<int, int>, int> counts;
+  for (auto begin = records.begin(); begin != records.end();) {
+    const auto end = std::find_if(begin, records.end(), [&](const auto& record) {
+      return record.first != begin->first;
+    });
+
+    for (auto a = begin; a != end; ++a) {
+      for (auto b = std::next(a); b != end; ++b) {
+        ++counts[{a->second, b->second}];
+      }
+    }
+
+    begin = end;
+  }
+
+  std::vector<PluginList::RecordConflict> result;
+  for (const auto& [pair, count] : counts) {
+    const PluginToScan& first  = plugins[pair.first];
+    const PluginToScan& second = plugins[pair.second];
+
+    // overriding the records of a master is what masters are for
+    if (first.masters.count(second.name) != 0 ||
+        second.masters.count(first.name) != 0) {
+      continue;
+    }
+
+    result.push_back({first.name, second.name, count});
+  }
+
+  std::stable_sort(result.begin(), result.end(), [](const auto& lhs, const auto& rhs) {
+    return lhs.records > rhs.records;
+  });
+
+  return result;
+}
+
+}  // namespace
+
+QFuture<std::vector<PluginList::RecordConflict>>
+PluginList::findRecordConflicts() const
+{
+  // enabled plugins in priority order
+  std::vector<PluginToScan> plugins;
+  for (int index : m_ESPsByPriority) {
+    const ESPInfo& esp = m_ESPs[index];
+    if (esp.enabled) {
+      plugins.push_back({esp.name, esp.fullPath, esp.masters});
+    }
+  }
+
+  return QtConcurrent::run([plugins = std::move(plugins)](
+                               QPromise<std::vector<RecordConflict>>& promise) {
+    promise.setProgressRange(0, static_cast<int>(plugins.size()));
+
+    // a record is identified by the plugin defining it and its object id, so
+    // every plugin or master name gets a number
+    std::map<QString, uint64_t> owners;
+    auto ownerId = [&](const QString& name) {
+      return owners.emplace(name.toLower(), owners.size()).first->second;
+    };
+
+    // every record with the position of the plugin containing it
+    std::vector<std::pair<uint64_t, int>> records;
+
+    for (int position = 0; position < static_cast<int>(plugins.size()); ++position) {
+      if (promise.isCanceled()) {
+        return;
+      }
+
+      const PluginToScan& plugin = plugins[position];
+
+      try {
+        ESP::File file(ToWString(plugin.fullPath));
+
+        std::vector<uint64_t> masterOwners;
+        for (const auto& master : file.masterList()) {
+          masterOwners.push_back(ownerId(QString::fromStdString(master)));
+        }
+
+        const uint64_t self = ownerId(plugin.name);
+
+        for (uint32_t formId : file.recordFormIds()) {
+          const uint32_t masterIndex = formId >> 24;
+          const uint64_t owner =
+              masterIndex < masterOwners.size() ? masterOwners[masterIndex] : self;
+
+          records.emplace_back((owner << 24) | (formId & 0x00FFFFFF), position);
+        }
+      } catch (const std::exception& e) {
+        log::warn("failed to read the records of {}: {}", plugin.name, e.what());
+      }
+
+      promise.setProgressValue(position + 1);
+    }
+
+    promise.addResult(recordConflicts(plugins, std::move(records)));
+  });
+}
+
+QFuture<std::vector<PluginList::LightCandidate>>
+PluginList::findLightCandidates() const
+{
+  // regular plugins in priority order, none if the game has no light plugins
+  std::vector<PluginToScan> plugins;
+
+  auto gamePlugins = m_Organizer.gameFeatures().gameFeature<GamePlugins>();
+  if (gamePlugins && gamePlugins->lightPluginsAreSupported()) {
+    for (int index : m_ESPsByPriority) {
+      const ESPInfo& esp = m_ESPs[index];
+      if (!esp.forceLoaded && !esp.isLightFlagged && !esp.hasLightExtension &&
+          !esp.isMediumFlagged) {
+        plugins.push_back({esp.name, esp.fullPath, esp.masters});
+      }
+    }
+  }
+
+  return QtConcurrent::run([plugins = std::move(plugins)](
+                               QPromise<std::vector<LightCandidate>>& promise) {
+    promise.setProgressRange(0, static_cast<int>(plugins.size()));
+
+    std::vector<LightCandidate> result;
+
+    for (int i = 0; i < static_cast<int>(plugins.size()); ++i) {
+      if (promise.isCanceled()) {
+        return;
+      }
+
+      const PluginToScan& plugin = plugins[i];
+
+      try {
+        ESP::File file(ToWString(plugin.fullPath));
+
+        // only record headers are read, compressed records don't matter
+        const uint32_t newIndex = static_cast<uint32_t>(file.masterList().size());
+        int newRecords          = 0;
+        uint32_t highest        = 0;
+
+        for (uint32_t formId : file.recordFormIds()) {
+          // the TES4 header isn't a record the plugin adds, it has form id 0
+          // which would count as new in a plugin without masters
+          if (formId == 0) {
+            continue;
+          }
+
+          if ((formId >> 24) == newIndex) {
+            ++newRecords;
+            highest = std::max(highest, formId & 0x00FFFFFF);
+          }
+        }
+
+        if (newRecords <= 0x800) {
+          result.push_back({plugin.name, newRecords, highest > 0xFFF});
+        }
+      } catch (const std::exception& e) {
+        log::warn("failed to read the records of {}: {}", plugin.name, e.what());
+      }
+
+      promise.setProgressValue(i + 1);
+    }
+
+    promise.addResult(std::move(result));
+  });
+}
+
+QString PluginList::OrderViolation::suggestion() const
+{
+  return PluginList::tr("move %1 below %2").arg(plugin).arg(master);
+}
+
+std::vector<PluginList::OrderViolation> PluginList::validateMasterOrder() const
+{
+  std::vector<OrderViolation> result;
+
+  for (int index : m_ESPsByPriority) {
+    const ESPInfo& esp = m_ESPs[index];
+    if (!esp.enabled) {
+      continue;
+    }
+
+    for (const QString& master : esp.masters) {
+      auto iter = m_ESPsByName.find(master);
+      if (iter == m_ESPsByName.end()) {
+        continue;
+      }
+
+      const ESPInfo& masterEsp = m_ESPs[iter->second];
+      if (masterEsp.enabled && masterEsp.priority > esp.priority) {
+        result.push_back({esp.name, masterEsp.name});
+      }
+    }
+  }
+
+  return result;
+}
+
 QString PluginList::origin(const QString& name) const
 {
   auto iter = m_ESPsByName.find(name);
@@ -1567,6 +2005,14 @@ QVariant PluginList::tooltipData(const QModelIndex& modelIndex) const
            "and may have mismatched record references. Use it at your own risk.");
   }
 
+  if (esp.exceedsLightLimit) {
+    toolTip += "<br><br>" +
+               tr("WARNING: This plugin is loaded as a light plugin but adds records "
+                  "with form IDs beyond the light plugin range (up to FFF). The game "
+                  "will fail to load it. Compact the form IDs or remove the light "
+                  "flag.");
+  }
+
   if (esp.hasNoRecords) {
     toolTip += "<br><br>" + tr("This is a dummy plugin. It contains no records and is "
                                "typically used to load a paired archive file.");
@@ -1725,6 +2171,10 @@ bool PluginList::isProblematic(const ESPInfo& esp, const AdditionalInfo* info) c
     return true;
   }
 
+  if (esp.exceedsLightLimit) {
+    return true;
+  }
+
   if (info) {
     if (!info->loot.incompatibilities.empty()) {
       return true;
@@ -1980,6 +2430,9 @@ void PluginList::changePluginPriority(std::vector<int> rows, int newPriority)
     setPluginPriority(*iter, newPriority);
   }
 
+  // moving several plugins at once can still leave a plugin above a master
+  sortRespectingMasters();
+
   layoutChange.finish();
   refreshLoadOrder();
   emit writePluginsList();
@@ -2035,6 +2488,41 @@ QModelIndex PluginList::parent(const QModelIndex&) const
   return QModelIndex();
 }
 
+namespace
+{
+
+struct HighestObjectId
+{
+  QDateTime modified;
+  qint64 size;
+  uint32_t id;
+};
+
+// highestNewObjectId() walks every record of the plugin, so the result is
+// kept per file until its modification time or size changes
+uint32_t cachedHighestNewObjectId(ESP::File& file, const QString& path)
+{
+  static std::mutex mutex;
+  static std::map<QString, HighestObjectId> cache;
+
+  const QFileInfo info(path);
+  const QDateTime modified = info.lastModified();
+  const qint64 size        = info.size();
+
+  std::scoped_lock lock(mutex);
+
+  auto it = cache.find(path);
+  if (it != cache.end() && it->second.modified == modified && it->second.size == size) {
+    return it->second.id;
+  }
+
+  const uint32_t id = file.highestNewObjectId();
+  cache[path]       = {modified, size, id};
+  return id;
+}
+
+}  // namespace
+
 PluginList::ESPInfo::ESPInfo(const QString& name, bool forceLoaded, bool forceEnabled,
                              bool forceDisabled, const QString& originName,
                              const QString& fullPath, bool hasIni,
@@ -2081,6 +2569,19 @@ PluginList::ESPInfo::ESPInfo(const QString& name, bool forceLoaded, bool forceEn
                          file.isBlueprint();
     hasNoRecords = file.isDummy();
 
+    // light plugins only have room for object ids up to 0xFFF, the game
+    // refuses to load the plugin when a new record goes past that
+    exceedsLightLimit = false;
+    if (isLightFlagged || (lightSupported && hasLightExtension)) {
+      const uint32_t highest = cachedHighestNewObjectId(file, parsePath);
+      if (highest > 0xFFF) {
+        log::warn("light plugin {} has records with object ids up to {:#x}, past the "
+                  "light plugin limit of 0xfff",
+                  name, highest);
+        exceedsLightLimit = true;
+      }
+    }
+
     formVersion   = file.formVersion();
     headerVersion = file.headerVersion();
     author        = QString::fromLatin1(file.author().c_str());
@@ -2098,6 +2599,7 @@ PluginList::ESPInfo::ESPInfo(const QString& name, bool forceLoaded, bool forceEn
     isMediumFlagged    = false;
     isBlueprintFlagged = false;
     hasNoRecords       = false;
+    exceedsLightLimit  = false;
   }
 }
 
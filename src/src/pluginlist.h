@@ -31,6 +31,7 @@ class IPluginGame;
 }
 
 #include <QElapsedTimer>
+#include <QFuture>
 #include <QListWidget>
 #include <QString>
 #include <QTemporaryFile>
@@ -103,6 +104,28 @@ public:
 
   using PluginStates = MOBase::IPluginList::PluginStates;
 
+  // format of a file the game reads its load order from
+  enum class LoadOrderFormat
+  {
+    // plugins.txt listing every plugin that isn't force loaded, enabled ones
+    // prefixed with '*'; Skyrim SE, Fallout 4, Starfield and their variants
+    AsteriskPlugins,
+
+    // plugins.txt listing only the enabled plugins; the older games, which
+    // take the order of all plugins from loadorder.txt
+    ActivePlugins,
+
+    // loadorder.txt listing every plugin, enabled or not
+    LoadOrder,
+  };
+
+  struct LoadOrderEntry
+  {
+    QString name;
+    bool enabled;
+    bool forceLoaded;
+  };
+
   friend class PluginListProxy;
 
   using SignalRefreshed   = boost::signals2::signal<void()>;
@@ -232,6 +255,46 @@ public:
 
   void refreshLoadOrder();
 
+  /**
+   * @brief merge a load order read from a game-native plugins.txt
+   *
+   * listed plugins are moved to the front in the given order and enabled or
+   * disabled as listed; plugins missing from the list keep their state and
+   * relative order after them
+   * @param plugins (name, enabled) pairs in load order
+   * @return number of listed plugins that exist in this list
+   **/
+  int importLoadOrder(const QList<QPair<QString, bool>>& plugins);
+
+  /**
+   * @brief reorder the plugins to satisfy the rules of a LOOT userlist.yaml
+   *
+   * only the after, req and group rules are used; plugins keep their relative
+   * order wherever the rules allow it and masters still load before plugins
+   * @param userlistYaml content of the userlist
+   * @throw MyException if the userlist can't be parsed or its rules form a cycle
+   **/
+  void applyLootUserlist(const QString& userlistYaml);
+
+  /**
+   * @return the format of the plugins.txt the given game reads
+   **/
+  static LoadOrderFormat pluginsFileFormat(const MOBase::IPluginGame* game);
+
+  /**
+   * @brief lines of a load order file in the given format
+   * @param plugins all plugins, in load order
+   **/
+  static QStringList loadOrderLines(const QList<LoadOrderEntry>& plugins,
+                                    LoadOrderFormat format);
+
+  /**
+   * @brief writes the current load order to a file in the given format, for
+   *        games and tools reading it from a path outside the profile
+   * @return false if the file couldn't be written
+   **/
+  bool writeCombined(const QString& path, LoadOrderFormat format) const;
+
   void disconnectSlots();
 
 public:
@@ -242,6 +305,63 @@ public:
   int loadOrder(const QString& name) const;
   bool setPriority(const QString& name, int newPriority);
   QStringList masters(const QString& name) const;
+
+  // masters of an enabled plugin that are not enabled themselves
+  QStringList missingMasters(const QString& name) const;
+
+  // two enabled plugins that contain the same records while neither is a
+  // master of the other, `second` loads later and wins
+  //
+  struct RecordConflict
+  {
+    QString first;
+    QString second;
+    int records;
+  };
+
+  // reads all records of the enabled plugins on a worker thread and returns
+  // the pairs changing the same ones, most records first; this goes through
+  // every plugin on disk so it's only done on request, the future reports one
+  // step per plugin and can be canceled
+  //
+  QFuture<std::vector<RecordConflict>> findRecordConflicts() const;
+
+  // a regular plugin that adds few enough records to be flagged as light
+  //
+  struct LightCandidate
+  {
+    QString name;
+
+    // number of records the plugin adds, as opposed to overriding
+    int newRecords;
+
+    // the form ids have to be compacted into the light range first
+    bool needsCompacting;
+  };
+
+  // reads the records of all plugins that aren't light yet on a worker thread
+  // and returns the ones adding at most 0x800 records; empty if the game has no
+  // light plugins, the future reports progress like findRecordConflicts()
+  //
+  QFuture<std::vector<LightCandidate>> findLightCandidates() const;
+
+  // an enabled plugin that loads before one of its masters, which makes the
+  // game fail to load it
+  //
+  struct OrderViolation
+  {
+    QString plugin;
+    QString master;
+
+    // how to fix it, for display
+    QString suggestion() const;
+  };
+
+  // every enabled plugin loading before one of its enabled masters, in load
+  // order; masters that are missing or disabled are reported by
+  // missingMasters() instead
+  //
+  std::vector<OrderViolation> validateMasterOrder() const;
   QString origin(const QString& name) const;
   void setLoadOrder(const QStringList& pluginList);
 
@@ -351,6 +471,7 @@ private:
     bool isLightFlagged;
     bool isBlueprintFlagged;
     bool hasNoRecords;
+    bool exceedsLightLimit;
     bool modSelected;
     bool isMasterOfSelectedPlugin;
     int formVersion;
@@ -387,6 +508,14 @@ private:
   void fixPriorities();
   void fixPluginRelationships();
 
+  /**
+   * @brief stable-sorts the load order so masters come before regular plugins
+   * (blueprint plugins stay at the end), keeping the user's order within each
+   * group
+   * @return true if any priority changed
+   **/
+  bool sortRespectingMasters();
+
   int findPluginByPriority(int priority);
 
   /**
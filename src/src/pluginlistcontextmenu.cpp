@@ -1,13 +1,61 @@
 #include "pluginlistcontextmenu.h"
 
+#include <QApplication>
+#include <QFile>
+#include <QFileDialog>
+#include <QFutureWatcher>
+#include <QProgressDialog>
+#include <log.h>
 #include <report.h>
 #include <utility.h>
 
 #include "organizercore.h"
 #include "pluginlistview.h"
+#include "profile.h"
+#include "savetextasdialog.h"
 
 using namespace MOBase;
 
+namespace
+{
+
+// shows a progress dialog while `future` runs on a worker thread and calls
+// `done` with its result unless it was canceled; the menu is gone by then, so
+// everything is owned by the dialog
+//
+template <class T, class F>
+void showProgress(QWidget* parent, const QString& title, const QString& label,
+                  QFuture<T> future, F done)
+{
+  auto* progress = new QProgressDialog(label, QObject::tr("Cancel"), 0, 0, parent);
+  progress->setWindowTitle(title);
+  progress->setWindowModality(Qt::WindowModal);
+  progress->setAutoClose(false);
+  progress->setAutoReset(false);
+
+  auto* watcher = new QFutureWatcher<T>(progress);
+  QObject::connect(watcher, &QFutureWatcher<T>::progressRangeChanged, progress,
+                   &QProgressDialog::setRange);
+  QObject::connect(watcher, &QFutureWatcher<T>::progressValueChanged, progress,
+                   &QProgressDialog::setValue);
+  QObject::connect(progress, &QProgressDialog::canceled, watcher,
+                   &QFutureWatcher<T>::cancel);
+
+  QObject::connect(watcher, &QFutureWatcher<T>::finished, progress, [=]() {
+    progress->hide();
+    progress->deleteLater();
+
+    if (!watcher->isCanceled()) {
+      done(watcher->result());
+    }
+  });
+
+  watcher->setFuture(future);
+  progress->show();
+}
+
+}  // namespace
+
 PluginListContextMenu::PluginListContextMenu(const QModelIndex& index,
                                              OrganizerCore& core, PluginListView* view)
     : QMenu(view), m_core(core),
@@ -46,6 +94,27 @@ PluginListContextMenu::PluginListContextMenu(const QModelIndex& index,
     }
   });
 
+  addSeparator();
+  addAction(tr("Find record conflicts..."), [=, this]() {
+    showRecordConflicts();
+  });
+  addAction(tr("Find light plugin candidates..."), [=, this]() {
+    showLightCandidates();
+  });
+  addAction(tr("Check master order..."), [=, this]() {
+    showMasterOrderViolations();
+  });
+
+#ifndef _WIN32
+  addSeparator();
+  addAction(tr("Import load order from prefix"), [=, this]() {
+    importLoadOrderFromPrefix();
+  });
+#endif
+  addAction(tr("Apply LOOT userlist..."), [=, this]() {
+    applyLootUserlist();
+  });
+
   if (!m_selected.isEmpty()) {
     addSeparator();
     addMenu(createSendToContextMenu());
@@ -99,6 +168,86 @@ PluginListContextMenu::PluginListContextMenu(const QModelIndex& index,
   }
 }
 
+void PluginListContextMenu::showRecordConflicts()
+{
+  QWidget* parent = m_view->topLevelWidget();
+
+  showProgress(
+      parent, tr("Record conflicts"),
+      tr("Reading the records of the enabled plugins..."),
+      m_core.pluginList()->findRecordConflicts(), [parent](const auto& conflicts) {
+        if (conflicts.empty()) {
+          QMessageBox::information(parent, tr("Record conflicts"),
+                                   tr("No enabled plugins change the same records."));
+          return;
+        }
+
+        QString text;
+        for (const auto& conflict : conflicts) {
+          text += tr("%1 and %2: %n record(s), %2 wins", "", conflict.records)
+                      .arg(conflict.first, conflict.second) +
+                  "\n";
+        }
+
+        SaveTextAsDialog dialog(parent);
+        dialog.setText(text);
+        dialog.exec();
+      });
+}
+
+void PluginListContextMenu::showLightCandidates()
+{
+  QWidget* parent = m_view->topLevelWidget();
+
+  showProgress(
+      parent, tr("Light plugin candidates"), tr("Reading the records of the plugins..."),
+      m_core.pluginList()->findLightCandidates(), [parent](const auto& candidates) {
+        if (candidates.empty()) {
+          QMessageBox::information(parent, tr("Light plugin candidates"),
+                                   tr("No plugins can be flagged as light."));
+          return;
+        }
+
+        QString text;
+        for (const auto& candidate : candidates) {
+          text +=
+              tr("%1: %n new record(s)", "", candidate.newRecords).arg(candidate.name);
+          if (candidate.needsCompacting) {
+            text += ", " + tr("form IDs need compacting");
+          }
+          text += "\n";
+        }
+
+        SaveTextAsDialog dialog(parent);
+        dialog.setText(text);
+        dialog.exec();
+      });
+}
+
+void PluginListContextMenu::showMasterOrderViolations()
+{
+  const auto violations = m_core.pluginList()->validateMasterOrder();
+
+  if (violations.empty()) {
+    QMessageBox::information(m_view->topLevelWidget(), tr("Master order"),
+                             tr("All plugins load after their masters."));
+    return;
+  }
+
+  QString text;
+  for (const auto& violation : violations) {
+    text += tr("%1 loads before its master %2, %3")
+                .arg(violation.plugin)
+                .arg(violation.master)
+                .arg(violation.suggestion());
+    text += "\n";
+  }
+
+  SaveTextAsDialog dialog(m_view->topLevelWidget());
+  dialog.setText(text);
+  dialog.exec();
+}
+
 QMenu* PluginListContextMenu::createSendToContextMenu()
 {
   QMenu* menu = new QMenu(m_view);
@@ -163,3 +312,47 @@ void PluginListContextMenu::openOriginInformation(const QModelIndex& index)
     reportError(e.what());
   }
 }
+
+#ifndef _WIN32
+void PluginListContextMenu::importLoadOrderFromPrefix()
+{
+  if (QMessageBox::question(
+          m_view->topLevelWidget(), tr("Confirm"),
+          tr("Replace the load order of this profile with the one from the game's "
+             "plugins.txt in the wine prefix?"),
+          QMessageBox::Yes | QMessageBox::No) != QMessageBox::Yes) {
+    return;
+  }
+
+  const int found = m_core.importLoadOrderFromPrefix();
+  if (found < 0) {
+    reportError(tr("No plugins.txt was found in the wine prefix."));
+  } else {
+    log::info("{} plugins imported from the prefix load order", found);
+  }
+}
+#endif
+
+void PluginListContextMenu::applyLootUserlist()
+{
+  const QString path = QFileDialog::getOpenFileName(
+      m_view->topLevelWidget(), tr("Select LOOT userlist"),
+      m_core.currentProfile()->absolutePath(), tr("LOOT userlist (*.yaml *.yml)"));
+
+  if (path.isEmpty()) {
+    return;
+  }
+
+  QFile file(path);
+  if (!file.open(QIODevice::ReadOnly)) {
+    reportError(tr("Failed to open %1: %2").arg(path, file.errorString()));
+    return;
+  }
+
+  try {
+    m_core.pluginList()->applyLootUserlist(QString::fromUtf8(file.readAll()));
+    log::info("plugins reordered by the LOOT userlist '{}'", path);
+  } catch (const std::exception& e) {
+    reportError(tr("Failed to apply the LOOT userlist: %1").arg(e.what()));
+  }
+}
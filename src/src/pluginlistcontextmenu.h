@@ -37,11 +37,34 @@ public:
   //
   void setESPLock(const QModelIndexList& indices, bool locked);
 
+  // compares the records of all enabled plugins and shows the pairs changing
+  // the same ones
+  //
+  void showRecordConflicts();
+
+  // lists the plugins that could be flagged as light
+  //
+  void showLightCandidates();
+
+  // lists the plugins loading before one of their masters
+  //
+  void showMasterOrderViolations();
+
   // open explorer or mod information for the origin of the plugins
   //
   void openOriginExplorer(const QModelIndexList& indices);
   void openOriginInformation(const QModelIndex& index);
 
+#ifndef _WIN32
+  // seed the load order from the game's plugins.txt in the wine prefix
+  //
+  void importLoadOrderFromPrefix();
+#endif
+
+  // reorder the plugins of the profile by the rules of a LOOT userlist.yaml
+  //
+  void applyLootUserlist();
+
   OrganizerCore& m_core;
   QModelIndex m_index;
   QModelIndexList m_selected;
@@ -10,30 +10,108 @@
 #include <uibase/utility.h>
 #ifndef _WIN32
 #include <QCoreApplication>
+#include <QDeadlineTimer>
 #include <QEventLoop>
 #include <QFile>
 #include <QFileInfo>
 #include <QMetaObject>
 #include <QPointer>
 #include <QProcess>
+#include <QRegularExpression>
 #include <QThread>
+#include <QTimer>
 #include <cerrno>
 #include <deque>
 #include <dirent.h>
+#include <functional>
 #include <fstream>
 #include <signal.h>
 #include <unordered_map>
 #include <unordered_set>
+#include <nak_ffi.h>
 #include <sys/wait.h>
 #endif
 
 using namespace MOBase;
 
+// replaces every path below a mod folder in the arguments by the same path in
+// the data directory, both as a Linux path and as a Z: path for Proton
+//
+QString redirectModPaths(const QString& arguments, QString modsPath,
+                         const QString& dataPath)
+{
+  while (modsPath.endsWith('/')) {
+    modsPath.chop(1);
+  }
+
+  QString result = arguments;
+
+  static const QRegularExpression whitespace("\\s");
+
+  auto redirect = [&](const QString& prefix, const QString& target, QChar separator) {
+    // the mod folder name runs up to the next separator or quote
+    const QRegularExpression re(QRegularExpression::escape(prefix + separator) +
+                                    QString("[^%1\"]+").arg(
+                                        QRegularExpression::escape(separator)),
+                                QRegularExpression::CaseInsensitiveOption);
+
+    QString out;
+    qsizetype last = 0;
+    for (auto match = re.match(result); match.hasMatch();
+         match      = re.match(result, last)) {
+      const qsizetype start     = match.capturedStart();
+      const qsizetype nameStart = start + prefix.size() + 1;
+      qsizetype end             = match.capturedEnd();
+
+      // only a quoted path can have spaces in the mod folder name, an unquoted
+      // one ends at the next whitespace
+      if (result.left(start).count('"') % 2 == 0) {
+        const qsizetype space = result.indexOf(whitespace, nameStart);
+        if (space >= 0 && space < end) {
+          end = space;
+        }
+      }
+
+      if (end == nameStart) {
+        // the mods folder itself, not a mod
+        out += result.mid(last, end - last);
+      } else {
+        out += result.mid(last, start - last) + target;
+      }
+
+      last = end;
+    }
+
+    result = out + result.mid(last);
+  };
+
+  redirect(modsPath, dataPath, '/');
+
+  QString winModsPath = "Z:" + modsPath;
+  QString winDataPath = "Z:" + dataPath;
+  redirect(winModsPath.replace('/', '\\'), winDataPath.replace('/', '\\'), '\\');
+
+  return result;
+}
+
 void adjustForVirtualized(const IPluginGame* game, spawn::SpawnParameters& sp,
                           const Settings& settings)
 {
   const QString modsPath = settings.paths().mods();
 
+  // files a tool writes through the data directory land in overwrite instead
+  // of silently changing a mod
+  if (sp.redirectModPaths) {
+    const QString arguments =
+        redirectModPaths(sp.arguments, modsPath, game->dataDirectory().absolutePath());
+
+    if (arguments != sp.arguments) {
+      log::debug("redirected mod paths in arguments: '{}' -> '{}'", sp.arguments,
+                 arguments);
+      sp.arguments = arguments;
+    }
+  }
+
   // Check if this a request with either an executable or a working directory
   // under our mods folder then will start the process in a virtualized
   // "environment" with the appropriate paths fixed:
@@ -606,6 +684,131 @@ QStringList buildExpectedExecutables(const QFileInfo& binary, const QString& arg
   return expected;
 }
 
+// executables started by the given binary if it is a known game launcher that
+// starts the game and exits right away, lowercase file names only
+//
+QStringList relaunchedExecutables(const QFileInfo& binary)
+{
+  char* raw =
+      nak_resolve_game_launcher(binary.absoluteFilePath().toUtf8().constData());
+  if (raw == nullptr) {
+    return {};
+  }
+
+  const QString paths = QString::fromUtf8(raw);
+  nak_string_free(raw);
+
+  QStringList names;
+  for (const QString& path : paths.split('\n', Qt::SkipEmptyParts)) {
+    names.push_back(QFileInfo(path).fileName().toLower());
+  }
+
+  return names;
+}
+
+// whether the given /proc comm matches one of the lowercase executable names;
+// the kernel truncates comm to 15 characters, so "cyberpunk2077.exe" shows up
+// as "cyberpunk2077.e"
+//
+bool commMatches(const QString& comm, const QStringList& names)
+{
+  const QString lower = comm.toLower();
+  for (const QString& name : names) {
+    if (lower == name || (lower.size() == 15 && name.startsWith(lower))) {
+      return true;
+    }
+  }
+
+  return false;
+}
+
+// the wine prefix and start time of a launcher, to tell the game it starts
+// apart from other processes with the same name
+//
+struct LaunchSession
+{
+  QString winePrefix;
+
+  // clock ticks after boot, 0 if unknown
+  unsigned long long startTime = 0;
+};
+
+// value of the given environment variable of a process, empty if it isn't set
+// or the process can't be read
+//
+QString readProcEnv(pid_t pid, const QByteArray& name)
+{
+  QFile f(QString("/proc/%1/environ").arg(pid));
+  if (!f.open(QIODevice::ReadOnly)) {
+    return {};
+  }
+
+  const QByteArray prefix = name + '=';
+  for (const QByteArray& var : f.readAll().split('\0')) {
+    if (var.startsWith(prefix)) {
+      return QString::fromUtf8(var.mid(prefix.size()));
+    }
+  }
+
+  return {};
+}
+
+// start time of a process in clock ticks after boot, 0 if it can't be read
+//
+unsigned long long readProcStartTime(pid_t pid)
+{
+  QFile f(QString("/proc/%1/stat").arg(pid));
+  if (!f.open(QIODevice::ReadOnly)) {
+    return 0;
+  }
+
+  // the name in parentheses can contain spaces, so the fields are split after
+  // it, starting with the 3rd; starttime is the 22nd
+  constexpr int StartTime = 22 - 3;
+
+  const QByteArray stat = f.readAll();
+  const qsizetype paren = stat.lastIndexOf(')');
+  const auto fields     = stat.mid(paren + 2).split(' ');
+
+  if (paren < 0 || fields.size() <= StartTime) {
+    return 0;
+  }
+
+  return fields[StartTime].toULongLong();
+}
+
+LaunchSession launchSessionOf(pid_t pid)
+{
+  return {readProcEnv(pid, "WINEPREFIX"), readProcStartTime(pid)};
+}
+
+// whether the process was started by the launcher of the given session: it
+// can't be older than the launcher and must run in the same prefix
+//
+bool inLaunchSession(pid_t pid, const LaunchSession& session)
+{
+  if (session.startTime > 0 && readProcStartTime(pid) < session.startTime) {
+    return false;
+  }
+
+  if (!session.winePrefix.isEmpty() &&
+      readProcEnv(pid, "WINEPREFIX") != session.winePrefix) {
+    return false;
+  }
+
+  return true;
+}
+
+// a process of the given launch session that has one of the given lowercase
+// executable names
+//
+pid_t findProcessByName(const QStringList& names, const LaunchSession& session)
+{
+  return env::findProcess([&](pid_t pid) {
+    return commMatches(readProcComm(pid), names) && inLaunchSession(pid, session);
+  });
+}
+
 pid_t findTrackedProcess(pid_t rootPid, const QStringList& expected,
                          QString* trackedNameOut)
 {
@@ -626,8 +829,7 @@ pid_t findTrackedProcess(pid_t rootPid, const QStringList& expected,
     if (comm.isEmpty()) {
       continue;
     }
-    const QString lower = comm.toLower();
-    if (expected.contains(lower)) {
+    if (commMatches(comm, expected)) {
       best = pid;
       bestName = comm;
       break;
@@ -801,6 +1003,100 @@ ProcessRunner::Results waitForProcesses(const std::vector<HANDLE>& initialProces
   return ProcessRunner::Completed;
 }
 
+// how long a launcher that exited has to start the game
+constexpr std::chrono::seconds RelaunchTimeout(15);
+
+// called once a launcher has exited: finds the game it started, which is not
+// necessarily a descendant of the launcher anymore, and waits for it
+//
+ProcessRunner::Results waitForRelaunchedGame(const QStringList& games,
+                                             const LaunchSession& session,
+                                             LPDWORD exitCode, UILocker::Session* ls)
+{
+  // the launcher may exit slightly before the game shows up, or the user may
+  // have closed it without starting the game at all
+  QDeadlineTimer deadline(RelaunchTimeout);
+  pid_t game = 0;
+
+  while ((game = findProcessByName(games, session)) <= 0) {
+    if (deadline.hasExpired()) {
+      log::debug("launcher exited without starting any of '{}'", games.join(", "));
+      return ProcessRunner::Completed;
+    }
+
+    if (ls != nullptr) {
+      switch (ls->result()) {
+      case UILocker::StillLocked:
+        break;
+
+      case UILocker::ForceUnlocked:
+        return ProcessRunner::ForceUnlocked;
+
+      case UILocker::Cancelled:
+        return ProcessRunner::Cancelled;
+
+      case UILocker::NoResult:
+      default:
+        return ProcessRunner::Error;
+      }
+    }
+
+    QCoreApplication::processEvents(QEventLoop::AllEvents, 20);
+    QThread::msleep(250);
+  }
+
+  log::debug("launcher started {} ({}), waiting for it instead", game,
+             readProcComm(game));
+
+  return waitForPid(game, exitCode, ls, {});
+}
+
+// same as waitForRelaunchedGame() without blocking, for when nothing is locked:
+// polls from a timer on the gui thread and calls done once the game has exited,
+// with whether it was started at all
+//
+void watchRelaunchedGame(QObject* context, const QStringList& games,
+                         const LaunchSession& session,
+                         std::function<void(bool started)> done)
+{
+  auto* timer = new QTimer(context);
+  timer->setInterval(250);
+
+  QObject::connect(
+      timer, &QTimer::timeout, context,
+      [timer, games, session, done = std::move(done),
+       deadline = QDeadlineTimer(RelaunchTimeout), game = pid_t(0)]() mutable {
+        if (game <= 0) {
+          game = findProcessByName(games, session);
+
+          if (game > 0) {
+            log::debug("launcher started {} ({}), waiting for it instead", game,
+                       readProcComm(game));
+          } else if (deadline.hasExpired()) {
+            log::debug("launcher exited without starting any of '{}'",
+                       games.join(", "));
+            timer->stop();
+            timer->deleteLater();
+            done(false);
+          }
+
+          return;
+        }
+
+        // EPERM means it's still there but can't be signalled
+        if (::kill(game, 0) == 0 || errno == EPERM) {
+          return;
+        }
+
+        log::debug("process {} completed", game);
+        timer->stop();
+        timer->deleteLater();
+        done(true);
+      });
+
+  timer->start();
+}
+
 #endif // _WIN32
 
 ProcessRunner::ProcessRunner(OrganizerCore& core, IUserInterface* ui)
@@ -843,6 +1139,18 @@ ProcessRunner& ProcessRunner::setSteamID(const QString& steamID)
   return *this;
 }
 
+ProcessRunner& ProcessRunner::setForceX11(bool b)
+{
+  m_sp.forceX11 = b;
+  return *this;
+}
+
+ProcessRunner& ProcessRunner::setRedirectModPaths(bool b)
+{
+  m_sp.redirectModPaths = b;
+  return *this;
+}
+
 ProcessRunner& ProcessRunner::setCustomOverwrite(const QString& customOverwrite)
 {
   m_customOverwrite = customOverwrite;
@@ -935,6 +1243,8 @@ ProcessRunner& ProcessRunner::setFromExecutable(const Executable& exe)
   setArguments(exe.arguments());
   setCurrentDirectory(currentDirectory);
   setSteamID(exe.steamAppID());
+  setForceX11(exe.forceX11());
+  setRedirectModPaths(exe.redirectModPaths());
   setCustomOverwrite(customOverwrite);
   setForcedLibraries(forcedLibraries);
 
@@ -1007,6 +1317,8 @@ ProcessRunner& ProcessRunner::setFromFileOrExecutable(
       const Executable& exe = m_core.executablesList()->getByBinary(m_sp.binary);
 
       setSteamID(exe.steamAppID());
+      setForceX11(exe.forceX11());
+      setRedirectModPaths(exe.redirectModPaths());
       setCustomOverwrite(profile->setting("custom_overwrites", exe.title()).toString());
 
       if (profile->forcedLibrariesEnabled(exe.title())) {
@@ -1021,6 +1333,8 @@ ProcessRunner& ProcessRunner::setFromFileOrExecutable(
       const Executable& exe = m_core.executablesList()->get(executable);
 
       setSteamID(exe.steamAppID());
+      setForceX11(exe.forceX11());
+      setRedirectModPaths(exe.redirectModPaths());
       setCustomOverwrite(profile->setting("custom_overwrites", exe.title()).toString());
 
       if (profile->forcedLibrariesEnabled(exe.title())) {
@@ -1167,6 +1481,22 @@ std::optional<ProcessRunner::Results> ProcessRunner::runBinary()
     return Error;
   }
 
+  // warn if a 64-bit program is run in a 32-bit prefix
+  if (!checkArchitecture(parent, m_sp)) {
+    return Error;
+  }
+
+  // warn if the game needs a steam app id for its drm and none is set
+  if (!checkSteamAppId(parent, m_sp, game->gameDirectory(), settings)) {
+    return Error;
+  }
+
+  // warn if the selected proton is too old or not the one recommended for
+  // the game
+  if (!checkProtonVersion(parent, m_sp)) {
+    return Error;
+  }
+
   // if the executable is inside the mods folder another instance of
   // ModOrganizer.exe is spawned instead to launch it
   adjustForVirtualized(game, m_sp, settings);
@@ -1242,9 +1572,25 @@ ProcessRunner::Results ProcessRunner::postRun()
   }
 
   const bool lockEnabled = m_core.settings().interface().lockGUI();
-  const QStringList expectedExecutables =
+  QStringList expectedExecutables =
       buildExpectedExecutables(m_sp.binary, m_sp.arguments);
 
+#ifndef _WIN32
+  // the lifetime of a launcher that starts the game and exits has nothing to do
+  // with the game's, follow the game instead so the ui stays locked and the
+  // post-run refresh happens once the game has actually exited
+  const QStringList relaunched = relaunchedExecutables(m_sp.binary);
+  LaunchSession session;
+  if (!relaunched.isEmpty()) {
+    log::debug("process runner: {} is a launcher for '{}'", m_sp.binary.fileName(),
+               relaunched.join(", "));
+    expectedExecutables.append(relaunched);
+
+    // read while the launcher is still running
+    session = launchSessionOf(handleToPid(m_handle.get()));
+  }
+#endif
+
   if (mustWait) {
     if (!lockEnabled) {
       // at least tell the user what's going on
@@ -1264,7 +1610,7 @@ ProcessRunner::Results ProcessRunner::postRun()
         const QFileInfo binary = m_sp.binary;
         QPointer<OrganizerCore> core = &m_core;
 
-        std::thread([core, binary, pid]() {
+        std::thread([core, binary, pid, relaunched, session]() {
           int status = 0;
           pid_t waited = -1;
           do {
@@ -1287,11 +1633,26 @@ ProcessRunner::Results ProcessRunner::postRun()
             return;
           }
 
+          // the game is looked for from the gui thread, the ui must not be
+          // touched from this one
           QMetaObject::invokeMethod(
-              core, [core, binary, exitCode]() {
-                if (core) {
+              core,
+              [core, binary, exitCode, relaunched, session]() {
+                if (!core) {
+                  return;
+                }
+
+                if (relaunched.isEmpty()) {
                   core->afterRun(binary, exitCode);
+                  return;
                 }
+
+                watchRelaunchedGame(core, relaunched, session,
+                                    [core, binary, exitCode](bool started) {
+                                      if (core) {
+                                        core->afterRun(binary, started ? 0 : exitCode);
+                                      }
+                                    });
               },
               Qt::QueuedConnection);
         }).detach();
@@ -1325,9 +1686,19 @@ ProcessRunner::Results ProcessRunner::postRun()
     // MO will be running in the background with no visual feedback, but that's
     // how it is
     r = waitForProcess(m_handle.get(), &m_exitCode, nullptr, expectedExecutables);
+#ifndef _WIN32
+    if (r == Completed && !relaunched.isEmpty()) {
+      r = waitForRelaunchedGame(relaunched, session, &m_exitCode, nullptr);
+    }
+#endif
   } else {
     withLock([&](auto& ls) {
       r = waitForProcess(m_handle.get(), &m_exitCode, &ls, expectedExecutables);
+#ifndef _WIN32
+      if (r == Completed && !relaunched.isEmpty()) {
+        r = waitForRelaunchedGame(relaunched, session, &m_exitCode, &ls);
+      }
+#endif
     });
   }
 
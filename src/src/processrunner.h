@@ -85,6 +85,8 @@ public:
   ProcessRunner& setArguments(const QString& arguments);
   ProcessRunner& setCurrentDirectory(const QDir& directory);
   ProcessRunner& setSteamID(const QString& steamID);
+  ProcessRunner& setForceX11(bool b);
+  ProcessRunner& setRedirectModPaths(bool b);
   ProcessRunner& setCustomOverwrite(const QString& customOverwrite);
   ProcessRunner& setForcedLibraries(const ForcedLibraries& forcedLibraries);
   ProcessRunner& setProfileName(const QString& profileName);
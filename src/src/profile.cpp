@@ -400,6 +400,11 @@ void Profile::renameModInList(QFile& modList, const QString& oldName,
 }
 
 void Profile::refreshModStatus()
+{
+  reconcileModList();
+}
+
+Profile::ModListReconciliation Profile::reconcileModList()
 {
   // this function refreshes mod status (enabled/disabled) and priority
   // using the profile mod list file and the mods in the mods folder using
@@ -450,6 +455,7 @@ void Profile::refreshModStatus()
 
   bool warnAboutOverwrite = false;
   unsigned int modsNotFound = 0;
+  ModListReconciliation result;
 
   // load mods from file and update enabled state and priority for them
   int index = 0;
@@ -494,6 +500,7 @@ void Profile::refreshModStatus()
         log::warn("mod not found: \"{}\" (profile \"{}\")", modName, m_Directory.path());
       }
       ++modsNotFound;
+      result.dropped.append(modName);
       // need to rewrite the modlist to fix this
       modStatusModified = true;
       continue;
@@ -560,6 +567,7 @@ void Profile::refreshModStatus()
       } else if (!modInfo->isBackup()) {
         m_ModStatus[i].m_Priority = index++;
         ++m_NumRegularMods;
+        result.added.append(modInfo->name());
       }
 
       // also, mark the mod-list as changed
@@ -567,6 +575,18 @@ void Profile::refreshModStatus()
     }
   }
 
+  if (!result.added.isEmpty()) {
+    log::info("refreshModStatus: {} mods were not in modlist.txt and were added "
+              "disabled at the bottom (profile \"{}\"): {}",
+              result.added.size(), m_Directory.path(), result.added.join(", "));
+  }
+
+  if (!result.dropped.isEmpty()) {
+    log::info("refreshModStatus: {} modlist.txt entries have no mod folder and "
+              "were dropped (profile \"{}\"): {}",
+              result.dropped.size(), m_Directory.path(), result.dropped.join(", "));
+  }
+
   // to support insertion of new mods at the top we may now have mods with negative
   // priority, so shift them all up to align priority with 0
   if (topInsert < 0) {
@@ -604,6 +624,8 @@ void Profile::refreshModStatus()
   if (modStatusModified) {
     m_ModListWriter.write();
   }
+
+  return result;
 }
 
 void Profile::dumpModStatus() const
@@ -754,14 +776,32 @@ bool Profile::setModPriority(unsigned int index, int& newPriority)
 Profile* Profile::createPtrFrom(const QString& name, const Profile& reference,
                                 MOBase::IPluginGame const* gamePlugin)
 {
-  QString profileDirectory = Settings::instance().paths().profiles() + "/" + name;
-  reference.copyFilesTo(profileDirectory);
+  QString fixedName = name;
+  if (!fixDirectoryName(fixedName)) {
+    throw MyException(tr("invalid profile name: %1").arg(name));
+  }
+
+  const QDir profilesDir(Settings::instance().paths().profiles());
+
+  // profile names are case insensitive on Windows, don't allow a clone to
+  // differ from an existing profile only by case
+  for (const auto& existing : profilesDir.entryList(QDir::Dirs | QDir::NoDotAndDotDot)) {
+    if (existing.compare(fixedName, Qt::CaseInsensitive) == 0) {
+      throw MyException(tr("a profile named \"%1\" already exists").arg(existing));
+    }
+  }
+
+  const QString profileDirectory = profilesDir.absoluteFilePath(fixedName);
+  if (!reference.copyFilesTo(profileDirectory)) {
+    throw MyException(tr("failed to copy profile to %1").arg(profileDirectory));
+  }
+
   return new Profile(QDir(profileDirectory), gamePlugin, reference.m_GameFeatures);
 }
 
-void Profile::copyFilesTo(QString& target) const
+bool Profile::copyFilesTo(const QString& target) const
 {
-  copyDir(m_Directory.absolutePath(), target, false);
+  return copyDir(m_Directory.absolutePath(), target, false);
 }
 
 std::vector<std::wstring> Profile::splitDZString(const wchar_t* buffer) const
@@ -1066,6 +1106,30 @@ QString Profile::savePath() const
   return QDir::cleanPath(m_Directory.absoluteFilePath("saves"));
 }
 
+QString Profile::saveSubdirOverride() const
+{
+  return setting("", "SaveSubdir").toString();
+}
+
+bool Profile::setSaveSubdirOverride(const QString& subdir)
+{
+  const QString trimmed = QDir::fromNativeSeparators(subdir.trimmed());
+  if (trimmed.isEmpty()) {
+    removeSetting("", "SaveSubdir");
+    return true;
+  }
+
+  const QString cleaned = QDir::cleanPath(trimmed);
+  // a drive letter would point outside the documents directory in the prefix too
+  if (QDir::isAbsolutePath(cleaned) || cleaned.contains(':') || cleaned == "." ||
+      cleaned == ".." || cleaned.startsWith("../")) {
+    return false;
+  }
+
+  storeSetting("", "SaveSubdir", cleaned);
+  return true;
+}
+
 void Profile::rename(const QString& newName)
 {
   QDir profileDir(Settings::instance().paths().profiles());
@@ -1154,6 +1218,55 @@ void Profile::storeSettingsByArray(const QString& prefix,
   m_Settings->endArray();
 }
 
+std::pair<QString, QString> Profile::conflictPair(const QString& modA,
+                                                  const QString& modB)
+{
+  // pairs are unordered, compare them with the names sorted
+  QString first  = modA.toLower();
+  QString second = modB.toLower();
+  if (second < first) {
+    std::swap(first, second);
+  }
+
+  return {first, second};
+}
+
+static std::pair<QString, QString> conflictPair(const QVariantMap& entry)
+{
+  return Profile::conflictPair(entry.value("first").toString(),
+                               entry.value("second").toString());
+}
+
+std::set<std::pair<QString, QString>> Profile::acknowledgedConflicts() const
+{
+  std::set<std::pair<QString, QString>> pairs;
+  for (const auto& entry : settingsByArray("acknowledged_conflicts")) {
+    pairs.insert(conflictPair(entry));
+  }
+
+  return pairs;
+}
+
+void Profile::setConflictAcknowledged(const QString& modA, const QString& modB,
+                                      bool acknowledged)
+{
+  const auto pair = conflictPair(modA, modB);
+  QList<QVariantMap> entries = settingsByArray("acknowledged_conflicts");
+  entries.removeIf([&](const QVariantMap& entry) {
+    return conflictPair(entry) == pair;
+  });
+
+  if (acknowledged) {
+    entries.append({{"first", modA}, {"second", modB}});
+  }
+
+  // a shorter array would leave the old entries past its end behind
+  m_Settings->remove("acknowledged_conflicts");
+  if (!entries.isEmpty()) {
+    storeSettingsByArray("acknowledged_conflicts", entries);
+  }
+}
+
 bool Profile::forcedLibrariesEnabled(const QString& executable) const
 {
   return setting("forced_libraries", executable + "/enabled", true).toBool();
@@ -34,8 +34,10 @@ along with Mod Organizer.  If not, see <http://www.gnu.org/licenses/>.
 
 #include <boost/shared_ptr.hpp>
 
+#include <set>
 #include <string>
 #include <tuple>
+#include <utility>
 #include <vector>
 
 namespace MOBase
@@ -105,6 +107,8 @@ public:
   /**
    * @param name of the new profile
    * @param reference profile to copy from
+   * @throw MyException if the name is invalid or a profile with that name
+   *        already exists, ignoring case
    **/
   static Profile* createPtrFrom(const QString& name, const Profile& reference,
                                 MOBase::IPluginGame const* gamePlugin);
@@ -227,6 +231,21 @@ public:
    **/
   QString savePath() const;
 
+  /**
+   * @return folder, relative to the game's documents directory, that local
+   *         saves are deployed to and synced back from; empty if it's worked
+   *         out from the game instead
+   **/
+  QString saveSubdirOverride() const;
+
+  /**
+   * @brief sets the folder local saves are deployed to and synced back from
+   * @param subdir folder relative to the game's documents directory, or an
+   *        empty string to work it out from the game again
+   * @return false if subdir is not a folder inside the documents directory
+   **/
+  bool setSaveSubdirOverride(const QString& subdir);
+
   /**
    * @brief rename profile
    * @param newName new name of profile
@@ -240,11 +259,29 @@ public:
    * of the game-ini of this profile with ini tweaks applied */
   void createTweakedIniFile();
 
+  // what reconcileModList() changed to make modlist.txt match the mods folder
+  //
+  struct ModListReconciliation
+  {
+    // mods with a folder that modlist.txt didn't list, added at the bottom
+    QStringList added;
+
+    // modlist.txt entries without a mod folder, dropped from the list
+    QStringList dropped;
+  };
+
   /**
    * @brief re-read the modlist.txt and update the mod status from it
    **/
   void refreshModStatus();
 
+  /**
+   * @brief same as refreshModStatus(), but also returns the mods that were
+   *        added to or dropped from the list because modlist.txt didn't match
+   *        the mods folder
+   **/
+  ModListReconciliation reconcileModList();
+
   /**
    * @brief retrieve a list of mods that are enabled in this profile
    *
@@ -336,6 +373,23 @@ public:
   QList<QVariantMap> settingsByArray(const QString& prefix) const;
   void storeSettingsByArray(const QString& prefix, const QList<QVariantMap>& values);
 
+  /**
+   * @brief pairs of mods whose conflicts were acknowledged as safe
+   *
+   * acknowledged pairs are unordered and don't show up as conflicts in the
+   * mod list; look them up with conflictPair()
+   **/
+  std::set<std::pair<QString, QString>> acknowledgedConflicts() const;
+  void setConflictAcknowledged(const QString& modA, const QString& modB,
+                               bool acknowledged);
+
+  /**
+   * @brief the two mods as stored in acknowledgedConflicts(), lowercase and
+   * sorted
+   **/
+  static std::pair<QString, QString> conflictPair(const QString& modA,
+                                                  const QString& modB);
+
   bool forcedLibrariesEnabled(const QString& executable) const;
   void setForcedLibrariesEnabled(const QString& executable, bool enabled);
   QList<MOBase::ExecutableForcedLoadSetting>
@@ -386,7 +440,7 @@ private:
 private:
   void updateIndices();
 
-  void copyFilesTo(QString& target) const;
+  bool copyFilesTo(const QString& target) const;
 
   std::vector<std::wstring> splitDZString(const wchar_t* buffer) const;
   void mergeTweak(const QString& tweakName, const QString& tweakedIni) const;
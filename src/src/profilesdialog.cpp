@@ -359,6 +359,7 @@ void ProfilesDialog::on_profilesList_currentItemChanged(QListWidgetItem* current
 
       bool localSaves = currentProfile->localSavesEnabled();
       ui->transferButton->setEnabled(localSaves);
+      ui->saveFolderButton->setEnabled(localSaves);
       // prevent the stateChanged-event for the saves-box from triggering, otherwise it
       // may think local saves were disabled and delete the files/rename the dir
       ui->localSavesBox->blockSignals(true);
@@ -400,6 +401,7 @@ void ProfilesDialog::on_localSavesBox_stateChanged(int state)
 
   if (currentProfile->enableLocalSaves(state == Qt::Checked)) {
     ui->transferButton->setEnabled(state == Qt::Checked);
+    ui->saveFolderButton->setEnabled(state == Qt::Checked);
   } else {
     // revert checkbox-state
     ui->localSavesBox->setChecked(state != Qt::Checked);
@@ -414,6 +416,28 @@ void ProfilesDialog::on_transferButton_clicked()
   transferDialog.exec();
 }
 
+void ProfilesDialog::on_saveFolderButton_clicked()
+{
+  const Profile::Ptr currentProfile =
+      ui->profilesList->currentItem()->data(Qt::UserRole).value<Profile::Ptr>();
+
+  bool ok = false;
+  const QString subdir = QInputDialog::getText(
+      this, tr("Save Folder"),
+      tr("Folder the save games of this profile are placed in, relative to the "
+         "game's documents folder.\nLeave empty to use the folder the game is "
+         "configured for."),
+      QLineEdit::Normal, currentProfile->saveSubdirOverride(), &ok);
+  if (!ok) {
+    return;
+  }
+
+  if (!currentProfile->setSaveSubdirOverride(subdir)) {
+    reportError(tr("\"%1\" is not a folder inside the game's documents folder.")
+                    .arg(subdir));
+  }
+}
+
 void ProfilesDialog::on_localIniFilesBox_stateChanged(int state)
 {
   Profile::Ptr currentProfile =
@@ -125,6 +125,8 @@ private slots:
 
   void on_transferButton_clicked();
 
+  void on_saveFolderButton_clicked();
+
   void on_renameButton_clicked();
 
 private:
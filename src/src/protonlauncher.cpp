@@ -133,12 +133,82 @@ void maybeWrapForFlatpak(QString& program, QStringList& arguments,
     }
   }
 
+  // The host session may still have variables that were removed on purpose,
+  // such as WAYLAND_DISPLAY when forcing X11.
+  for (const QString& key : sysEnv.keys()) {
+    if (!env.contains(key)) {
+      wrappedArgs.append(QStringLiteral("--unset-env=%1").arg(key));
+    }
+  }
+
   wrappedArgs.append(program);
   wrappedArgs.append(arguments);
   program   = QStringLiteral("flatpak-spawn");
   arguments = wrappedArgs;
 }
 
+// the X display served by the only socket in /tmp/.X11-unix, such as ":1" for
+// X1; empty if there is none or more than one, since there's no telling which
+// one belongs to this session
+QString displayFromX11Sockets()
+{
+  const QStringList sockets =
+      QDir("/tmp/.X11-unix").entryList({"X*"}, QDir::System | QDir::NoDotAndDotDot);
+
+  if (sockets.size() != 1) {
+    return {};
+  }
+
+  bool ok                = false;
+  const int displayIndex = sockets.front().mid(1).toInt(&ok);
+  if (!ok) {
+    return {};
+  }
+
+  return QStringLiteral(":%1").arg(displayIndex);
+}
+
+// Windows programs under Proton open their windows through XWayland unless
+// Wine's Wayland driver is enabled, and some desktop launchers start us
+// without DISPLAY on Wayland sessions, so make sure the display variables are
+// usable before starting anything.
+void applyDisplayEnvironment(QProcessEnvironment& env, bool forceX11)
+{
+  // in Flatpak the display variables point into the sandbox and the host
+  // process gets the session's own values, so leave them alone
+  if (!isFlatpak()) {
+    if (!env.contains("DISPLAY") && env.contains("WAYLAND_DISPLAY")) {
+      const QString display = displayFromX11Sockets();
+      if (!display.isEmpty()) {
+        env.insert("DISPLAY", display);
+      } else {
+        MOBase::log::warn("DISPLAY is not set and the XWayland display can't be "
+                          "told from /tmp/.X11-unix, programs that need X11 may "
+                          "fail to open a window");
+      }
+    }
+
+    if (!env.contains("XAUTHORITY")) {
+      const QString xauthority = QDir::home().filePath(".Xauthority");
+      if (QFileInfo::exists(xauthority)) {
+        env.insert("XAUTHORITY", xauthority);
+      }
+    }
+  }
+
+  if (forceX11) {
+    env.remove("WAYLAND_DISPLAY");
+    env.remove("PROTON_ENABLE_WAYLAND");
+    env.insert("GDK_BACKEND", "x11");
+    env.insert("QT_QPA_PLATFORM", "xcb");
+    env.insert("SDL_VIDEODRIVER", "x11");
+  }
+
+  MOBase::log::debug("display environment: DISPLAY='{}' WAYLAND_DISPLAY='{}'{}",
+                     env.value("DISPLAY"), env.value("WAYLAND_DISPLAY"),
+                     forceX11 ? " (forcing X11)" : "");
+}
+
 bool isValidEnvKey(const QString& key)
 {
   if (key.isEmpty()) {
@@ -179,7 +249,7 @@ bool parseEnvAssignment(const QString& token, QString& keyOut, QString& valueOut
 
 ProtonLauncher::ProtonLauncher()
     : m_steamAppId(0), m_useUmu(false), m_preferSystemUmu(false),
-      m_useSteamRun(false)
+      m_useSteamRun(false), m_forceX11(false)
 {}
 
 ProtonLauncher& ProtonLauncher::setBinary(const QString& path)
@@ -188,6 +258,12 @@ ProtonLauncher& ProtonLauncher::setBinary(const QString& path)
   return *this;
 }
 
+ProtonLauncher& ProtonLauncher::setPrefixBinary(const QString& path)
+{
+  m_prefixBinary = path.trimmed();
+  return *this;
+}
+
 ProtonLauncher& ProtonLauncher::setArguments(const QStringList& args)
 {
   m_arguments = args;
@@ -258,6 +334,18 @@ ProtonLauncher& ProtonLauncher::setUseSteamRun(bool useSteamRun)
   return *this;
 }
 
+ProtonLauncher& ProtonLauncher::setForceX11(bool forceX11)
+{
+  m_forceX11 = forceX11;
+  return *this;
+}
+
+ProtonLauncher& ProtonLauncher::setGamescope(const GamescopeOptions& options)
+{
+  m_gamescope = options;
+  return *this;
+}
+
 ProtonLauncher& ProtonLauncher::addEnvVar(const QString& key, const QString& value)
 {
   if (!key.isEmpty()) {
@@ -267,6 +355,52 @@ ProtonLauncher& ProtonLauncher::addEnvVar(const QString& key, const QString& val
   return *this;
 }
 
+QStringList ProtonLauncher::wrapperCommands() const
+{
+  if (!m_gamescope.enabled) {
+    return m_wrapperCommands;
+  }
+
+  // in Flatpak gamescope runs on the host and can't be looked up from here
+  QString gamescope = QStandardPaths::findExecutable("gamescope");
+  if (gamescope.isEmpty()) {
+    if (!isFlatpak()) {
+      MOBase::log::warn("gamescope is enabled but was not found in PATH, "
+                        "launching without it");
+      return m_wrapperCommands;
+    }
+
+    gamescope = QStringLiteral("gamescope");
+  }
+
+  QStringList commands{gamescope};
+
+  if (m_gamescope.width > 0 && m_gamescope.height > 0) {
+    commands << "-W" << QString::number(m_gamescope.width) << "-H"
+             << QString::number(m_gamescope.height);
+  }
+
+  if (m_gamescope.fullscreen) {
+    commands << "-f";
+  }
+
+  if (!m_gamescope.filter.isEmpty()) {
+    commands << "-F" << m_gamescope.filter;
+  }
+
+  // gamescope goes first so the user's wrappers (gamemoderun, mangohud, ...)
+  // run inside the session together with the game
+  commands << "--";
+  commands.append(m_wrapperCommands);
+
+  return commands;
+}
+
+QString ProtonLauncher::prefixBinary() const
+{
+  return m_prefixBinary.isEmpty() ? m_binary : m_prefixBinary;
+}
+
 std::pair<bool, qint64> ProtonLauncher::launch() const
 {
   qint64 pid = -1;
@@ -298,16 +432,18 @@ bool ProtonLauncher::launchWithProton(qint64& pid) const
     protonScript = QDir(m_protonPath).filePath("proton");
   }
 
-  const QStringList protonArgs = QStringList() << "run" << m_binary << m_arguments;
+  const QStringList protonArgs =
+      QStringList() << "run" << prefixBinary() << m_arguments;
 
   QString program;
   QStringList arguments;
-  wrapProgram(m_wrapperCommands, protonScript, protonArgs, program, arguments);
+  wrapProgram(wrapperCommands(), protonScript, protonArgs, program, arguments);
   maybeWrapWithSteamRun(m_useSteamRun, program, arguments);
 
   // Build environment BEFORE flatpak wrapping (flatpak-spawn needs --env= flags).
   QProcessEnvironment env = QProcessEnvironment::systemEnvironment();
   env.remove("PYTHONHOME");
+  applyDisplayEnvironment(env, m_forceX11);
 
   if (!m_prefixPath.isEmpty()) {
     env.insert("WINEPREFIX", m_prefixPath);
@@ -351,7 +487,7 @@ bool ProtonLauncher::launchWithProton(qint64& pid) const
 
   maybeWrapForFlatpak(program, arguments, env);
 
-  MOBase::log::info("Proton launch: '{}' run '{}'", protonScript, m_binary);
+  MOBase::log::info("Proton launch: '{}' run '{}'", protonScript, prefixBinary());
   return startDetachedWithEnv(program, arguments, m_workingDir, env, pid);
 }
 
@@ -423,16 +559,17 @@ bool ProtonLauncher::launchWithUmu(qint64& pid) const
     return false;
   }
 
-  const QStringList umuArgs = QStringList() << m_binary << m_arguments;
+  const QStringList umuArgs = QStringList() << prefixBinary() << m_arguments;
 
   QString program;
   QStringList arguments;
-  wrapProgram(m_wrapperCommands, umuRun, umuArgs, program, arguments);
+  wrapProgram(wrapperCommands(), umuRun, umuArgs, program, arguments);
   maybeWrapWithSteamRun(m_useSteamRun, program, arguments);
 
   // Build environment BEFORE flatpak wrapping (flatpak-spawn needs --env= flags).
   QProcessEnvironment env = QProcessEnvironment::systemEnvironment();
   env.remove("PYTHONHOME");
+  applyDisplayEnvironment(env, m_forceX11);
 
   if (!m_prefixPath.isEmpty()) {
     env.insert("WINEPREFIX", m_prefixPath);
@@ -488,7 +625,7 @@ bool ProtonLauncher::launchWithUmu(qint64& pid) const
   maybeWrapForFlatpak(program, arguments, env);
 
   MOBase::log::info("UMU launch: '{}' '{}' (game id: {}, steam: '{}')", umuRun,
-                    m_binary,
+                    prefixBinary(),
                     (effectiveSteamAppId == 0
                          ? QStringLiteral("<unset>")
                          : QStringLiteral("umu-") +
@@ -505,11 +642,12 @@ bool ProtonLauncher::launchDirect(qint64& pid) const
 
   QString program;
   QStringList arguments;
-  wrapProgram(m_wrapperCommands, m_binary, m_arguments, program, arguments);
+  wrapProgram(wrapperCommands(), m_binary, m_arguments, program, arguments);
   maybeWrapWithSteamRun(m_useSteamRun, program, arguments);
 
   QProcessEnvironment env = QProcessEnvironment::systemEnvironment();
   env.remove("PYTHONHOME");
+  applyDisplayEnvironment(env, m_forceX11);
   for (auto it = m_wrapperEnvVars.cbegin(); it != m_wrapperEnvVars.cend(); ++it) {
     env.insert(it.key(), it.value());
   }
@@ -7,12 +7,32 @@
 #include <cstdint>
 #include <utility>
 
+// options for running a launch inside a gamescope session
+struct GamescopeOptions
+{
+  bool enabled = false;
+
+  // output resolution, gamescope picks one when either is 0
+  int width  = 0;
+  int height = 0;
+
+  bool fullscreen = false;
+
+  // upscaling filter passed to -F, such as "fsr" or "nis"; empty for
+  // gamescope's default
+  QString filter;
+};
+
 class ProtonLauncher
 {
 public:
   ProtonLauncher();
 
   ProtonLauncher& setBinary(const QString& path);
+
+  // the binary's path inside the prefix, passed to Proton and UMU instead of
+  // the host path when set
+  ProtonLauncher& setPrefixBinary(const QString& path);
   ProtonLauncher& setArguments(const QStringList& args);
   ProtonLauncher& setWorkingDir(const QString& dir);
   ProtonLauncher& setProtonPath(const QString& path);
@@ -22,6 +42,11 @@ public:
   ProtonLauncher& setUmu(bool useUmu);
   ProtonLauncher& setPreferSystemUmu(bool preferSystemUmu);
   ProtonLauncher& setUseSteamRun(bool useSteamRun);
+
+  // for tools that don't work under Wayland: hides WAYLAND_DISPLAY and asks
+  // the common toolkits to use X11
+  ProtonLauncher& setForceX11(bool forceX11);
+  ProtonLauncher& setGamescope(const GamescopeOptions& options);
   ProtonLauncher& addEnvVar(const QString& key, const QString& value);
 
   // Launch dispatch: UMU -> Proton -> Direct
@@ -33,7 +58,14 @@ private:
   bool launchDirect(qint64& pid) const;
   static bool ensureSteamRunning();
 
+  // the wrapper command with gamescope in front of it when enabled
+  QStringList wrapperCommands() const;
+
+  // the binary as programs in the prefix see it
+  QString prefixBinary() const;
+
   QString m_binary;
+  QString m_prefixBinary;
   QStringList m_arguments;
   QString m_workingDir;
   QString m_protonPath;
@@ -43,6 +75,8 @@ private:
   bool m_useUmu;
   bool m_preferSystemUmu;
   bool m_useSteamRun;
+  bool m_forceX11;
+  GamescopeOptions m_gamescope;
   QMap<QString, QString> m_envVars;
   QMap<QString, QString> m_wrapperEnvVars;
 };
@@ -5,8 +5,10 @@
 #include <iplugingame.h>
 #include <isavegameinfowidget.h>
 #include <localsavegames.h>
+#include <nak_ffi.h>
 #include <new>
 #include <report.h>
+#include <utility>
 
 using namespace MOBase;
 
@@ -47,6 +49,22 @@ bool isLikelyCorruptSaveText(QString const& in)
   }
 
   return suspicious > (in.size() / 8);
+}
+
+// level and location from the save's header, empty for games whose saves nak
+// can't read
+std::pair<QString, QString> readLevelAndLocation(QString const& savePath)
+{
+  NakSaveHeader header = nak_parse_save_header(savePath.toUtf8().constData());
+  std::pair<QString, QString> result;
+
+  if (header.error == nullptr) {
+    result.first  = QString::number(header.level);
+    result.second = sanitizeText(QString::fromUtf8(header.location));
+  }
+
+  nak_save_header_free(header);
+  return result;
 }
 }  // namespace
 
@@ -75,6 +93,10 @@ SavesTab::SavesTab(QWidget* window, OrganizerCore& core, Ui::MainWindow* mwui)
   connect(ui.list, &QTreeWidget::itemEntered, [&](auto* item) {
     saveSelectionChanged(item);
   });
+
+  connect(mwui->cleanCosavesButton, &QPushButton::clicked, [&] {
+    cleanOrphanedCosaves();
+  });
 }
 
 bool SavesTab::eventFilter(QObject* object, QEvent* e)
@@ -266,7 +288,11 @@ void SavesTab::refreshSaveList()
       if (display.trimmed().isEmpty() || isLikelyCorruptSaveText(rawName)) {
         display = sanitizeText(QFileInfo(save->getFilepath()).completeBaseName(), 300);
       }
-      ui.list->addTopLevelItem(new QTreeWidgetItem(ui.list, {display, relpath}));
+      const auto [level, location] = readLevelAndLocation(save->getFilepath());
+
+      auto* item = new QTreeWidgetItem(ui.list, {display, level, location, relpath});
+      item->setTextAlignment(1, Qt::AlignRight | Qt::AlignVCenter);
+      ui.list->addTopLevelItem(item);
     }
   } catch (std::exception& e) {
     // listSaves() can throw
@@ -391,3 +417,66 @@ void SavesTab::openInExplorer()
   auto& saveGame = m_SaveGames[sel[0].row()];
   shell::Explore(saveGame->getFilepath());
 }
+
+void SavesTab::cleanOrphanedCosaves()
+{
+  const QByteArray savesDir = currentSavesDir().absolutePath().toUtf8();
+
+  QStringList orphans;
+  NakCosaveList list = nak_find_orphaned_cosaves(savesDir.constData());
+  for (size_t i = 0; i < list.count; ++i) {
+    orphans.push_back(QString::fromUtf8(list.paths[i]));
+  }
+  nak_cosave_list_free(list);
+
+  if (orphans.isEmpty()) {
+    QMessageBox::information(m_window, tr("Clean orphaned co-saves"),
+                             tr("There are no co-saves without a save."));
+    return;
+  }
+
+  QString label;
+  for (int i = 0; i < orphans.size() && i < 10; ++i) {
+    label += "<li>" + QFileInfo(orphans[i]).fileName().toHtmlEscaped() + "</li>";
+  }
+
+  if (orphans.size() > 10) {
+    label += "<li><i>... " + tr("%1 more").arg(orphans.size() - 10) + "</i></li>";
+  }
+
+  if (QMessageBox::question(
+          m_window, tr("Confirm"),
+          tr("The saves of the following %n co-save(s) were deleted. Delete "
+             "them too?<br><ul>%1</ul>",
+             "", orphans.size())
+              .arg(label),
+          QMessageBox::Yes | QMessageBox::No) != QMessageBox::Yes) {
+    return;
+  }
+
+  // delete exactly what was confirmed, not whatever a new scan finds
+  std::vector<QByteArray> paths;
+  paths.reserve(orphans.size());
+  for (const QString& orphan : orphans) {
+    paths.push_back(orphan.toUtf8());
+  }
+
+  std::vector<const char*> pathPtrs;
+  pathPtrs.reserve(paths.size() + 1);
+  for (const auto& path : paths) {
+    pathPtrs.push_back(path.constData());
+  }
+  pathPtrs.push_back(nullptr);
+
+  size_t deleted = 0;
+  char* error    = nak_delete_cosaves(pathPtrs.data(), &deleted);
+
+  if (error != nullptr) {
+    reportError(tr("Failed to delete orphaned co-saves: %1").arg(QString::fromUtf8(error)));
+    nak_string_free(error);
+    return;
+  }
+
+  log::info("deleted {} orphaned co-saves from {}", deleted,
+            QString::fromUtf8(savesDir));
+}
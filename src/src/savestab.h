@@ -61,6 +61,7 @@ private:
   void fixMods(MOBase::SaveGameInfo::MissingAssets const& missingAssets);
   void refreshSavesIfOpen();
   void openInExplorer();
+  void cleanOrphanedCosaves();
 };
 
 #endif  // MODORGANIZER_SAVESTAB_INCLUDED
@@ -36,6 +36,10 @@ using namespace MOShared;
 
 namespace
 {
+// version of the Fluorine specific settings of an instance, bumped whenever
+// Settings::processFluorineUpdates() gets a new step
+constexpr int FluorineSettingsVersion = 1;
+
 bool usesBaseDirVariable(const QString& path)
 {
   return path.contains(PathSettings::BaseDirVariable, Qt::CaseInsensitive);
@@ -220,6 +224,56 @@ void Settings::processUpdates(const QVersionNumber& currentVersion,
   log::debug("updating done");
 }
 
+void Settings::processFluorineUpdates()
+{
+  const int lastVersion = get<int>(m_Settings, "fluorine", "settings_version", 0);
+  if (lastVersion >= FluorineSettingsVersion) {
+    return;
+  }
+
+  log::info("updating fluorine settings from version {} to {}", lastVersion,
+            FluorineSettingsVersion);
+
+  auto version = [&](int v, auto&& f) {
+    if (lastVersion < v) {
+      log::debug("processing fluorine updates for version {}", v);
+      f();
+    }
+  };
+
+  // moves the first of the old keys that is set to the new key, replacing
+  // what's there; the old keys were read first so their value is the one that
+  // was in effect, and they are removed either way
+  auto move = [&](const QString& key,
+                  std::initializer_list<std::pair<QString, QString>> oldKeys) {
+    bool moved = false;
+
+    for (auto&& [section, oldKey] : oldKeys) {
+      const auto value = getOptional<QString>(m_Settings, section, oldKey);
+
+      if (!moved && value && !value->trimmed().isEmpty()) {
+        log::debug("moving {}/{} to fluorine/{}", section, oldKey, key);
+        set(m_Settings, "fluorine", key, value->trimmed());
+        moved = true;
+      }
+
+      remove(m_Settings, section, oldKey);
+    }
+  };
+
+  version(1, [&] {
+    // older versions stored the prefix and Proton under several keys, the
+    // command line and the launcher only use the fluorine section now
+    move("prefix_path", {{"Settings", "proton_prefix_path"},
+                         {"Settings", "prefix_path"},
+                         {"Proton", "prefix_path"}});
+
+    move("proton_path", {{"Settings", "proton_path"}, {"Proton", "path"}});
+  });
+
+  set(m_Settings, "fluorine", "settings_version", FluorineSettingsVersion);
+}
+
 QString Settings::filename() const
 {
   return m_Settings.fileName();
@@ -375,6 +429,26 @@ void Settings::setSkipDirectories(const QStringList& s)
   set(m_Settings, "Settings", "skip_directories", s);
 }
 
+QStringList Settings::conflictExtensions() const
+{
+  return get<QStringList>(m_Settings, "Settings", "conflict_extensions", {});
+}
+
+void Settings::setConflictExtensions(const QStringList& s)
+{
+  set(m_Settings, "Settings", "conflict_extensions", s);
+}
+
+QStringList Settings::ignoredModFolders() const
+{
+  return get<QStringList>(m_Settings, "Settings", "ignored_mod_folders", {});
+}
+
+void Settings::setIgnoredModFolders(const QStringList& s)
+{
+  set(m_Settings, "Settings", "ignored_mod_folders", s);
+}
+
 void Settings::setMotdHash(uint hash)
 {
   set(m_Settings, "General", "motd_hash", hash);
@@ -395,6 +469,76 @@ void Settings::setArchiveParsing(bool b)
   set(m_Settings, "Settings", "archive_parsing_experimental", b);
 }
 
+bool Settings::compressStaging() const
+{
+  return get<bool>(m_Settings, "Settings", "compress_staging", false);
+}
+
+void Settings::setCompressStaging(bool b)
+{
+  set(m_Settings, "Settings", "compress_staging", b);
+}
+
+bool Settings::reviewStagedFiles() const
+{
+  return get<bool>(m_Settings, "Settings", "review_staged_files", false);
+}
+
+void Settings::setReviewStagedFiles(bool b)
+{
+  set(m_Settings, "Settings", "review_staged_files", b);
+}
+
+bool Settings::flushStagingPeriodically() const
+{
+  return get<bool>(m_Settings, "Settings", "flush_staging_periodically", false);
+}
+
+void Settings::setFlushStagingPeriodically(bool b)
+{
+  set(m_Settings, "Settings", "flush_staging_periodically", b);
+}
+
+int Settings::stagingFlushPolicy() const
+{
+  return get<int>(m_Settings, "Settings", "staging_flush_policy", 0);
+}
+
+void Settings::setStagingFlushPolicy(int policy)
+{
+  set(m_Settings, "Settings", "staging_flush_policy", policy);
+}
+
+bool Settings::vfsReadOnly() const
+{
+  return get<bool>(m_Settings, "Settings", "vfs_read_only", false);
+}
+
+void Settings::setVfsReadOnly(bool b)
+{
+  set(m_Settings, "Settings", "vfs_read_only", b);
+}
+
+int Settings::stagingUmask() const
+{
+  return get<int>(m_Settings, "Settings", "staging_umask", 022) & 0777;
+}
+
+void Settings::setStagingUmask(int mask)
+{
+  set(m_Settings, "Settings", "staging_umask", mask & 0777);
+}
+
+bool Settings::symlinkDeployment() const
+{
+  return get<bool>(m_Settings, "Settings", "symlink_deployment", false);
+}
+
+void Settings::setSymlinkDeployment(bool b)
+{
+  set(m_Settings, "Settings", "symlink_deployment", b);
+}
+
 std::vector<std::map<QString, QVariant>> Settings::executables() const
 {
   ScopedReadArray sra(m_Settings, "customExecutables");
@@ -449,6 +593,49 @@ void Settings::setExecutables(const std::vector<std::map<QString, QVariant>>& v)
   }
 }
 
+std::vector<std::map<QString, QVariant>> Settings::toolChains() const
+{
+  ScopedReadArray sra(m_Settings, "toolChains");
+  std::vector<std::map<QString, QVariant>> v;
+
+  sra.for_each([&] {
+    std::map<QString, QVariant> map;
+
+    for (auto&& key : sra.keys()) {
+      map[key] = sra.get<QVariant>(key);
+    }
+
+    v.push_back(map);
+  });
+
+  return v;
+}
+
+void Settings::setToolChains(const std::vector<std::map<QString, QVariant>>& v)
+{
+  const auto current = toolChains();
+
+  if (current == v) {
+    // no change
+    return;
+  }
+
+  if (current.size() > v.size()) {
+    // Qt can't remove array elements, the section must be cleared
+    removeSection(m_Settings, "toolChains");
+  }
+
+  ScopedWriteArray swa(m_Settings, "toolChains", v.size());
+
+  for (const auto& map : v) {
+    swa.next();
+
+    for (auto&& p : map) {
+      swa.set(p.first, p.second);
+    }
+  }
+}
+
 bool Settings::keepBackupOnInstall() const
 {
   return get<bool>(m_Settings, "General", "backup_install", false);
@@ -2026,6 +2213,16 @@ void NexusSettings::setCategoryMappings(bool b) const
   set(m_Settings, "Settings", "category_mappings", b);
 }
 
+bool NexusSettings::cleanModNames() const
+{
+  return get<bool>(m_Settings, "Settings", "clean_mod_names", true);
+}
+
+void NexusSettings::setCleanModNames(bool b) const
+{
+  set(m_Settings, "Settings", "clean_mod_names", b);
+}
+
 void NexusSettings::registerAsNXMHandler(bool force)
 {
 #ifndef _WIN32
@@ -2251,6 +2448,16 @@ void InterfaceSettings::setCollapsibleSeparatorsPerProfile(bool b)
   set(m_Settings, "Settings", "collapsible_separators_per_profile", b);
 }
 
+bool InterfaceSettings::collapseSeparatorsOnOpen() const
+{
+  return get<bool>(m_Settings, "Settings", "collapse_separators_on_open", false);
+}
+
+void InterfaceSettings::setCollapseSeparatorsOnOpen(bool b)
+{
+  set(m_Settings, "Settings", "collapse_separators_on_open", b);
+}
+
 bool InterfaceSettings::saveFilters() const
 {
   return get<bool>(m_Settings, "Settings", "save_filters", false);
@@ -524,6 +524,12 @@ public:
   bool categoryMappings() const;
   void setCategoryMappings(bool b) const;
 
+  // returns whether version numbers and Nexus suffixes should be stripped from
+  // mod names guessed from archive names
+  //
+  bool cleanModNames() const;
+  void setCleanModNames(bool b) const;
+
   // registers MO as the handler for nxm links
   //
   // if 'force' is true, the registration dialog will be shown even if the user
@@ -624,6 +630,12 @@ public:
   bool collapsibleSeparatorsPerProfile() const;
   void setCollapsibleSeparatorsPerProfile(bool b);
 
+  // whether all separators start collapsed when the instance is opened,
+  // instead of restoring the last expansion state
+  //
+  bool collapseSeparatorsOnOpen() const;
+  void setCollapseSeparatorsOnOpen(bool b);
+
   // whether to save/restore filter states between runs
   //
   bool saveFilters() const;
@@ -778,6 +790,11 @@ public:
   //
   void processUpdates(const QVersionNumber& current, const QVersionNumber& last);
 
+  // moves settings written by older Fluorine versions to where the current one
+  // expects them; runs on every instance load, does nothing once up to date
+  //
+  void processFluorineUpdates();
+
   // whether MO has been started for the first time
   //
   bool firstStart() const;
@@ -788,6 +805,11 @@ public:
   std::vector<std::map<QString, QVariant>> executables() const;
   void setExecutables(const std::vector<std::map<QString, QVariant>>& v);
 
+  // configured tool chains, each one a name and a list of executable titles
+  //
+  std::vector<std::map<QString, QVariant>> toolChains() const;
+  void setToolChains(const std::vector<std::map<QString, QVariant>>& v);
+
   // whether to backup existing mods on install
   //
   bool keepBackupOnInstall() const;
@@ -806,6 +828,20 @@ public:
   QStringList skipDirectories() const;
   void setSkipDirectories(const QStringList& s);
 
+  // file extensions considered when looking for conflicts between mods; plain
+  // entries limit the check to those extensions, entries starting with '!'
+  // are ignored, an empty list checks everything
+  //
+  QStringList conflictExtensions() const;
+  void setConflictExtensions(const QStringList& s);
+
+  // wildcard patterns, such as "*.disabled" or "_old*", for folders in the
+  // mods directory that are not listed as mods; matched case-insensitively
+  // against the folder name
+  //
+  QStringList ignoredModFolders() const;
+  void setIgnoredModFolders(const QStringList& s);
+
   // ? looks obsolete, only used by dead code
   //
   unsigned int motdHash() const;
@@ -816,6 +852,53 @@ public:
   bool archiveParsing() const;
   void setArchiveParsing(bool b);
 
+  // whether files written by programs running in the vfs should be compressed
+  // by btrfs while they sit in staging; this only sets the filesystem's
+  // compression attribute, so it has no effect on other filesystems
+  //
+  bool compressStaging() const;
+  void setCompressStaging(bool b);
+
+  // whether files written by programs running in the vfs should be listed when
+  // the program exits so the user can move them to a mod or discard them
+  // instead of sending everything to overwrite
+  //
+  bool reviewStagedFiles() const;
+  void setReviewStagedFiles(bool b);
+
+  // whether staged files that programs are done with should be moved to
+  // overwrite every few minutes while the vfs is mounted instead of all at
+  // once when it's unmounted; ignored when staged files are reviewed
+  //
+  bool flushStagingPeriodically() const;
+  void setFlushStagingPeriodically(bool b);
+
+  // what happens when a staged file is moved to a path in overwrite that
+  // already exists, one of FuseConnector::FlushPolicy
+  //
+  int stagingFlushPolicy() const;
+  void setStagingFlushPolicy(int policy);
+
+  // whether the vfs should be mounted read-only, so programs can't create,
+  // change or remove any file in the data directory and nothing ends up in
+  // overwrite
+  //
+  bool vfsReadOnly() const;
+  void setVfsReadOnly(bool b);
+
+  // permission bits removed from files and directories that programs create
+  // in the vfs, 022 by default; files changed by programs keep the mode of
+  // the original
+  //
+  int stagingUmask() const;
+  void setStagingUmask(int mask);
+
+  // whether mods are deployed as symlinks in the game's data directory instead
+  // of mounting the vfs over it, for systems where fuse isn't available
+  //
+  bool symlinkDeployment() const;
+  void setSymlinkDeployment(bool b);
+
   // whether the user wants to check for updates
   //
   bool checkForUpdates() const;
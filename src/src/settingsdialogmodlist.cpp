@@ -37,6 +37,8 @@ ModListSettingsTab::ModListSettingsTab(Settings& s, SettingsDialog& d)
       settings().interface().collapsibleSeparatorsHighlightTo());
   ui->collapsibleSeparatorsPerProfileBox->setChecked(
       settings().interface().collapsibleSeparatorsPerProfile());
+  ui->collapseSeparatorsOnOpenBox->setChecked(
+      settings().interface().collapseSeparatorsOnOpen());
   ui->saveFiltersBox->setChecked(settings().interface().saveFilters());
   ui->autoCollapseDelayBox->setChecked(settings().interface().autoCollapseOnHover());
   ui->checkUpdateInstallBox->setChecked(
@@ -61,6 +63,8 @@ void ModListSettingsTab::update()
       ui->collapsibleSeparatorsHighlightToBox->isChecked());
   settings().interface().setCollapsibleSeparatorsPerProfile(
       ui->collapsibleSeparatorsPerProfileBox->isChecked());
+  settings().interface().setCollapseSeparatorsOnOpen(
+      ui->collapseSeparatorsOnOpenBox->isChecked());
   settings().interface().setSaveFilters(ui->saveFiltersBox->isChecked());
   settings().interface().setAutoCollapseOnHover(ui->autoCollapseDelayBox->isChecked());
   settings().interface().setCheckUpdateAfterInstallation(
@@ -6,6 +6,7 @@
 
 #include <QtConcurrent/QtConcurrentRun>
 #include <log.h>
+#include <iplugingame.h>
 #include <nak_ffi.h>
 #include <atomic>
 #include <QComboBox>
@@ -26,6 +27,7 @@
 #include <QSettings>
 #include <QScopeGuard>
 #include <QStandardPaths>
+#include <QVariant>
 #include <QVBoxLayout>
 
 namespace
@@ -49,6 +51,31 @@ ProtonSettingsTab::ProtonSettingsTab(Settings& s, SettingsDialog& d)
   ui->launchWrapperEdit->setPlaceholderText("mangohud --dlsym");
   ui->launchWrapperEdit->setText(QSettings().value("fluorine/launch_wrapper").toString());
 
+  ui->gamescopeFilterCombo->addItem(tr("Default"), QString());
+  ui->gamescopeFilterCombo->addItem(tr("Linear"), QStringLiteral("linear"));
+  ui->gamescopeFilterCombo->addItem(tr("Nearest"), QStringLiteral("nearest"));
+  ui->gamescopeFilterCombo->addItem(tr("AMD FSR"), QStringLiteral("fsr"));
+  ui->gamescopeFilterCombo->addItem(tr("NVIDIA NIS"), QStringLiteral("nis"));
+  ui->gamescopeFilterCombo->addItem(tr("Pixel"), QStringLiteral("pixel"));
+
+  ui->gamescopeCheckBox->setChecked(
+      QSettings().value("fluorine/gamescope", false).toBool());
+  ui->gamescopeWidthSpin->setValue(
+      QSettings().value("fluorine/gamescope_width", 0).toInt());
+  ui->gamescopeHeightSpin->setValue(
+      QSettings().value("fluorine/gamescope_height", 0).toInt());
+  ui->gamescopeFullscreenCheckBox->setChecked(
+      QSettings().value("fluorine/gamescope_fullscreen", false).toBool());
+  const int filterIndex = ui->gamescopeFilterCombo->findData(
+      QSettings().value("fluorine/gamescope_filter").toString());
+  ui->gamescopeFilterCombo->setCurrentIndex(filterIndex >= 0 ? filterIndex : 0);
+
+  if (QStandardPaths::findExecutable("gamescope").isEmpty() &&
+      !QFileInfo::exists("/.flatpak-info")) {
+    ui->gamescopeCheckBox->setToolTip(
+        tr("gamescope was not found in PATH, launches will run without it."));
+  }
+
   populateProtons();
 
   QObject::connect(ui->protonVersionCombo, &QComboBox::currentIndexChanged, this,
@@ -101,6 +128,13 @@ void ProtonSettingsTab::update()
   QSettings().setValue("fluorine/use_steam_run",
                        ui->steamRunCheckBox->isChecked());
   QSettings().setValue("fluorine/launch_wrapper", ui->launchWrapperEdit->text());
+  QSettings().setValue("fluorine/gamescope", ui->gamescopeCheckBox->isChecked());
+  QSettings().setValue("fluorine/gamescope_width", ui->gamescopeWidthSpin->value());
+  QSettings().setValue("fluorine/gamescope_height", ui->gamescopeHeightSpin->value());
+  QSettings().setValue("fluorine/gamescope_fullscreen",
+                       ui->gamescopeFullscreenCheckBox->isChecked());
+  QSettings().setValue("fluorine/gamescope_filter",
+                       ui->gamescopeFilterCombo->currentData().toString());
 }
 
 void ProtonSettingsTab::populateProtons()
@@ -192,6 +226,10 @@ void ProtonSettingsTab::onCreatePrefix()
     return;
   }
 
+  if (!confirmProtonVersion(protonName)) {
+    return;
+  }
+
   const QString basePath = ui->prefixLocationEdit->text().trimmed();
   if (basePath.isEmpty()) {
     ui->protonStatusLabel->setText(tr("Select a prefix location first"));
@@ -210,7 +248,7 @@ void ProtonSettingsTab::onCreatePrefix()
   startInstallTask(0, pfxPath, protonName, protonPath,
                    ui->umuCheckBox->isChecked(),
                    ui->umuSystemCheckBox->isChecked(),
-                   ui->steamRunCheckBox->isChecked());
+                   ui->steamRunCheckBox->isChecked(), false);
 }
 
 void ProtonSettingsTab::onDeletePrefix()
@@ -245,6 +283,10 @@ void ProtonSettingsTab::onRecreatePrefix()
     return;
   }
 
+  if (!confirmProtonVersion(cfg->proton_name)) {
+    return;
+  }
+
   QDir prefixDir(cfg->prefix_path);
   if (prefixDir.exists() && !prefixDir.removeRecursively()) {
     ui->protonStatusLabel->setText(tr("Failed to delete existing prefix"));
@@ -258,7 +300,7 @@ void ProtonSettingsTab::onRecreatePrefix()
   startInstallTask(cfg->app_id, cfg->prefix_path, cfg->proton_name,
                    cfg->proton_path, ui->umuCheckBox->isChecked(),
                    ui->umuSystemCheckBox->isChecked(),
-                   ui->steamRunCheckBox->isChecked());
+                   ui->steamRunCheckBox->isChecked(), true);
 }
 
 void ProtonSettingsTab::onOpenPrefixFolder()
@@ -351,6 +393,32 @@ QString ProtonSettingsTab::findProtonWine(const QString& protonPath)
   return {};
 }
 
+bool ProtonSettingsTab::confirmProtonVersion(const QString& protonName)
+{
+  auto* game = qApp->property("managed_game").value<MOBase::IPluginGame*>();
+  if (game == nullptr || game->steamAPPId().isEmpty()) {
+    return true;
+  }
+
+  char* raw = nak_proton_warning(game->steamAPPId().toUtf8().constData(),
+                                 protonName.toUtf8().constData());
+  if (raw == nullptr) {
+    return true;
+  }
+
+  const QString warning = QString::fromUtf8(raw);
+  nak_string_free(raw);
+
+  const auto answer = QMessageBox::warning(
+      parentWidget(), tr("Proton Version"),
+      tr("%1. The game may not start or may not work correctly.\n\n"
+         "Continue anyway?")
+          .arg(warning),
+      QMessageBox::Yes | QMessageBox::No, QMessageBox::No);
+
+  return answer == QMessageBox::Yes;
+}
+
 void ProtonSettingsTab::onWinetricks()
 {
   auto cfg = FluorineConfig::load();
@@ -579,7 +647,7 @@ void ProtonSettingsTab::startInstallTask(uint32_t appId, const QString& prefixPa
                                          const QString& protonPath,
                                          bool useUmuForPrefix,
                                          bool preferSystemUmu,
-                                         bool useSteamRun)
+                                         bool useSteamRun, bool force)
 {
   m_pendingAppId      = appId;
   m_pendingPrefixPath = prefixPath;
@@ -597,7 +665,8 @@ void ProtonSettingsTab::startInstallTask(uint32_t appId, const QString& prefixPa
       protonPath,
       useUmuForPrefix,
       preferSystemUmu,
-      useSteamRun]() -> InstallResult {
+      useSteamRun,
+      force]() -> InstallResult {
     const QByteArray prefixPathUtf8 = prefixPath.toUtf8();
     const QByteArray protonNameUtf8 = protonName.toUtf8();
     const QByteArray protonPathUtf8 = protonPath.toUtf8();
@@ -656,7 +725,7 @@ void ProtonSettingsTab::startInstallTask(uint32_t appId, const QString& prefixPa
         prefixPathUtf8.constData(), protonNameUtf8.constData(),
         protonPathUtf8.constData(), &ProtonSettingsTab::statusCallback,
         &ProtonSettingsTab::logCallback, &ProtonSettingsTab::progressCallback,
-        &cancelFlag, appId);
+        &cancelFlag, appId, force ? 1 : 0);
 
     InstallResult r;
     if (error != nullptr) {
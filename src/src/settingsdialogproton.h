@@ -38,10 +38,15 @@ private:
   QString ensureWinetricks();
   QString findProtonWine(const QString& protonPath);
 
+  // asks whether to go on when the managed game is known to need a newer
+  // Proton than the given one or to work better with another; true if it
+  // doesn't or the user wants to anyway
+  bool confirmProtonVersion(const QString& protonName);
+
   void startInstallTask(uint32_t appId, const QString& prefixPath,
                         const QString& protonName, const QString& protonPath,
                         bool useUmuForPrefix, bool preferSystemUmu,
-                        bool useSteamRun);
+                        bool useSteamRun, bool force);
 
   void enqueueStatus(const QString& message);
   void enqueueProgress(float progress);
@@ -13,6 +13,13 @@ WorkaroundsSettingsTab::WorkaroundsSettingsTab(Settings& s, SettingsDialog& d)
   ui->forceEnableBox->setChecked(settings().game().forceEnableCoreFiles());
   ui->lockGUIBox->setChecked(settings().interface().lockGUI());
   ui->enableArchiveParsingBox->setChecked(settings().archiveParsing());
+  ui->compressStagingBox->setChecked(settings().compressStaging());
+  ui->reviewStagedFilesBox->setChecked(settings().reviewStagedFiles());
+  ui->flushStagingBox->setChecked(settings().flushStagingPeriodically());
+  ui->stagingFlushPolicy->setCurrentIndex(settings().stagingFlushPolicy());
+  ui->vfsReadOnlyBox->setChecked(settings().vfsReadOnly());
+  ui->stagingUmask->setValue(settings().stagingUmask());
+  ui->symlinkDeploymentBox->setChecked(settings().symlinkDeployment());
 
   // steam
   QString username, password;
@@ -36,6 +43,8 @@ WorkaroundsSettingsTab::WorkaroundsSettingsTab(Settings& s, SettingsDialog& d)
   m_ExecutableBlacklist = settings().executablesBlacklist();
   m_SkipFileSuffixes    = settings().skipFileSuffixes();
   m_SkipDirectories     = settings().skipDirectories();
+  m_ConflictExtensions  = settings().conflictExtensions();
+  m_IgnoredModFolders   = settings().ignoredModFolders();
 
   QObject::connect(ui->bsaDateBtn, &QPushButton::clicked, [&] {
     on_bsaDateBtn_clicked();
@@ -49,6 +58,12 @@ WorkaroundsSettingsTab::WorkaroundsSettingsTab(Settings& s, SettingsDialog& d)
   QObject::connect(ui->skipDirectoriesBtn, &QPushButton::clicked, [&] {
     on_skipDirectoriesBtn_clicked();
   });
+  QObject::connect(ui->conflictExtensionsBtn, &QPushButton::clicked, [&] {
+    on_conflictExtensionsBtn_clicked();
+  });
+  QObject::connect(ui->ignoredModFoldersBtn, &QPushButton::clicked, [&] {
+    on_ignoredModFoldersBtn_clicked();
+  });
   QObject::connect(ui->resetGeometryBtn, &QPushButton::clicked, [&] {
     on_resetGeometryBtn_clicked();
   });
@@ -60,6 +75,13 @@ void WorkaroundsSettingsTab::update()
   settings().game().setForceEnableCoreFiles(ui->forceEnableBox->isChecked());
   settings().interface().setLockGUI(ui->lockGUIBox->isChecked());
   settings().setArchiveParsing(ui->enableArchiveParsingBox->isChecked());
+  settings().setCompressStaging(ui->compressStagingBox->isChecked());
+  settings().setReviewStagedFiles(ui->reviewStagedFilesBox->isChecked());
+  settings().setFlushStagingPeriodically(ui->flushStagingBox->isChecked());
+  settings().setStagingFlushPolicy(ui->stagingFlushPolicy->currentIndex());
+  settings().setVfsReadOnly(ui->vfsReadOnlyBox->isChecked());
+  settings().setStagingUmask(ui->stagingUmask->value());
+  settings().setSymlinkDeployment(ui->symlinkDeploymentBox->isChecked());
 
   // steam
   if (ui->appIDEdit->text() != settings().game().plugin()->steamAPPId()) {
@@ -79,6 +101,8 @@ void WorkaroundsSettingsTab::update()
   settings().setExecutablesBlacklist(m_ExecutableBlacklist);
   settings().setSkipFileSuffixes(m_SkipFileSuffixes);
   settings().setSkipDirectories(m_SkipDirectories);
+  settings().setConflictExtensions(m_ConflictExtensions);
+  settings().setIgnoredModFolders(m_IgnoredModFolders);
 }
 
 bool WorkaroundsSettingsTab::changeBlacklistNow(QWidget* parent, Settings& settings)
@@ -189,6 +213,70 @@ WorkaroundsSettingsTab::changeSkipDirectories(QWidget* parent,
   return directories;
 }
 
+std::optional<QStringList>
+WorkaroundsSettingsTab::changeConflictExtensions(QWidget* parent,
+                                                 const QStringList& current)
+{
+  bool ok = false;
+
+  QString result = QInputDialog::getMultiLineText(
+      parent, QObject::tr("Conflict Extensions"),
+      QObject::tr(
+          "Enter one file extension per line. Only files with these extensions are "
+          "considered when looking for conflicts between mods. Extensions starting "
+          "with ! are ignored instead. Leave empty to check all files.\n\n"
+          "Example:\n"
+          "  dds\n"
+          "  nif\n"
+          "  !txt"),
+      current.join("\n"), &ok);
+
+  if (!ok) {
+    return {};
+  }
+
+  QStringList extensions;
+  for (auto& ext : result.split("\n")) {
+    auto trimmed = ext.trimmed();
+    if (!trimmed.isEmpty()) {
+      extensions << trimmed;
+    }
+  }
+
+  return extensions;
+}
+
+std::optional<QStringList>
+WorkaroundsSettingsTab::changeIgnoredModFolders(QWidget* parent,
+                                                const QStringList& current)
+{
+  bool ok = false;
+
+  QString result = QInputDialog::getMultiLineText(
+      parent, QObject::tr("Ignored Mod Folders"),
+      QObject::tr("Enter one folder name per line. Folders in the mods directory "
+                  "matching any of these are not listed as mods. * and ? can be "
+                  "used as wildcards, case is ignored.\n\n"
+                  "Example:\n"
+                  "  *.disabled\n"
+                  "  _old*"),
+      current.join("\n"), &ok);
+
+  if (!ok) {
+    return {};
+  }
+
+  QStringList patterns;
+  for (auto& pattern : result.split("\n")) {
+    auto trimmed = pattern.trimmed();
+    if (!trimmed.isEmpty()) {
+      patterns << trimmed;
+    }
+  }
+
+  return patterns;
+}
+
 void WorkaroundsSettingsTab::on_execBlacklistBtn_clicked()
 {
   if (auto s = changeBlacklistLater(parentWidget(), m_ExecutableBlacklist)) {
@@ -210,6 +298,20 @@ void WorkaroundsSettingsTab::on_skipDirectoriesBtn_clicked()
   }
 }
 
+void WorkaroundsSettingsTab::on_conflictExtensionsBtn_clicked()
+{
+  if (auto s = changeConflictExtensions(parentWidget(), m_ConflictExtensions)) {
+    m_ConflictExtensions = *s;
+  }
+}
+
+void WorkaroundsSettingsTab::on_ignoredModFoldersBtn_clicked()
+{
+  if (auto s = changeIgnoredModFolders(parentWidget(), m_IgnoredModFolders)) {
+    m_IgnoredModFolders = *s;
+  }
+}
+
 void WorkaroundsSettingsTab::on_bsaDateBtn_clicked()
 {
   const auto* game = qApp->property("managed_game").value<MOBase::IPluginGame*>();
@@ -32,17 +32,33 @@ public:
   static std::optional<QStringList> changeSkipDirectories(QWidget* parent,
                                                           const QStringList& current);
 
+  // shows the conflict extensions dialog from the given list and returns the
+  // new list if the user accepted it
+  //
+  static std::optional<QStringList>
+  changeConflictExtensions(QWidget* parent, const QStringList& current);
+
+  // shows the ignored mod folders dialog from the given list and returns the
+  // new list if the user accepted it
+  //
+  static std::optional<QStringList>
+  changeIgnoredModFolders(QWidget* parent, const QStringList& current);
+
   void update();
 
 private:
   QString m_ExecutableBlacklist;
   QStringList m_SkipFileSuffixes;
   QStringList m_SkipDirectories;
+  QStringList m_ConflictExtensions;
+  QStringList m_IgnoredModFolders;
 
   void on_bsaDateBtn_clicked();
   void on_execBlacklistBtn_clicked();
   void on_skipFileSuffixBtn_clicked();
   void on_skipDirectoriesBtn_clicked();
+  void on_conflictExtensionsBtn_clicked();
+  void on_ignoredModFoldersBtn_clicked();
   void on_resetGeometryBtn_clicked();
 };
 
@@ -27,6 +27,7 @@ along with Mod Organizer.  If not, see <http://www.gnu.org/licenses/>.
 #include "settingsdialogworkarounds.h"
 #include "shared/appconfig.h"
 #include <QApplication>
+#include <QFile>
 #include <QMessageBox>
 #include <QSettings>
 #include <QtDebug>
@@ -44,6 +45,8 @@ along with Mod Organizer.  If not, see <http://www.gnu.org/licenses/>.
 #else
 #include <QProcess>
 #include <QStandardPaths>
+#include <iplugingame.h>
+#include <nak_ffi.h>
 #include <cerrno>
 #include <cstring>
 #include <signal.h>
@@ -467,6 +470,82 @@ confirmBlacklisted(QWidget* parent, const SpawnParameters& sp, Settings& setting
   return r;
 }
 
+QMessageBox::StandardButton confirmArchitectureMismatch(QWidget* parent,
+                                                        const SpawnParameters& sp,
+                                                        const QString& details)
+{
+  const auto title    = QObject::tr("Architecture mismatch");
+  const auto mainText = QObject::tr("The program %1 is 64-bit but the Wine prefix "
+                                    "is 32-bit")
+                            .arg(sp.binary.fileName());
+  const auto content = QObject::tr(
+      "A 32-bit (win32) prefix can't start 64-bit programs, Wine only reports a "
+      "bad executable format. Recreate the prefix as a 64-bit (win64) one to "
+      "run this program.");
+
+  return MOBase::TaskDialog(parent, title)
+      .main(mainText)
+      .content(content)
+      .details(details)
+      .icon(QMessageBox::Warning)
+      .remember("architectureMismatch", sp.binary.fileName())
+      .button({QObject::tr("Continue"), QObject::tr("The program might fail to run."),
+               QMessageBox::Yes})
+      .button({QObject::tr("Cancel"), QMessageBox::Cancel})
+      .exec();
+}
+
+QMessageBox::StandardButton confirmMissingSteamAppId(QWidget* parent,
+                                                     const SpawnParameters& sp,
+                                                     const QString& details)
+{
+  const auto title    = QObject::tr("Missing Steam app ID");
+  const auto mainText = QObject::tr("No Steam app ID is set for %1")
+                            .arg(sp.binary.fileName());
+  const auto content  = QObject::tr(
+      "The game uses the Steam API but no Steam app ID could be found for it. "
+      "Proton needs the app ID for the game's DRM and the game will probably "
+      "fail to start.\r\n\r\n"
+      "Set the app ID in the Executables dialog with \"Overwrite Steam AppID\", "
+      "or for all executables in the Workarounds tab of the settings.");
+
+  return MOBase::TaskDialog(parent, title)
+      .main(mainText)
+      .content(content)
+      .details(details)
+      .icon(QMessageBox::Warning)
+      .remember("missingSteamAppId", sp.binary.fileName())
+      .button({QObject::tr("Continue"), QObject::tr("The program might fail to run."),
+               QMessageBox::Yes})
+      .button({QObject::tr("Cancel"), QMessageBox::Cancel})
+      .exec();
+}
+
+QMessageBox::StandardButton confirmProtonVersion(QWidget* parent,
+                                                 const SpawnParameters& sp,
+                                                 const QString& protonName,
+                                                 const QString& warning)
+{
+  const auto title    = QObject::tr("Proton version");
+  const auto mainText = QObject::tr("%1 might not work with %2")
+                            .arg(sp.binary.fileName())
+                            .arg(protonName);
+  const auto content  = QObject::tr(
+      "%1.\r\n\r\n"
+      "Another Proton version can be selected in the Proton tab of the settings.")
+                           .arg(warning);
+
+  return MOBase::TaskDialog(parent, title)
+      .main(mainText)
+      .content(content)
+      .icon(QMessageBox::Warning)
+      .remember("protonVersion", protonName)
+      .button({QObject::tr("Continue"), QObject::tr("The program might fail to run."),
+               QMessageBox::Yes})
+      .button({QObject::tr("Cancel"), QMessageBox::Cancel})
+      .exec();
+}
+
 }  // namespace spawn::dialogs
 
 namespace spawn
@@ -503,18 +582,6 @@ uint32_t parseSteamAppId(const QString& steamAppId)
   return (ok ? n : 0u);
 }
 
-QString firstExistingSetting(const QSettings& settings, const QStringList& keys)
-{
-  for (const QString& key : keys) {
-    const QString value = settings.value(key).toString().trimmed();
-    if (!value.isEmpty()) {
-      return value;
-    }
-  }
-
-  return {};
-}
-
 QString resolvePrefixPath()
 {
   if (auto cfg = FluorineConfig::load(); cfg.has_value() && cfg->prefixExists()) {
@@ -527,9 +594,33 @@ QString resolvePrefixPath()
   }
 
   const QSettings instanceSettings(settings->filename(), QSettings::IniFormat);
-  return firstExistingSetting(
-      instanceSettings, {"Settings/proton_prefix_path", "Settings/prefix_path",
-                         "Proton/prefix_path", "fluorine/prefix_path"});
+  return instanceSettings.value("fluorine/prefix_path").toString().trimmed();
+}
+
+// wine only sees host paths through the drive letters of the prefix, so makes
+// sure the given path is covered by one, mapping Z: to / if needed; returns
+// the windows path, or an empty string if the path can't be reached
+//
+QString ensureReachableFromPrefix(const QString& prefixPath, const QString& path)
+{
+  if (path.isEmpty()) {
+    return {};
+  }
+
+  char* raw = nak_ensure_prefix_windows_path(prefixPath.toUtf8().constData(),
+                                             path.toUtf8().constData());
+  if (raw == nullptr) {
+    MOBase::log::warn("'{}' is not reachable from the wine prefix '{}', programs "
+                      "will not be able to open it",
+                      path, prefixPath);
+    return {};
+  }
+
+  const QString windowsPath = QString::fromUtf8(raw);
+  nak_string_free(raw);
+
+  MOBase::log::debug("'{}' is '{}' in the prefix", path, windowsPath);
+  return windowsPath;
 }
 
 QString resolveProtonPath()
@@ -547,10 +638,9 @@ QString resolveProtonPath()
   }
 
   const QSettings instanceSettings(settings->filename(), QSettings::IniFormat);
-  return firstExistingSetting(instanceSettings,
-                              {"Settings/proton_path", "Proton/path",
-                               "fluorine/proton_path"});
+  return instanceSettings.value("fluorine/proton_path").toString().trimmed();
 }
+
 #endif
 
 #ifdef _WIN32
@@ -643,7 +733,8 @@ int spawn(const SpawnParameters& sp, pid_t& processId)
       .setPreferSystemUmu(
           QSettings().value("fluorine/prefer_system_umu", false).toBool())
       .setUseSteamRun(
-          QSettings().value("fluorine/use_steam_run", false).toBool());
+          QSettings().value("fluorine/use_steam_run", false).toBool())
+      .setForceX11(sp.forceX11);
 
   const QString prefixPath = resolvePrefixPath();
   if (prefixPath.isEmpty()) {
@@ -654,6 +745,18 @@ int spawn(const SpawnParameters& sp, pid_t& processId)
   } else {
     MOBase::log::info("Using Wine prefix: {}", prefixPath);
     launcher.setPrefix(prefixPath);
+
+    // the binary, its working directory and the data directory the vfs is
+    // mounted over must all have a windows path in the prefix; the binary is
+    // passed by that path, wine translates the working directory itself
+    launcher.setPrefixBinary(ensureReachableFromPrefix(prefixPath, bin));
+    ensureReachableFromPrefix(prefixPath, cwd);
+
+    if (Settings* settings = Settings::maybeInstance()) {
+      if (const auto* game = settings->game().plugin()) {
+        ensureReachableFromPrefix(prefixPath, game->dataDirectory().absolutePath());
+      }
+    }
   }
 
   const QString protonPath = resolveProtonPath();
@@ -666,6 +769,16 @@ int spawn(const SpawnParameters& sp, pid_t& processId)
     launcher.setWrapper(wrapper);
   }
 
+  const QSettings fluorineSettings;
+  GamescopeOptions gamescope;
+  gamescope.enabled = fluorineSettings.value("fluorine/gamescope", false).toBool();
+  gamescope.width   = fluorineSettings.value("fluorine/gamescope_width", 0).toInt();
+  gamescope.height  = fluorineSettings.value("fluorine/gamescope_height", 0).toInt();
+  gamescope.fullscreen =
+      fluorineSettings.value("fluorine/gamescope_fullscreen", false).toBool();
+  gamescope.filter = fluorineSettings.value("fluorine/gamescope_filter").toString();
+  launcher.setGamescope(gamescope);
+
   const auto [ok, pid] = launcher.launch();
   if (!ok) {
     return (errno != 0 ? errno : EIO);
@@ -973,6 +1086,117 @@ bool checkBlacklist(QWidget* parent, const SpawnParameters& sp, Settings& settin
   }
 }
 
+bool checkArchitecture(QWidget* parent, const SpawnParameters& sp)
+{
+#ifdef _WIN32
+  // 64-bit windows runs both
+  Q_UNUSED(parent);
+  Q_UNUSED(sp);
+  return true;
+#else
+  const QString prefixPath = resolvePrefixPath();
+  if (prefixPath.isEmpty()) {
+    return true;
+  }
+
+  // a win64 prefix runs 32-bit programs through wow64, so only a 64-bit
+  // program in a win32 prefix can't start
+  const QByteArray binary = sp.binary.absoluteFilePath().toUtf8();
+  char* raw = nak_prefix_arch_mismatch(prefixPath.toUtf8().constData(), binary.constData());
+  if (raw == nullptr) {
+    return true;
+  }
+
+  const QString details = QString::fromUtf8(raw);
+  nak_string_free(raw);
+
+  log::warn("{}", details);
+
+  return (dialogs::confirmArchitectureMismatch(parent, sp, details) ==
+          QMessageBox::Yes);
+#endif
+}
+
+bool checkSteamAppId(QWidget* parent, const SpawnParameters& sp,
+                     const QDir& gameDirectory, const Settings& settings)
+{
+#ifdef _WIN32
+  // steam provides the app id itself
+  return true;
+#else
+  // the game runs through Proton, so look for the Windows libraries
+  static const std::vector<QString> steamFiles = {"steam_api.dll", "steam_api64.dll"};
+
+  if (parseSteamAppId(sp.steamAppID.trimmed()) != 0 ||
+      parseSteamAppId(settings.steam().appID().trimmed()) != 0) {
+    return true;
+  }
+
+  QString steamApi;
+  for (const auto& file : steamFiles) {
+    const QFileInfo fi(gameDirectory.absoluteFilePath(file));
+    if (fi.exists()) {
+      steamApi = fi.absoluteFilePath();
+      break;
+    }
+  }
+
+  if (steamApi.isEmpty()) {
+    return true;
+  }
+
+  const auto details = QString("Executable: %1\nSteam API: %2\nSteam app ID: '%3'")
+                           .arg(sp.binary.absoluteFilePath())
+                           .arg(steamApi)
+                           .arg(sp.steamAppID);
+
+  log::warn("game uses the steam api at '{}' but no steam app id is set for '{}'",
+            steamApi, sp.binary.fileName());
+
+  return (dialogs::confirmMissingSteamAppId(parent, sp, details) == QMessageBox::Yes);
+#endif
+}
+
+bool checkProtonVersion(QWidget* parent, const SpawnParameters& sp)
+{
+#ifdef _WIN32
+  // nothing runs through Proton
+  return true;
+#else
+  const Settings* settings = Settings::maybeInstance();
+  if (settings == nullptr || settings->game().plugin() == nullptr) {
+    return true;
+  }
+
+  QString protonName;
+  if (auto cfg = FluorineConfig::load(); cfg.has_value()) {
+    protonName = cfg->proton_name.trimmed();
+  }
+  if (protonName.isEmpty()) {
+    protonName = QFileInfo(resolveProtonPath()).fileName();
+  }
+
+  const QString appId = settings->game().plugin()->steamAPPId();
+  if (appId.isEmpty() || protonName.isEmpty()) {
+    return true;
+  }
+
+  char* raw = nak_proton_warning(appId.toUtf8().constData(),
+                                 protonName.toUtf8().constData());
+  if (raw == nullptr) {
+    return true;
+  }
+
+  const QString warning = QString::fromUtf8(raw);
+  nak_string_free(raw);
+
+  log::warn("{}", warning);
+
+  return (dialogs::confirmProtonVersion(parent, sp, protonName, warning) ==
+          QMessageBox::Yes);
+#endif
+}
+
 #ifdef _WIN32
 HANDLE startBinary(QWidget* parent, const SpawnParameters& sp)
 {
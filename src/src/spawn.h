@@ -55,7 +55,11 @@ struct SpawnParameters
   QString arguments;
   QDir currentDirectory;
   QString steamAppID;
-  bool hooked = false;
+  bool hooked   = false;
+  bool forceX11 = false;
+
+  // arguments pointing into a mod are changed to the data directory
+  bool redirectModPaths = false;
 #ifdef _WIN32
   HANDLE stdOut = INVALID_HANDLE_VALUE;
   HANDLE stdErr = INVALID_HANDLE_VALUE;
@@ -70,6 +74,24 @@ bool checkSteam(QWidget* parent, const SpawnParameters& sp, const QDir& gameDire
 
 bool checkBlacklist(QWidget* parent, const SpawnParameters& sp, Settings& settings);
 
+// warns if the binary is a 64-bit program and the wine prefix is win32, which
+// can't start it; 32-bit programs run in any prefix; returns false if the user
+// cancelled
+//
+bool checkArchitecture(QWidget* parent, const SpawnParameters& sp);
+
+// warns if the game uses the Steam API but no app id can be resolved for the
+// launch; Proton needs it for the game's DRM and the game usually fails to
+// start without it; returns false if the user cancelled
+//
+bool checkSteamAppId(QWidget* parent, const SpawnParameters& sp,
+                     const QDir& gameDirectory, const Settings& settings);
+
+// warns if the selected Proton is older than the game needs or isn't the
+// build recommended for it; returns false if the user cancelled
+//
+bool checkProtonVersion(QWidget* parent, const SpawnParameters& sp);
+
 /**
  * @brief spawn a binary with Mod Organizer injected
  * @return the process handle
@@ -0,0 +1,109 @@
+#include "stagingreviewdialog.h"
+#include "settings.h"
+#include "ui_stagingreviewdialog.h"
+
+#include <QComboBox>
+#include <QPushButton>
+
+namespace
+{
+
+// index of the mod in m_Mods, or one of these for the other destinations
+constexpr int OverwriteIndex = -1;
+constexpr int DiscardIndex   = -2;
+
+}  // namespace
+
+StagingReviewDialog::StagingReviewDialog(const QStringList& files,
+                                         std::vector<std::pair<QString, QString>> mods,
+                                         QWidget* parent)
+    : QDialog(parent), ui(new Ui::StagingReviewDialog), m_Mods(std::move(mods))
+{
+  ui->setupUi(this);
+  ui->buttonBox->button(QDialogButtonBox::Ok)->setText(tr("Move Files"));
+
+  for (const QString& file : files) {
+    auto* item = new QTreeWidgetItem({file});
+    ui->files->addTopLevelItem(item);
+
+    auto* combo = new QComboBox(ui->files);
+    fillDestinations(combo);
+    ui->files->setItemWidget(item, 1, combo);
+  }
+
+  ui->files->resizeColumnToContents(0);
+  fillDestinations(ui->selectionDestination);
+
+  connect(ui->applyToSelection, &QPushButton::clicked, [&] {
+    applyToSelection();
+  });
+}
+
+StagingReviewDialog::~StagingReviewDialog()
+{
+  delete ui;
+}
+
+int StagingReviewDialog::exec()
+{
+  GeometrySaver gs(Settings::instance(), this);
+  return QDialog::exec();
+}
+
+QHash<QString, FuseConnector::StagingRoute> StagingReviewDialog::routes() const
+{
+  QHash<QString, FuseConnector::StagingRoute> map;
+
+  for (int i = 0; i < ui->files->topLevelItemCount(); ++i) {
+    auto* item  = ui->files->topLevelItem(i);
+    auto* combo = qobject_cast<QComboBox*>(ui->files->itemWidget(item, 1));
+    if (combo != nullptr) {
+      map.insert(item->text(0), routeFor(combo));
+    }
+  }
+
+  return map;
+}
+
+void StagingReviewDialog::fillDestinations(QComboBox* combo) const
+{
+  combo->addItem(tr("Overwrite"), OverwriteIndex);
+  combo->addItem(tr("Discard"), DiscardIndex);
+
+  if (!m_Mods.empty()) {
+    combo->insertSeparator(combo->count());
+  }
+
+  for (std::size_t i = 0; i < m_Mods.size(); ++i) {
+    combo->addItem(m_Mods[i].first, static_cast<int>(i));
+  }
+}
+
+FuseConnector::StagingRoute StagingReviewDialog::routeFor(const QComboBox* combo) const
+{
+  using Destination = FuseConnector::StagingRoute::Destination;
+
+  FuseConnector::StagingRoute route;
+  const int index = combo->currentData().toInt();
+
+  if (index == DiscardIndex) {
+    route.destination = Destination::Discard;
+  } else if (index >= 0 && static_cast<std::size_t>(index) < m_Mods.size()) {
+    route.destination = Destination::Mod;
+    route.modPath     = m_Mods[index].second;
+  }
+
+  return route;
+}
+
+void StagingReviewDialog::applyToSelection()
+{
+  const int index = ui->selectionDestination->currentIndex();
+
+  for (auto* item : ui->files->selectedItems()) {
+    auto* combo = qobject_cast<QComboBox*>(ui->files->itemWidget(item, 1));
+    if (combo != nullptr) {
+      combo->setCurrentIndex(index);
+    }
+  }
+}
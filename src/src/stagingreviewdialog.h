@@ -0,0 +1,48 @@
+#ifndef STAGINGREVIEWDIALOG_H
+#define STAGINGREVIEWDIALOG_H
+
+#include "fuseconnector.h"
+#include <QDialog>
+#include <QHash>
+
+namespace Ui
+{
+class StagingReviewDialog;
+}
+
+class QComboBox;
+
+// lists the files a program left in staging when the vfs is unmounted and
+// lets the user send each one to overwrite, into a mod or discard it
+//
+class StagingReviewDialog : public QDialog
+{
+  Q_OBJECT
+
+public:
+  // files are relative to the data directory, mods are (name, absolute path)
+  // pairs offered as destinations
+  //
+  StagingReviewDialog(const QStringList& files,
+                      std::vector<std::pair<QString, QString>> mods,
+                      QWidget* parent = nullptr);
+  ~StagingReviewDialog();
+
+  // also saves and restores geometry
+  //
+  int exec() override;
+
+  // destination picked for every file, keyed by the relative path
+  //
+  QHash<QString, FuseConnector::StagingRoute> routes() const;
+
+private:
+  Ui::StagingReviewDialog* ui;
+  std::vector<std::pair<QString, QString>> m_Mods;
+
+  void fillDestinations(QComboBox* combo) const;
+  FuseConnector::StagingRoute routeFor(const QComboBox* combo) const;
+  void applyToSelection();
+};
+
+#endif  // STAGINGREVIEWDIALOG_H
@@ -0,0 +1,226 @@
+#include "supportsummary.h"
+#include "fluorineconfig.h"
+#include "game_features.h"
+#include "gameplugins.h"
+#include "modinfo.h"
+#include "organizercore.h"
+#include "plugincontainer.h"
+#include "pluginlist.h"
+#include "profile.h"
+
+#include <QDir>
+#include <QFileInfo>
+#include <QRegularExpression>
+#include <QTextDocument>
+
+#include <uibase/iplugindiagnose.h>
+#include <uibase/iplugingame.h>
+
+using namespace MOBase;
+
+namespace
+{
+
+QString redactHome(QString s)
+{
+  const QString home = QDir::cleanPath(QDir::homePath());
+  if (home.isEmpty() || home == "/") {
+    return s;
+  }
+
+  // only whole path components, so /home/al doesn't rewrite /home/alice; the
+  // summary is one entry per line, so the end of a line ends a path too
+  const QRegularExpression re(QRegularExpression::escape(home) + "(?=/|$)",
+                              QRegularExpression::MultilineOption);
+
+  return s.replace(re, "~");
+}
+
+#ifndef _WIN32
+QString mountStateName(FuseConnector::MountState s)
+{
+  switch (s) {
+  case FuseConnector::MountState::Unmounted:
+    return "unmounted";
+  case FuseConnector::MountState::Mounting:
+    return "mounting";
+  case FuseConnector::MountState::Mounted:
+    return "mounted";
+  case FuseConnector::MountState::Stale:
+    return "stale";
+  default:
+    return "unknown";
+  }
+}
+#endif
+
+// diagnose plugins return rich text, the summary is plain markdown
+//
+QString plainText(const QString& html)
+{
+  QTextDocument doc;
+  doc.setHtml(html);
+  return doc.toPlainText().simplified();
+}
+
+}  // namespace
+
+QString supportSummary(OrganizerCore& core, PluginContainer& plugins)
+{
+  QStringList lines;
+  QStringList problems;
+
+  const IPluginGame* game = core.managedGame();
+
+  lines << "## Instance";
+  lines << QString("- Version: %1").arg(core.getVersion().string());
+
+  if (game != nullptr) {
+    lines << QString("- Game: %1").arg(game->displayGameName());
+    lines << QString("- Game version: %1").arg(game->gameVersion());
+    lines << QString("- Game directory: %1")
+                 .arg(game->gameDirectory().absolutePath());
+
+    const QString dataDir = game->dataDirectory().absolutePath();
+    lines << QString("- Data directory: %1").arg(dataDir);
+
+    const QFileInfo dataInfo(dataDir);
+    if (!dataInfo.exists()) {
+      problems << QString("Data directory does not exist: %1").arg(dataDir);
+    } else if (!dataInfo.isDir()) {
+      problems << QString("Data directory is not a directory: %1").arg(dataDir);
+    }
+  } else {
+    lines << "- Game: none";
+  }
+
+  if (auto profile = core.currentProfile()) {
+    lines << QString("- Profile: %1").arg(profile->name());
+  }
+
+  lines << "";
+  lines << "## Proton";
+
+  if (auto cfg = FluorineConfig::load()) {
+    lines << QString("- Proton: %1").arg(cfg->proton_name);
+    lines << QString("- Prefix: %1").arg(cfg->prefix_path);
+    if (!cfg->prefixExists()) {
+      problems << QString("Wine prefix does not exist: %1").arg(cfg->prefix_path);
+    }
+  } else {
+    lines << "- Not configured";
+  }
+
+#ifndef _WIN32
+  lines << QString("- Mount state: %1").arg(mountStateName(core.vfsMountState()));
+#endif
+
+  int mods        = 0;
+  int enabledMods = 0;
+  int separators  = 0;
+
+  auto profile = core.currentProfile();
+  for (unsigned int i = 0; i < ModInfo::getNumMods(); ++i) {
+    auto mod = ModInfo::getByIndex(i);
+    if (mod->isOverwrite() || mod->isForeign() || mod->isBackup()) {
+      continue;
+    }
+
+    if (mod->isSeparator()) {
+      ++separators;
+      continue;
+    }
+
+    ++mods;
+    if (profile && profile->modEnabled(i)) {
+      ++enabledMods;
+    }
+  }
+
+  lines << "";
+  lines << "## Mods";
+  lines << QString("- Installed: %1").arg(mods);
+  lines << QString("- Enabled: %1").arg(enabledMods);
+  lines << QString("- Separators: %1").arg(separators);
+
+  auto gamePlugins = core.gameFeatures().gameFeature<GamePlugins>();
+  const bool lightSupported =
+      gamePlugins ? gamePlugins->lightPluginsAreSupported() : false;
+  const bool mediumSupported =
+      gamePlugins ? gamePlugins->mediumPluginsAreSupported() : false;
+
+  // 0xFE and 0xFD are taken by light and medium plugins when supported
+  const int fullLimit = mediumSupported ? 253 : (lightSupported ? 254 : 255);
+
+  const PluginList* pluginList = core.pluginList();
+  const QStringList pluginNames = pluginList->pluginNames();
+
+  int full   = 0;
+  int light  = 0;
+  int medium = 0;
+
+  for (const QString& name : pluginNames) {
+    if (pluginList->state(name) != IPluginList::STATE_ACTIVE) {
+      continue;
+    }
+
+    if (mediumSupported && pluginList->isMediumFlagged(name)) {
+      ++medium;
+    } else if (lightSupported && (pluginList->hasLightExtension(name) ||
+                                  pluginList->isLightFlagged(name))) {
+      ++light;
+    } else {
+      ++full;
+    }
+
+    const QStringList missing = pluginList->missingMasters(name);
+    if (!missing.isEmpty()) {
+      problems << QString("%1 is missing masters: %2").arg(name).arg(missing.join(", "));
+    }
+  }
+
+  lines << "";
+  lines << "## Plugins";
+  lines << QString("- Installed: %1").arg(pluginNames.size());
+  lines << QString("- Full slots: %1 / %2").arg(full).arg(fullLimit);
+  if (lightSupported) {
+    lines << QString("- Light slots: %1 / 4096").arg(light);
+  }
+  if (mediumSupported) {
+    lines << QString("- Medium slots: %1 / 256").arg(medium);
+  }
+
+  if (full > fullLimit) {
+    problems << QString("Too many full plugins enabled (%1, limit is %2)")
+                    .arg(full)
+                    .arg(fullLimit);
+  }
+
+  for (QObject* pluginObj : plugins.plugins<QObject>()) {
+    IPlugin* plugin = qobject_cast<IPlugin*>(pluginObj);
+    if (plugin != nullptr && !plugins.isEnabled(plugin)) {
+      continue;
+    }
+
+    IPluginDiagnose* diagnose = qobject_cast<IPluginDiagnose*>(pluginObj);
+    if (diagnose == nullptr) {
+      continue;
+    }
+
+    for (unsigned int key : diagnose->activeProblems()) {
+      problems << plainText(diagnose->shortDescription(key));
+    }
+  }
+
+  lines << "";
+  lines << "## Problems";
+  if (problems.isEmpty()) {
+    lines << "- None detected";
+  } else {
+    for (const QString& p : problems) {
+      lines << QString("- %1").arg(p);
+    }
+  }
+
+  return redactHome(lines.join("\n"));
+}
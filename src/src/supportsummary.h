@@ -0,0 +1,18 @@
+#ifndef SUPPORTSUMMARY_H
+#define SUPPORTSUMMARY_H
+
+#include <QString>
+
+class OrganizerCore;
+class PluginContainer;
+
+// builds a markdown summary of the current instance meant to be pasted into
+// bug reports: game, proton prefix, mod and plugin counts, mount state and any
+// problems that were detected
+//
+// paths under the user's home directory are shown relative to "~" so the
+// summary doesn't leak the user name
+//
+QString supportSummary(OrganizerCore& core, PluginContainer& plugins);
+
+#endif  // SUPPORTSUMMARY_H
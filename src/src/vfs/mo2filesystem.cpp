@@ -1,13 +1,16 @@
 #include "mo2filesystem.h"
 
 #include <fcntl.h>
+#include <sys/xattr.h>
 #include <unistd.h>
 
 #include <algorithm>
 #include <chrono>
 #include <cstring>
+#include <ctime>
 #include <filesystem>
 #include <fstream>
+#include <unordered_set>
 
 namespace
 {
@@ -172,6 +175,17 @@ bool isWritableOpen(int flags)
   return (flags & O_WRONLY) != 0 || (flags & O_RDWR) != 0;
 }
 
+// replies EROFS and returns true when the mount doesn't accept changes
+bool rejectReadOnly(fuse_req_t req, const Mo2FsContext* ctx)
+{
+  if (!ctx->read_only) {
+    return false;
+  }
+
+  fuse_reply_err(req, EROFS);
+  return true;
+}
+
 std::chrono::system_clock::time_point fileMtimeOrNow(const std::string& path)
 {
   std::error_code ec;
@@ -186,6 +200,46 @@ std::chrono::system_clock::time_point fileMtimeOrNow(const std::string& path)
                       mtime - nowFs);
 }
 
+bool isUserXattr(const char* name)
+{
+  return std::strncmp(name, "user.", 5) == 0;
+}
+
+// opens whatever currently backs a path so its attributes can be read;
+// directories only have attributes once they exist in staging
+int openForXattr(const Mo2FsContext* ctx, const std::string& path,
+                 const NodeSnapshot& snap)
+{
+  if (snap.is_directory) {
+    const std::string staged = ctx->overwrite->stagingPath(path);
+    return open(staged.c_str(), O_RDONLY | O_DIRECTORY);
+  }
+
+  if (snap.is_backing && ctx->backing_dir_fd >= 0) {
+    return openat(ctx->backing_dir_fd, path.c_str(), O_RDONLY);
+  }
+
+  return open(snap.real_path.c_str(), O_RDONLY);
+}
+
+void replyXattr(fuse_req_t req, size_t size, const char* data, size_t len)
+{
+  if (size == 0) {
+    fuse_reply_xattr(req, len);
+  } else if (len > size) {
+    fuse_reply_err(req, ERANGE);
+  } else {
+    fuse_reply_buf(req, data, len);
+  }
+}
+
+void notifyStagingChanged(const Mo2FsContext* ctx)
+{
+  if (ctx->on_staging_changed) {
+    ctx->on_staging_changed();
+  }
+}
+
 void updateFileNode(Mo2FsContext* ctx, const std::string& relative,
                     const std::string& realPath, const std::string& origin)
 {
@@ -193,9 +247,11 @@ void updateFileNode(Mo2FsContext* ctx, const std::string& relative,
   const uint64_t size = static_cast<uint64_t>(fs::file_size(realPath, ec));
   const auto mtime    = fileMtimeOrNow(realPath);
 
+  const auto components = splitPath(relative);
+
   std::unique_lock lock(ctx->tree_mutex);
-  ctx->tree->root.insertFile(splitPath(relative), realPath, ec ? 0 : size, mtime,
-                             origin);
+  ctx->tree->root.insertFile(components, realPath, ec ? 0 : size, mtime, origin);
+  ctx->tree->indexFile(components);
 }
 
 }  // namespace
@@ -217,7 +273,9 @@ void mo2_lookup(fuse_req_t req, fuse_ino_t parent, const char* name)
 
   const std::string childPath = joinPath(parentPath, name);
   const auto snap             = snapshotForPath(ctx, childPath);
+  ctx->stats.lookups.fetch_add(1, std::memory_order_relaxed);
   if (!snap.found) {
+    ctx->stats.failed_lookups.fetch_add(1, std::memory_order_relaxed);
     fuse_reply_err(req, ENOENT);
     return;
   }
@@ -341,6 +399,8 @@ void mo2_open(fuse_req_t req, fuse_ino_t ino, struct fuse_file_info* fi)
     return;
   }
 
+  std::shared_lock stagingLock(ctx->staging_mutex);
+
   bool ok = false;
   const std::string path = inodeToPath(ctx, ino, &ok);
   if (!ok) {
@@ -358,6 +418,10 @@ void mo2_open(fuse_req_t req, fuse_ino_t ino, struct fuse_file_info* fi)
   const bool writable  = isWritableOpen(fi->flags);
   bool isBacking       = snap.is_backing;
 
+  if (writable && rejectReadOnly(req, ctx)) {
+    return;
+  }
+
   if (writable) {
     try {
       if (isBacking && ctx->backing_dir_fd >= 0) {
@@ -384,6 +448,9 @@ void mo2_open(fuse_req_t req, fuse_ino_t ino, struct fuse_file_info* fi)
     ctx->open_files[fh] = std::move(of);
   }
 
+  ctx->stats.opens.fetch_add(1, std::memory_order_relaxed);
+  ctx->stats.open_handles.fetch_add(1, std::memory_order_relaxed);
+
   fi->fh         = fh;
   fi->keep_cache = 1;
   fuse_reply_open(req, fi);
@@ -432,6 +499,8 @@ void mo2_read(fuse_req_t req, fuse_ino_t /*ino*/, size_t size, off_t off,
     return;
   }
 
+  ctx->stats.reads.fetch_add(1, std::memory_order_relaxed);
+  ctx->stats.read_bytes.fetch_add(static_cast<uint64_t>(n), std::memory_order_relaxed);
   fuse_reply_buf(req, out.data(), static_cast<size_t>(n));
 }
 
@@ -444,6 +513,10 @@ void mo2_write(fuse_req_t req, fuse_ino_t /*ino*/, const char* buf, size_t size,
     return;
   }
 
+  if (rejectReadOnly(req, ctx)) {
+    return;
+  }
+
   Mo2FsContext::OpenFile open;
   {
     std::scoped_lock lock(ctx->open_files_mutex);
@@ -481,10 +554,13 @@ void mo2_write(fuse_req_t req, fuse_ino_t /*ino*/, const char* buf, size_t size,
   }
 
   updateFileNode(ctx, open.relative_path, open.real_path, "Staging");
+  notifyStagingChanged(ctx);
+  ctx->stats.writes.fetch_add(1, std::memory_order_relaxed);
+  ctx->stats.write_bytes.fetch_add(size, std::memory_order_relaxed);
   fuse_reply_write(req, size);
 }
 
-void mo2_create(fuse_req_t req, fuse_ino_t parent, const char* name, mode_t /*mode*/,
+void mo2_create(fuse_req_t req, fuse_ino_t parent, const char* name, mode_t mode,
                 struct fuse_file_info* fi)
 {
   Mo2FsContext* ctx = getContext(req);
@@ -493,6 +569,12 @@ void mo2_create(fuse_req_t req, fuse_ino_t parent, const char* name, mode_t /*mo
     return;
   }
 
+  if (rejectReadOnly(req, ctx)) {
+    return;
+  }
+
+  std::shared_lock stagingLock(ctx->staging_mutex);
+
   bool ok = false;
   const std::string parentPath = inodeToPath(ctx, parent, &ok);
   if (!ok) {
@@ -504,7 +586,7 @@ void mo2_create(fuse_req_t req, fuse_ino_t parent, const char* name, mode_t /*mo
 
   std::string realPath;
   try {
-    realPath = ctx->overwrite->writeFile(relative, {});
+    realPath = ctx->overwrite->writeFile(relative, {}, mode);
   } catch (...) {
     fuse_reply_err(req, EIO);
     return;
@@ -515,6 +597,7 @@ void mo2_create(fuse_req_t req, fuse_ino_t parent, const char* name, mode_t /*mo
     std::unique_lock lock(ctx->tree_mutex);
     ++ctx->tree->file_count;
   }
+  notifyStagingChanged(ctx);
 
   fuse_ino_t newIno;
   {
@@ -539,6 +622,9 @@ void mo2_create(fuse_req_t req, fuse_ino_t parent, const char* name, mode_t /*mo
     ctx->open_files[fh] = std::move(of);
   }
 
+  ctx->stats.opens.fetch_add(1, std::memory_order_relaxed);
+  ctx->stats.open_handles.fetch_add(1, std::memory_order_relaxed);
+
   fi->fh         = fh;
   fi->keep_cache = 1;
 
@@ -561,6 +647,12 @@ void mo2_rename(fuse_req_t req, fuse_ino_t parent, const char* name,
     return;
   }
 
+  if (rejectReadOnly(req, ctx)) {
+    return;
+  }
+
+  std::shared_lock stagingLock(ctx->staging_mutex);
+
   bool okParent = false;
   bool okNewParent = false;
   const std::string parentPath = inodeToPath(ctx, parent, &okParent);
@@ -594,8 +686,10 @@ void mo2_rename(fuse_req_t req, fuse_ino_t parent, const char* name,
       const std::string staged = ctx->overwrite->stagingPath(newRelative);
       const std::string over   = ctx->overwrite->overwritePath(newRelative);
       const std::string real   = fs::exists(staged) ? staged : over;
-      ctx->tree->root.insertFile(splitPath(newRelative), real, oldSnap.size,
+      const auto components = splitPath(newRelative);
+      ctx->tree->root.insertFile(components, real, oldSnap.size,
                                  std::chrono::system_clock::now(), "Staging");
+      ctx->tree->indexFile(components);
     }
   }
 
@@ -604,6 +698,7 @@ void mo2_rename(fuse_req_t req, fuse_ino_t parent, const char* name,
     ctx->inodes->rename(oldRelative, newRelative);
   }
 
+  notifyStagingChanged(ctx);
   fuse_reply_err(req, 0);
 }
 
@@ -616,6 +711,8 @@ void mo2_setattr(fuse_req_t req, fuse_ino_t ino, struct stat* attr, int to_set,
     return;
   }
 
+  std::shared_lock stagingLock(ctx->staging_mutex);
+
   if (ino == 1) {
     struct stat st;
     fillStatForDir(&st, 1, ctx->uid, ctx->gid);
@@ -631,6 +728,10 @@ void mo2_setattr(fuse_req_t req, fuse_ino_t ino, struct stat* attr, int to_set,
   }
 
   if ((to_set & FUSE_SET_ATTR_SIZE) != 0 && attr != nullptr) {
+    if (rejectReadOnly(req, ctx)) {
+      return;
+    }
+
     std::string target;
     bool targetIsBacking = false;
     uint64_t fh = 0;
@@ -687,6 +788,7 @@ void mo2_setattr(fuse_req_t req, fuse_ino_t ino, struct stat* attr, int to_set,
     }
 
     updateFileNode(ctx, path, target, "Staging");
+    notifyStagingChanged(ctx);
   }
 
   const auto snap = snapshotForPath(ctx, path);
@@ -712,6 +814,12 @@ void mo2_unlink(fuse_req_t req, fuse_ino_t parent, const char* name)
     return;
   }
 
+  if (rejectReadOnly(req, ctx)) {
+    return;
+  }
+
+  std::shared_lock stagingLock(ctx->staging_mutex);
+
   bool ok = false;
   const std::string parentPath = inodeToPath(ctx, parent, &ok);
   if (!ok) {
@@ -732,10 +840,11 @@ void mo2_unlink(fuse_req_t req, fuse_ino_t parent, const char* name)
     }
   }
 
+  notifyStagingChanged(ctx);
   fuse_reply_err(req, 0);
 }
 
-void mo2_mkdir(fuse_req_t req, fuse_ino_t parent, const char* name, mode_t /*mode*/)
+void mo2_mkdir(fuse_req_t req, fuse_ino_t parent, const char* name, mode_t mode)
 {
   Mo2FsContext* ctx = getContext(req);
   if (ctx == nullptr || name == nullptr) {
@@ -743,6 +852,10 @@ void mo2_mkdir(fuse_req_t req, fuse_ino_t parent, const char* name, mode_t /*mod
     return;
   }
 
+  if (rejectReadOnly(req, ctx)) {
+    return;
+  }
+
   bool ok = false;
   const std::string parentPath = inodeToPath(ctx, parent, &ok);
   if (!ok) {
@@ -751,7 +864,7 @@ void mo2_mkdir(fuse_req_t req, fuse_ino_t parent, const char* name, mode_t /*mod
   }
 
   const std::string relative = joinPath(parentPath, name);
-  if (!ctx->overwrite->createDirectory(relative)) {
+  if (!ctx->overwrite->createDirectory(relative, mode)) {
     fuse_reply_err(req, EIO);
     return;
   }
@@ -761,6 +874,7 @@ void mo2_mkdir(fuse_req_t req, fuse_ino_t parent, const char* name, mode_t /*mod
     ctx->tree->root.insertDirectory(splitPath(relative));
     ++ctx->tree->dir_count;
   }
+  notifyStagingChanged(ctx);
 
   fuse_ino_t dirIno;
   {
@@ -785,10 +899,387 @@ void mo2_release(fuse_req_t req, fuse_ino_t /*ino*/, struct fuse_file_info* fi)
     return;
   }
 
+  bool released = false;
   {
     std::scoped_lock lock(ctx->open_files_mutex);
-    ctx->open_files.erase(fi->fh);
+    released = ctx->open_files.erase(fi->fh) > 0;
+  }
+
+  if (released) {
+    ctx->stats.open_handles.fetch_sub(1, std::memory_order_relaxed);
   }
 
   fuse_reply_err(req, 0);
 }
+
+void mo2_setxattr(fuse_req_t req, fuse_ino_t ino, const char* name,
+                  const char* value, size_t size, int flags)
+{
+  Mo2FsContext* ctx = getContext(req);
+  if (ctx == nullptr || name == nullptr) {
+    fuse_reply_err(req, EINVAL);
+    return;
+  }
+
+  if (rejectReadOnly(req, ctx)) {
+    return;
+  }
+
+  std::shared_lock stagingLock(ctx->staging_mutex);
+
+  if (!isUserXattr(name)) {
+    fuse_reply_err(req, ENOTSUP);
+    return;
+  }
+
+  bool ok = false;
+  const std::string path = inodeToPath(ctx, ino, &ok);
+  if (!ok) {
+    fuse_reply_err(req, ENOENT);
+    return;
+  }
+
+  const auto snap = snapshotForPath(ctx, path);
+  if (!snap.found) {
+    fuse_reply_err(req, ENOENT);
+    return;
+  }
+
+  const std::string stagedPath = ctx->overwrite->stagingPath(path);
+  std::string target           = snap.real_path;
+
+  if (snap.is_directory) {
+    if (!ctx->overwrite->createDirectory(path)) {
+      fuse_reply_err(req, EIO);
+      return;
+    }
+    target = stagedPath;
+  } else if (fs::path(target).lexically_normal().string() !=
+             fs::path(stagedPath).lexically_normal().string()) {
+    try {
+      if (snap.is_backing && ctx->backing_dir_fd >= 0) {
+        target = ctx->overwrite->copyOnWriteFromFd(ctx->backing_dir_fd, path);
+      } else {
+        target = ctx->overwrite->copyOnWrite(target, path);
+      }
+    } catch (...) {
+      fuse_reply_err(req, EIO);
+      return;
+    }
+
+    updateFileNode(ctx, path, target, "Staging");
+  }
+
+  if (::setxattr(target.c_str(), name, value, size, flags) != 0) {
+    fuse_reply_err(req, errno);
+    return;
+  }
+
+  notifyStagingChanged(ctx);
+  fuse_reply_err(req, 0);
+}
+
+void mo2_getxattr(fuse_req_t req, fuse_ino_t ino, const char* name, size_t size)
+{
+  Mo2FsContext* ctx = getContext(req);
+  if (ctx == nullptr || name == nullptr) {
+    fuse_reply_err(req, EINVAL);
+    return;
+  }
+
+  if (!isUserXattr(name)) {
+    fuse_reply_err(req, ENODATA);
+    return;
+  }
+
+  bool ok = false;
+  const std::string path = inodeToPath(ctx, ino, &ok);
+  if (!ok) {
+    fuse_reply_err(req, ENOENT);
+    return;
+  }
+
+  const auto snap = snapshotForPath(ctx, path);
+  if (!snap.found) {
+    fuse_reply_err(req, ENOENT);
+    return;
+  }
+
+  const int fd = openForXattr(ctx, path, snap);
+  if (fd < 0) {
+    fuse_reply_err(req, snap.is_directory ? ENODATA : errno);
+    return;
+  }
+
+  std::vector<char> buf(size);
+  const ssize_t len = ::fgetxattr(fd, name, size > 0 ? buf.data() : nullptr, size);
+  const int err     = errno;
+  close(fd);
+
+  if (len < 0) {
+    fuse_reply_err(req, err);
+    return;
+  }
+
+  replyXattr(req, size, buf.data(), static_cast<size_t>(len));
+}
+
+void mo2_listxattr(fuse_req_t req, fuse_ino_t ino, size_t size)
+{
+  Mo2FsContext* ctx = getContext(req);
+  if (ctx == nullptr) {
+    fuse_reply_err(req, EINVAL);
+    return;
+  }
+
+  bool ok = false;
+  const std::string path = inodeToPath(ctx, ino, &ok);
+  if (!ok) {
+    fuse_reply_err(req, ENOENT);
+    return;
+  }
+
+  const auto snap = snapshotForPath(ctx, path);
+  if (!snap.found) {
+    fuse_reply_err(req, ENOENT);
+    return;
+  }
+
+  const int fd = openForXattr(ctx, path, snap);
+  if (fd < 0) {
+    if (snap.is_directory) {
+      replyXattr(req, size, nullptr, 0);
+    } else {
+      fuse_reply_err(req, errno);
+    }
+    return;
+  }
+
+  std::vector<char> names;
+  ssize_t len = ::flistxattr(fd, nullptr, 0);
+  if (len > 0) {
+    names.resize(static_cast<size_t>(len));
+    len = ::flistxattr(fd, names.data(), names.size());
+  }
+  const int err = errno;
+  close(fd);
+
+  if (len < 0) {
+    fuse_reply_err(req, err);
+    return;
+  }
+
+  // only expose the namespace that can also be set through the mount
+  std::string list;
+  for (size_t i = 0; i < static_cast<size_t>(len);) {
+    const char* n = names.data() + i;
+    const size_t l = std::strlen(n);
+    if (isUserXattr(n)) {
+      list.append(n, l + 1);
+    }
+    i += l + 1;
+  }
+
+  replyXattr(req, size, list.data(), list.size());
+}
+
+void mo2_removexattr(fuse_req_t req, fuse_ino_t ino, const char* name)
+{
+  Mo2FsContext* ctx = getContext(req);
+  if (ctx == nullptr || name == nullptr) {
+    fuse_reply_err(req, EINVAL);
+    return;
+  }
+
+  if (rejectReadOnly(req, ctx)) {
+    return;
+  }
+
+  if (!isUserXattr(name)) {
+    fuse_reply_err(req, ENOTSUP);
+    return;
+  }
+
+  bool ok = false;
+  const std::string path = inodeToPath(ctx, ino, &ok);
+  if (!ok) {
+    fuse_reply_err(req, ENOENT);
+    return;
+  }
+
+  // only staged files can be changed, attributes anywhere else are read-only
+  // and removing one from there is treated like it was never set
+  const std::string stagedPath = ctx->overwrite->stagingPath(path);
+  std::error_code ec;
+  if (!fs::exists(stagedPath, ec)) {
+    fuse_reply_err(req, ENODATA);
+    return;
+  }
+
+  if (::removexattr(stagedPath.c_str(), name) != 0) {
+    fuse_reply_err(req, errno);
+    return;
+  }
+
+  notifyStagingChanged(ctx);
+  fuse_reply_err(req, 0);
+}
+
+std::string stagingBackupDir(const std::string& overwrite_dir)
+{
+  const std::time_t now = std::time(nullptr);
+  std::tm local{};
+  localtime_r(&now, &local);
+
+  char stamp[32];
+  std::strftime(stamp, sizeof(stamp), "%Y%m%d-%H%M%S", &local);
+
+  return (fs::path(overwrite_dir).parent_path() / "VFS_backups" / stamp).string();
+}
+
+StagedMoveResult moveStagedFile(const std::string& staged, const std::string& dest,
+                                const std::string& relative,
+                                StagingFlushPolicy policy,
+                                const std::string& backup_dir)
+{
+  std::error_code ec;
+  bool backedUp = false;
+
+  if (fs::exists(dest, ec)) {
+    if (policy == StagingFlushPolicy::Skip) {
+      fs::remove(staged, ec);
+      return StagedMoveResult::Skipped;
+    }
+
+    if (policy == StagingFlushPolicy::Backup) {
+      const fs::path backup = fs::path(backup_dir) / relative;
+      fs::create_directories(backup.parent_path(), ec);
+      fs::rename(dest, backup, ec);
+      if (ec) {
+        ec.clear();
+        fs::copy_file(dest, backup, fs::copy_options::overwrite_existing, ec);
+      }
+
+      if (ec) {
+        // don't replace a file that couldn't be backed up
+        return StagedMoveResult::Failed;
+      }
+
+      backedUp = true;
+    }
+  }
+
+  fs::create_directories(fs::path(dest).parent_path(), ec);
+  fs::rename(staged, dest, ec);
+  if (ec) {
+    ec.clear();
+    fs::copy_file(staged, dest, fs::copy_options::overwrite_existing, ec);
+    if (ec) {
+      return StagedMoveResult::Failed;
+    }
+    fs::remove(staged, ec);
+  }
+
+  return (backedUp ? StagedMoveResult::BackedUp : StagedMoveResult::Moved);
+}
+
+size_t flushStableStaging(Mo2FsContext* ctx, std::chrono::seconds min_age,
+                          StagingFlushPolicy policy)
+{
+  if (ctx == nullptr || ctx->overwrite == nullptr) {
+    return 0;
+  }
+
+  const fs::path staging(ctx->overwrite->stagingDir());
+
+  const auto isStable = [&](const fs::path& path) {
+    std::error_code ec;
+    const auto mtime = fs::last_write_time(path, ec);
+    return !ec && fs::file_time_type::clock::now() - mtime >= min_age;
+  };
+
+  // look for candidates without blocking the session, the scan can take a
+  // while on a large staging directory
+  std::vector<fs::path> candidates;
+  std::error_code ec;
+  for (auto it = fs::recursive_directory_iterator(
+           staging, fs::directory_options::skip_permission_denied, ec);
+       !ec && it != fs::recursive_directory_iterator(); it.increment(ec)) {
+    if (it.depth() >= MaxScanDepth) {
+      it.disable_recursion_pending();
+    }
+
+    std::error_code entryEc;
+    if (it->is_symlink(entryEc) || !it->is_regular_file(entryEc)) {
+      continue;
+    }
+
+    if (isStable(it->path())) {
+      candidates.push_back(it->path().lexically_normal());
+    }
+  }
+
+  if (candidates.empty()) {
+    return 0;
+  }
+
+  std::unique_lock stagingLock(ctx->staging_mutex);
+
+  std::unordered_set<std::string> openPaths;
+  {
+    std::scoped_lock lock(ctx->open_files_mutex);
+    for (const auto& [fh, open] : ctx->open_files) {
+      openPaths.insert(fs::path(open.real_path).lexically_normal().string());
+    }
+  }
+
+  const std::string backupDir = stagingBackupDir(ctx->overwrite->overwriteDir());
+
+  size_t moved = 0;
+  for (const auto& path : candidates) {
+    // it may have been opened or written to since the scan
+    if (openPaths.contains(path.string()) || !isStable(path)) {
+      continue;
+    }
+
+    const std::string relative = path.lexically_relative(staging).generic_string();
+    const fs::path dest        = ctx->overwrite->overwritePath(relative);
+
+    // the tree still shows the staged file, dropping it is left to the final
+    // flush
+    if (policy == StagingFlushPolicy::Skip && fs::exists(dest, ec)) {
+      continue;
+    }
+
+    const auto result =
+        moveStagedFile(path.string(), dest.string(), relative, policy, backupDir);
+    if (result == StagedMoveResult::Failed) {
+      continue;
+    }
+
+    updateFileNode(ctx, relative, dest.string(), "Overwrite");
+    ++moved;
+  }
+
+  return moved;
+}
+
+Mo2FsStatsSnapshot snapshotStats(const Mo2FsContext* ctx)
+{
+  Mo2FsStatsSnapshot s;
+  if (ctx == nullptr) {
+    return s;
+  }
+
+  const auto& stats = ctx->stats;
+  s.lookups         = stats.lookups.load(std::memory_order_relaxed);
+  s.failed_lookups  = stats.failed_lookups.load(std::memory_order_relaxed);
+  s.opens           = stats.opens.load(std::memory_order_relaxed);
+  s.reads           = stats.reads.load(std::memory_order_relaxed);
+  s.read_bytes      = stats.read_bytes.load(std::memory_order_relaxed);
+  s.writes          = stats.writes.load(std::memory_order_relaxed);
+  s.write_bytes     = stats.write_bytes.load(std::memory_order_relaxed);
+  s.open_handles    = stats.open_handles.load(std::memory_order_relaxed);
+
+  return s;
+}
@@ -8,12 +8,43 @@
 #include "vfstree.h"
 
 #include <atomic>
+#include <chrono>
+#include <functional>
 #include <memory>
 #include <mutex>
 #include <shared_mutex>
 #include <string>
 #include <unordered_map>
 
+// counters updated by the fuse callbacks for diagnostics; relaxed atomics so
+// they don't add locking to the read path
+struct Mo2FsStats
+{
+  std::atomic<uint64_t> lookups{0};
+  std::atomic<uint64_t> failed_lookups{0};
+  std::atomic<uint64_t> opens{0};
+  std::atomic<uint64_t> reads{0};
+  std::atomic<uint64_t> read_bytes{0};
+  std::atomic<uint64_t> writes{0};
+  std::atomic<uint64_t> write_bytes{0};
+
+  // handles opened or created and not released yet
+  std::atomic<uint64_t> open_handles{0};
+};
+
+// copy of the counters at one point in time
+struct Mo2FsStatsSnapshot
+{
+  uint64_t lookups        = 0;
+  uint64_t failed_lookups = 0;
+  uint64_t opens          = 0;
+  uint64_t reads          = 0;
+  uint64_t read_bytes     = 0;
+  uint64_t writes         = 0;
+  uint64_t write_bytes    = 0;
+  uint64_t open_handles   = 0;
+};
+
 struct Mo2FsContext
 {
   std::shared_ptr<VfsTree> tree;
@@ -36,12 +67,30 @@ struct Mo2FsContext
 
   std::unordered_map<uint64_t, OpenFile> open_files;
   mutable std::mutex open_files_mutex;
+
+  // held shared by the operations that resolve or put files in staging, and
+  // exclusively while flushStableStaging() moves files out of it, so a path
+  // can't be resolved to a staged file that is moved before it's used
+  mutable std::shared_mutex staging_mutex;
   std::atomic<uint64_t> next_fh{1};
 
   uid_t uid = 0;
   gid_t gid = 0;
+
+  // rejects every change with EROFS; nothing is ever put in staging, so the
+  // staging directory doesn't have to exist
+  bool read_only = false;
+
+  // called from the fuse worker threads after a file or directory in staging
+  // was created, written, renamed or removed; set before the session starts
+  // and must be cheap, it runs on every write
+  std::function<void()> on_staging_changed;
+
+  Mo2FsStats stats;
 };
 
+Mo2FsStatsSnapshot snapshotStats(const Mo2FsContext* ctx);
+
 void mo2_lookup(fuse_req_t req, fuse_ino_t parent, const char* name);
 void mo2_getattr(fuse_req_t req, fuse_ino_t ino, struct fuse_file_info* fi);
 void mo2_readdir(fuse_req_t req, fuse_ino_t ino, size_t size, off_t off,
@@ -61,4 +110,57 @@ void mo2_unlink(fuse_req_t req, fuse_ino_t parent, const char* name);
 void mo2_mkdir(fuse_req_t req, fuse_ino_t parent, const char* name, mode_t mode);
 void mo2_release(fuse_req_t req, fuse_ino_t ino, struct fuse_file_info* fi);
 
+// extended attributes, mostly so wine can keep DOS attributes (hidden, system,
+// ...) in "user.DOSATTRIB"; only the "user." namespace is supported and
+// setting an attribute copies the file or directory to staging first
+void mo2_setxattr(fuse_req_t req, fuse_ino_t ino, const char* name,
+                  const char* value, size_t size, int flags);
+void mo2_getxattr(fuse_req_t req, fuse_ino_t ino, const char* name, size_t size);
+void mo2_listxattr(fuse_req_t req, fuse_ino_t ino, size_t size);
+void mo2_removexattr(fuse_req_t req, fuse_ino_t ino, const char* name);
+
+// what happens when a staged file is flushed to a path that already exists
+enum class StagingFlushPolicy
+{
+  // the staged file replaces the existing one
+  Overwrite = 0,
+
+  // the existing file is kept and the staged one is discarded
+  Skip,
+
+  // the existing file is moved to a backup directory first, see
+  // stagingBackupDir()
+  Backup
+};
+
+// what moveStagedFile() did
+enum class StagedMoveResult
+{
+  Moved,
+  BackedUp,
+  Skipped,
+  Failed
+};
+
+// directory for the backups of one flush, named after the current time; it's
+// next to the overwrite directory so the backups don't show up in the data
+// directory
+std::string stagingBackupDir(const std::string& overwrite_dir);
+
+// moves a staged file to dest, following policy when dest exists; a backup
+// goes to relative below backup_dir, the staged file is removed when skipped
+// and left in place when moving or backing up failed
+StagedMoveResult moveStagedFile(const std::string& staged, const std::string& dest,
+                                const std::string& relative,
+                                StagingFlushPolicy policy,
+                                const std::string& backup_dir);
+
+// moves files that haven't been written to for at least min_age and aren't
+// open from staging to overwrite while the session is running, and points the
+// tree at their new location; files that would replace one in overwrite stay
+// in staging with StagingFlushPolicy::Skip, the final flush drops them;
+// returns the number of files moved
+size_t flushStableStaging(Mo2FsContext* ctx, std::chrono::seconds min_age,
+                          StagingFlushPolicy policy);
+
 #endif
@@ -1,6 +1,7 @@
 #include "overwritemanager.h"
 
 #include <fcntl.h>
+#include <sys/stat.h>
 #include <unistd.h>
 
 #include <filesystem>
@@ -23,15 +24,21 @@ std::string sanitizeRelative(const std::string& relative)
   }
   return out;
 }
+
+// sets the permission bits of a file created in staging; failures are
+// ignored, the file is still usable with the default mode
+void applyMode(const fs::path& path, mode_t mode)
+{
+  ::chmod(path.c_str(), mode & 07777);
+}
 }  // namespace
 
 OverwriteManager::OverwriteManager(const std::string& staging_dir,
                                    const std::string& overwrite_dir)
     : m_stagingDir(staging_dir), m_overwriteDir(overwrite_dir)
 {
-  std::error_code ec;
-  fs::create_directories(m_stagingDir, ec);
-  fs::create_directories(m_overwriteDir, ec);
+  // the directories are created by the callers, staging isn't created at all
+  // for read-only mounts and every write creates the parents it needs
 }
 
 std::string OverwriteManager::stagingPath(const std::string& relative_path) const
@@ -61,9 +68,15 @@ std::string OverwriteManager::copyOnWrite(const std::string& source_path,
     if (ec) {
       throw fs::filesystem_error("copyOnWrite", fs::path(source_path), dest, ec);
     }
+
+    struct stat st;
+    if (::stat(source_path.c_str(), &st) == 0) {
+      applyMode(dest, st.st_mode);
+    }
   } else {
     std::ofstream out(dest, std::ios::binary);
     out.close();
+    applyMode(dest, 0666 & ~m_umask);
   }
 
   return dest.string();
@@ -87,9 +100,13 @@ std::string OverwriteManager::copyOnWriteFromFd(int dir_fd,
     // Source doesn't exist in backing dir, create empty file
     std::ofstream out(dest, std::ios::binary);
     out.close();
+    applyMode(dest, 0666 & ~m_umask);
     return dest.string();
   }
 
+  struct stat st;
+  const bool haveMode = (::fstat(src_fd, &st) == 0);
+
   std::ofstream out(dest, std::ios::binary);
   if (!out) {
     close(src_fd);
@@ -104,15 +121,23 @@ std::string OverwriteManager::copyOnWriteFromFd(int dir_fd,
   }
 
   close(src_fd);
+  out.close();
+
+  if (haveMode) {
+    applyMode(dest, st.st_mode);
+  }
+
   return dest.string();
 }
 
 std::string OverwriteManager::writeFile(const std::string& relative_path,
-                                        const std::vector<uint8_t>& data)
+                                        const std::vector<uint8_t>& data,
+                                        mode_t mode)
 {
   const fs::path path = stagingPath(relative_path);
   std::error_code ec;
   fs::create_directories(path.parent_path(), ec);
+  const bool created = !fs::exists(path, ec);
 
   std::ofstream out(path, std::ios::binary | std::ios::trunc);
   if (!out) {
@@ -123,6 +148,10 @@ std::string OverwriteManager::writeFile(const std::string& relative_path,
     out.write(reinterpret_cast<const char*>(data.data()), static_cast<std::streamsize>(data.size()));
   }
 
+  if (created) {
+    applyMode(path, mode & ~m_umask);
+  }
+
   return path.string();
 }
 
@@ -163,10 +192,15 @@ bool OverwriteManager::removeFile(const std::string& relative_path)
   return false;
 }
 
-bool OverwriteManager::createDirectory(const std::string& relative_path)
+bool OverwriteManager::createDirectory(const std::string& relative_path, mode_t mode)
 {
+  const fs::path path = stagingPath(relative_path);
   std::error_code ec;
-  fs::create_directories(stagingPath(relative_path), ec);
+
+  if (fs::create_directories(path, ec)) {
+    applyMode(path, mode & ~m_umask);
+  }
+
   return !ec;
 }
 
@@ -1,6 +1,8 @@
 #ifndef VFS_OVERWRITEMANAGER_H
 #define VFS_OVERWRITEMANAGER_H
 
+#include <sys/types.h>
+
 #include <cstdint>
 #include <string>
 #include <vector>
@@ -15,20 +17,31 @@ public:
 
   std::string copyOnWriteFromFd(int dir_fd, const std::string& relative_path);
 
+  // `mode` is only used when the file doesn't exist yet, after removing the
+  // umask from it
   std::string writeFile(const std::string& relative_path,
-                        const std::vector<uint8_t>& data);
+                        const std::vector<uint8_t>& data, mode_t mode = 0666);
 
   bool rename(const std::string& old_relative, const std::string& new_relative);
   bool removeFile(const std::string& relative_path);
-  bool createDirectory(const std::string& relative_path);
+  bool createDirectory(const std::string& relative_path, mode_t mode = 0777);
+
+  // permission bits removed from files and directories created in staging;
+  // files copied up from a mod or the game keep the mode of their source
+  void setUmask(mode_t mask) { m_umask = mask & 0777; }
+  mode_t umask() const { return m_umask; }
 
   bool exists(const std::string& relative_path) const;
   std::string overwritePath(const std::string& relative_path) const;
   std::string stagingPath(const std::string& relative_path) const;
 
+  const std::string& stagingDir() const { return m_stagingDir; }
+  const std::string& overwriteDir() const { return m_overwriteDir; }
+
 private:
   std::string m_stagingDir;
   std::string m_overwriteDir;
+  mode_t m_umask = 022;
 };
 
 #endif
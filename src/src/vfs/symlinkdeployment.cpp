@@ -0,0 +1,244 @@
+#include "symlinkdeployment.h"
+
+#include <algorithm>
+#include <filesystem>
+#include <fstream>
+
+namespace
+{
+namespace fs = std::filesystem;
+
+// one entry per line, "L\t<link>\t<target>" for links and "D\t<directory>" for
+// directories, in the order they were created
+constexpr char LinkEntry      = 'L';
+constexpr char DirectoryEntry = 'D';
+
+struct Deployment
+{
+  std::ofstream manifest;
+  SymlinkDeployResult result;
+};
+
+void deployNode(Deployment& d, const VfsNode& node, const fs::path& dest,
+                const std::string& relative)
+{
+  for (const auto& [name, child] : node.listChildren()) {
+    const fs::path childDest = dest / name;
+    const std::string childRelative =
+        relative.empty() ? name : relative + "/" + name;
+
+    std::error_code ec;
+    const auto status = fs::symlink_status(childDest, ec);
+    const bool exists = !ec && fs::exists(status);
+
+    if (child->is_directory) {
+      if (!exists) {
+        if (!fs::create_directory(childDest, ec)) {
+          d.result.failed.push_back(childRelative);
+          continue;
+        }
+
+        d.manifest << DirectoryEntry << '\t' << childDest.string() << '\n';
+        ++d.result.directories;
+      } else if (!fs::is_directory(childDest, ec)) {
+        d.result.skipped.push_back(childRelative);
+        continue;
+      }
+
+      deployNode(d, *child, childDest, childRelative);
+      continue;
+    }
+
+    // base game files are already where they belong
+    if (child->file_info.is_backing) {
+      continue;
+    }
+
+    if (exists) {
+      d.result.skipped.push_back(childRelative);
+      continue;
+    }
+
+    fs::create_symlink(child->file_info.real_path, childDest, ec);
+    if (ec) {
+      d.result.failed.push_back(childRelative);
+      continue;
+    }
+
+    d.manifest << LinkEntry << '\t' << childDest.string() << '\t'
+               << child->file_info.real_path << '\n';
+    ++d.result.links;
+  }
+}
+
+bool moveFile(const fs::path& from, const fs::path& to)
+{
+  std::error_code ec;
+  fs::create_directories(to.parent_path(), ec);
+
+  fs::rename(from, to, ec);
+  if (!ec) {
+    return true;
+  }
+
+  // different filesystems
+  ec.clear();
+  fs::copy_file(from, to, fs::copy_options::overwrite_existing, ec);
+  if (ec) {
+    return false;
+  }
+
+  fs::remove(from, ec);
+  return true;
+}
+
+}  // namespace
+
+SymlinkDeployResult deploySymlinks(const VfsTree& tree, const std::string& data_dir,
+                                   const std::string& manifest_path)
+{
+  Deployment d;
+  d.manifest.open(manifest_path, std::ios::out | std::ios::trunc);
+
+  if (!d.manifest) {
+    // without a manifest the links couldn't be removed again
+    d.result.failed.push_back(manifest_path);
+    return d.result;
+  }
+
+  deployNode(d, tree.root, fs::path(data_dir), {});
+  d.manifest.flush();
+
+  return d.result;
+}
+
+size_t undeploySymlinks(const std::string& manifest_path)
+{
+  std::ifstream manifest(manifest_path);
+  if (!manifest) {
+    return 0;
+  }
+
+  std::vector<std::string> directories;
+  size_t removed = 0;
+  std::string line;
+
+  while (std::getline(manifest, line)) {
+    if (line.size() < 3 || line[1] != '\t') {
+      continue;
+    }
+
+    const std::string entry = line.substr(2);
+    std::error_code ec;
+
+    if (line[0] == DirectoryEntry) {
+      directories.push_back(entry);
+      continue;
+    }
+
+    if (line[0] != LinkEntry) {
+      continue;
+    }
+
+    const auto tab = entry.find('\t');
+    if (tab == std::string::npos) {
+      continue;
+    }
+
+    const fs::path link(entry.substr(0, tab));
+    const fs::path target(entry.substr(tab + 1));
+
+    // a program may have replaced the link with a real file, which is left for
+    // collectNewFiles()
+    if (!fs::is_symlink(link, ec) || fs::read_symlink(link, ec) != target) {
+      continue;
+    }
+
+    if (fs::remove(link, ec)) {
+      ++removed;
+    }
+  }
+
+  manifest.close();
+
+  // children were created after their parents
+  for (auto itor = directories.rbegin(); itor != directories.rend(); ++itor) {
+    std::error_code ec;
+    if (fs::is_directory(*itor, ec) && !fs::is_symlink(*itor, ec) &&
+        fs::is_empty(*itor, ec)) {
+      fs::remove(*itor, ec);
+    }
+  }
+
+  std::error_code ec;
+  fs::remove(manifest_path, ec);
+
+  return removed;
+}
+
+size_t collectNewFiles(const std::string& data_dir,
+                       const std::unordered_set<std::string>& base_files,
+                       const std::string& overwrite_dir)
+{
+  const fs::path dataDir(data_dir);
+  const fs::path overwrite(overwrite_dir);
+
+  std::vector<fs::path> newFiles;
+  std::vector<fs::path> newDirectories;
+  std::error_code ec;
+
+  for (auto it = fs::recursive_directory_iterator(
+           dataDir, fs::directory_options::skip_permission_denied, ec);
+       it != fs::recursive_directory_iterator(); it.increment(ec)) {
+    if (ec) {
+      break;
+    }
+
+    const auto& entry = *it;
+
+    if (it.depth() >= MaxScanDepth && entry.is_directory(ec) &&
+        !entry.is_symlink(ec)) {
+      it.disable_recursion_pending();
+    }
+
+    if (entry.is_symlink(ec)) {
+      continue;
+    }
+
+    const fs::path rel = fs::relative(entry.path(), dataDir, ec);
+    if (ec || rel.empty() || base_files.contains(rel.generic_string())) {
+      continue;
+    }
+
+    if (entry.is_directory(ec)) {
+      newDirectories.push_back(entry.path());
+      continue;
+    }
+
+    if (entry.is_regular_file(ec)) {
+      newFiles.push_back(rel);
+    }
+  }
+
+  // moved once the walk is done, the directories are changed by it
+  size_t moved = 0;
+  for (const auto& rel : newFiles) {
+    if (moveFile(dataDir / rel, overwrite / rel)) {
+      ++moved;
+    }
+  }
+
+  // deepest first so parents are empty once their children are gone
+  std::sort(newDirectories.begin(), newDirectories.end(),
+            [](const fs::path& a, const fs::path& b) {
+              return a.native().size() > b.native().size();
+            });
+
+  for (const auto& dir : newDirectories) {
+    if (fs::is_empty(dir, ec)) {
+      fs::remove(dir, ec);
+    }
+  }
+
+  return moved;
+}
@@ -0,0 +1,49 @@
+#ifndef VFS_SYMLINKDEPLOYMENT_H
+#define VFS_SYMLINKDEPLOYMENT_H
+
+#include "vfstree.h"
+
+#include <cstddef>
+#include <string>
+#include <unordered_set>
+#include <vector>
+
+// Deploys a vfs tree as symlinks in the real data directory, for systems where
+// fuse isn't available.  Every link and directory that is created is recorded
+// in a manifest so undeploying removes exactly those and nothing else.
+
+struct SymlinkDeployResult
+{
+  size_t links       = 0;
+  size_t directories = 0;
+
+  // paths relative to the data directory that weren't deployed because a real
+  // file or directory is in the way; these are never replaced
+  std::vector<std::string> skipped;
+
+  // paths relative to the data directory that couldn't be created
+  std::vector<std::string> failed;
+};
+
+// links every file of the tree that isn't a base game file into data_dir,
+// creating missing directories; the manifest is overwritten
+SymlinkDeployResult deploySymlinks(const VfsTree& tree, const std::string& data_dir,
+                                   const std::string& manifest_path);
+
+// removes the links listed in the manifest that still point where they did
+// when they were created, then the listed directories that are empty; the
+// manifest is removed afterwards
+//
+// returns the number of links removed
+size_t undeploySymlinks(const std::string& manifest_path);
+
+// moves regular files in data_dir that aren't in base_files to the same path in
+// overwrite_dir, and removes directories that aren't in base_files once they're
+// empty; base_files holds paths relative to data_dir, as from scanDataDir()
+//
+// returns the number of files moved
+size_t collectNewFiles(const std::string& data_dir,
+                       const std::unordered_set<std::string>& base_files,
+                       const std::string& overwrite_dir);
+
+#endif
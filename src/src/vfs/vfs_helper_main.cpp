@@ -14,6 +14,7 @@
 #include <sys/wait.h>
 #include <unistd.h>
 
+#include <cstdlib>
 #include <cstring>
 #include <filesystem>
 #include <fstream>
@@ -31,6 +32,9 @@ struct HelperConfig
   std::string game_dir;
   std::string data_dir_name;
   std::string overwrite_dir;
+  bool read_only = false;
+  mode_t umask   = 022;
+  StagingFlushPolicy flush_policy = StagingFlushPolicy::Overwrite;
   std::vector<std::pair<std::string, std::string>> mods;
   std::vector<std::pair<std::string, std::string>> extra_files;
 };
@@ -62,6 +66,12 @@ static HelperConfig readConfig(const std::string& path)
       cfg.data_dir_name = val;
     } else if (key == "overwrite_dir") {
       cfg.overwrite_dir = val;
+    } else if (key == "read_only") {
+      cfg.read_only = (val == "1");
+    } else if (key == "umask") {
+      cfg.umask = static_cast<mode_t>(std::strtoul(val.c_str(), nullptr, 8));
+    } else if (key == "flush_policy") {
+      cfg.flush_policy = static_cast<StagingFlushPolicy>(std::atoi(val.c_str()));
     } else if (key == "mod") {
       const auto pipe = val.find('|');
       if (pipe != std::string::npos) {
@@ -97,7 +107,7 @@ static void tryUnmountStale(const std::string& path)
 }
 
 static void flushStaging(const std::string& stagingDir,
-                         const std::string& overwriteDir)
+                         const std::string& overwriteDir, StagingFlushPolicy policy)
 {
   const fs::path staging(stagingDir);
   const fs::path overwrite(overwriteDir);
@@ -106,10 +116,22 @@ static void flushStaging(const std::string& stagingDir,
   }
 
   std::error_code ec;
+  bool complete = true;
+  bool failed   = false;
+
+  // one directory for all the backups of this flush
+  const std::string backupDir = stagingBackupDir(overwriteDir);
+
   for (auto it = fs::recursive_directory_iterator(
            staging, fs::directory_options::skip_permission_denied);
        it != fs::recursive_directory_iterator(); ++it) {
     const auto& entry = *it;
+    if (it.depth() >= MaxScanDepth && entry.is_directory(ec) &&
+        !entry.is_symlink(ec)) {
+      it.disable_recursion_pending();
+      complete = false;
+    }
+
     const fs::path rel = fs::relative(entry.path(), staging, ec);
     if (ec || rel.empty()) {
       continue;
@@ -125,17 +147,27 @@ static void flushStaging(const std::string& stagingDir,
       continue;
     }
 
-    fs::create_directories(dest.parent_path(), ec);
-    fs::rename(entry.path(), dest, ec);
-    if (ec) {
-      ec.clear();
-      fs::copy_file(entry.path(), dest, fs::copy_options::overwrite_existing, ec);
-      if (!ec) {
-        fs::remove(entry.path(), ec);
-      }
+    if (moveStagedFile(entry.path().string(), dest.string(), rel.generic_string(),
+                       policy, backupDir) == StagedMoveResult::Failed) {
+      std::cerr << "failed to move staged file '" << entry.path().string()
+                << "' to '" << dest.string() << "'" << std::endl;
+      failed = true;
     }
   }
 
+  if (failed) {
+    // don't throw away what couldn't be moved
+    return;
+  }
+
+  if (!complete) {
+    // don't throw away what couldn't be moved
+    std::cerr << "staging directory '" << stagingDir << "' is nested deeper than "
+              << MaxScanDepth << " levels, leaving the remaining files in place"
+              << std::endl;
+    return;
+  }
+
   fs::remove_all(staging, ec);
 }
 
@@ -154,6 +186,11 @@ static void setupFuseOps(struct fuse_lowlevel_ops* ops)
   ops->unlink  = mo2_unlink;
   ops->mkdir   = mo2_mkdir;
   ops->release = mo2_release;
+
+  ops->setxattr    = mo2_setxattr;
+  ops->getxattr    = mo2_getxattr;
+  ops->listxattr   = mo2_listxattr;
+  ops->removexattr = mo2_removexattr;
 }
 
 static struct fuse_session* g_session = nullptr;
@@ -191,7 +228,9 @@ int main(int argc, char* argv[])
   }
 
   std::error_code ec;
-  fs::create_directories(stagingDir, ec);
+  if (!config.read_only) {
+    fs::create_directories(stagingDir, ec);
+  }
   fs::create_directories(config.overwrite_dir, ec);
 
   // Scan base game files BEFORE mounting (after mount they're hidden)
@@ -219,9 +258,11 @@ int main(int argc, char* argv[])
   context->inodes         = std::make_unique<InodeTable>();
   context->overwrite =
       std::make_unique<OverwriteManager>(stagingDir, config.overwrite_dir);
+  context->overwrite->setUmask(config.umask);
   context->backing_dir_fd = backingFd;
   context->uid            = ::getuid();
   context->gid            = ::getgid();
+  context->read_only      = config.read_only;
 
   // Setup FUSE
   std::vector<std::string> argvStorage = {
@@ -289,8 +330,10 @@ int main(int argc, char* argv[])
       config = newConfig;
       std::cout << "ok" << std::endl;
     } else if (line == "flush") {
-      flushStaging(stagingDir, config.overwrite_dir);
-      fs::create_directories(stagingDir, ec);
+      flushStaging(stagingDir, config.overwrite_dir, config.flush_policy);
+      if (!config.read_only) {
+        fs::create_directories(stagingDir, ec);
+      }
 
       auto newTree = std::make_shared<VfsTree>(buildDataDirVfs(
           baseFileCache, dataDirPath, config.mods, config.overwrite_dir));
@@ -303,6 +346,7 @@ int main(int argc, char* argv[])
 
       context->overwrite =
           std::make_unique<OverwriteManager>(stagingDir, config.overwrite_dir);
+      context->overwrite->setUmask(config.umask);
       std::cout << "ok" << std::endl;
     } else if (line == "quit") {
       break;
@@ -320,7 +364,7 @@ int main(int argc, char* argv[])
   fuse_session_destroy(session);
   g_session = nullptr;
 
-  flushStaging(stagingDir, config.overwrite_dir);
+  flushStaging(stagingDir, config.overwrite_dir, config.flush_policy);
   close(backingFd);
 
   std::cout << "ok" << std::endl;
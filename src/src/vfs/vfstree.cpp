@@ -3,6 +3,8 @@
 #include <algorithm>
 #include <cctype>
 #include <filesystem>
+#include <iostream>
+#include <optional>
 
 namespace
 {
@@ -42,6 +44,25 @@ std::vector<std::string> splitPath(const std::string& path)
   return out;
 }
 
+// Stops the iterator from descending past MaxScanDepth; returns true when the
+// current entry is a directory that won't be walked.
+bool pruneTooDeep(fs::recursive_directory_iterator& it)
+{
+  if (it.depth() < MaxScanDepth) {
+    return false;
+  }
+
+  std::error_code ec;
+  if (!it->is_directory(ec) || it->is_symlink(ec)) {
+    return false;
+  }
+
+  it.disable_recursion_pending();
+  std::cerr << "vfs: not descending into '" << it->path().string() << "', it is "
+            << "nested deeper than " << MaxScanDepth << " levels" << std::endl;
+  return true;
+}
+
 void addDirectoryToTree(VfsTree& tree, const fs::path& walkDir,
                         const fs::path& stripPrefix, const std::string& origin,
                         const std::vector<std::string>& prefix,
@@ -54,6 +75,8 @@ void addDirectoryToTree(VfsTree& tree, const fs::path& walkDir,
   for (auto it = fs::recursive_directory_iterator(
            walkDir, fs::directory_options::skip_permission_denied);
        it != fs::recursive_directory_iterator(); ++it) {
+    pruneTooDeep(it);
+
     const auto& entry = *it;
     std::error_code ec;
 
@@ -89,6 +112,7 @@ void addDirectoryToTree(VfsTree& tree, const fs::path& walkDir,
                          ec ? std::chrono::system_clock::time_point{}
                             : fsTimeToSystemClock(mtime),
                          origin, is_backing);
+    tree.indexFile(components);
     ++tree.file_count;
   }
 }
@@ -125,6 +149,165 @@ bool removeNodeRecursive(VfsNode* node, const std::vector<std::string>& componen
   return true;
 }
 
+struct ModEntry
+{
+  fs::path real_path;
+  std::string relative;
+  uint64_t size = 0;
+  std::chrono::system_clock::time_point mtime{};
+  bool is_dir = false;
+};
+
+// normalized relative path -> entry
+using ModListing = std::unordered_map<std::string, ModEntry>;
+
+// the files and directories addDirectoryToTree() would add for a mod
+ModListing listModDirectory(const fs::path& dir)
+{
+  ModListing listing;
+
+  std::error_code ec;
+  if (!fs::exists(dir, ec)) {
+    return listing;
+  }
+
+  for (auto it = fs::recursive_directory_iterator(
+           dir, fs::directory_options::skip_permission_denied);
+       it != fs::recursive_directory_iterator(); ++it) {
+    pruneTooDeep(it);
+
+    const auto& entry = *it;
+
+    const fs::path rel = fs::relative(entry.path(), dir, ec);
+    if (ec || rel.empty()) {
+      continue;
+    }
+
+    ModEntry me;
+    me.relative = rel.generic_string();
+    if (me.relative == "meta.ini") {
+      continue;
+    }
+
+    me.real_path = entry.path();
+    me.is_dir    = entry.is_directory(ec);
+
+    if (!me.is_dir) {
+      if (!entry.is_regular_file(ec)) {
+        continue;
+      }
+      me.size          = entry.file_size(ec);
+      const auto mtime = entry.last_write_time(ec);
+      me.mtime = ec ? std::chrono::system_clock::time_point{}
+                    : fsTimeToSystemClock(mtime);
+    }
+
+    // a later entry with the same name in another case replaces the earlier
+    // one in the tree as well
+    listing.insert_or_assign(normalizeForLookup(me.relative), std::move(me));
+  }
+
+  return listing;
+}
+
+// appends the names on disk matching components from index on to relative;
+// every case-insensitive match is tried since a mod can have both "Textures"
+// and "textures", the last one in directory order first because it's the one
+// a scan keeps
+bool matchComponents(const fs::path& root, fs::path& relative,
+                     const std::vector<std::string>& components, size_t index,
+                     bool directory)
+{
+  std::error_code ec;
+  const fs::path current = root / relative;
+
+  if (index == components.size()) {
+    return directory ? fs::is_directory(current, ec)
+                     : fs::is_regular_file(current, ec);
+  }
+
+  // directories are only walked into when they're real, like the scans do
+  if (index > 0 && fs::is_symlink(current, ec)) {
+    return false;
+  }
+
+  const std::string key = normalizeForLookup(components[index]);
+  std::vector<std::string> names;
+
+  for (const auto& entry : fs::directory_iterator(current, ec)) {
+    std::string candidate = entry.path().filename().string();
+    if (normalizeForLookup(candidate) == key) {
+      names.push_back(std::move(candidate));
+    }
+  }
+
+  for (auto itor = names.rbegin(); itor != names.rend(); ++itor) {
+    relative /= *itor;
+    if (matchComponents(root, relative, components, index + 1, directory)) {
+      return true;
+    }
+    relative = relative.parent_path();
+  }
+
+  return false;
+}
+
+// looks for a file or directory below root, matching the components
+// case-insensitively
+std::optional<ModEntry> findOnDisk(const fs::path& root,
+                                   const std::vector<std::string>& components,
+                                   bool directory)
+{
+  fs::path relative;
+  if (components.empty() ||
+      !matchComponents(root, relative, components, 0, directory)) {
+    return {};
+  }
+
+  std::error_code ec;
+  ModEntry me;
+  me.real_path = root / relative;
+  me.relative  = relative.generic_string();
+  me.is_dir    = directory;
+
+  if (!directory) {
+    const fs::path& current = me.real_path;
+    me.size          = fs::file_size(current, ec);
+    const auto mtime = fs::last_write_time(current, ec);
+    me.mtime = ec ? std::chrono::system_clock::time_point{} : fsTimeToSystemClock(mtime);
+  }
+
+  return me;
+}
+
+// normalized relative path -> relative path with display names of every file
+// in the tree won by one of origins
+void collectFilesByOrigin(const VfsNode& node, const std::string& relative,
+                          const std::unordered_set<std::string>& origins,
+                          std::unordered_map<std::string, std::string>& out)
+{
+  for (const auto& [name, child] : node.listChildren()) {
+    const std::string path = relative.empty() ? name : relative + "/" + name;
+
+    if (child->is_directory) {
+      collectFilesByOrigin(*child, path, origins, out);
+    } else if (origins.contains(child->file_info.origin)) {
+      out.emplace(normalizeForLookup(path), path);
+    }
+  }
+}
+
+// adds every parent directory of the given relative path
+void addParents(const std::string& relative,
+                std::unordered_map<std::string, std::string>& dirs)
+{
+  for (auto slash = relative.rfind('/'); slash != std::string::npos && slash > 0;
+       slash = relative.rfind('/', slash - 1)) {
+    const std::string parent = relative.substr(0, slash);
+    dirs.emplace(normalizeForLookup(parent), parent);
+  }
+}
+
 }  // namespace
 
 std::string normalizeForLookup(const std::string& path)
@@ -263,6 +446,305 @@ bool VfsNode::removeFromTree(const std::vector<std::string>& components)
   return removeNodeRecursive(this, components, 0);
 }
 
+void VfsTree::indexFile(const std::vector<std::string>& components)
+{
+  if (components.empty()) {
+    return;
+  }
+
+  const std::string name = normalizeForLookup(components.back());
+  const auto dot         = name.rfind('.');
+  if (dot == std::string::npos || dot + 1 == name.size()) {
+    return;
+  }
+
+  std::string path;
+  for (const auto& part : components) {
+    if (part.empty()) {
+      continue;
+    }
+
+    if (!path.empty()) {
+      path += '/';
+    }
+    path += normalizeForLookup(part);
+  }
+
+  extension_index[name.substr(dot + 1)].insert(std::move(path));
+}
+
+std::vector<std::pair<std::string, std::string>>
+VfsTree::filesByExtension(const std::string& ext) const
+{
+  std::vector<std::pair<std::string, std::string>> out;
+
+  std::string key = normalizeForLookup(ext);
+  if (!key.empty() && key.front() == '.') {
+    key.erase(0, 1);
+  }
+
+  const auto it = extension_index.find(key);
+  if (it == extension_index.end()) {
+    return out;
+  }
+
+  for (const auto& path : it->second) {
+    // the index isn't updated when files are removed, so every path is looked
+    // up again; display names are collected on the way
+    const VfsNode* node = &root;
+    std::string display;
+
+    for (const auto& part : splitPath(path)) {
+      if (!node->is_directory) {
+        node = nullptr;
+        break;
+      }
+
+      const auto child = node->dir_info.children.find(part);
+      if (child == node->dir_info.children.end()) {
+        node = nullptr;
+        break;
+      }
+
+      const auto name = node->dir_info.display_names.find(part);
+      if (!display.empty()) {
+        display += '/';
+      }
+      display += name != node->dir_info.display_names.end() ? name->second : part;
+
+      node = child->second.get();
+    }
+
+    if (node == nullptr || node->is_directory) {
+      continue;
+    }
+
+    out.emplace_back(std::move(display), node->file_info.origin);
+  }
+
+  std::sort(out.begin(), out.end());
+  return out;
+}
+
+void VfsTree::applyDiff(const VfsModDiff& diff,
+                        const std::vector<CachedBaseFile>& cached_files,
+                        const std::vector<std::pair<std::string, std::string>>& mods,
+                        const std::string& overwrite_dir)
+{
+  if (diff.empty()) {
+    return;
+  }
+
+  std::unordered_map<std::string, size_t> priorities;
+  for (size_t i = 0; i < mods.size(); ++i) {
+    priorities[mods[i].first] = i;
+  }
+
+  // everything the added and reordered mods provide is read from disk, these
+  // may win paths they didn't before
+  std::unordered_map<std::string, ModListing> listings;
+  std::unordered_map<std::string, std::string> files;
+  std::unordered_map<std::string, std::string> dirs;
+
+  auto addListing = [&](const std::string& name, const std::string& path) {
+    auto& listing = listings[name] = listModDirectory(fs::path(path));
+    for (const auto& [key, entry] : listing) {
+      (entry.is_dir ? dirs : files).emplace(key, entry.relative);
+    }
+  };
+
+  for (const auto& [name, path] : diff.added) {
+    addListing(name, path);
+  }
+
+  for (const auto& name : diff.reordered) {
+    const auto itor = priorities.find(name);
+    if (itor != priorities.end()) {
+      addListing(name, mods[itor->second].second);
+    }
+  }
+
+  // the paths currently won by removed or reordered mods may fall to another
+  // mod; removed mods can be gone from disk, so these come from the tree
+  std::unordered_set<std::string> lost(diff.reordered.begin(), diff.reordered.end());
+  for (const auto& [name, path] : diff.removed) {
+    lost.insert(name);
+
+    // only for empty directories that may have to go
+    for (const auto& [key, entry] : listModDirectory(fs::path(path))) {
+      if (entry.is_dir) {
+        dirs.emplace(key, entry.relative);
+      }
+    }
+  }
+
+  collectFilesByOrigin(root, {}, lost, files);
+
+  std::unordered_map<std::string, const CachedBaseFile*> baseFiles;
+  for (const auto& cf : cached_files) {
+    baseFiles.emplace(normalizeForLookup(cf.relative_path), &cf);
+  }
+
+  for (const auto& [key, relative] : files) {
+    const auto components = splitPath(relative);
+    const VfsNode* node   = root.resolve(components);
+
+    if (node != nullptr && node->is_directory) {
+      continue;
+    }
+
+    const std::string origin = node != nullptr ? node->file_info.origin : std::string();
+    if (origin == "_profile" || origin == "Staging") {
+      continue;
+    }
+
+    // a winner that isn't part of the diff still beats every other mod that
+    // isn't, only the listed mods can take the path from it
+    const auto current = priorities.find(origin);
+    const bool kept    = current != priorities.end() && !listings.contains(origin);
+
+    std::optional<size_t> best;
+    if (kept) {
+      best = current->second;
+    }
+
+    std::optional<ModEntry> winner;
+    std::string winnerOrigin;
+
+    for (const auto& [name, listing] : listings) {
+      const auto entry = listing.find(key);
+      if (entry == listing.end() || entry->second.is_dir) {
+        continue;
+      }
+
+      const size_t priority = priorities.at(name);
+      if (!best || priority > *best) {
+        best         = priority;
+        winner       = entry->second;
+        winnerOrigin = name;
+      }
+    }
+
+    if (kept && !winner) {
+      continue;
+    }
+
+    // when the winner is gone, another mod outside the diff may have the path
+    // too, those above the best listed one are checked on disk; if the path
+    // was won by overwrite or the game, no mod outside the diff has it
+    if (lost.contains(origin)) {
+      for (size_t i = mods.size(); i > 0 && (!best || i - 1 > *best); --i) {
+        const auto& [name, path] = mods[i - 1];
+        if (listings.contains(name)) {
+          continue;
+        }
+
+        if (auto found = findOnDisk(fs::path(path), components, false)) {
+          winner       = std::move(found);
+          winnerOrigin = name;
+          break;
+        }
+      }
+    }
+
+    if (!winner) {
+      if (origin == "Overwrite" || origin == "_base_game") {
+        continue;
+      }
+
+      winner = findOnDisk(fs::path(overwrite_dir), components, false);
+      winnerOrigin = "Overwrite";
+    }
+
+    if (winner) {
+      const auto parts = splitPath(winner->relative);
+      root.insertFile(parts, winner->real_path.string(), winner->size, winner->mtime,
+                      winnerOrigin);
+      indexFile(parts);
+    } else if (const auto base = baseFiles.find(key);
+               base != baseFiles.end() && !base->second->is_dir) {
+      const auto& cf   = *base->second;
+      const auto parts = splitPath(cf.relative_path);
+      root.insertFile(parts, cf.relative_path, cf.size, cf.mtime, "_base_game",
+                      /*is_backing=*/true);
+      indexFile(parts);
+    } else {
+      if (root.removeFromTree(components) && file_count > 0) {
+        --file_count;
+      }
+      addParents(relative, dirs);
+      continue;
+    }
+
+    if (node == nullptr) {
+      ++file_count;
+    }
+  }
+
+  // removing files prunes directories that became empty, some of which still
+  // exist in another layer; deepest first so parents are checked after their
+  // children
+  std::vector<std::pair<std::string, std::string>> sortedDirs(dirs.begin(), dirs.end());
+  std::sort(sortedDirs.begin(), sortedDirs.end(), [](const auto& a, const auto& b) {
+    return std::count(a.first.begin(), a.first.end(), '/') >
+           std::count(b.first.begin(), b.first.end(), '/');
+  });
+
+  for (const auto& [key, relative] : sortedDirs) {
+    const auto components = splitPath(relative);
+    const VfsNode* node   = root.resolve(components);
+
+    if (node != nullptr &&
+        (!node->is_directory || !node->dir_info.children.empty())) {
+      continue;
+    }
+
+    std::optional<std::string> found;
+
+    for (const auto& [name, listing] : listings) {
+      const auto entry = listing.find(key);
+      if (entry != listing.end() && entry->second.is_dir) {
+        found = entry->second.relative;
+        break;
+      }
+    }
+
+    for (size_t i = mods.size(); !found && i > 0; --i) {
+      const auto& [name, path] = mods[i - 1];
+      if (listings.contains(name)) {
+        continue;
+      }
+
+      if (auto entry = findOnDisk(fs::path(path), components, true)) {
+        found = entry->relative;
+      }
+    }
+
+    if (!found) {
+      if (auto entry = findOnDisk(fs::path(overwrite_dir), components, true)) {
+        found = entry->relative;
+      }
+    }
+
+    if (!found) {
+      const auto base = baseFiles.find(key);
+      if (base != baseFiles.end() && base->second->is_dir) {
+        found = base->second->relative_path;
+      }
+    }
+
+    if (found && node == nullptr) {
+      root.insertDirectory(splitPath(*found));
+      ++dir_count;
+    } else if (!found && node != nullptr) {
+      root.removeFromTree(components);
+      if (dir_count > 0) {
+        --dir_count;
+      }
+    }
+  }
+}
+
 VfsTree buildVfsTree(const std::vector<std::pair<std::string, std::string>>& mods,
                      const std::string& overwrite_dir)
 {
@@ -315,6 +797,8 @@ std::vector<CachedBaseFile> scanDataDir(const std::string& data_dir_path)
   for (auto it = fs::recursive_directory_iterator(
            dataDir, fs::directory_options::skip_permission_denied);
        it != fs::recursive_directory_iterator(); ++it) {
+    pruneTooDeep(it);
+
     const auto& entry = *it;
     std::error_code ec;
 
@@ -363,6 +847,7 @@ VfsTree buildDataDirVfs(const std::vector<CachedBaseFile>& cached_files,
     } else {
       tree.root.insertFile(components, cf.relative_path, cf.size, cf.mtime,
                            "_base_game", /*is_backing=*/true);
+      tree.indexFile(components);
       ++tree.file_count;
     }
   }
@@ -379,6 +864,228 @@ VfsTree buildDataDirVfs(const std::vector<CachedBaseFile>& cached_files,
   return tree;
 }
 
+std::vector<std::pair<std::string, std::string>>
+resolveOrigins(const std::vector<CachedBaseFile>& cached_files,
+               const std::string& data_dir,
+               const std::vector<std::pair<std::string, std::string>>& mods,
+               const std::string& overwrite_dir, const std::string& virtual_path)
+{
+  std::vector<std::pair<std::string, std::string>> origins;
+
+  const auto components = splitPath(virtual_path);
+  if (components.empty()) {
+    return origins;
+  }
+
+  for (auto itor = mods.rbegin(); itor != mods.rend(); ++itor) {
+    if (auto found = findOnDisk(fs::path(itor->second), components, false)) {
+      origins.emplace_back(itor->first, found->real_path.string());
+    }
+  }
+
+  if (auto found = findOnDisk(fs::path(overwrite_dir), components, false)) {
+    origins.emplace_back("Overwrite", found->real_path.string());
+  }
+
+  std::string key;
+  for (const auto& component : components) {
+    key += (key.empty() ? "" : "/") + normalizeForLookup(component);
+  }
+
+  for (const auto& cf : cached_files) {
+    if (!cf.is_dir && normalizeForLookup(cf.relative_path) == key) {
+      origins.emplace_back(GameOriginName,
+                           (fs::path(data_dir) / cf.relative_path).string());
+      break;
+    }
+  }
+
+  return origins;
+}
+
+std::string VfsWinnerExplanation::describe() const
+{
+  const auto join = [](const std::vector<std::string>& names) {
+    std::string out;
+    for (const auto& name : names) {
+      out += (out.empty() ? "" : ", ") + name;
+    }
+    return out;
+  };
+
+  std::string s;
+
+  switch (reason) {
+  case VfsWinReason::NotFound:
+    return "No mod, overwrite or game file provides this path.";
+
+  case VfsWinReason::Priority:
+    s = "Won by \"" + origin + "\", the mod with the highest priority providing it.";
+    break;
+
+  case VfsWinReason::Overwrite:
+    s = "Won by Overwrite since no mod provides it.";
+    break;
+
+  case VfsWinReason::Game:
+    s = "Provided by the game since no mod or Overwrite provides it.";
+    break;
+  }
+
+  if (case_folded) {
+    s += " The file on disk is " + real_path +
+         ", which only matches when ignoring case.";
+  }
+
+  if (!case_collisions.empty()) {
+    s += " Files only differing in case from it are hidden: " +
+         join(case_collisions) + ".";
+  }
+
+  if (!losers.empty()) {
+    s += " Overrides " + join(losers) + ".";
+  }
+
+  return s;
+}
+
+VfsWinnerExplanation
+explainWinner(const std::vector<CachedBaseFile>& cached_files,
+              const std::string& data_dir,
+              const std::vector<std::pair<std::string, std::string>>& mods,
+              const std::string& overwrite_dir, const std::string& virtual_path)
+{
+  VfsWinnerExplanation out;
+
+  const auto origins =
+      resolveOrigins(cached_files, data_dir, mods, overwrite_dir, virtual_path);
+  if (origins.empty()) {
+    return out;
+  }
+
+  out.origin    = origins.front().first;
+  out.real_path = origins.front().second;
+
+  for (size_t i = 1; i < origins.size(); ++i) {
+    out.losers.push_back(origins[i].first);
+  }
+
+  fs::path root;
+  const auto mod = std::find_if(mods.begin(), mods.end(), [&](auto&& m) {
+    return m.first == out.origin;
+  });
+
+  if (mod != mods.end()) {
+    out.reason = VfsWinReason::Priority;
+    root       = mod->second;
+  } else if (out.origin == "Overwrite") {
+    out.reason = VfsWinReason::Overwrite;
+    root       = overwrite_dir;
+  } else {
+    out.reason = VfsWinReason::Game;
+    root       = data_dir;
+  }
+
+  std::string requested;
+  for (const auto& component : splitPath(virtual_path)) {
+    requested += (requested.empty() ? "" : "/") + component;
+  }
+
+  const fs::path real(out.real_path);
+  out.case_folded = real.lexically_relative(root).generic_string() != requested;
+
+  // the data directory is the mount point while the vfs runs, so only mods and
+  // overwrite are listed for other names of the same file
+  if (out.reason != VfsWinReason::Game) {
+    const std::string name = real.filename().string();
+    const std::string key  = normalizeForLookup(name);
+
+    std::error_code ec;
+    for (const auto& entry : fs::directory_iterator(real.parent_path(), ec)) {
+      std::string candidate = entry.path().filename().string();
+      if (candidate != name && normalizeForLookup(candidate) == key) {
+        out.case_collisions.push_back(std::move(candidate));
+      }
+    }
+
+    std::sort(out.case_collisions.begin(), out.case_collisions.end());
+  }
+
+  return out;
+}
+
+VfsModDiff
+diffModLists(const std::vector<std::pair<std::string, std::string>>& old_mods,
+             const std::vector<std::pair<std::string, std::string>>& new_mods)
+{
+  VfsModDiff diff;
+
+  std::unordered_map<std::string, size_t> oldIndices;
+  for (size_t i = 0; i < old_mods.size(); ++i) {
+    oldIndices[old_mods[i].first] = i;
+  }
+
+  // (index in old_mods, name) of mods in both lists, in the new order
+  std::vector<std::pair<size_t, std::string>> common;
+  std::unordered_set<std::string> newNames;
+
+  for (const auto& [name, path] : new_mods) {
+    newNames.insert(name);
+
+    const auto itor = oldIndices.find(name);
+    if (itor == oldIndices.end()) {
+      diff.added.emplace_back(name, path);
+    } else if (old_mods[itor->second].second != path) {
+      diff.reordered.push_back(name);
+    } else {
+      common.emplace_back(itor->second, name);
+    }
+  }
+
+  for (const auto& [name, path] : old_mods) {
+    if (!newNames.contains(name)) {
+      diff.removed.emplace_back(name, path);
+    }
+  }
+
+  // the longest run of mods that kept their relative order stays, everything
+  // else in common moved; patience sorting over the old indices
+  std::vector<size_t> tails;
+  std::vector<std::optional<size_t>> previous(common.size());
+
+  for (size_t i = 0; i < common.size(); ++i) {
+    const auto itor = std::lower_bound(tails.begin(), tails.end(), common[i].first,
+                                       [&](size_t tail, size_t index) {
+                                         return common[tail].first < index;
+                                       });
+
+    if (itor != tails.begin()) {
+      previous[i] = *(itor - 1);
+    }
+
+    if (itor == tails.end()) {
+      tails.push_back(i);
+    } else {
+      *itor = i;
+    }
+  }
+
+  std::vector<bool> inOrder(common.size(), false);
+  if (!tails.empty()) {
+    for (std::optional<size_t> i = tails.back(); i; i = previous[*i]) {
+      inOrder[*i] = true;
+    }
+  }
+
+  for (size_t i = 0; i < common.size(); ++i) {
+    if (!inOrder[i]) {
+      diff.reordered.push_back(common[i].second);
+    }
+  }
+
+  return diff;
+}
+
 void injectExtraFiles(
     VfsTree& tree,
     const std::vector<std::pair<std::string, std::string>>& extra_files)
@@ -394,6 +1101,7 @@ void injectExtraFiles(
     tree.root.insertFile(components, realPath, ec ? 0ULL : size,
                          std::chrono::system_clock::now(), "_profile",
                          /*is_backing=*/false);
+    tree.indexFile(components);
     ++tree.file_count;
   }
 }
@@ -6,6 +6,7 @@
 #include <memory>
 #include <string>
 #include <unordered_map>
+#include <unordered_set>
 #include <utility>
 #include <vector>
 
@@ -54,13 +55,62 @@ struct VfsNode
   bool removeFromTree(const std::vector<std::string>& components);
 };
 
+// Mods that differ between two mod lists, see diffModLists()
+struct VfsModDiff
+{
+  // (name, path) of mods only in the new or only in the old list
+  std::vector<std::pair<std::string, std::string>> added;
+  std::vector<std::pair<std::string, std::string>> removed;
+
+  // mods in both lists that moved relative to the other mods in both lists, or
+  // whose path changed; all other mods keep the files they win
+  std::vector<std::string> reordered;
+
+  bool empty() const
+  {
+    return added.empty() && removed.empty() && reordered.empty();
+  }
+};
+
 struct VfsTree
 {
   VfsNode root;
   size_t file_count = 0;
   size_t dir_count  = 0;
+
+  // lowercase extension without the dot -> normalized paths of files with that
+  // extension, filled while the tree is built so extension queries don't have
+  // to walk the whole tree
+  std::unordered_map<std::string, std::unordered_set<std::string>> extension_index;
+
+  // must be called for every file inserted into root to keep the index
+  // complete; removed files don't have to be unindexed
+  void indexFile(const std::vector<std::string>& components);
+
+  // (virtual path, origin) of the winning file for every path with the given
+  // extension, sorted by path; the extension is matched case-insensitively
+  // with or without the leading dot
+  std::vector<std::pair<std::string, std::string>>
+  filesByExtension(const std::string& ext) const;
+
+  // updates a tree built by buildDataDirVfs() for the old mod list of diff to
+  // what it would be for mods, only looking at the paths provided or won by
+  // the mods in diff; files from staging and injected extra files are kept
+  //
+  // display names of directories may differ from a full build when mods use
+  // different cases for the same directory
+  //
+  void applyDiff(const VfsModDiff& diff, const std::vector<CachedBaseFile>& cached_files,
+                 const std::vector<std::pair<std::string, std::string>>& mods,
+                 const std::string& overwrite_dir);
 };
 
+// Deepest directory level any recursive walk over mods, overwrite or the data
+// directory descends into.  Directory symlinks are never followed, so loops
+// can't happen through them, but this still guarantees every walk terminates
+// on pathological trees (bind mounts onto a parent, runaway tool output, ...).
+constexpr int MaxScanDepth = 64;
+
 std::string normalizeForLookup(const std::string& path);
 
 VfsTree buildVfsTree(const std::vector<std::pair<std::string, std::string>>& mods,
@@ -77,6 +127,71 @@ VfsTree buildDataDirVfs(const std::vector<CachedBaseFile>& cached_files,
                         const std::vector<std::pair<std::string, std::string>>& mods,
                         const std::string& overwrite_dir);
 
+// Origin reported by resolveOrigins() for files of the game itself
+inline constexpr const char* GameOriginName = "<Game>";
+
+// (origin, real path) of every layer of a tree built by buildDataDirVfs() that
+// provides the file at virtual_path, the winning one first: mods from the
+// highest priority down, then "Overwrite", then GameOriginName; looked up on
+// disk, so it also works for files the tree doesn't contain anymore
+std::vector<std::pair<std::string, std::string>>
+resolveOrigins(const std::vector<CachedBaseFile>& cached_files,
+               const std::string& data_dir,
+               const std::vector<std::pair<std::string, std::string>>& mods,
+               const std::string& overwrite_dir, const std::string& virtual_path);
+
+// Why a layer won a file, see explainWinner()
+enum class VfsWinReason
+{
+  // no layer provides the file
+  NotFound,
+
+  // the mod with the highest priority providing the file
+  Priority,
+
+  // no mod provides the file, overwrite is above the game
+  Overwrite,
+
+  // only the game's data directory provides the file
+  Game
+};
+
+struct VfsWinnerExplanation
+{
+  VfsWinReason reason = VfsWinReason::NotFound;
+  std::string origin;
+  std::string real_path;
+
+  // the winning file only matches the virtual path when ignoring case
+  bool case_folded = false;
+
+  // other files in the winner's directory whose names only differ in case;
+  // one of them is picked, the others never show up in the vfs
+  std::vector<std::string> case_collisions;
+
+  // origins that also provide the file and lost, highest priority first
+  std::vector<std::string> losers;
+
+  // one sentence per fact, for tooltips and logs
+  std::string describe() const;
+};
+
+// explains which layer of a tree built by buildDataDirVfs() wins the file at
+// virtual_path and why, for when the winner isn't the one a user expected from
+// the mod list; like resolveOrigins(), everything is looked up on disk
+VfsWinnerExplanation
+explainWinner(const std::vector<CachedBaseFile>& cached_files,
+              const std::string& data_dir,
+              const std::vector<std::pair<std::string, std::string>>& mods,
+              const std::string& overwrite_dir, const std::string& virtual_path);
+
+// Mods added, removed and moved between two mod lists in priority order.  Only
+// the fewest mods needed to explain the new order are reported as reordered,
+// so moving one mod doesn't mark every mod it was moved past.
+VfsModDiff
+diffModLists(const std::vector<std::pair<std::string, std::string>>& old_mods,
+             const std::vector<std::pair<std::string, std::string>>& new_mods);
+
 // Inject individual file mappings into an already-built VFS tree.
 // Each entry is (relative_vfs_path, absolute_real_path).  Inserted with
 // highest priority (overwrites any existing entry at the same path).
@@ -130,44 +130,58 @@ QString WinePrefix::appdataLocal() const
   return QDir(driveC()).filePath("users/steamuser/AppData/Local");
 }
 
-bool WinePrefix::deployPlugins(const QStringList& plugins, const QString& dataDir) const
+QString WinePrefix::ensurePluginsDirectory(const QString& dataDir) const
 {
   if (!isValid()) {
-    return false;
+    return {};
   }
 
   const QString pluginsDir = QDir(appdataLocal()).filePath(dataDir);
   if (!QDir().mkpath(pluginsDir)) {
-    return false;
+    return {};
   }
 
-  QFile pluginsFile(QDir(pluginsDir).filePath("Plugins.txt"));
-  if (!pluginsFile.open(QIODevice::WriteOnly | QIODevice::Truncate | QIODevice::Text)) {
-    return false;
+  return pluginsDir;
+}
+
+QList<QPair<QString, bool>> WinePrefix::readPlugins(const QString& dataDir,
+                                                    bool asteriskFormat) const
+{
+  if (!isValid()) {
+    return {};
   }
 
-  QTextStream pluginsStream(&pluginsFile);
-  for (const QString& plugin : plugins) {
-    pluginsStream << plugin << "\r\n";
+  const QString pluginsDir = QDir(appdataLocal()).filePath(dataDir);
+  const QStringList variants =
+      findCaseVariants(QDir(pluginsDir).filePath("Plugins.txt"));
+  if (variants.isEmpty()) {
+    return {};
   }
-  pluginsFile.close();
 
-  QFile loadOrderFile(QDir(pluginsDir).filePath("loadorder.txt"));
-  if (!loadOrderFile.open(QIODevice::WriteOnly | QIODevice::Truncate | QIODevice::Text)) {
-    return false;
+  QFile pluginsFile(variants.first());
+  if (!pluginsFile.open(QIODevice::ReadOnly | QIODevice::Text)) {
+    return {};
   }
 
-  QTextStream loadOrderStream(&loadOrderFile);
-  for (const QString& plugin : plugins) {
-    QString line = plugin;
-    if (line.startsWith('*')) {
-      line.remove(0, 1);
+  QList<QPair<QString, bool>> result;
+
+  QTextStream stream(&pluginsFile);
+  while (!stream.atEnd()) {
+    const QString line = stream.readLine().trimmed();
+    if (line.isEmpty() || line.startsWith('#')) {
+      continue;
     }
 
-    loadOrderStream << line << "\r\n";
+    if (!asteriskFormat) {
+      result.append({line, true});
+    } else if (line.startsWith('*')) {
+      result.append({line.mid(1).trimmed(), true});
+    } else {
+      result.append({line, false});
+    }
   }
 
-  return true;
+  return result;
 }
 
 bool WinePrefix::deployProfileIni(const QString& sourceIniPath,
@@ -17,14 +17,23 @@ public:
   QString myGamesPath() const;    // .../Documents/My Games
   QString appdataLocal() const;   // .../AppData/Local
 
+  // Directory the game reads plugins.txt and loadorder.txt from, created if
+  // needed; empty if it couldn't be
+  QString ensurePluginsDirectory(const QString& dataDir) const;
+
   // Deploy profile files into prefix
-  bool deployPlugins(const QStringList& plugins, const QString& dataDir) const;
   bool deployProfileIni(const QString& sourceIniPath,
                         const QString& targetIniPath) const;
   bool deployProfileSaves(const QString& profileSaveDir, const QString& gameName,
                           const QString& saveRelativePath,
                           bool clearDestination) const;
 
+  // Read the game's own plugins.txt from the prefix, as (name, enabled)
+  // pairs in load order. In the asterisk format of the newer games only lines
+  // prefixed with '*' are enabled, in the older format every entry is.
+  QList<QPair<QString, bool>> readPlugins(const QString& dataDir,
+                                          bool asteriskFormat) const;
+
   // Sync saves back from prefix to profile
   bool syncSavesBack(const QString& profileSaveDir, const QString& gameName,
                      const QString& saveRelativePath) const;